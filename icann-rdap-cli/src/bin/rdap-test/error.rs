@@ -16,6 +16,8 @@ pub enum RdapTestError {
     TestsCompletedWarningsFound,
     #[error("Tests completed, error checks found.")]
     TestsCompletedErrorsFound,
+    #[error("Test plan completed, assertions failed.")]
+    TestPlanAssertionsFailed,
     #[error(transparent)]
     RdapClient(#[from] RdapClientError),
     #[error(transparent)]
@@ -26,9 +28,13 @@ pub enum RdapTestError {
     IoError(#[from] std::io::Error),
     #[error("Unknown output type")]
     UnknownOutputType,
+    #[error("Output type is only supported when testing a single query")]
+    UnsupportedOutputType,
     #[error(transparent)]
     Json(#[from] serde_json::Error),
     #[error(transparent)]
+    Yaml(#[from] serde_yaml::Error),
+    #[error(transparent)]
     Iana(#[from] IanaResponseError),
     #[error("Invalid IANA bootstrap file")]
     InvalidBootstrap,
@@ -48,6 +54,7 @@ impl Termination for RdapTestError {
             Self::TestsCompletedExecutionErrors => 1,
             Self::TestsCompletedWarningsFound => 2,
             Self::TestsCompletedErrorsFound => 3,
+            Self::TestPlanAssertionsFailed => 4,
 
             // Internal Errors
             Self::Termimad(_) => 10,
@@ -65,6 +72,8 @@ impl Termination for RdapTestError {
 
             // User Errors
             Self::UnknownOutputType => 200,
+            Self::Yaml(_) => 201,
+            Self::UnsupportedOutputType => 205,
 
             // RDAP Client Errors
             Self::RdapClient(e) => match e {
@@ -74,6 +83,7 @@ impl Termination for RdapTestError {
 
                 // RDAP Server Errors
                 RdapClientError::Response(_) => 60,
+                RdapClientError::PageUnavailable { .. } => 61,
                 RdapClientError::ParsingError(_) => 62,
                 RdapClientError::Json(_) => 63,
 
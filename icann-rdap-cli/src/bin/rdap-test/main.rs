@@ -20,7 +20,14 @@ use {
     icann_rdap_cli::{
         dirs,
         dirs::fcbs::FileCacheBootstrapStore,
-        rt::exec::{execute_tests, ExtensionGroup, TestOptions},
+        rt::{
+            exec::{
+                execute_multi_http_tests, execute_test_plan, execute_tests, ExtensionGroup,
+                TestOptions,
+            },
+            plan::TestPlan,
+            summary::SummaryMatrix,
+        },
     },
     icann_rdap_client::{http::ClientConfig, md::MdOptions, rdap::QueryType},
     icann_rdap_common::check::CheckClass,
@@ -157,6 +164,44 @@ struct HttpArgGroup {
     #[arg(required = false)]
     query_value: Option<String>,
 
+    /// File of targets to test.
+    ///
+    /// Each line of the file is a query value to test, or the base URL of an
+    /// RDAP server to send the query of the query value to. Empty lines and
+    /// lines beginning with '#' are ignored. The targets are tested at the
+    /// same time, and the output is a summary of the checks found on each
+    /// server by check type.
+    #[arg(long, required = false, value_name = "FILE")]
+    targets: Option<String>,
+
+    /// Number of targets to test at the same time.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_TEST_CONCURRENCY",
+        default_value = "4",
+        value_parser = clap::value_parser!(u16).range(1..),
+        requires = "targets"
+    )]
+    concurrency: u16,
+
+    /// File of a test plan to run.
+    ///
+    /// The test plan is a YAML file, or a JSON file if the file name ends
+    /// with '.json', listing queries and what is expected of their responses:
+    /// the HTTP status, extensions, checks that must or must not be found,
+    /// link relations and redactions. The output is the result of each
+    /// assertion. The exit code is 1 if a query could not be tested and 4 if
+    /// an assertion fails.
+    #[arg(
+        long,
+        required = false,
+        value_name = "FILE",
+        env = "RDAP_TEST_PLAN",
+        conflicts_with_all = ["query_value", "targets"]
+    )]
+    test_plan: Option<String>,
+
     /// DNS Resolver
     ///
     /// Specifies the address and port of the DNS resolver to query.
@@ -168,6 +213,14 @@ struct HttpArgGroup {
     )]
     dns_resolver: String,
 
+    /// DNS parent server.
+    ///
+    /// Specifies the address and port of a server of the parent zone to
+    /// query for the delegation of a domain in the DNS checks. Otherwise,
+    /// the servers of the parent zone are found with the DNS resolver.
+    #[arg(long, required = false, env = "RDAP_TEST_DNS_PARENT_SERVER")]
+    dns_parent_server: Option<String>,
+
     /// Allow HTTP connections.
     ///
     /// When given, allows connections to RDAP servers using HTTP.
@@ -273,6 +326,23 @@ struct HttpArgGroup {
     #[arg(long, required = false, env = "RDAP_TEST_ONE_ADDR")]
     one_addr: bool,
 
+    /// Run negative tests.
+    ///
+    /// Also sends requests derived from the query that are erroneous
+    /// or unusual, such as malformed queries or queries for objects that
+    /// do not exist, to test how the server answers them.
+    #[arg(long, required = false, env = "RDAP_TEST_NEGATIVE_TESTS")]
+    negative_tests: bool,
+
+    /// Run DNS checks.
+    ///
+    /// For a domain, compares the nameservers and their IP addresses with
+    /// the NS records and glue of the delegation given by a server of the
+    /// parent zone, the DS records with what the DNS resolver gives, and the
+    /// ttl0 values with the TTLs of those records.
+    #[arg(long, required = false, env = "RDAP_TEST_DNS_CHECKS")]
+    dns_checks: bool,
+
     /// Origin header value.
     ///
     /// Specifies the origin header value.
@@ -335,6 +405,12 @@ enum OtypeArg {
 
     /// Results are output as Pretty Compact JSON.
     PrettyCompactJson,
+
+    /// Results are output as JUnit XML, with a test case for each check of each test run.
+    Junit,
+
+    /// Results are output as SARIF, with a rule for each check.
+    Sarif,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -362,6 +438,15 @@ enum CheckTypeArg {
 
     /// Gtld Profile errors.
     GtldProfileError,
+
+    /// RFC 9537 redaction errors.
+    Rfc9537Error,
+
+    /// DNS consistency errors.
+    DnsError,
+
+    /// NRO Profile errors.
+    NroProfileError,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -369,10 +454,10 @@ enum ExtensionGroupArg {
     /// The gTLD RDAP profiles.
     Gtld,
 
-    /// The base NRO profiles.
+    /// The base NRO profiles, with the NRO profile checks.
     Nro,
 
-    /// The NRO ASN profiles including the base profile.
+    /// The NRO ASN profiles including the base profile, with the NRO profile checks.
     NroAsn,
 }
 
@@ -472,6 +557,9 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
                 CheckTypeArg::Std95Error => CheckClass::Std95Error,
                 CheckTypeArg::Cidr0Error => CheckClass::Cidr0Error,
                 CheckTypeArg::GtldProfileError => CheckClass::GtldProfileError,
+                CheckTypeArg::Rfc9537Error => CheckClass::Rfc9537Error,
+                CheckTypeArg::DnsError => CheckClass::DnsConsistencyError,
+                CheckTypeArg::NroProfileError => CheckClass::NroProfileError,
                 _ => panic!("check type should have been handled."),
             })
             .collect::<Vec<CheckClass>>()
@@ -500,8 +588,36 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
         .max_retries(cli.http.max_retries)
         .build();
 
-    let test_type = if cli.http.query_value.is_some() {
-        let query_type = QueryType::from_str(&cli.http.query_value.unwrap_or_default())?;
+    let targets = match cli.http.targets {
+        Some(ref targets) => Some(
+            std::fs::read_to_string(targets)?
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+                .collect::<Vec<String>>(),
+        ),
+        None => None,
+    };
+
+    let test_plan = match cli.http.test_plan {
+        Some(ref test_plan) => {
+            let text = std::fs::read_to_string(test_plan)?;
+            if test_plan.ends_with(".json") {
+                Some(TestPlan::from_json(&text)?)
+            } else {
+                Some(TestPlan::from_yaml(&text)?)
+            }
+        }
+        None => None,
+    };
+
+    let test_type = if cli.http.query_value.is_some() || targets.is_some() || test_plan.is_some() {
+        // with targets or a test plan, each target or test gets its own query type
+        let query_type = match cli.http.query_value {
+            Some(ref query_value) => QueryType::from_str(query_value)?,
+            None => QueryType::Help,
+        };
         let link_params = params_from_args(&query_type, cli.http.link_target_args);
         TestType::Http(Box::new(HttpTestOptions {
             skip_v4: cli.http.skip_v4,
@@ -509,7 +625,10 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
             skip_origin: cli.http.skip_origin,
             origin_value: cli.http.origin_value,
             one_addr: cli.http.one_addr,
+            negative_tests: cli.http.negative_tests,
+            dns_checks: cli.http.dns_checks,
             dns_resolver: Some(cli.http.dns_resolver),
+            dns_parent_server: cli.http.dns_parent_server,
             link_params,
             value: query_type,
             client_config,
@@ -533,6 +652,61 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
         test_type,
     };
 
+    let output_type = if cli.json {
+        OtypeArg::PrettyCompactJson
+    } else {
+        cli.output_type
+    };
+
+    if let Some(targets) = targets {
+        let query = match &options.test_type {
+            TestType::Http(http_options) if cli.http.query_value.is_some() => {
+                Some(http_options.value.clone())
+            }
+            _ => None,
+        };
+        let target_results = execute_multi_http_tests(
+            &bs,
+            &targets,
+            query.as_ref(),
+            &options,
+            cli.http.concurrency.into(),
+        )
+        .await?
+        .into_iter()
+        .map(|r| r.filter_test_results(check_classes.clone()))
+        .collect::<Vec<_>>();
+        let matrix = SummaryMatrix::new(&check_classes, &target_results);
+        write_output(output_type, &matrix.to_md(&MdOptions::default()), &matrix)?;
+        return exit_status(&check_classes, |classes| {
+            if target_results.iter().any(|r| r.execution_errors()) {
+                None
+            } else {
+                Some(
+                    target_results
+                        .iter()
+                        .any(|r| r.are_there_checks(classes.clone())),
+                )
+            }
+        });
+    }
+
+    if let Some(test_plan) = test_plan {
+        let plan_results = execute_test_plan(&bs, &test_plan, &options).await?;
+        write_output(
+            output_type,
+            &plan_results.to_md(&MdOptions::default()),
+            &plan_results,
+        )?;
+        return if plan_results.passed() {
+            Ok(())
+        } else if plan_results.execution_errors() {
+            Err(RdapTestError::TestsCompletedExecutionErrors)
+        } else {
+            Err(RdapTestError::TestPlanAssertionsFailed)
+        };
+    }
+
     // execute tests
     let test_results = execute_tests(&bs, &options).await?;
 
@@ -540,12 +714,32 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
     let test_results = test_results.filter_test_results(check_classes.clone());
 
     // output results
-    let md_options = MdOptions::default();
-    let output_type = if cli.json {
-        OtypeArg::PrettyCompactJson
-    } else {
-        cli.output_type
-    };
+    match output_type {
+        OtypeArg::Junit => println!("{}", test_results.to_junit()),
+        OtypeArg::Sarif => println!(
+            "{}",
+            serde_json::to_string_pretty(&test_results.to_sarif())?
+        ),
+        _ => write_output(
+            output_type,
+            &test_results.to_md(&MdOptions::default()),
+            &test_results,
+        )?,
+    }
+    exit_status(&check_classes, |classes| {
+        if test_results.execution_errors() {
+            None
+        } else {
+            Some(test_results.are_there_checks(classes))
+        }
+    })
+}
+
+fn write_output<T: Serialize>(
+    output_type: OtypeArg,
+    md: &str,
+    results: &T,
+) -> Result<(), RdapTestError> {
     match output_type {
         OtypeArg::RenderedMarkdown => {
             let mut skin = MadSkin::default_dark();
@@ -563,27 +757,36 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
             skin.table.set_fg(DarkGrey);
             skin.table.align = Alignment::Center;
             skin.inline_code.set_fgbg(Cyan, Reset);
-            skin.write_text_on(&mut stdout(), &test_results.to_md(&md_options))?;
+            skin.write_text_on(&mut stdout(), md)?;
         }
         OtypeArg::Markdown => {
-            println!("{}", test_results.to_md(&md_options));
+            println!("{md}");
         }
         OtypeArg::Json => {
-            println!("{}", serde_json::to_string(&test_results).unwrap());
+            println!("{}", serde_json::to_string(results).unwrap());
         }
         OtypeArg::PrettyJson => {
-            println!("{}", serde_json::to_string_pretty(&test_results).unwrap());
+            println!("{}", serde_json::to_string_pretty(results).unwrap());
         }
         OtypeArg::PrettyCompactJson => {
             let formatter = PrettyCompactFormatter::new();
             let mut serializer = Serializer::with_formatter(stdout(), formatter);
-            test_results.serialize(&mut serializer)?;
+            results.serialize(&mut serializer)?;
         }
+        OtypeArg::Junit | OtypeArg::Sarif => return Err(RdapTestError::UnsupportedOutputType),
     }
+    Ok(())
+}
 
+/// Gets the exit status of the tests, given a function that says if there are
+/// checks of the given classes or `None` if some tests could not execute.
+fn exit_status(
+    check_classes: &[CheckClass],
+    are_there_checks: impl Fn(Vec<CheckClass>) -> Option<bool>,
+) -> Result<(), RdapTestError> {
     // if some tests could not execute
     //
-    if test_results.execution_errors() {
+    if are_there_checks(vec![]).is_none() {
         return Err(RdapTestError::TestsCompletedExecutionErrors);
     }
 
@@ -595,13 +798,18 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
         .filter(|c| {
             matches!(
                 c,
-                CheckClass::Std95Error | CheckClass::Cidr0Error | CheckClass::GtldProfileError
+                CheckClass::Std95Error
+                    | CheckClass::Cidr0Error
+                    | CheckClass::GtldProfileError
+                    | CheckClass::Rfc9537Error
+                    | CheckClass::DnsConsistencyError
+                    | CheckClass::NroProfileError
             )
         })
         .copied()
         .collect::<Vec<CheckClass>>();
     // return proper exit code if errors found
-    if test_results.are_there_checks(error_classes) {
+    if are_there_checks(error_classes) == Some(true) {
         return Err(RdapTestError::TestsCompletedErrorsFound);
     }
 
@@ -614,7 +822,7 @@ pub async fn wrapped_main() -> Result<(), RdapTestError> {
         .copied()
         .collect::<Vec<CheckClass>>();
     // return proper exit code if errors found
-    if test_results.are_there_checks(warning_classes) {
+    if are_there_checks(warning_classes) == Some(true) {
         return Err(RdapTestError::TestsCompletedWarningsFound);
     }
 
@@ -0,0 +1,262 @@
+use std::{
+    fs::File,
+    io::{stdin, BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+
+use {
+    futures::{stream, StreamExt},
+    icann_rdap_client::{http::Client, rdap::QueryType},
+    serde::Serialize,
+    tokio::sync::Mutex,
+    tracing::{debug, info},
+};
+
+use icann_rdap_common::response::RdapResponse;
+
+use crate::{
+    error::RdapCliError,
+    query::{determine_base_url, ProcessingParams},
+    request::request_and_process,
+};
+
+/// The format of the results of bulk queries.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum BulkFormat {
+    /// One JSON object per query, one per line.
+    JsonLines,
+
+    /// One row per query of comma separated values, with a header row.
+    Csv,
+}
+
+pub(crate) struct BulkParams {
+    pub concurrency: usize,
+    pub format: BulkFormat,
+}
+
+/// The result of one query of a bulk run.
+#[derive(Serialize, Debug)]
+struct BulkRecord {
+    /// The line number of the query value in the input.
+    line: usize,
+
+    /// The query value.
+    query: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query_type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status_code: Option<u16>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rdap_type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rdap: Option<RdapResponse>,
+
+    /// The error preventing a response, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl BulkRecord {
+    fn new(line: usize, query: &str) -> Self {
+        Self {
+            line,
+            query: query.to_string(),
+            query_type: None,
+            url: None,
+            status_code: None,
+            rdap_type: None,
+            rdap: None,
+            error: None,
+        }
+    }
+}
+
+const CSV_HEADER: &str = "line,query,query_type,url,status_code,rdap_type,error";
+
+/// Reads the query values to be queried in bulk, one per line, from a file
+/// or from stdin if the path is `-`. Empty lines and lines beginning with `#`
+/// are skipped.
+///
+/// Returns the values with their line numbers.
+pub(crate) fn read_query_values(path: &Path) -> Result<Vec<(usize, String)>, RdapCliError> {
+    let reader: Box<dyn BufRead> = if path == Path::new("-") {
+        Box::new(BufReader::new(stdin()))
+    } else {
+        Box::new(BufReader::new(File::open(path)?))
+    };
+    let mut values = vec![];
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let value = line.trim();
+        if !value.is_empty() && !value.starts_with('#') {
+            values.push((i + 1, value.to_string()));
+        }
+    }
+    Ok(values)
+}
+
+/// Queries each value, with the query type inferred from the value, and writes
+/// a record of each query in the order of the values. A query that fails is
+/// written as a record with an error, and does not stop the other queries.
+pub(crate) async fn exec_bulk_queries<W: std::io::Write>(
+    values: Vec<(usize, String)>,
+    bulk_params: &BulkParams,
+    processing_params: &ProcessingParams,
+    client: &Client,
+    write: &mut W,
+) -> Result<(), RdapCliError> {
+    info!(
+        "querying {} values with a concurrency of {}",
+        values.len(),
+        bulk_params.concurrency
+    );
+    // the bootstrap files are shared, so only bootstrap one query at a time
+    let bootstrap_lock = Mutex::new(());
+    let mut records = stream::iter(values)
+        .map(|(line, value)| bulk_query(line, value, processing_params, client, &bootstrap_lock))
+        .buffered(bulk_params.concurrency.max(1));
+
+    if bulk_params.format == BulkFormat::Csv {
+        writeln!(write, "{CSV_HEADER}")?;
+    }
+    while let Some(record) = records.next().await {
+        match bulk_params.format {
+            BulkFormat::JsonLines => writeln!(write, "{}", serde_json::to_string(&record)?)?,
+            BulkFormat::Csv => writeln!(write, "{}", csv_row(&record))?,
+        }
+    }
+    Ok(())
+}
+
+async fn bulk_query(
+    line: usize,
+    value: String,
+    processing_params: &ProcessingParams,
+    client: &Client,
+    bootstrap_lock: &Mutex<()>,
+) -> BulkRecord {
+    let mut record = BulkRecord::new(line, &value);
+    let query_type = match QueryType::from_str(&value) {
+        Ok(query_type) => query_type,
+        Err(e) => {
+            record.error = Some(e.to_string());
+            return record;
+        }
+    };
+    record.query_type = Some(query_type.to_string());
+    debug!("query type is {query_type} for value '{value}' on line {line}");
+
+    let base_url = {
+        let _guard = bootstrap_lock.lock().await;
+        determine_base_url(&query_type, processing_params, client).await
+    };
+    let base_url = match base_url {
+        Ok(base_url) => base_url,
+        Err(e) => {
+            record.error = Some(e.to_string());
+            return record;
+        }
+    };
+    record.url = query_type.query_url(&base_url).ok();
+
+    match request_and_process(&base_url, &query_type, processing_params, client).await {
+        Ok(response) => {
+            if let Some(request_uri) = response.http_data.request_uri() {
+                record.url = Some(request_uri.to_string());
+            }
+            record.status_code = Some(response.http_data.status_code());
+            record.rdap_type = Some(response.rdap_type);
+            record.rdap = Some(response.rdap);
+        }
+        Err(e) => record.error = Some(e.to_string()),
+    }
+    record
+}
+
+fn csv_row(record: &BulkRecord) -> String {
+    [
+        record.line.to_string(),
+        record.query.clone(),
+        record.query_type.clone().unwrap_or_default(),
+        record.url.clone().unwrap_or_default(),
+        record
+            .status_code
+            .map(|code| code.to_string())
+            .unwrap_or_default(),
+        record.rdap_type.clone().unwrap_or_default(),
+        record.error.clone().unwrap_or_default(),
+    ]
+    .iter()
+    .map(|field| csv_field(field))
+    .collect::<Vec<String>>()
+    .join(",")
+}
+
+/// Quotes a CSV field if it contains a comma, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::{csv_field, csv_row, read_query_values, BulkRecord};
+
+    #[test]
+    fn test_read_query_values_skips_blank_lines_and_comments() {
+        // GIVEN
+        let mut file = tempfile::NamedTempFile::new().expect("creating temp file");
+        writeln!(file, "# nightly audit\nfoo.example\n\n  192.0.2.0/24  \n").expect("writing");
+
+        // WHEN
+        let actual = read_query_values(file.path()).expect("reading values");
+
+        // THEN
+        assert_eq!(
+            actual,
+            [
+                (2, "foo.example".to_string()),
+                (4, "192.0.2.0/24".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        // GIVEN
+        let fields = ["plain", "a,b", "say \"hi\""];
+
+        // WHEN
+        let actual = fields.map(csv_field);
+
+        // THEN
+        assert_eq!(actual, ["plain", "\"a,b\"", "\"say \"\"hi\"\"\""]);
+    }
+
+    #[test]
+    fn test_csv_row_of_error_record() {
+        // GIVEN
+        let mut record = BulkRecord::new(3, "not a value");
+        record.error = Some("ambiguous query type".to_string());
+
+        // WHEN
+        let actual = csv_row(&record);
+
+        // THEN
+        assert_eq!(actual, "3,not a value,,,,,ambiguous query type");
+    }
+}
@@ -80,6 +80,7 @@ impl RdapCliError {
 
                 // RDAP Server Errors
                 RdapClientError::Response(_) => 60,
+                RdapClientError::PageUnavailable { .. } => 61,
                 RdapClientError::ParsingError(_) => 62,
                 RdapClientError::Json(_) => 63,
 
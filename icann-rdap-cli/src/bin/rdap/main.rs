@@ -3,17 +3,18 @@ use std::collections::HashSet;
 use enumflags2::BitFlags;
 use icann_rdap_cli::args::target::{params_from_args, LinkTargetArgs};
 use icann_rdap_client::http::default_exts_list;
-use icann_rdap_common::check::StringCheck;
+use icann_rdap_common::{check::StringCheck, response::FieldSet};
 #[cfg(debug_assertions)]
 use tracing::warn;
 use {
     bootstrap::BootstrapType,
+    bulk::{exec_bulk_queries, read_query_values, BulkFormat, BulkParams},
     clap::builder::{styling::AnsiColor, Styles},
     error::RdapCliError,
     icann_rdap_cli::dirs,
     icann_rdap_client::http::{create_client, Client, ClientConfig},
     query::{InrBackupBootstrap, ProcessingParams, TldLookup},
-    std::{io::IsTerminal, str::FromStr},
+    std::{io::IsTerminal, path::PathBuf, str::FromStr},
     tracing::{error, info},
     tracing_subscriber::filter::LevelFilter,
     write::{FmtWrite, PagerWrite},
@@ -21,7 +22,7 @@ use {
 
 use {
     clap::{ArgGroup, Parser, ValueEnum},
-    icann_rdap_client::rdap::QueryType,
+    icann_rdap_client::rdap::{PagingLimits, QueryType, DEFAULT_MAX_PAGES},
     icann_rdap_common::VERSION,
     query::OutputType,
     tokio::{join, task::spawn_blocking},
@@ -30,6 +31,7 @@ use {
 use crate::query::{exec_queries, RedactionFlag};
 
 pub mod bootstrap;
+pub mod bulk;
 pub mod error;
 pub mod query;
 pub mod request;
@@ -55,7 +57,7 @@ impl CliStyles {
 #[command(group(
             ArgGroup::new("input")
                 .required(true)
-                .args(["query_value", "server_help", "reset"]),
+                .args(["query_value", "input_file", "server_help", "reset"]),
         ))]
 #[command(group(
             ArgGroup::new("base_specify")
@@ -97,6 +99,38 @@ struct Cli {
     #[arg(short = 'S', long, conflicts_with = "query_type")]
     server_help: bool,
 
+    /// File of query values to be queried in bulk.
+    ///
+    /// Each line of the file is a query value, with the query type inferred
+    /// from the value. Empty lines and lines beginning with '#' are ignored.
+    /// Use '-' to read the query values from stdin. A record of each query
+    /// is written in the order of the query values, and queries that fail are
+    /// written as records with an error. Links are not followed.
+    #[arg(long = "input", value_name = "FILE", conflicts_with = "query_type")]
+    input_file: Option<PathBuf>,
+
+    /// Number of bulk queries to run at the same time.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_BULK_CONCURRENCY",
+        default_value = "4",
+        value_parser = clap::value_parser!(u16).range(1..),
+        requires = "input_file"
+    )]
+    concurrency: u16,
+
+    /// Format of the records of bulk queries.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_BULK_FORMAT",
+        value_enum,
+        default_value_t = BulkFormatArg::JsonLines,
+        requires = "input_file"
+    )]
+    bulk_format: BulkFormatArg,
+
     /// An RDAP base signifier.
     ///
     /// This option gets a base URL from the RDAP bootstrap registries maintained
@@ -231,6 +265,46 @@ struct Cli {
     #[arg(long, required = false, env = "RDAP_CACHE_SELF_LINKS")]
     self_link_caching: bool,
 
+    /// Get all pages of search results.
+    ///
+    /// When given, the "next" links of paged search results (RFC 8977)
+    /// are followed and the pages are merged into one set of results.
+    /// If a later page cannot be retrieved, the command fails rather
+    /// than returning partial results.
+    #[arg(long, required = false, env = "RDAP_ALL_PAGES")]
+    all_pages: bool,
+
+    /// Max pages.
+    ///
+    /// Specifies the maximum number of pages of search results to get
+    /// when getting all pages.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_MAX_PAGES",
+        default_value_t = DEFAULT_MAX_PAGES,
+        requires = "all_pages"
+    )]
+    max_pages: usize,
+
+    /// Max results.
+    ///
+    /// Specifies the maximum number of search results to get
+    /// when getting all pages.
+    #[arg(
+        long,
+        required = false,
+        env = "RDAP_MAX_RESULTS",
+        requires = "all_pages"
+    )]
+    max_results: Option<usize>,
+
+    /// Field set.
+    ///
+    /// Requests a field set (RFC 8982) of the objects in search results.
+    #[arg(long, required = false, env = "RDAP_FIELD_SET", value_enum)]
+    field_set: Option<FieldSetArg>,
+
     /// Allow HTTP connections.
     ///
     /// When given, allows connections to RDAP servers using HTTP.
@@ -371,6 +445,18 @@ enum QtypeArg {
     /// Nameserver IP Address Search
     NsIp,
 
+    /// IP Network Handle Search
+    IpHandle,
+
+    /// IP Network Name Search
+    IpName,
+
+    /// Autnum Handle Search
+    AutnumHandle,
+
+    /// Autnum Name Search
+    AutnumName,
+
     /// RDAP URL
     Url,
 }
@@ -472,6 +558,27 @@ enum InrBackupBootstrapArg {
     None,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum FieldSetArg {
+    /// Only the key fields, such as names and handles.
+    Id,
+
+    /// The key fields plus a summary chosen by the server.
+    Brief,
+
+    /// All the fields.
+    Full,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum BulkFormatArg {
+    /// One JSON object per line.
+    JsonLines,
+
+    /// Comma separated values.
+    Csv,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum RedactionFlagArg {
     /// Highlight Simple Redactions.
@@ -530,11 +637,13 @@ pub async fn wrapped_main() -> Result<(), RdapCliError> {
 
     let query_type = query_type_from_cli(&cli)?;
 
-    let use_pager = match cli.page_output {
-        PagerType::Embedded => true,
-        PagerType::None => false,
-        PagerType::Auto => std::io::stdout().is_terminal(),
-    };
+    // bulk queries are written as records, which are not for the pager
+    let use_pager = cli.input_file.is_none()
+        && match cli.page_output {
+            PagerType::Embedded => true,
+            PagerType::None => false,
+            PagerType::Auto => std::io::stdout().is_terminal(),
+        };
 
     let output_type = if cli.json {
         OutputType::PrettyCompactJson
@@ -617,6 +726,17 @@ pub async fn wrapped_main() -> Result<(), RdapCliError> {
         link_params,
         to_jscontact: cli.to_jscontact,
         self_link_caching: cli.self_link_caching,
+        field_set: cli.field_set.map(|field_set| match field_set {
+            FieldSetArg::Id => FieldSet::Id,
+            FieldSetArg::Brief => FieldSet::Brief,
+            FieldSetArg::Full => FieldSet::Full,
+        }),
+        paging_limits: cli.all_pages.then(|| {
+            PagingLimits::builder()
+                .max_pages(cli.max_pages)
+                .and_max_results(cli.max_results)
+                .build()
+        }),
     };
 
     let exts_list = if cli.no_exts_list {
@@ -643,14 +763,32 @@ pub async fn wrapped_main() -> Result<(), RdapCliError> {
                 .with_writer(std::io::stderr)
                 .init();
             let output = &mut std::io::stdout();
-            let res1 = join!(exec(
-                cli.query_value,
-                &query_type,
-                &processing_params,
-                &client,
-                output,
-            ));
-            res1.0?;
+            if let Some(input_file) = cli.input_file {
+                let bulk_params = BulkParams {
+                    concurrency: cli.concurrency.into(),
+                    format: match cli.bulk_format {
+                        BulkFormatArg::JsonLines => BulkFormat::JsonLines,
+                        BulkFormatArg::Csv => BulkFormat::Csv,
+                    },
+                };
+                exec_bulk(
+                    &input_file,
+                    &bulk_params,
+                    &processing_params,
+                    &client,
+                    output,
+                )
+                .await?;
+            } else {
+                let res1 = join!(exec(
+                    cli.query_value,
+                    &query_type,
+                    &processing_params,
+                    &client,
+                    output,
+                ));
+                res1.0?;
+            }
         } else {
             let pager = minus::Pager::new();
             pager
@@ -714,6 +852,30 @@ async fn exec<W: std::io::Write>(
     }
 }
 
+async fn exec_bulk<W: std::io::Write>(
+    input_file: &std::path::Path,
+    bulk_params: &BulkParams,
+    processing_params: &ProcessingParams,
+    client: &Client,
+    mut output: W,
+) -> Result<(), RdapCliError> {
+    info!("ICANN RDAP {} Command Line Interface", VERSION);
+
+    #[cfg(debug_assertions)]
+    warn!("This is a development build of this software.");
+
+    let values = read_query_values(input_file)?;
+    let result =
+        exec_bulk_queries(values, bulk_params, processing_params, client, &mut output).await;
+    match result {
+        Ok(_) => Ok(()),
+        Err(error) => {
+            error!("{}", error);
+            Err(error)
+        }
+    }
+}
+
 fn query_type_from_cli(cli: &Cli) -> Result<QueryType, RdapCliError> {
     let Some(query_value) = cli.query_value.clone() else {
         return Ok(QueryType::Help);
@@ -739,6 +901,10 @@ fn query_type_from_cli(cli: &Cli) -> Result<QueryType, RdapCliError> {
         QtypeArg::DomainNsIp => QueryType::domain_ns_ip_search(&query_value)?,
         QtypeArg::NsName => QueryType::NameserverNameSearch(query_value),
         QtypeArg::NsIp => QueryType::ns_ip_search(&query_value)?,
+        QtypeArg::IpHandle => QueryType::NetworkHandleSearch(query_value),
+        QtypeArg::IpName => QueryType::NetworkNameSearch(query_value),
+        QtypeArg::AutnumHandle => QueryType::AutnumHandleSearch(query_value),
+        QtypeArg::AutnumName => QueryType::AutnumNameSearch(query_value),
         QtypeArg::Url => QueryType::Url(query_value),
     };
     Ok(q)
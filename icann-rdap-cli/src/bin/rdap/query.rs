@@ -7,7 +7,7 @@ use {
     icann_rdap_client::{
         gtld::{GtldParams, ToGtldWhois},
         md::{MdOptions, MdParams, ToMd},
-        rdap::{QueryType, RequestData, RequestResponse, ResponseData},
+        rdap::{PagingLimits, QueryType, RequestData, RequestResponse, ResponseData},
     },
     termimad::{crossterm::style::Color::*, Alignment, MadSkin},
 };
@@ -21,9 +21,10 @@ use icann_rdap_common::{
         process::do_check_processing, traverse_checks, ALL_CHECK_CLASSES, WARNING_CHECK_CLASSES,
     },
     prelude::{get_relationship_links, Event, RdapResponse},
-    response::ObjectCommonFields,
+    response::{FieldSet, ObjectCommonFields},
 };
 use json_pretty_compact::PrettyCompactFormatter;
+use reqwest::Url;
 use serde::Serialize;
 use serde_json::Serializer;
 use tracing::warn;
@@ -125,6 +126,10 @@ pub(crate) struct ProcessingParams {
     pub link_params: LinkParams,
     pub to_jscontact: bool,
     pub self_link_caching: bool,
+    /// If present, the field set requested for searches.
+    pub field_set: Option<FieldSet>,
+    /// If present, the pages of search results are followed within these limits.
+    pub paging_limits: Option<PagingLimits>,
 }
 
 pub(crate) async fn exec_queries<W: std::io::Write>(
@@ -184,7 +189,7 @@ pub(crate) async fn exec_queries<W: std::io::Write>(
                 "Found next target with relationship(s) of '{}'.",
                 processing_params.link_params.link_targets.join(" ")
             );
-            query_type = QueryType::Url(url.to_string());
+            query_type = QueryType::Url(absolute_url(url, &response));
             transactions.push(RequestResponse {
                 req_data,
                 res_data: response,
@@ -210,7 +215,20 @@ pub(crate) async fn exec_queries<W: std::io::Write>(
     Ok(())
 }
 
-async fn determine_base_url(
+/// Resolves a link found in a response against the URL of the response.
+fn absolute_url(href: &str, response: &ResponseData) -> String {
+    response
+        .http_data
+        .request_uri()
+        .and_then(|request_uri| {
+            Url::parse(request_uri)
+                .and_then(|base| base.join(href))
+                .ok()
+        })
+        .map_or_else(|| href.to_string(), |url| url.to_string())
+}
+
+pub(crate) async fn determine_base_url(
     query_type: &QueryType,
     processing_params: &ProcessingParams,
     client: &Client,
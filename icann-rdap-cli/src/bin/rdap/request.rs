@@ -15,7 +15,7 @@ use url::Url;
 use {
     icann_rdap_client::{
         http::Client,
-        rdap::{rdap_url_request, QueryType, ResponseData},
+        rdap::{rdap_follow_pages, rdap_url_request, QueryType, ResponseData},
     },
     icann_rdap_common::{httpdata::HttpData, response::GetSelfLink},
     pct_str::{PctString, UriReserved},
@@ -38,7 +38,7 @@ pub(crate) async fn do_request(
     if processing_params.no_cache {
         info!("Cache has been disabled.")
     }
-    let query_url = query_type.query_url(base_url)?;
+    let query_url = query_url(base_url, query_type, processing_params)?;
     if !processing_params.no_cache {
         let file_name = format!(
             "{}.cache",
@@ -70,7 +70,6 @@ pub(crate) async fn do_request(
                 if response.http_data.should_cache() {
                     let data = serde_json::to_string_pretty(&response)?;
                     let cache_contents = response.http_data.to_lines(&data)?;
-                    let query_url = query_type.query_url(base_url)?;
                     let file_name = format!(
                         "{}.cache",
                         PctString::encode(query_url.chars(), UriReserved::Any)
@@ -157,6 +156,10 @@ pub(crate) async fn request_and_process(
     client: &Client,
 ) -> Result<ResponseData, RdapCliError> {
     let response = do_request(base_url, query_type, processing_params, client).await?;
+    let response = match processing_params.paging_limits {
+        Some(paging_limits) => rdap_follow_pages(response, client, paging_limits).await?,
+        None => response,
+    };
     let processed_rdap = process_redactions(response.rdap, &processing_params.redaction_flags);
     let processed_rdap = process_jscontact(processed_rdap, processing_params.to_jscontact);
 
@@ -167,6 +170,17 @@ pub(crate) async fn request_and_process(
     })
 }
 
+fn query_url(
+    base_url: &str,
+    query_type: &QueryType,
+    processing_params: &ProcessingParams,
+) -> Result<String, RdapClientError> {
+    match processing_params.field_set {
+        Some(field_set) => query_type.query_url_with_field_set(base_url, field_set),
+        None => query_type.query_url(base_url),
+    }
+}
+
 fn is_same_origin(url1: &str, url2: &str) -> bool {
     let u1 = Url::parse(url1);
     let u2 = Url::parse(url2);
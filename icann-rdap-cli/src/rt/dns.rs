@@ -0,0 +1,503 @@
+//! Checks of RDAP domain data against the DNS.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+};
+
+use {
+    hickory_client::{
+        client::{AsyncClient, ClientConnection, ClientHandle},
+        op::Query,
+        proto::xfer::{DnsHandle, DnsRequestOptions, FirstAnswer},
+        rr::{DNSClass, Name, RData, Record, RecordType},
+        udp::UdpClientConnection,
+    },
+    icann_rdap_common::{
+        check::{Check, CheckItem},
+        response::Domain,
+    },
+    tracing::{debug, info},
+};
+
+use super::exec::TestExecutionError;
+
+/// The port of the servers of parent zones.
+const DNS_PORT: u16 = 53;
+
+/// A DS record as its key tag, algorithm, digest type, and digest in upper case hexadecimal.
+type DsRecord = (u32, u8, u8, String);
+
+/// The distinct records of an RRset and the largest TTL of them.
+#[derive(Debug)]
+struct RrSet<T> {
+    records: BTreeSet<T>,
+    ttl: Option<u32>,
+}
+
+impl<T> Default for RrSet<T> {
+    fn default() -> Self {
+        Self {
+            records: BTreeSet::new(),
+            ttl: None,
+        }
+    }
+}
+
+/// The records found in DNS for a domain and its nameservers.
+#[derive(Debug, Default)]
+struct DnsRecords {
+    ns: RrSet<String>,
+    ds: RrSet<DsRecord>,
+    /// The A glue records by nameserver name.
+    a: HashMap<String, RrSet<IpAddr>>,
+    /// The AAAA glue records by nameserver name.
+    aaaa: HashMap<String, RrSet<IpAddr>>,
+}
+
+/// Checks the RDAP data of a domain against the DNS.
+///
+/// The NS records and glue of the delegation are those in the referral given by
+/// a server of the parent zone when queried without recursion. That server is
+/// the given parent server, or otherwise one found with the DNS resolver. The DS
+/// records are queried from the DNS resolver.
+pub async fn dns_consistency_checks(
+    domain: &Domain,
+    dns_resolver: &str,
+    parent_server: Option<&str>,
+) -> Result<Vec<CheckItem>, TestExecutionError> {
+    let Some(ldh_name) = domain.ldh_name() else {
+        return Ok(vec![]);
+    };
+    let mut client = connect(dns_resolver.parse()?).await?;
+
+    info!("Checking {ldh_name} against DNS");
+    let name = Name::from_str(ldh_name)?;
+    let parent_servers = match parent_server {
+        Some(parent_server) => vec![parent_server.parse()?],
+        None => parent_servers(&mut client, &name).await?,
+    };
+    let mut records = None;
+    for parent_server in parent_servers {
+        match delegation(parent_server, &name).await {
+            Ok(delegation) => {
+                records = Some(delegation);
+                break;
+            }
+            Err(e) => debug!("No delegation of {name} from {parent_server}: {e}"),
+        }
+    }
+    let mut records = records.ok_or(TestExecutionError::NoDelegation)?;
+    records.ds = rrset(
+        &query(&mut client, name.clone(), RecordType::DS).await?,
+        ds_record,
+    );
+
+    Ok(compare(domain, &records))
+}
+
+async fn connect(server: SocketAddr) -> Result<AsyncClient, TestExecutionError> {
+    let conn = UdpClientConnection::new(server)?.new_stream(None);
+    let (client, bg) = AsyncClient::connect(conn).await?;
+
+    // make sure to run the background task
+    tokio::spawn(bg);
+    Ok(client)
+}
+
+/// Finds the addresses of the servers of the parent zone of a domain with the DNS resolver.
+async fn parent_servers(
+    client: &mut AsyncClient,
+    name: &Name,
+) -> Result<Vec<SocketAddr>, TestExecutionError> {
+    let parent = name.base_name();
+    let ns_names = query(client, parent, RecordType::NS)
+        .await?
+        .iter()
+        .filter_map(|r| r.data().and_then(RData::as_ns).map(|ns| ns.0.clone()))
+        .collect::<BTreeSet<_>>();
+    let mut servers = vec![];
+    for ns_name in ns_names {
+        for record_type in [RecordType::A, RecordType::AAAA] {
+            for record in query(client, ns_name.clone(), record_type).await? {
+                if let Some(ip) = record.data().and_then(RData::ip_addr) {
+                    servers.push(SocketAddr::new(ip, DNS_PORT));
+                }
+            }
+        }
+    }
+    Ok(servers)
+}
+
+/// Gets the NS records and glue of the delegation of a domain by querying a
+/// server of the parent zone without recursion.
+async fn delegation(server: SocketAddr, name: &Name) -> Result<DnsRecords, TestExecutionError> {
+    let client = connect(server).await?;
+    let mut options = DnsRequestOptions::default();
+    options.recursion_desired = false;
+    let response = client
+        .lookup(Query::query(name.clone(), RecordType::NS), options)
+        .first_answer()
+        .await?;
+
+    // a referral has the NS records in the authority section, but a server
+    // that is also authoritative for the domain answers with them
+    let ns = response
+        .answers()
+        .iter()
+        .chain(response.name_servers())
+        .filter(|r| r.record_type() == RecordType::NS && r.name() == name)
+        .cloned()
+        .collect::<Vec<_>>();
+    if ns.is_empty() {
+        return Err(TestExecutionError::NoDelegation);
+    }
+    let mut records = DnsRecords {
+        ns: rrset(&ns, |rdata| {
+            rdata.as_ns().map(|ns| normalize(&ns.0.to_string()))
+        }),
+        ..Default::default()
+    };
+    for ns_name in &records.ns.records {
+        let glue = |record_type: RecordType| {
+            response
+                .additionals()
+                .iter()
+                .filter(|r| {
+                    r.record_type() == record_type && normalize(&r.name().to_string()) == *ns_name
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let a = glue(RecordType::A);
+        if !a.is_empty() {
+            records.a.insert(ns_name.clone(), rrset(&a, RData::ip_addr));
+        }
+        let aaaa = glue(RecordType::AAAA);
+        if !aaaa.is_empty() {
+            records
+                .aaaa
+                .insert(ns_name.clone(), rrset(&aaaa, RData::ip_addr));
+        }
+    }
+    Ok(records)
+}
+
+async fn query(
+    client: &mut AsyncClient,
+    name: Name,
+    record_type: RecordType,
+) -> Result<Vec<Record>, TestExecutionError> {
+    let response = client.query(name, DNSClass::IN, record_type).await?;
+    Ok(response
+        .answers()
+        .iter()
+        .filter(|r| r.record_type() == record_type)
+        .cloned()
+        .collect())
+}
+
+fn rrset<T: Ord>(records: &[Record], rdata_value: impl Fn(&RData) -> Option<T>) -> RrSet<T> {
+    RrSet {
+        records: records
+            .iter()
+            .filter_map(|r| r.data().and_then(&rdata_value))
+            .collect(),
+        ttl: records.iter().map(|r| r.ttl()).max(),
+    }
+}
+
+/// Gets a DS record from its wire format, as DS records are unknown to the client.
+fn ds_record(rdata: &RData) -> Option<DsRecord> {
+    let RData::Unknown {
+        code: RecordType::DS,
+        rdata,
+    } = rdata
+    else {
+        return None;
+    };
+    let bytes = rdata.anything();
+    if bytes.len() < 4 {
+        return None;
+    }
+    let digest = bytes[4..].iter().map(|b| format!("{b:02X}")).collect();
+    Some((
+        u16::from_be_bytes([bytes[0], bytes[1]]).into(),
+        bytes[2],
+        bytes[3],
+        digest,
+    ))
+}
+
+fn normalize(name: &str) -> String {
+    name.trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// True if there is a TTL in DNS greater than the ttl0 value.
+fn exceeds_ttl0(ttl0: Option<u32>, ttl: Option<u32>) -> bool {
+    // TTLs from a resolver count down, so only greater ones are a difference
+    matches!((ttl0, ttl), (Some(ttl0), Some(ttl)) if ttl > ttl0)
+}
+
+fn compare(domain: &Domain, records: &DnsRecords) -> Vec<CheckItem> {
+    let mut items = vec![];
+    let mut ttl_exceeded = false;
+
+    // nameservers
+    let nameservers = domain
+        .nameservers()
+        .iter()
+        .filter_map(|ns| ns.ldh_name())
+        .map(normalize)
+        .collect::<BTreeSet<_>>();
+    if !nameservers.is_empty() && nameservers != records.ns.records {
+        debug!(
+            "RDAP nameservers {nameservers:?} are not DNS NS records {:?}",
+            records.ns.records
+        );
+        items.push(Check::DnsNameserversMismatch.check_item());
+    }
+    let ttl0 = domain.ttl0_data();
+    ttl_exceeded |= exceeds_ttl0(ttl0.and_then(|t| t.ns_value()), records.ns.ttl);
+
+    // addresses of the nameservers
+    let mut addresses_mismatched = false;
+    for nameserver in domain.nameservers() {
+        let (Some(ns_name), Some(ip_addresses)) =
+            (nameserver.ldh_name(), nameserver.ip_addresses())
+        else {
+            continue;
+        };
+        let ns_name = normalize(ns_name);
+        let ttl0 = nameserver.ttl0_data();
+        for (addresses, rrset, ttl0_value) in [
+            (
+                ip_addresses.v4s(),
+                records.a.get(&ns_name),
+                ttl0.and_then(|t| t.a_value()),
+            ),
+            (
+                ip_addresses.v6s(),
+                records.aaaa.get(&ns_name),
+                ttl0.and_then(|t| t.aaaa_value()),
+            ),
+        ] {
+            if addresses.is_empty() {
+                continue;
+            }
+            let addresses = addresses
+                .iter()
+                .filter_map(|a| IpAddr::from_str(a).ok())
+                .collect::<BTreeSet<_>>();
+            let dns_addresses = rrset.map(|r| &r.records);
+            if dns_addresses != Some(&addresses) {
+                debug!("RDAP addresses {addresses:?} of {ns_name} are not DNS {dns_addresses:?}");
+                addresses_mismatched = true;
+            }
+            ttl_exceeded |= exceeds_ttl0(ttl0_value, rrset.and_then(|r| r.ttl));
+        }
+    }
+    if addresses_mismatched {
+        items.push(Check::DnsIpAddressesMismatch.check_item());
+    }
+
+    // DS records
+    if let Some(secure_dns) = domain.secure_dns() {
+        let ds_data = secure_dns
+            .ds_data()
+            .iter()
+            .filter_map(|ds| {
+                Some((
+                    ds.key_tag()?,
+                    ds.algorithm()?,
+                    ds.digest_type()?,
+                    ds.digest()?
+                        .chars()
+                        .filter(|c| !c.is_whitespace())
+                        .collect::<String>()
+                        .to_ascii_uppercase(),
+                ))
+            })
+            .collect::<BTreeSet<_>>();
+        // without dsData, only an unsigned delegation can be compared
+        let mismatched = if !ds_data.is_empty() {
+            ds_data != records.ds.records
+        } else {
+            secure_dns.delegation_signed() == Some(false) && !records.ds.records.is_empty()
+        };
+        if mismatched {
+            debug!(
+                "RDAP dsData {ds_data:?} is not DNS DS records {:?}",
+                records.ds.records
+            );
+            items.push(Check::DnsDsDataMismatch.check_item());
+        }
+    }
+    ttl_exceeded |= exceeds_ttl0(ttl0.and_then(|t| t.ds_value()), records.ds.ttl);
+
+    if ttl_exceeded {
+        items.push(Check::DnsTtlGreaterThanTtl0.check_item());
+    }
+    items
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::net::IpAddr;
+
+    use icann_rdap_common::{
+        check::Check,
+        response::{ttl::Ttl0Data, Domain, DsDatum, Nameserver, SecureDns},
+    };
+
+    use super::{compare, DnsRecords, RrSet};
+
+    fn domain() -> Domain {
+        Domain::response_obj()
+            .ldh_name("foo.example")
+            .nameserver(
+                Nameserver::response_obj()
+                    .ldh_name("ns1.foo.example")
+                    .address("192.0.2.1".to_string())
+                    .ttl0_data(Ttl0Data::builder().a_value(3600).build())
+                    .build()
+                    .expect("nameserver"),
+            )
+            .nameserver(
+                Nameserver::response_obj()
+                    .ldh_name("ns2.example.net")
+                    .build()
+                    .expect("nameserver"),
+            )
+            .secure_dns(
+                SecureDns::builder()
+                    .delegation_signed(true)
+                    .ds_data(
+                        DsDatum::builder()
+                            .key_tag(12345)
+                            .algorithm(13)
+                            .digest_type(2)
+                            .digest("abcdef01".to_string())
+                            .build(),
+                    )
+                    .build(),
+            )
+            .ttl0_data(Ttl0Data::builder().ns_value(86400).ds_value(3600).build())
+            .build()
+    }
+
+    fn rrset<T: Ord>(records: Vec<T>, ttl: u32) -> RrSet<T> {
+        RrSet {
+            records: records.into_iter().collect(),
+            ttl: Some(ttl),
+        }
+    }
+
+    fn records() -> DnsRecords {
+        let mut records = DnsRecords {
+            ns: rrset(
+                vec!["ns1.foo.example".to_string(), "ns2.example.net".to_string()],
+                86400,
+            ),
+            ds: rrset(vec![(12345, 13, 2, "ABCDEF01".to_string())], 3600),
+            ..Default::default()
+        };
+        records.a.insert(
+            "ns1.foo.example".to_string(),
+            rrset(vec![IpAddr::from([192, 0, 2, 1])], 3600),
+        );
+        records
+    }
+
+    fn checks(domain: &Domain, records: &DnsRecords) -> Vec<Check> {
+        compare(domain, records).iter().map(|c| c.check).collect()
+    }
+
+    #[test]
+    fn GIVEN_matching_dns_WHEN_compare_THEN_no_checks() {
+        // GIVEN
+        let domain = domain();
+        let records = records();
+
+        // WHEN
+        let actual = checks(&domain, &records);
+
+        // THEN
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn GIVEN_other_ns_WHEN_compare_THEN_nameservers_mismatch() {
+        // GIVEN
+        let domain = domain();
+        let mut records = records();
+        records.ns = rrset(vec!["ns1.foo.example".to_string()], 86400);
+
+        // WHEN
+        let actual = checks(&domain, &records);
+
+        // THEN
+        assert_eq!(actual, vec![Check::DnsNameserversMismatch]);
+    }
+
+    #[test]
+    fn GIVEN_other_addresses_WHEN_compare_THEN_ip_addresses_mismatch() {
+        // GIVEN
+        let domain = domain();
+        let mut records = records();
+        records.a.insert(
+            "ns1.foo.example".to_string(),
+            rrset(vec![IpAddr::from([192, 0, 2, 2])], 3600),
+        );
+
+        // WHEN
+        let actual = checks(&domain, &records);
+
+        // THEN
+        assert_eq!(actual, vec![Check::DnsIpAddressesMismatch]);
+    }
+
+    #[test]
+    fn GIVEN_other_ds_digest_WHEN_compare_THEN_ds_data_mismatch() {
+        // GIVEN
+        let domain = domain();
+        let mut records = records();
+        records.ds = rrset(vec![(12345, 13, 2, "ABCDEF02".to_string())], 3600);
+
+        // WHEN
+        let actual = checks(&domain, &records);
+
+        // THEN
+        assert_eq!(actual, vec![Check::DnsDsDataMismatch]);
+    }
+
+    #[test]
+    fn GIVEN_ttl_greater_than_ttl0_WHEN_compare_THEN_ttl_greater() {
+        // GIVEN
+        let domain = domain();
+        let mut records = records();
+        records.ds.ttl = Some(7200);
+
+        // WHEN
+        let actual = checks(&domain, &records);
+
+        // THEN
+        assert_eq!(actual, vec![Check::DnsTtlGreaterThanTtl0]);
+    }
+
+    #[test]
+    fn GIVEN_ttl_less_than_ttl0_WHEN_compare_THEN_no_checks() {
+        // GIVEN
+        let domain = domain();
+        let mut records = records();
+        records.ns.ttl = Some(100);
+
+        // WHEN
+        let actual = checks(&domain, &records);
+
+        // THEN
+        assert!(actual.is_empty());
+    }
+}
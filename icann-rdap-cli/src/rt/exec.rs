@@ -8,11 +8,13 @@ use std::{
 
 use icann_rdap_client::rdap::ResponseData;
 use icann_rdap_common::{
+    check::Check,
     httpdata::HttpData,
     prelude::{get_relationship_links, RdapResponse},
 };
 
 use {
+    futures::{stream, StreamExt},
     hickory_client::{
         client::{AsyncClient, ClientConnection, ClientHandle},
         error::ClientError,
@@ -29,17 +31,24 @@ use {
     icann_rdap_common::response::ExtensionId,
     reqwest::{header::HeaderValue, Url},
     thiserror::Error,
-    tracing::{debug, info},
+    tracing::{debug, info, warn},
     url::ParseError,
 };
 
 use crate::{
     args::target::LinkParams,
-    rt::results::{HttpResults, RunFeature, TestRun},
+    rt::{
+        dns::dns_consistency_checks,
+        plan::{PlanResults, PlanTestResult, TestPlan},
+        probe::execute_probes,
+        results::{HttpResults, RunFeature, TargetResults, TestRun},
+    },
 };
 
 use super::results::{DnsData, StringResult, TestResults};
 
+const DEFAULT_DNS_RESOLVER: &str = "8.8.8.8:53";
+
 pub struct TestOptions {
     pub test_type: TestType,
     pub expect_extensions: Vec<String>,
@@ -77,7 +86,10 @@ pub struct HttpTestOptions {
     pub skip_origin: bool,
     pub origin_value: String,
     pub one_addr: bool,
+    pub negative_tests: bool,
+    pub dns_checks: bool,
     pub dns_resolver: Option<String>,
+    pub dns_parent_server: Option<String>,
     pub link_params: LinkParams,
 }
 
@@ -115,6 +127,8 @@ pub enum TestExecutionError {
     UnsupportedQueryType,
     #[error("No referral to chase")]
     NoReferralToChase,
+    #[error("No delegation found in the parent zone")]
+    NoDelegation,
     #[error("Unregistered extension")]
     UnregisteredExtension,
     #[error("Hickory Client Error: {0}")]
@@ -177,7 +191,7 @@ pub async fn execute_http_tests<BS: BootstrapStore>(
                 .first()
         {
             info!("Found referral to {url}");
-            query_url = url.to_string();
+            query_url = Url::parse(&query_url)?.join(url)?.to_string();
         } else if req_number < http_options.link_params.min_link_depth {
             return Err(TestExecutionError::NoReferralToChase);
         } else {
@@ -227,9 +241,122 @@ pub async fn execute_http_tests<BS: BootstrapStore>(
     )
     .await?;
 
+    if http_options.dns_checks {
+        // the domain of any of the runs is checked
+        let domain = http_results
+            .test_runs
+            .iter()
+            .filter_map(|r| r.response_data.as_ref())
+            .find_map(|r| match &r.rdap {
+                RdapResponse::Domain(domain) => Some(domain.clone()),
+                _ => None,
+            });
+        if let Some(domain) = domain {
+            let dns_resolver = http_options
+                .dns_resolver
+                .as_deref()
+                .unwrap_or(DEFAULT_DNS_RESOLVER);
+            let parent_server = http_options.dns_parent_server.as_deref();
+            match dns_consistency_checks(&domain, dns_resolver, parent_server).await {
+                Ok(checks) => http_results.service_checks.extend(checks),
+                Err(e) => {
+                    warn!("Unable to check the domain against DNS: {e}");
+                    http_results
+                        .service_checks
+                        .push(Check::DnsQueryFailed.check_item());
+                }
+            }
+        }
+    }
+
+    if http_options.negative_tests {
+        http_results.probe_results = execute_probes(&query_url, http_options).await;
+    }
+
     http_results.end(options);
     info!("Testing complete.");
-    Ok(TestResults::Http(http_results))
+    Ok(TestResults::Http(Box::new(http_results)))
+}
+
+/// Tests many targets concurrently, each being either a query value or,
+/// when a query is given, the base URL of a server to send the query to.
+///
+/// The results are in the order of the targets. A target that cannot be tested
+/// has the error in its results instead of stopping the other tests.
+pub async fn execute_multi_http_tests<BS: BootstrapStore>(
+    bs: &BS,
+    targets: &[String],
+    query: Option<&QueryType>,
+    options: &TestOptions,
+    concurrency: usize,
+) -> Result<Vec<TargetResults>, TestExecutionError> {
+    let TestType::Http(http_options) = &options.test_type else {
+        return Err(TestExecutionError::UnsupportedQueryType);
+    };
+    let results = stream::iter(targets)
+        .map(|target| async move {
+            let results = match target_query_type(target, query) {
+                Ok(value) => {
+                    let http_options = HttpTestOptions {
+                        value,
+                        ..*http_options.clone()
+                    };
+                    execute_http_tests(bs, &http_options, options).await
+                }
+                Err(e) => Err(e),
+            };
+            TargetResults::new(target.to_owned(), results)
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+    Ok(results)
+}
+
+/// Tests the queries of a test plan one after the other and evaluates the
+/// assertions of each test against the results of its query.
+///
+/// A query that cannot be tested fails its test instead of stopping the plan.
+pub async fn execute_test_plan<BS: BootstrapStore>(
+    bs: &BS,
+    plan: &TestPlan,
+    options: &TestOptions,
+) -> Result<PlanResults, TestExecutionError> {
+    let TestType::Http(http_options) = &options.test_type else {
+        return Err(TestExecutionError::UnsupportedQueryType);
+    };
+    let mut tests = vec![];
+    for test in &plan.tests {
+        info!("Testing plan query {}", test.query);
+        let results = match target_query_type(&test.query, None) {
+            Ok(value) => {
+                let http_options = HttpTestOptions {
+                    value,
+                    ..*http_options.clone()
+                };
+                execute_http_tests(bs, &http_options, options).await
+            }
+            Err(e) => Err(e),
+        };
+        tests.push(PlanTestResult::new(test, results));
+    }
+    Ok(PlanResults {
+        name: plan.name.clone(),
+        tests,
+    })
+}
+
+/// Gets the query type to test for a target of [execute_multi_http_tests].
+pub fn target_query_type(
+    target: &str,
+    query: Option<&QueryType>,
+) -> Result<QueryType, TestExecutionError> {
+    match query {
+        Some(query) if target.starts_with("https://") || target.starts_with("http://") => {
+            Ok(QueryType::Url(query.query_url(target)?))
+        }
+        _ => Ok(QueryType::from_str(target)?),
+    }
 }
 
 // Helper function for a family of addresses (v4 or v6)
@@ -342,11 +469,10 @@ async fn get_dns_records(
         });
     }
 
-    let def_dns_resolver = "8.8.8.8:53".to_string();
     let dns_resolver = http_options
         .dns_resolver
-        .as_ref()
-        .unwrap_or(&def_dns_resolver);
+        .as_deref()
+        .unwrap_or(DEFAULT_DNS_RESOLVER);
     let conn = UdpClientConnection::new(dns_resolver.parse()?)?.new_stream(None);
     let (mut client, bg) = AsyncClient::connect(conn).await?;
 
@@ -0,0 +1,332 @@
+//! JUnit XML rendering of test results.
+
+use std::io;
+
+use {
+    chrono::{DateTime, Utc},
+    icann_rdap_common::check::{CheckClass, CheckItem},
+    quick_xml::{
+        events::{BytesDecl, BytesText, Event},
+        Writer,
+    },
+};
+
+use super::{
+    probe::ProbeResult,
+    results::{socket_addr_string, RunOutcome, TestResults, TestRun},
+};
+
+/// A test suite, which is a test run, the service checks or the negative tests.
+struct Suite {
+    name: String,
+    timestamp: Option<DateTime<Utc>>,
+    end_time: Option<DateTime<Utc>>,
+    cases: Vec<Case>,
+}
+
+struct Case {
+    name: String,
+    classname: String,
+    result: CaseResult,
+}
+
+enum CaseResult {
+    Passed,
+    Skipped,
+    Failure { kind: String, message: String },
+    Error { kind: String, message: String },
+}
+
+impl TestResults {
+    /// Renders the results as JUnit XML.
+    ///
+    /// Each test run is a test suite with a test case for each of its checks.
+    /// Informational checks and specification notes pass, and all other checks
+    /// are failures. A test run that could not test the server is an error.
+    pub fn to_junit(&self) -> String {
+        let suites = match self {
+            Self::Http(http_results) => {
+                let mut suites = http_results
+                    .test_runs
+                    .iter()
+                    .map(run_suite)
+                    .collect::<Vec<_>>();
+                if !http_results.service_checks.is_empty() {
+                    suites.push(Suite {
+                        name: "service checks".to_string(),
+                        timestamp: Some(http_results.start_time),
+                        end_time: None,
+                        cases: http_results
+                            .service_checks
+                            .iter()
+                            .map(|item| check_case(item, "service"))
+                            .collect(),
+                    });
+                }
+                if !http_results.probe_results.is_empty() {
+                    suites.push(Suite {
+                        name: "negative tests".to_string(),
+                        timestamp: Some(http_results.start_time),
+                        end_time: None,
+                        cases: http_results
+                            .probe_results
+                            .iter()
+                            .flat_map(probe_cases)
+                            .collect(),
+                    });
+                }
+                suites
+            }
+            Self::String(string_result) => string_result.test_run.iter().map(run_suite).collect(),
+        };
+        let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+        write_suites(&mut writer, &suites).expect("writing XML to memory");
+        String::from_utf8(writer.into_inner()).expect("XML is UTF-8")
+    }
+}
+
+fn run_suite(test_run: &TestRun) -> Suite {
+    let cases = match test_run.outcome {
+        RunOutcome::Tested => {
+            let summaries = test_run.summaries.as_deref().unwrap_or_default();
+            if summaries.is_empty() {
+                vec![Case {
+                    name: "checks".to_string(),
+                    classname: "test_run".to_string(),
+                    result: CaseResult::Passed,
+                }]
+            } else {
+                summaries
+                    .iter()
+                    .map(|summary| check_case(&summary.item, &summary.structure))
+                    .collect()
+            }
+        }
+        RunOutcome::Skipped => vec![Case {
+            name: "outcome".to_string(),
+            classname: "test_run".to_string(),
+            result: CaseResult::Skipped,
+        }],
+        _ => vec![Case {
+            name: "outcome".to_string(),
+            classname: "test_run".to_string(),
+            result: CaseResult::Error {
+                kind: test_run.outcome.to_string(),
+                message: test_run.outcome.to_string(),
+            },
+        }],
+    };
+    Suite {
+        name: format!(
+            "{} - {}",
+            socket_addr_string(test_run.socket_addr),
+            test_run.attribute_set()
+        ),
+        timestamp: Some(test_run.start_time),
+        end_time: test_run.end_time,
+        cases,
+    }
+}
+
+fn probe_cases(probe_result: &ProbeResult) -> Vec<Case> {
+    let classname = probe_result.probe.to_string();
+    if let Some(execution_error) = &probe_result.execution_error {
+        vec![Case {
+            name: "outcome".to_string(),
+            classname,
+            result: CaseResult::Error {
+                kind: "execution_error".to_string(),
+                message: execution_error.clone(),
+            },
+        }]
+    } else if probe_result.checks.is_empty() {
+        vec![Case {
+            name: "checks".to_string(),
+            classname,
+            result: CaseResult::Passed,
+        }]
+    } else {
+        probe_result
+            .checks
+            .iter()
+            .map(|item| check_case(item, &classname))
+            .collect()
+    }
+}
+
+fn check_case(item: &CheckItem, classname: &str) -> Case {
+    let result = match item.check_class {
+        CheckClass::Informational | CheckClass::SpecificationNote => CaseResult::Passed,
+        _ => CaseResult::Failure {
+            kind: item.check_class.to_string(),
+            message: item.to_string(),
+        },
+    };
+    Case {
+        name: item.check.to_string(),
+        classname: classname.to_string(),
+        result,
+    }
+}
+
+/// The number of tests, failures, errors and skipped tests in the cases.
+fn counts<'a>(cases: impl Iterator<Item = &'a Case>) -> [(&'static str, String); 4] {
+    let (mut tests, mut failures, mut errors, mut skipped) = (0, 0, 0, 0);
+    for case in cases {
+        tests += 1;
+        match case.result {
+            CaseResult::Passed => {}
+            CaseResult::Skipped => skipped += 1,
+            CaseResult::Failure { .. } => failures += 1,
+            CaseResult::Error { .. } => errors += 1,
+        }
+    }
+    [
+        ("tests", tests.to_string()),
+        ("failures", failures.to_string()),
+        ("errors", errors.to_string()),
+        ("skipped", skipped.to_string()),
+    ]
+}
+
+fn write_suites(writer: &mut Writer<Vec<u8>>, suites: &[Suite]) -> io::Result<()> {
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    let totals = counts(suites.iter().flat_map(|s| &s.cases));
+    writer
+        .create_element("testsuites")
+        .with_attribute(("name", "rdap-test"))
+        .with_attributes(totals.iter().map(|(k, v)| (*k, v.as_str())))
+        .write_inner_content(|writer| {
+            for suite in suites {
+                write_suite(writer, suite)?;
+            }
+            Ok(())
+        })?;
+    Ok(())
+}
+
+fn write_suite(writer: &mut Writer<Vec<u8>>, suite: &Suite) -> io::Result<()> {
+    let mut attributes = vec![("name", suite.name.clone())];
+    attributes.extend(counts(suite.cases.iter()));
+    if let Some(timestamp) = suite.timestamp {
+        attributes.push((
+            "timestamp",
+            timestamp.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        ));
+        if let Some(end_time) = suite.end_time {
+            let millis = (end_time - timestamp).num_milliseconds();
+            attributes.push(("time", format!("{:.3}", millis as f64 / 1000.0)));
+        }
+    }
+    writer
+        .create_element("testsuite")
+        .with_attributes(attributes.iter().map(|(k, v)| (*k, v.as_str())))
+        .write_inner_content(|writer| {
+            for case in &suite.cases {
+                write_case(writer, case)?;
+            }
+            Ok(())
+        })?;
+    Ok(())
+}
+
+fn write_case(writer: &mut Writer<Vec<u8>>, case: &Case) -> io::Result<()> {
+    let element = writer.create_element("testcase").with_attributes([
+        ("name", case.name.as_str()),
+        ("classname", case.classname.as_str()),
+    ]);
+    match &case.result {
+        CaseResult::Passed => {
+            element.write_empty()?;
+        }
+        CaseResult::Skipped => {
+            element.write_inner_content(|writer| {
+                writer.create_element("skipped").write_empty()?;
+                Ok(())
+            })?;
+        }
+        CaseResult::Failure { kind, message } | CaseResult::Error { kind, message } => {
+            let name = if matches!(case.result, CaseResult::Failure { .. }) {
+                "failure"
+            } else {
+                "error"
+            };
+            element.write_inner_content(|writer| {
+                writer
+                    .create_element(name)
+                    .with_attributes([("type", kind.as_str()), ("message", message.as_str())])
+                    .write_text_content(BytesText::new(message))?;
+                Ok(())
+            })?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::check::{Check, CheckClass, CheckItem, CheckSummary};
+
+    use crate::rt::results::{RunOutcome, StringResult, TestResults, TestRun};
+
+    fn string_results(outcome: RunOutcome, items: Vec<CheckItem>) -> TestResults {
+        let mut test_run = TestRun::new(vec![]);
+        test_run.outcome = outcome;
+        test_run.summaries = Some(
+            items
+                .into_iter()
+                .map(|item| CheckSummary {
+                    code: item.check as usize,
+                    structure: "Domain".to_string(),
+                    message: item.to_string(),
+                    item,
+                })
+                .collect(),
+        );
+        TestResults::String(Box::new(StringResult::new(test_run)))
+    }
+
+    #[test]
+    fn GIVEN_error_and_info_checks_WHEN_to_junit_THEN_error_is_failure() {
+        // GIVEN
+        let results = string_results(
+            RunOutcome::Tested,
+            vec![
+                CheckItem {
+                    check_class: CheckClass::Std95Error,
+                    check: Check::LdhNameInvalid,
+                },
+                CheckItem {
+                    check_class: CheckClass::Informational,
+                    check: Check::VariantEmptyDomain,
+                },
+            ],
+        );
+
+        // WHEN
+        let xml = results.to_junit();
+
+        // THEN
+        assert!(xml.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+        assert!(xml.contains(
+            r#"<testsuites name="rdap-test" tests="2" failures="1" errors="0" skipped="0">"#
+        ));
+        assert!(xml.contains(r#"<testcase name="ldh_name_invalid" classname="Domain">"#));
+        assert!(xml.contains(r#"<failure type="Std95Err""#));
+        assert!(xml.contains(r#"<testcase name="variant_empty_domain" classname="Domain"/>"#));
+    }
+
+    #[test]
+    fn GIVEN_untested_run_WHEN_to_junit_THEN_error() {
+        // GIVEN
+        let results = string_results(RunOutcome::JsonError, vec![]);
+
+        // WHEN
+        let xml = results.to_junit();
+
+        // THEN
+        assert!(xml.contains(r#"tests="1" failures="0" errors="1""#));
+        assert!(xml.contains(r#"<error type="JSON_ERROR" message="JSON_ERROR">"#));
+    }
+}
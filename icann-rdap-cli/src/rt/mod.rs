@@ -1,2 +1,8 @@
+pub mod dns;
 pub mod exec;
+pub mod junit;
+pub mod plan;
+pub mod probe;
 pub mod results;
+pub mod sarif;
+pub mod summary;
@@ -0,0 +1,477 @@
+//! Test plans, which list queries and what is expected of their responses.
+
+use std::fmt;
+
+use {
+    icann_rdap_client::{
+        md::{string::StringUtil, table::MultiPartTable, MdOptions},
+        rdap::ResponseData,
+    },
+    icann_rdap_common::{check::Check, response::RdapResponse},
+    serde::{Deserialize, Serialize},
+};
+
+use super::{
+    exec::TestExecutionError,
+    results::{HttpResults, RunOutcome, TestResults},
+};
+
+/// A test plan, as read from a YAML or JSON file.
+///
+/// ```yaml
+/// name: example.com regression suite
+/// tests:
+///   - query: example.com
+///     status: 200
+///     extensions: [rdap_level_0, redacted]
+///     forbidden_checks: [content_type_is_not_rdap]
+///     link_relations: [self]
+///     redactions: [Registry Domain ID]
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TestPlan {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub tests: Vec<PlanTest>,
+}
+
+/// A test of a test plan.
+///
+/// The query is a query value or the URL of a query. Each of the other fields
+/// is an assertion about the responses to the query.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PlanTest {
+    pub query: String,
+    #[serde(default)]
+    pub status: Option<u16>,
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    #[serde(default)]
+    pub required_checks: Vec<Check>,
+    #[serde(default)]
+    pub forbidden_checks: Vec<Check>,
+    #[serde(default)]
+    pub link_relations: Vec<String>,
+    #[serde(default)]
+    pub redactions: Vec<String>,
+}
+
+impl TestPlan {
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl PlanTest {
+    fn assertions(&self) -> Vec<Assertion> {
+        let mut assertions = vec![];
+        assertions.extend(self.status.map(Assertion::Status));
+        assertions.extend(self.extensions.iter().cloned().map(Assertion::Extension));
+        assertions.extend(
+            self.required_checks
+                .iter()
+                .map(|c| Assertion::RequiredCheck(*c)),
+        );
+        assertions.extend(
+            self.forbidden_checks
+                .iter()
+                .map(|c| Assertion::ForbiddenCheck(*c)),
+        );
+        assertions.extend(
+            self.link_relations
+                .iter()
+                .cloned()
+                .map(Assertion::LinkRelation),
+        );
+        assertions.extend(self.redactions.iter().cloned().map(Assertion::Redaction));
+        assertions
+    }
+}
+
+/// An assertion about the responses to the query of a test.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Assertion {
+    /// Every response has the HTTP status code.
+    Status(u16),
+    /// Every response has the extension in its `rdapConformance`.
+    Extension(String),
+    /// The check is found by the tests.
+    RequiredCheck(Check),
+    /// The check is not found by the tests.
+    ForbiddenCheck(Check),
+    /// Every response has a link with the relation.
+    LinkRelation(String),
+    /// Every response has a redaction with the name, which is either the
+    /// registered type or the description of the redaction.
+    Redaction(String),
+}
+
+impl fmt::Display for Assertion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Status(status) => write!(f, "HTTP status {status}"),
+            Self::Extension(extension) => write!(f, "extension {extension}"),
+            Self::RequiredCheck(check) => write!(f, "required check {check}"),
+            Self::ForbiddenCheck(check) => write!(f, "forbidden check {check}"),
+            Self::LinkRelation(rel) => write!(f, "link relation {rel}"),
+            Self::Redaction(name) => write!(f, "redaction {name}"),
+        }
+    }
+}
+
+impl Assertion {
+    /// Evaluates the assertion against the results of testing the query.
+    ///
+    /// Assertions about the responses fail when there are no responses.
+    fn passes(&self, http_results: &HttpResults) -> bool {
+        let responses = http_results
+            .test_runs
+            .iter()
+            .filter(|r| matches!(r.outcome, RunOutcome::Tested))
+            .filter_map(|r| r.response_data.as_ref())
+            .collect::<Vec<&ResponseData>>();
+        let every_response = |f: &dyn Fn(&ResponseData) -> bool| {
+            !responses.is_empty() && responses.iter().all(|r| f(r))
+        };
+        match self {
+            Self::Status(status) => every_response(&|r| r.http_data.status_code() == *status),
+            Self::Extension(extension) => every_response(&|r| r.rdap.has_extension(extension)),
+            Self::RequiredCheck(check) => found_checks(http_results).contains(check),
+            Self::ForbiddenCheck(check) => !found_checks(http_results).contains(check),
+            Self::LinkRelation(rel) => every_response(&|r| {
+                r.rdap
+                    .get_links()
+                    .is_some_and(|links| links.iter().any(|l| l.rel() == Some(rel.as_str())))
+            }),
+            Self::Redaction(name) => every_response(&|r| {
+                redaction_names(&r.rdap)
+                    .iter()
+                    .any(|n| n.eq_ignore_ascii_case(name))
+            }),
+        }
+    }
+}
+
+/// Gets the checks found in the test runs, the service checks and the probes.
+fn found_checks(http_results: &HttpResults) -> Vec<Check> {
+    let run_checks = http_results
+        .test_runs
+        .iter()
+        .flat_map(|r| r.summaries.as_deref().unwrap_or_default())
+        .map(|s| &s.item);
+    let probe_checks = http_results.probe_results.iter().flat_map(|p| &p.checks);
+    run_checks
+        .chain(&http_results.service_checks)
+        .chain(probe_checks)
+        .map(|i| i.check)
+        .collect()
+}
+
+/// Gets the registered types and descriptions of the redactions of a response.
+fn redaction_names(rdap: &RdapResponse) -> Vec<&str> {
+    let redacted = match rdap {
+        RdapResponse::Entity(e) => e.object_common.redacted.as_deref(),
+        RdapResponse::Domain(d) => d.object_common.redacted.as_deref(),
+        RdapResponse::Nameserver(n) => n.object_common.redacted.as_deref(),
+        RdapResponse::Autnum(a) => a.object_common.redacted.as_deref(),
+        RdapResponse::Network(n) => n.object_common.redacted.as_deref(),
+        _ => None,
+    };
+    redacted
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|r| [r.name().type_field(), r.name().description()])
+        .flatten()
+        .collect()
+}
+
+/// The result of an assertion.
+#[derive(Debug, Serialize, Clone)]
+pub struct AssertionResult {
+    pub assertion: Assertion,
+    pub passed: bool,
+}
+
+/// The results of a test of a test plan.
+///
+/// When the query cannot be tested, every assertion fails and the error is given.
+#[derive(Debug, Serialize, Clone)]
+pub struct PlanTestResult {
+    pub query: String,
+    pub assertions: Vec<AssertionResult>,
+    pub execution_error: Option<String>,
+}
+
+impl PlanTestResult {
+    pub fn new(test: &PlanTest, results: Result<TestResults, TestExecutionError>) -> Self {
+        let (http_results, execution_error) = match results {
+            Ok(TestResults::Http(http_results)) => (Some(http_results), None),
+            Ok(TestResults::String(_)) => (None, None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        let assertions = test
+            .assertions()
+            .into_iter()
+            .map(|assertion| AssertionResult {
+                passed: http_results.as_ref().is_some_and(|r| assertion.passes(r)),
+                assertion,
+            })
+            .collect();
+        Self {
+            query: test.query.clone(),
+            assertions,
+            execution_error,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.execution_error.is_none() && self.assertions.iter().all(|a| a.passed)
+    }
+}
+
+/// The results of a test plan.
+#[derive(Debug, Serialize, Clone)]
+pub struct PlanResults {
+    pub name: Option<String>,
+    pub tests: Vec<PlanTestResult>,
+}
+
+impl PlanResults {
+    pub fn passed(&self) -> bool {
+        self.tests.iter().all(|t| t.passed())
+    }
+
+    pub fn execution_errors(&self) -> bool {
+        self.tests.iter().any(|t| t.execution_error.is_some())
+    }
+
+    pub fn to_md(&self, options: &MdOptions) -> String {
+        let mut md = String::new();
+
+        let name = self.name.as_deref().unwrap_or("Test Plan");
+        md.push_str(&format!("\n{}\n", name.to_string().to_header(1, options)));
+
+        let mut table = MultiPartTable::new();
+        table = table.multi_raw(vec![
+            "Query".to_inline(options),
+            "Assertion".to_inline(options),
+            "Result".to_inline(options),
+        ]);
+        for test in &self.tests {
+            if let Some(execution_error) = &test.execution_error {
+                table = table.multi_raw(vec![
+                    test.query.clone(),
+                    "execution".to_string(),
+                    execution_error.to_em(options),
+                ]);
+            }
+            for result in &test.assertions {
+                table = table.multi_raw(vec![
+                    test.query.clone(),
+                    result.assertion.to_string(),
+                    if result.passed {
+                        "PASS".to_bold(options)
+                    } else {
+                        "FAIL".to_em(options)
+                    },
+                ]);
+            }
+        }
+        md.push_str(&table.to_md_table(options));
+        md.push('\n');
+
+        let passed = self.tests.iter().filter(|t| t.passed()).count();
+        md.push_str(&format!("{passed} of {} tests passed.\n", self.tests.len()));
+        md
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use {
+        icann_rdap_client::rdap::ResponseData,
+        icann_rdap_common::{
+            check::{Check, CheckClass, CheckItem},
+            httpdata::HttpData,
+            prelude::ToResponse,
+            response::{
+                redacted::{Name, Redacted},
+                Domain, Extension, Link,
+            },
+        },
+    };
+
+    use crate::rt::results::{DnsData, HttpResults, RunOutcome, TestResults, TestRun};
+
+    use super::{Assertion, PlanTest, PlanTestResult, TestPlan};
+
+    fn http_results(status_code: u16) -> HttpResults {
+        let domain = Domain::response_obj()
+            .ldh_name("foo.example")
+            .extension(Extension::from("redacted"))
+            .link(
+                Link::builder()
+                    .rel("self")
+                    .href("https://rdap.example/domain/foo.example")
+                    .value("https://rdap.example/domain/foo.example")
+                    .build(),
+            )
+            .redacted(vec![Redacted {
+                name: Name {
+                    description: None,
+                    type_field: Some("Registry Domain ID".to_string()),
+                },
+                reason: None,
+                pre_path: None,
+                post_path: None,
+                path_lang: None,
+                replacement_path: None,
+                method: None,
+            }])
+            .build();
+        let mut test_run = TestRun::new(vec![]);
+        test_run.outcome = RunOutcome::Tested;
+        test_run.response_data = Some(ResponseData {
+            rdap: domain.to_response(),
+            rdap_type: "Domain".to_string(),
+            http_data: HttpData::example().status_code(status_code).build(),
+        });
+        let mut http_results = HttpResults::new(
+            "https://rdap.example/domain/foo.example".to_string(),
+            DnsData::default(),
+        );
+        http_results.add_test_run(test_run);
+        http_results.service_checks.push(CheckItem {
+            check_class: CheckClass::DnsConsistencyError,
+            check: Check::DnsIpAddressesMismatch,
+        });
+        http_results
+    }
+
+    #[test]
+    fn GIVEN_yaml_plan_WHEN_from_yaml_THEN_tests_parsed() {
+        // GIVEN
+        let yaml = r#"
+name: regression
+tests:
+  - query: foo.example
+    status: 200
+    extensions: [redacted]
+    required_checks: [dns_ip_addresses_mismatch]
+  - query: 192.0.2.1
+"#;
+
+        // WHEN
+        let plan = TestPlan::from_yaml(yaml).expect("parsing test plan");
+
+        // THEN
+        assert_eq!(plan.name.as_deref(), Some("regression"));
+        assert_eq!(plan.tests.len(), 2);
+        assert_eq!(plan.tests[0].status, Some(200));
+        assert_eq!(
+            plan.tests[0].required_checks,
+            vec![Check::DnsIpAddressesMismatch]
+        );
+        assert!(plan.tests[1].extensions.is_empty());
+    }
+
+    #[test]
+    fn GIVEN_misspelled_field_WHEN_from_json_THEN_error() {
+        // GIVEN
+        let json = r#"{"tests": [{"query": "foo.example", "stauts": 200}]}"#;
+
+        // WHEN
+        let plan = TestPlan::from_json(json);
+
+        // THEN
+        assert!(plan.is_err());
+    }
+
+    #[test]
+    fn GIVEN_matching_responses_WHEN_new_result_THEN_assertions_pass() {
+        // GIVEN
+        let test = PlanTest {
+            query: "foo.example".to_string(),
+            status: Some(200),
+            extensions: vec!["redacted".to_string()],
+            required_checks: vec![Check::DnsIpAddressesMismatch],
+            forbidden_checks: vec![Check::DnsNameserversMismatch],
+            link_relations: vec!["self".to_string()],
+            redactions: vec!["registry domain id".to_string()],
+        };
+
+        // WHEN
+        let result = PlanTestResult::new(&test, Ok(TestResults::Http(Box::new(http_results(200)))));
+
+        // THEN
+        assert_eq!(result.assertions.len(), 6);
+        assert!(result.passed());
+    }
+
+    #[test]
+    fn GIVEN_unmet_expectations_WHEN_new_result_THEN_those_assertions_fail() {
+        // GIVEN
+        let test = PlanTest {
+            query: "foo.example".to_string(),
+            status: Some(200),
+            extensions: vec!["cidr0".to_string()],
+            required_checks: vec![],
+            forbidden_checks: vec![Check::DnsIpAddressesMismatch],
+            link_relations: vec!["related".to_string()],
+            redactions: vec![],
+        };
+
+        // WHEN
+        let result = PlanTestResult::new(&test, Ok(TestResults::Http(Box::new(http_results(200)))));
+
+        // THEN
+        let failed = result
+            .assertions
+            .iter()
+            .filter(|a| !a.passed)
+            .map(|a| a.assertion.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            failed,
+            vec![
+                Assertion::Extension("cidr0".to_string()),
+                Assertion::ForbiddenCheck(Check::DnsIpAddressesMismatch),
+                Assertion::LinkRelation("related".to_string()),
+            ]
+        );
+        assert!(!result.passed());
+    }
+
+    #[test]
+    fn GIVEN_execution_error_WHEN_new_result_THEN_not_passed() {
+        // GIVEN
+        let test = PlanTest {
+            query: "foo.example".to_string(),
+            status: None,
+            extensions: vec![],
+            required_checks: vec![],
+            forbidden_checks: vec![Check::DnsIpAddressesMismatch],
+            link_relations: vec![],
+            redactions: vec![],
+        };
+
+        // WHEN
+        let result = PlanTestResult::new(
+            &test,
+            Err(crate::rt::exec::TestExecutionError::NoHostToResolve),
+        );
+
+        // THEN
+        assert!(!result.assertions[0].passed);
+        assert!(result.execution_error.is_some());
+        assert!(!result.passed());
+    }
+}
@@ -0,0 +1,446 @@
+//! Probes of how a server answers erroneous and unusual requests.
+
+use {
+    icann_rdap_client::http::{create_client, probe_request, Client, ClientConfig, ProbeResponse},
+    icann_rdap_common::{
+        check::{Check, CheckItem},
+        media_types::{JSON_MEDIA_TYPE, RDAP_MEDIA_TYPE},
+        response::RdapResponse,
+    },
+    reqwest::{header::HeaderValue, Method, Url},
+    serde::Serialize,
+    strum_macros::Display,
+    tracing::info,
+};
+
+use super::exec::{HttpTestOptions, TestExecutionError};
+
+/// The path segments starting a query, which follow the base URL of a server.
+const QUERY_SEGMENTS: [&str; 11] = [
+    "domain",
+    "ip",
+    "autnum",
+    "entity",
+    "nameserver",
+    "help",
+    "domains",
+    "nameservers",
+    "entities",
+    "ips",
+    "autnums",
+];
+
+/// The handle of an entity that is not expected to exist.
+const NON_EXISTENT_HANDLE: &str = "ICANN-RDAP-TEST-NONEXISTENT";
+
+const PROBES: [Probe; 8] = [
+    Probe::NotFound,
+    Probe::MalformedIp,
+    Probe::MalformedAutnum,
+    Probe::InvalidIdn,
+    Probe::UnsupportedSearch,
+    Probe::Head,
+    Probe::TrailingSlash,
+    Probe::JsonMediaType,
+];
+
+#[derive(Debug, Serialize, Display, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum Probe {
+    NotFound,
+    MalformedIp,
+    MalformedAutnum,
+    InvalidIdn,
+    UnsupportedSearch,
+    Head,
+    TrailingSlash,
+    JsonMediaType,
+}
+
+impl Probe {
+    /// Gets the method and URL of the probe, if they can be derived from the query URL.
+    fn request(&self, query_url: &str) -> Option<(Method, String)> {
+        let url = match self {
+            Self::NotFound => format!("{}/entity/{NON_EXISTENT_HANDLE}", base_url(query_url)?),
+            Self::MalformedIp => format!("{}/ip/999.999.999.999", base_url(query_url)?),
+            Self::MalformedAutnum => format!("{}/autnum/not-an-autnum", base_url(query_url)?),
+            Self::InvalidIdn => format!("{}/domain/xn--invalid-.example", base_url(query_url)?),
+            Self::UnsupportedSearch => format!(
+                "{}/domains?unsupportedSearchProperty=x",
+                base_url(query_url)?
+            ),
+            Self::Head => return Some((Method::HEAD, query_url.to_string())),
+            Self::TrailingSlash => trailing_slash_url(query_url)?,
+            Self::JsonMediaType => query_url.to_string(),
+        };
+        Some((Method::GET, url))
+    }
+
+    /// True if the probe is expected to get an error response.
+    fn is_error(&self) -> bool {
+        !matches!(self, Self::Head | Self::TrailingSlash | Self::JsonMediaType)
+    }
+}
+
+/// The result of a probe.
+#[derive(Debug, Serialize, Clone)]
+pub struct ProbeResult {
+    pub probe: Probe,
+    pub method: String,
+    pub url: String,
+    pub status_code: Option<u16>,
+    pub checks: Vec<CheckItem>,
+    pub execution_error: Option<String>,
+}
+
+/// Sends the probes derived from the query URL and checks how the server answers them.
+///
+/// The answers to the HEAD, trailing slash, and JSON media type probes are compared
+/// with the answer to the query itself. If the probes cannot be sent, such as when
+/// the query itself cannot be sent, each probe has the error.
+pub async fn execute_probes(query_url: &str, http_options: &HttpTestOptions) -> Vec<ProbeResult> {
+    let setup = probe_setup(query_url, http_options).await;
+    let mut results = vec![];
+    for probe in PROBES {
+        let Some((method, url)) = probe.request(query_url) else {
+            continue;
+        };
+        let response = match &setup {
+            Ok((client, query, has_origin)) => {
+                info!("Sending {probe} probe to {url}");
+                let accept = matches!(probe, Probe::JsonMediaType).then_some(JSON_MEDIA_TYPE);
+                probe_request(&url, method.clone(), accept, client)
+                    .await
+                    .map(|response| (probe_checks(probe, &response, query, *has_origin), response))
+                    .map_err(|e| e.to_string())
+            }
+            Err(e) => Err(e.to_string()),
+        };
+        let result = match response {
+            Ok((checks, response)) => ProbeResult {
+                probe,
+                method: method.to_string(),
+                url,
+                status_code: Some(response.http_data.status_code()),
+                checks,
+                execution_error: None,
+            },
+            Err(e) => ProbeResult {
+                probe,
+                method: method.to_string(),
+                url,
+                status_code: None,
+                checks: vec![],
+                execution_error: Some(e),
+            },
+        };
+        results.push(result);
+    }
+    results
+}
+
+/// Creates the client for the probes and sends the query they are compared with,
+/// giving the client, the answer to the query, and whether an origin is sent.
+async fn probe_setup(
+    query_url: &str,
+    http_options: &HttpTestOptions,
+) -> Result<(Client, ProbeResponse, bool), TestExecutionError> {
+    let origin = if http_options.skip_origin {
+        None
+    } else {
+        Some(HeaderValue::from_str(&http_options.origin_value)?)
+    };
+    let has_origin = origin.is_some();
+    let client_config = ClientConfig::from_config(&http_options.client_config)
+        .follow_redirects(false)
+        .and_origin(origin)
+        .build();
+    let client = create_client(&client_config)?;
+    let query = probe_request(query_url, Method::GET, None, &client).await?;
+    Ok((client, query, has_origin))
+}
+
+/// Checks the response to a probe, given the response to the query.
+fn probe_checks(
+    probe: Probe,
+    response: &ProbeResponse,
+    query: &ProbeResponse,
+    origin_sent: bool,
+) -> Vec<CheckItem> {
+    let mut items = vec![];
+    let status = response.http_data.status_code();
+    let query_status = query.http_data.status_code();
+    match probe {
+        Probe::NotFound => {
+            if status != 404 {
+                items.push(Check::NotFoundStatusIsNot404.check_item());
+            }
+        }
+        Probe::MalformedIp | Probe::MalformedAutnum | Probe::InvalidIdn => {
+            if status != 400 {
+                items.push(Check::MalformedQueryStatusIsNot400.check_item());
+            }
+        }
+        Probe::UnsupportedSearch => {
+            if status != 501 {
+                items.push(Check::UnsupportedQueryStatusIsNot501.check_item());
+            }
+        }
+        Probe::Head => {
+            if status != query_status {
+                items.push(Check::HeadNotAnsweredLikeGet.check_item());
+            }
+        }
+        Probe::TrailingSlash => {
+            // a redirect to the query is as good as answering it
+            if status != query_status && !(300..400).contains(&status) {
+                items.push(Check::TrailingSlashNotAnsweredLikeQuery.check_item());
+            }
+        }
+        Probe::JsonMediaType => {
+            let is_json = response
+                .http_data
+                .content_type()
+                .is_some_and(|c| c.starts_with(JSON_MEDIA_TYPE) || c.starts_with(RDAP_MEDIA_TYPE));
+            if status != query_status || !is_json {
+                items.push(Check::JsonMediaTypeNotAccepted.check_item());
+            }
+        }
+    }
+    if probe.is_error() && status >= 400 {
+        let is_rfc9083 = serde_json::from_str::<RdapResponse>(&response.text)
+            .is_ok_and(|rdap| matches!(rdap, RdapResponse::ErrorResponse(_)));
+        if !is_rfc9083 {
+            items.push(Check::ErrorResponseIsNotRfc9083.check_item());
+        }
+        if origin_sent && response.http_data.access_control_allow_origin().is_none() {
+            items.push(Check::CorsAllowOriginOnErrorRecommended.check_item());
+        }
+    }
+    items
+}
+
+/// Gets the base URL of a server from a query URL, which is the URL
+/// before the path segment starting the query.
+fn base_url(query_url: &str) -> Option<String> {
+    let mut url = Url::parse(query_url).ok()?;
+    let segments = url.path_segments()?.map(str::to_string).collect::<Vec<_>>();
+    let index = segments
+        .iter()
+        .position(|s| QUERY_SEGMENTS.contains(&s.as_str()))?;
+    url.set_path(&segments[..index].join("/"));
+    url.set_query(None);
+    url.set_fragment(None);
+    Some(url.as_str().trim_end_matches('/').to_string())
+}
+
+fn trailing_slash_url(query_url: &str) -> Option<String> {
+    let mut url = Url::parse(query_url).ok()?;
+    if url.path().ends_with('/') {
+        return None;
+    }
+    let path = format!("{}/", url.path());
+    url.set_path(&path);
+    Some(url.to_string())
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use {
+        icann_rdap_client::http::ProbeResponse,
+        icann_rdap_common::{
+            check::Check,
+            httpdata::HttpData,
+            media_types::{JSON_MEDIA_TYPE, RDAP_MEDIA_TYPE},
+        },
+        rstest::rstest,
+    };
+
+    use super::{base_url, probe_checks, trailing_slash_url, Probe};
+
+    fn response(status_code: u16, content_type: &str, cors: bool, text: &str) -> ProbeResponse {
+        let http_data = HttpData::example()
+            .status_code(status_code)
+            .content_type(content_type)
+            .and_access_control_allow_origin(cors.then(|| "*".to_string()))
+            .build();
+        ProbeResponse {
+            http_data,
+            text: text.to_string(),
+        }
+    }
+
+    fn query() -> ProbeResponse {
+        response(200, RDAP_MEDIA_TYPE, true, "{}")
+    }
+
+    const ERROR_BODY: &str = r#"{"errorCode": 404, "rdapConformance": ["rdap_level_0"]}"#;
+
+    #[rstest]
+    #[case(
+        "https://rdap.example.com/rdap/domain/foo.example",
+        "https://rdap.example.com/rdap"
+    )]
+    #[case("https://rdap.example.com/ip/192.0.2.0/24", "https://rdap.example.com")]
+    #[case("https://rdap.example.com/help", "https://rdap.example.com")]
+    #[case(
+        "https://rdap.example.com/rdap/domains?name=foo*",
+        "https://rdap.example.com/rdap"
+    )]
+    fn GIVEN_query_url_WHEN_base_url_THEN_base_url_returned(
+        #[case] query_url: &str,
+        #[case] expected: &str,
+    ) {
+        // GIVEN query_url
+
+        // WHEN
+        let actual = base_url(query_url);
+
+        // THEN
+        assert_eq!(actual.as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn GIVEN_url_without_query_segment_WHEN_base_url_THEN_none() {
+        // GIVEN
+        let query_url = "https://rdap.example.com/rdap/foo";
+
+        // WHEN
+        let actual = base_url(query_url);
+
+        // THEN
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn GIVEN_search_url_WHEN_trailing_slash_url_THEN_slash_before_query() {
+        // GIVEN
+        let query_url = "https://rdap.example.com/domains?name=foo*";
+
+        // WHEN
+        let actual = trailing_slash_url(query_url);
+
+        // THEN
+        assert_eq!(
+            actual.as_deref(),
+            Some("https://rdap.example.com/domains/?name=foo*")
+        );
+    }
+
+    #[test]
+    fn GIVEN_404_with_error_body_and_cors_WHEN_not_found_checks_THEN_no_checks() {
+        // GIVEN
+        let response = response(404, RDAP_MEDIA_TYPE, true, ERROR_BODY);
+
+        // WHEN
+        let checks = probe_checks(Probe::NotFound, &response, &query(), true);
+
+        // THEN
+        assert!(checks.is_empty());
+    }
+
+    #[test]
+    fn GIVEN_200_WHEN_not_found_checks_THEN_not_404() {
+        // GIVEN
+        let response = response(200, RDAP_MEDIA_TYPE, true, "{}");
+
+        // WHEN
+        let checks = probe_checks(Probe::NotFound, &response, &query(), true);
+
+        // THEN
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].check, Check::NotFoundStatusIsNot404);
+    }
+
+    #[test]
+    fn GIVEN_400_with_text_body_and_no_cors_WHEN_malformed_checks_THEN_body_and_cors_checks() {
+        // GIVEN
+        let response = response(400, "text/plain", false, "bad request");
+
+        // WHEN
+        let checks = probe_checks(Probe::MalformedAutnum, &response, &query(), true);
+
+        // THEN
+        let checks = checks.iter().map(|c| c.check).collect::<Vec<_>>();
+        assert_eq!(
+            checks,
+            vec![
+                Check::ErrorResponseIsNotRfc9083,
+                Check::CorsAllowOriginOnErrorRecommended
+            ]
+        );
+    }
+
+    #[test]
+    fn GIVEN_404_WHEN_unsupported_search_checks_THEN_not_501() {
+        // GIVEN
+        let response = response(404, RDAP_MEDIA_TYPE, true, ERROR_BODY);
+
+        // WHEN
+        let checks = probe_checks(Probe::UnsupportedSearch, &response, &query(), true);
+
+        // THEN
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].check, Check::UnsupportedQueryStatusIsNot501);
+    }
+
+    #[test]
+    fn GIVEN_head_answered_with_405_WHEN_head_checks_THEN_not_like_get() {
+        // GIVEN
+        let response = response(405, RDAP_MEDIA_TYPE, true, "");
+
+        // WHEN
+        let checks = probe_checks(Probe::Head, &response, &query(), true);
+
+        // THEN
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].check, Check::HeadNotAnsweredLikeGet);
+    }
+
+    #[rstest]
+    #[case(200, false)]
+    #[case(301, false)]
+    #[case(404, true)]
+    fn GIVEN_trailing_slash_status_WHEN_trailing_slash_checks_THEN_flagged_unless_answered(
+        #[case] status_code: u16,
+        #[case] expected: bool,
+    ) {
+        // GIVEN
+        let response = response(status_code, RDAP_MEDIA_TYPE, true, "{}");
+
+        // WHEN
+        let checks = probe_checks(Probe::TrailingSlash, &response, &query(), true);
+
+        // THEN
+        assert_eq!(
+            checks
+                .iter()
+                .any(|c| c.check == Check::TrailingSlashNotAnsweredLikeQuery),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(JSON_MEDIA_TYPE, false)]
+    #[case(RDAP_MEDIA_TYPE, false)]
+    #[case("text/html", true)]
+    fn GIVEN_content_type_WHEN_json_media_type_checks_THEN_flagged_unless_json(
+        #[case] content_type: &str,
+        #[case] expected: bool,
+    ) {
+        // GIVEN
+        let response = response(200, content_type, true, "{}");
+
+        // WHEN
+        let checks = probe_checks(Probe::JsonMediaType, &response, &query(), true);
+
+        // THEN
+        assert_eq!(
+            checks
+                .iter()
+                .any(|c| c.check == Check::JsonMediaTypeNotAccepted),
+            expected
+        );
+    }
+}
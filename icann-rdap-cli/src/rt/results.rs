@@ -21,11 +21,14 @@ use {
     strum_macros::Display,
 };
 
-use super::exec::TestOptions;
+use super::{
+    exec::{TestExecutionError, TestOptions},
+    probe::ProbeResult,
+};
 
 #[derive(Debug, Serialize, Clone)]
 pub enum TestResults {
-    Http(HttpResults),
+    Http(Box<HttpResults>),
     String(Box<StringResult>),
 }
 
@@ -54,7 +57,7 @@ impl TestResults {
     pub fn filter_test_results(&self, classes: Vec<CheckClass>) -> TestResults {
         match self {
             TestResults::Http(http_results) => {
-                TestResults::Http(http_results.clone().filter_test_results(&classes))
+                TestResults::Http(Box::new(http_results.clone().filter_test_results(&classes)))
             }
             TestResults::String(string_result) => TestResults::String(Box::new(
                 string_result.clone().filter_test_results(&classes),
@@ -63,6 +66,52 @@ impl TestResults {
     }
 }
 
+/// The results of testing one of many targets.
+#[derive(Debug, Serialize, Clone)]
+pub struct TargetResults {
+    pub target: String,
+    pub test_results: Option<TestResults>,
+    pub execution_error: Option<String>,
+}
+
+impl TargetResults {
+    pub fn new(target: String, results: Result<TestResults, TestExecutionError>) -> Self {
+        match results {
+            Ok(test_results) => Self {
+                target,
+                test_results: Some(test_results),
+                execution_error: None,
+            },
+            Err(e) => Self {
+                target,
+                test_results: None,
+                execution_error: Some(e.to_string()),
+            },
+        }
+    }
+
+    pub fn execution_errors(&self) -> bool {
+        self.execution_error.is_some()
+            || self
+                .test_results
+                .as_ref()
+                .is_some_and(|r| r.execution_errors())
+    }
+
+    pub fn are_there_checks(&self, classes: Vec<CheckClass>) -> bool {
+        self.test_results
+            .as_ref()
+            .is_some_and(|r| r.are_there_checks(classes))
+    }
+
+    pub fn filter_test_results(self, classes: Vec<CheckClass>) -> Self {
+        Self {
+            test_results: self.test_results.map(|r| r.filter_test_results(classes)),
+            ..self
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct HttpResults {
     pub query_url: String,
@@ -71,6 +120,7 @@ pub struct HttpResults {
     pub dns_data: DnsData,
     pub service_checks: Vec<CheckItem>,
     pub test_runs: Vec<TestRun>,
+    pub probe_results: Vec<ProbeResult>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -85,6 +135,7 @@ impl HttpResults {
             dns_data,
             service_checks: vec![],
             test_runs: vec![],
+            probe_results: vec![],
             start_time: Utc::now(),
             end_time: None,
         }
@@ -211,6 +262,45 @@ impl HttpResults {
             md.push('\n');
         }
 
+        // how the server answers erroneous and unusual requests
+        if !self.probe_results.is_empty() {
+            md.push_str(&"Negative Tests".to_string().to_header(1, options));
+            let mut table = MultiPartTable::new();
+
+            table = table.multi_raw(vec![
+                "Probe".to_inline(options),
+                "Status".to_inline(options),
+                "Message".to_inline(options),
+            ]);
+            for result in &self.probe_results {
+                let status = result
+                    .status_code
+                    .map_or("n/a".to_string(), |s| s.to_string());
+                if let Some(error) = &result.execution_error {
+                    table = table.multi_raw(vec![
+                        result.probe.to_string(),
+                        status.clone(),
+                        error.to_owned().to_em(options),
+                    ]);
+                } else if result.checks.is_empty() {
+                    table = table.multi_raw(vec![
+                        result.probe.to_string(),
+                        status.clone(),
+                        "No issues or errors.".to_string(),
+                    ]);
+                }
+                for c in &result.checks {
+                    table = table.multi_raw(vec![
+                        result.probe.to_string(),
+                        status.clone(),
+                        check_item_md(c, options),
+                    ]);
+                }
+            }
+            md.push_str(&table.to_md_table(options));
+            md.push('\n');
+        }
+
         // each run in detail
         for run in &self.test_runs {
             md.push_str(&run.to_md(options));
@@ -224,6 +314,10 @@ impl HttpResults {
             .filter(|r| !matches!(r.outcome, RunOutcome::Tested | RunOutcome::Skipped))
             .count()
             != 0
+            || self
+                .probe_results
+                .iter()
+                .any(|r| r.execution_error.is_some())
     }
 
     pub fn are_there_checks(&self, classes: Vec<CheckClass>) -> bool {
@@ -245,7 +339,14 @@ impl HttpResults {
             .iter()
             .filter(|c| classes.contains(&c.check_class))
             .count();
-        run_count + service_count != 0
+        // see if there are any classes in the probe results
+        let probe_count = self
+            .probe_results
+            .iter()
+            .flat_map(|r| &r.checks)
+            .filter(|c| classes.contains(&c.check_class))
+            .count();
+        run_count + service_count + probe_count != 0
     }
 
     pub fn filter_test_results(self, classes: &[CheckClass]) -> Self {
@@ -269,10 +370,20 @@ impl HttpResults {
             filtered_test_runs.push(test_run);
         }
 
+        // filter probe results
+        let mut filtered_probe_results = vec![];
+        for mut probe_result in self.probe_results {
+            probe_result
+                .checks
+                .retain(|c| classes.contains(&c.check_class));
+            filtered_probe_results.push(probe_result);
+        }
+
         // return
         Self {
             service_checks: filtered_service_checks,
             test_runs: filtered_test_runs,
+            probe_results: filtered_probe_results,
             ..self
         }
     }
@@ -457,6 +568,7 @@ impl TestRun {
                         RunOutcome::JsonError
                     }
                 }
+                RdapClientError::PageUnavailable { .. } => RunOutcome::HttpNon200Error,
                 RdapClientError::IoError(_) => RunOutcome::NetworkError,
                 RdapClientError::Client(e) => {
                     if e.is_redirect() {
@@ -543,7 +655,7 @@ impl TestRun {
         md
     }
 
-    fn attribute_set(&self) -> String {
+    pub(crate) fn attribute_set(&self) -> String {
         let socket_type = socket_type_string(self.socket_addr);
         if !self.features.is_empty() {
             format!(
@@ -572,7 +684,7 @@ fn socket_type_string(sock: Option<SocketAddr>) -> String {
     }
 }
 
-fn socket_addr_string(sock: Option<SocketAddr>) -> String {
+pub(crate) fn socket_addr_string(sock: Option<SocketAddr>) -> String {
     if let Some(sock) = sock {
         sock.to_string()
     } else {
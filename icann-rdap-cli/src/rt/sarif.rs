@@ -0,0 +1,308 @@
+//! SARIF rendering of test results.
+
+use {
+    icann_rdap_common::{
+        check::{CheckClass, CheckItem, ERROR_CHECK_CLASSES, WARNING_CHECK_CLASSES},
+        VERSION,
+    },
+    serde::Serialize,
+    strum::EnumMessage,
+};
+
+use super::results::{socket_addr_string, TestResults};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const INFORMATION_URI: &str = "https://github.com/icann/icann-rdap";
+
+/// A SARIF log of one run of rdap-test.
+#[derive(Debug, Serialize, Clone)]
+pub struct Sarif {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<Run>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Run {
+    pub tool: Tool,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Tool {
+    pub driver: Driver,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Driver {
+    pub name: String,
+    pub version: String,
+    pub information_uri: String,
+    pub rules: Vec<Rule>,
+}
+
+/// A rule, which is a [Check](icann_rdap_common::check::Check).
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    pub id: String,
+    pub short_description: Message,
+    pub properties: RuleProperties,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RuleProperties {
+    pub code: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Message {
+    pub text: String,
+}
+
+/// A result, which is a check found in the tests.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    pub level: String,
+    pub message: Message,
+    pub locations: Vec<Location>,
+    pub properties: ResultProperties,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultProperties {
+    pub check_class: CheckClass,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test_run: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub physical_location: Option<PhysicalLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub logical_locations: Vec<LogicalLocation>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+    pub artifact_location: ArtifactLocation,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ArtifactLocation {
+    pub uri: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogicalLocation {
+    pub fully_qualified_name: String,
+}
+
+/// A check found in the tests and where it was found.
+struct Finding<'a> {
+    item: &'a CheckItem,
+    uri: Option<&'a str>,
+    structure: Option<&'a str>,
+    test_run: Option<String>,
+}
+
+impl TestResults {
+    /// Renders the results as a SARIF log.
+    ///
+    /// Each check found is a rule, and each time it is found is a result
+    /// with a level given by its check class.
+    pub fn to_sarif(&self) -> Sarif {
+        let mut findings = vec![];
+        let (uri, test_runs) = match self {
+            Self::Http(http_results) => (
+                Some(http_results.query_url.as_str()),
+                http_results.test_runs.iter().collect::<Vec<_>>(),
+            ),
+            Self::String(string_result) => (None, string_result.test_run.iter().collect()),
+        };
+        for test_run in test_runs {
+            let run_name = format!(
+                "{} - {}",
+                socket_addr_string(test_run.socket_addr),
+                test_run.attribute_set()
+            );
+            for summary in test_run.summaries.as_deref().unwrap_or_default() {
+                findings.push(Finding {
+                    item: &summary.item,
+                    uri,
+                    structure: Some(&summary.structure),
+                    test_run: Some(run_name.clone()),
+                });
+            }
+        }
+        if let Self::Http(http_results) = self {
+            for item in &http_results.service_checks {
+                findings.push(Finding {
+                    item,
+                    uri,
+                    structure: None,
+                    test_run: None,
+                });
+            }
+            for probe_result in &http_results.probe_results {
+                for item in &probe_result.checks {
+                    findings.push(Finding {
+                        item,
+                        uri: Some(&probe_result.url),
+                        structure: None,
+                        test_run: Some(probe_result.probe.to_string()),
+                    });
+                }
+            }
+        }
+
+        let mut rules: Vec<Rule> = vec![];
+        let mut results = vec![];
+        for finding in findings {
+            let rule_id = finding.item.check.to_string();
+            let message = finding
+                .item
+                .check
+                .get_message()
+                .unwrap_or("[Check has no description]")
+                .to_string();
+            let rule_index = match rules.iter().position(|r| r.id == rule_id) {
+                Some(index) => index,
+                None => {
+                    rules.push(Rule {
+                        id: rule_id.clone(),
+                        short_description: Message {
+                            text: message.clone(),
+                        },
+                        properties: RuleProperties {
+                            code: finding.item.check as usize,
+                        },
+                    });
+                    rules.len() - 1
+                }
+            };
+            results.push(SarifResult {
+                rule_id,
+                rule_index,
+                level: level(finding.item.check_class).to_string(),
+                message: Message { text: message },
+                locations: vec![Location {
+                    physical_location: finding.uri.map(|uri| PhysicalLocation {
+                        artifact_location: ArtifactLocation {
+                            uri: uri.to_string(),
+                        },
+                    }),
+                    logical_locations: finding
+                        .structure
+                        .map(|structure| LogicalLocation {
+                            fully_qualified_name: structure.to_string(),
+                        })
+                        .into_iter()
+                        .collect(),
+                }],
+                properties: ResultProperties {
+                    check_class: finding.item.check_class,
+                    test_run: finding.test_run,
+                },
+            });
+        }
+
+        Sarif {
+            schema: SARIF_SCHEMA.to_string(),
+            version: SARIF_VERSION.to_string(),
+            runs: vec![Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: "rdap-test".to_string(),
+                        version: VERSION.to_string(),
+                        information_uri: INFORMATION_URI.to_string(),
+                        rules,
+                    },
+                },
+                results,
+            }],
+        }
+    }
+}
+
+/// Gets the SARIF level of a check class.
+fn level(check_class: CheckClass) -> &'static str {
+    if ERROR_CHECK_CLASSES.contains(&check_class) {
+        "error"
+    } else if WARNING_CHECK_CLASSES.contains(&check_class) {
+        "warning"
+    } else {
+        "note"
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use icann_rdap_common::check::{Check, CheckClass, CheckItem, CheckSummary};
+
+    use crate::rt::results::{RunOutcome, StringResult, TestResults, TestRun};
+
+    #[test]
+    fn GIVEN_repeated_check_WHEN_to_sarif_THEN_one_rule_per_check() {
+        // GIVEN
+        let items = [
+            (CheckClass::Std95Error, Check::LdhNameInvalid),
+            (CheckClass::Std95Warning, Check::VariantEmptyDomain),
+            (CheckClass::Std95Error, Check::LdhNameInvalid),
+        ];
+        let mut test_run = TestRun::new(vec![]);
+        test_run.outcome = RunOutcome::Tested;
+        test_run.summaries = Some(
+            items
+                .into_iter()
+                .map(|(check_class, check)| {
+                    let item = CheckItem { check_class, check };
+                    CheckSummary {
+                        code: check as usize,
+                        structure: "Domain".to_string(),
+                        message: item.to_string(),
+                        item,
+                    }
+                })
+                .collect(),
+        );
+        let results = TestResults::String(Box::new(StringResult::new(test_run)));
+
+        // WHEN
+        let sarif = results.to_sarif();
+
+        // THEN
+        let run = &sarif.runs[0];
+        let rules = run
+            .tool
+            .driver
+            .rules
+            .iter()
+            .map(|r| r.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(rules, vec!["ldh_name_invalid", "variant_empty_domain"]);
+        let results = run
+            .results
+            .iter()
+            .map(|r| (r.rule_index, r.level.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(results, vec![(0, "error"), (1, "warning"), (0, "error")]);
+        assert!(run.results[0].locations[0].physical_location.is_none());
+        assert_eq!(
+            run.results[0].locations[0].logical_locations[0].fully_qualified_name,
+            "Domain"
+        );
+    }
+}
@@ -0,0 +1,333 @@
+//! A summary of the results of testing many targets.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use {
+    icann_rdap_client::md::{string::StringUtil, table::MultiPartTable, MdOptions},
+    icann_rdap_common::check::{Check, CheckClass, CheckItem},
+    reqwest::Url,
+    serde::Serialize,
+};
+
+use super::results::{RunOutcome, TargetResults, TestResults};
+
+/// A matrix of the checks found on each server by check class.
+#[derive(Debug, Serialize, Clone)]
+pub struct SummaryMatrix {
+    pub check_classes: Vec<CheckClass>,
+    pub servers: Vec<ServerSummary>,
+}
+
+/// The checks found on a server across all the runs of all its targets.
+#[derive(Debug, Serialize, Clone)]
+pub struct ServerSummary {
+    pub server: String,
+    pub targets: Vec<String>,
+    pub tested_runs: usize,
+    pub execution_errors: usize,
+    pub checks: BTreeMap<CheckClass, ClassSummary>,
+}
+
+/// The checks of a check class found on a server.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ClassSummary {
+    /// The number of times the checks were found.
+    pub count: usize,
+
+    /// The distinct checks found.
+    pub checks: BTreeSet<Check>,
+}
+
+impl SummaryMatrix {
+    /// Aggregates the checks of the given classes in the results of each target,
+    /// with the servers in the order they were first tested.
+    pub fn new(check_classes: &[CheckClass], target_results: &[TargetResults]) -> Self {
+        let mut servers: Vec<ServerSummary> = vec![];
+        for target_results in target_results {
+            let server = server_of(target_results);
+            let summary = match servers.iter().position(|s| s.server == server) {
+                Some(i) => &mut servers[i],
+                None => {
+                    servers.push(ServerSummary::new(server, check_classes));
+                    servers.last_mut().expect("server summary")
+                }
+            };
+            summary.add(target_results);
+        }
+        Self {
+            check_classes: check_classes.to_vec(),
+            servers,
+        }
+    }
+
+    pub fn to_md(&self, options: &MdOptions) -> String {
+        let mut md = String::new();
+
+        md.push_str(&format!(
+            "\n{}\n",
+            "Summary".to_string().to_header(1, options)
+        ));
+
+        // the matrix
+        let mut table = MultiPartTable::new();
+        let mut header = vec![
+            "Server".to_inline(options),
+            "Targets".to_inline(options),
+            "Tested".to_inline(options),
+            "Errors".to_inline(options),
+        ];
+        header.extend(self.check_classes.iter().map(|c| c.to_inline(options)));
+        table = table.multi_raw(header);
+        for server in &self.servers {
+            let mut row = vec![
+                server.server.clone(),
+                server.targets.len().to_string(),
+                server.tested_runs.to_string(),
+                if server.execution_errors > 0 {
+                    server.execution_errors.to_em(options)
+                } else {
+                    server.execution_errors.to_string()
+                },
+            ];
+            row.extend(self.check_classes.iter().map(|c| {
+                let count = server.checks.get(c).map_or(0, |s| s.count);
+                if count > 0 && !is_informative(*c) {
+                    count.to_em(options)
+                } else {
+                    count.to_string()
+                }
+            }));
+            table = table.multi_raw(row);
+        }
+        md.push_str(&table.to_md_table(options));
+        md.push('\n');
+
+        // the checks behind the counts
+        let found = self
+            .servers
+            .iter()
+            .flat_map(|server| {
+                server
+                    .checks
+                    .iter()
+                    .filter(|(_, class_summary)| !class_summary.checks.is_empty())
+                    .map(move |(class, class_summary)| (server, class, class_summary))
+            })
+            .collect::<Vec<_>>();
+        if !found.is_empty() {
+            md.push_str(&"Checks".to_string().to_header(2, options));
+            let mut table = MultiPartTable::new();
+            table = table.multi_raw(vec![
+                "Server".to_inline(options),
+                "Class".to_inline(options),
+                "Checks".to_inline(options),
+            ]);
+            for (server, class, class_summary) in found {
+                table = table.multi_raw(vec![
+                    server.server.clone(),
+                    class.to_string(),
+                    class_summary
+                        .checks
+                        .iter()
+                        .map(|c| c.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                ]);
+            }
+            md.push_str(&table.to_md_table(options));
+            md.push('\n');
+        }
+        md
+    }
+}
+
+impl ServerSummary {
+    fn new(server: String, check_classes: &[CheckClass]) -> Self {
+        Self {
+            server,
+            targets: vec![],
+            tested_runs: 0,
+            execution_errors: 0,
+            checks: check_classes
+                .iter()
+                .map(|c| (*c, ClassSummary::default()))
+                .collect(),
+        }
+    }
+
+    fn add(&mut self, target_results: &TargetResults) {
+        self.targets.push(target_results.target.clone());
+        if target_results.execution_error.is_some() {
+            self.execution_errors += 1;
+        }
+        let Some(test_results) = &target_results.test_results else {
+            return;
+        };
+        let (test_runs, service_checks, probe_results) = match test_results {
+            TestResults::Http(http_results) => (
+                http_results.test_runs.iter().collect::<Vec<_>>(),
+                http_results.service_checks.as_slice(),
+                http_results.probe_results.as_slice(),
+            ),
+            TestResults::String(string_result) => {
+                (string_result.test_run.iter().collect(), &[][..], &[][..])
+            }
+        };
+        for item in service_checks {
+            self.add_check(item);
+        }
+        for probe_result in probe_results {
+            if probe_result.execution_error.is_some() {
+                self.execution_errors += 1;
+            }
+            for item in &probe_result.checks {
+                self.add_check(item);
+            }
+        }
+        for test_run in test_runs {
+            match test_run.outcome {
+                RunOutcome::Tested => self.tested_runs += 1,
+                RunOutcome::Skipped => {}
+                _ => self.execution_errors += 1,
+            }
+            for summary in test_run.summaries.as_deref().unwrap_or_default() {
+                self.add_check(&summary.item);
+            }
+        }
+    }
+
+    fn add_check(&mut self, item: &CheckItem) {
+        // only the classes of the matrix are counted
+        if let Some(class_summary) = self.checks.get_mut(&item.check_class) {
+            class_summary.count += 1;
+            class_summary.checks.insert(item.check);
+        }
+    }
+}
+
+/// Gets the server of a target, which is the origin of the URL tested.
+fn server_of(target_results: &TargetResults) -> String {
+    let url = match &target_results.test_results {
+        Some(TestResults::Http(http_results)) => http_results.query_url.as_str(),
+        Some(TestResults::String(_)) => return "file".to_string(),
+        None => target_results.target.as_str(),
+    };
+    match Url::parse(url) {
+        Ok(url) if url.has_host() => url.origin().ascii_serialization(),
+        _ => target_results.target.clone(),
+    }
+}
+
+fn is_informative(class: CheckClass) -> bool {
+    matches!(
+        class,
+        CheckClass::Informational | CheckClass::SpecificationNote
+    )
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use icann_rdap_common::check::{Check, CheckClass, CheckSummary};
+
+    use crate::rt::{
+        exec::TestExecutionError,
+        results::{DnsData, HttpResults, RunOutcome, TargetResults, TestResults, TestRun},
+    };
+
+    use super::SummaryMatrix;
+
+    fn target(query_url: &str, checks: &[Check]) -> TargetResults {
+        let mut http_results = HttpResults::new(query_url.to_string(), DnsData::default());
+        let mut test_run = TestRun::new_v4(vec![], Ipv4Addr::LOCALHOST, 443);
+        test_run.outcome = RunOutcome::Tested;
+        test_run.summaries = Some(
+            checks
+                .iter()
+                .map(|check| CheckSummary {
+                    code: *check as usize,
+                    structure: "Domain".to_string(),
+                    message: check.to_string(),
+                    item: check.check_item(),
+                })
+                .collect(),
+        );
+        http_results.add_test_run(test_run);
+        TargetResults::new(
+            query_url.to_string(),
+            Ok(TestResults::Http(Box::new(http_results))),
+        )
+    }
+
+    #[test]
+    fn GIVEN_targets_on_two_servers_WHEN_summarized_THEN_checks_counted_by_server_and_class() {
+        // GIVEN
+        let targets = [
+            target(
+                "https://a.example/rdap/domain/foo.example",
+                &[Check::RdapConformanceMissing, Check::UnknownExtension],
+            ),
+            target(
+                "https://a.example/rdap/domain/bar.example",
+                &[Check::RdapConformanceMissing],
+            ),
+            target("https://b.example/domain/foo.example", &[]),
+        ];
+
+        // WHEN
+        let actual = SummaryMatrix::new(
+            &[CheckClass::Std95Warning, CheckClass::Std95Error],
+            &targets,
+        );
+
+        // THEN
+        assert_eq!(actual.servers.len(), 2);
+        let a = &actual.servers[0];
+        assert_eq!(a.server, "https://a.example");
+        assert_eq!(a.targets.len(), 2);
+        assert_eq!(a.tested_runs, 2);
+        assert_eq!(a.checks[&CheckClass::Std95Error].count, 2);
+        assert_eq!(a.checks[&CheckClass::Std95Error].checks.len(), 1);
+        assert_eq!(a.checks[&CheckClass::Std95Warning].count, 1);
+        let b = &actual.servers[1];
+        assert_eq!(b.server, "https://b.example");
+        assert_eq!(b.checks[&CheckClass::Std95Error].count, 0);
+    }
+
+    #[test]
+    fn GIVEN_target_not_tested_WHEN_summarized_THEN_execution_error_counted() {
+        // GIVEN
+        let targets = [TargetResults::new(
+            "https://c.example/rdap".to_string(),
+            Err(TestExecutionError::NoHostToResolve),
+        )];
+
+        // WHEN
+        let actual = SummaryMatrix::new(&[CheckClass::Std95Error], &targets);
+
+        // THEN
+        assert_eq!(actual.servers[0].server, "https://c.example");
+        assert_eq!(actual.servers[0].execution_errors, 1);
+        assert_eq!(actual.servers[0].tested_runs, 0);
+    }
+
+    #[test]
+    fn GIVEN_matrix_WHEN_to_md_THEN_classes_are_columns() {
+        // GIVEN
+        let targets = [target(
+            "https://a.example/rdap/domain/foo.example",
+            &[Check::RdapConformanceMissing],
+        )];
+        let matrix = SummaryMatrix::new(&[CheckClass::Std95Error], &targets);
+
+        // WHEN
+        let actual = matrix.to_md(&Default::default());
+
+        // THEN
+        assert!(actual.contains("Std95Err"));
+        assert!(actual.contains("https://a.example"));
+        assert!(actual.contains("rdap_conformance_missing"));
+    }
+}
@@ -0,0 +1,81 @@
+use {icann_rdap_common::response::Domain, icann_rdap_srv::storage::StoreOps, serde_json::Value};
+
+use crate::test_jig::TestJig;
+
+async fn new_bulk_jig() -> TestJig {
+    let test_jig = TestJig::new_rdap().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    for ldh in ["foo.example", "bar.example"] {
+        tx.add_domain(&Domain::builder().ldh_name(ldh).build())
+            .await
+            .expect("add domain in tx");
+    }
+    tx.commit().await.expect("tx commit");
+    test_jig
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_bulk_json_lines() {
+    // GIVEN a server with domains
+    let mut test_jig = new_bulk_jig().await;
+
+    // WHEN querying them in bulk with values that are not found or not queries
+    test_jig
+        .cmd
+        .arg("--input")
+        .arg("-")
+        .arg("--concurrency")
+        .arg("2")
+        .write_stdin("foo.example\n# comment\nnope.example\nnot a query value\nbar.example\n");
+
+    // THEN a record for each value, in order, with an error for the bad one
+    let output = test_jig.cmd.output().expect("executing bulk query");
+    assert!(output.status.success());
+    let records = String::from_utf8(output.stdout)
+        .expect("utf8 stdout")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("parsing record"))
+        .collect::<Vec<Value>>();
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[0]["line"], 1);
+    assert_eq!(records[0]["query"], "foo.example");
+    assert_eq!(records[0]["status_code"], 200);
+    assert_eq!(records[0]["rdap"]["ldhName"], "foo.example");
+    assert_eq!(records[1]["query"], "nope.example");
+    assert_eq!(records[1]["status_code"], 404);
+    assert_eq!(records[2]["query"], "not a query value");
+    assert!(records[2]["error"].is_string());
+    assert!(records[2].get("rdap").is_none());
+    assert_eq!(records[3]["line"], 5);
+    assert_eq!(records[3]["rdap"]["ldhName"], "bar.example");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_bulk_csv() {
+    // GIVEN a server with domains and a file of query values
+    let mut test_jig = new_bulk_jig().await;
+    let input = tempfile::NamedTempFile::new().expect("creating temp file");
+    std::fs::write(input.path(), "foo.example\nbar.example\n").expect("writing query values");
+
+    // WHEN querying them in bulk as CSV
+    test_jig
+        .cmd
+        .arg("--input")
+        .arg(input.path())
+        .arg("--bulk-format")
+        .arg("csv");
+
+    // THEN a header and a row for each value
+    let output = test_jig.cmd.output().expect("executing bulk query");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("utf8 stdout");
+    let rows = stdout.lines().collect::<Vec<&str>>();
+    assert_eq!(
+        rows[0],
+        "line,query,query_type,url,status_code,rdap_type,error"
+    );
+    assert_eq!(rows.len(), 3);
+    assert!(rows[1].starts_with("1,foo.example,Domain Lookup,"));
+    assert!(rows[1].ends_with(",200,Domain,"));
+    assert!(rows[2].starts_with("2,bar.example,"));
+}
@@ -1,5 +1,8 @@
 mod bootstrap;
+mod bulk;
 mod cache;
+mod network_hierarchy;
 mod not_found;
+mod paging;
 mod queries;
 mod url;
@@ -0,0 +1,94 @@
+use {
+    icann_rdap_client::rdap::RequestResponse,
+    icann_rdap_common::{
+        prelude::ObjectCommonFields,
+        response::{Network, RdapResponse},
+    },
+    icann_rdap_srv::storage::{CommonConfig, StoreOps},
+};
+
+use crate::test_jig::{CommandType, TestJig};
+
+async fn new_network_hierarchy_jig() -> TestJig {
+    let common_config = CommonConfig::builder()
+        .network_hierarchy_enable(true)
+        .build();
+    let test_jig = TestJig::new_common_config(common_config, CommandType::Rdap).await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    for (cidr, handle) in [
+        ("10.0.0.0/8", "NET-10"),
+        ("10.0.0.0/16", "NET-10-0"),
+        ("10.0.0.0/24", "NET-10-0-0"),
+    ] {
+        tx.add_network(
+            &Network::builder()
+                .cidr(cidr)
+                .handle(handle)
+                .build()
+                .expect("cidr parsing"),
+        )
+        .await
+        .expect("add network in tx");
+    }
+    tx.commit().await.expect("tx commit");
+    test_jig
+}
+
+fn target_handles(stdout: &[u8]) -> Vec<String> {
+    let responses: Vec<RequestResponse> = serde_json::from_slice(stdout).expect("parsing stdout");
+    responses
+        .iter()
+        .filter(|r| r.req_data.req_target)
+        .flat_map(|r| match &r.res_data.rdap {
+            RdapResponse::Network(n) => vec![n.handle().unwrap_or_default().to_string()],
+            RdapResponse::NetworkSearchResults(results) => results
+                .results
+                .iter()
+                .map(|n| n.handle().unwrap_or_default().to_string())
+                .collect(),
+            _ => vec![],
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_network_up() {
+    // GIVEN a server with nested networks
+    let mut test_jig = new_network_hierarchy_jig().await;
+
+    // WHEN
+    test_jig.cmd.arg("--up").arg("10.0.0.1");
+
+    // THEN the parent of the most specific network is the target
+    let output = test_jig.cmd.output().expect("executing ip query");
+    assert!(output.status.success());
+    assert_eq!(target_handles(&output.stdout), ["NET-10-0"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_network_top() {
+    // GIVEN a server with nested networks
+    let mut test_jig = new_network_hierarchy_jig().await;
+
+    // WHEN
+    test_jig.cmd.arg("--top").arg("10.0.0.1");
+
+    // THEN the least specific network is the target
+    let output = test_jig.cmd.output().expect("executing ip query");
+    assert!(output.status.success());
+    assert_eq!(target_handles(&output.stdout), ["NET-10"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_network_down() {
+    // GIVEN a server with nested networks
+    let mut test_jig = new_network_hierarchy_jig().await;
+
+    // WHEN
+    test_jig.cmd.arg("--down").arg("10.0.0.0/8");
+
+    // THEN the child networks are the target
+    let output = test_jig.cmd.output().expect("executing ip query");
+    assert!(output.status.success());
+    assert_eq!(target_handles(&output.stdout), ["NET-10-0"]);
+}
@@ -0,0 +1,126 @@
+use {
+    icann_rdap_client::rdap::RequestResponse,
+    icann_rdap_common::response::{Domain, RdapResponse},
+    icann_rdap_srv::{
+        config::SearchConfig,
+        storage::{CommonConfig, StoreOps},
+    },
+};
+
+use crate::test_jig::{CommandType, TestJig};
+
+async fn new_paged_domains_jig() -> TestJig {
+    let common_config = CommonConfig::builder()
+        .domain_search_by_name_enable(true)
+        .build();
+    let search_config = SearchConfig::builder().page_size(1).build();
+    let test_jig =
+        TestJig::new_search_config(common_config, search_config, CommandType::Rdap).await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    for ldh in ["fooa.example", "foob.example", "fooc.example"] {
+        tx.add_domain(&Domain::builder().ldh_name(ldh).build())
+            .await
+            .expect("add domain in tx");
+    }
+    tx.commit().await.expect("tx commit");
+    test_jig
+}
+
+fn search_results_len(stdout: &[u8]) -> usize {
+    let responses: Vec<RequestResponse> = serde_json::from_slice(stdout).expect("parsing stdout");
+    let rdap = &responses.first().expect("response is empty").res_data.rdap;
+    let RdapResponse::DomainSearchResults(results) = rdap else {
+        panic!("not domain search results")
+    };
+    results.results.len()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_domain_search_first_page_only() {
+    // GIVEN a server returning one result per page
+    let mut test_jig = new_paged_domains_jig().await;
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("-t")
+        .arg("domain-name")
+        .arg("foo*.example");
+
+    // THEN only the first page is returned
+    let output = test_jig.cmd.output().expect("executing domain search");
+    assert!(output.status.success());
+    assert_eq!(search_results_len(&output.stdout), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_domain_search_all_pages() {
+    // GIVEN a server returning one result per page
+    let mut test_jig = new_paged_domains_jig().await;
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--all-pages")
+        .arg("-t")
+        .arg("domain-name")
+        .arg("foo*.example");
+
+    // THEN the results of every page are returned
+    let output = test_jig.cmd.output().expect("executing domain search");
+    assert!(output.status.success());
+    assert_eq!(search_results_len(&output.stdout), 3);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_domain_search_all_pages_with_max_pages() {
+    // GIVEN a server returning one result per page
+    let mut test_jig = new_paged_domains_jig().await;
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--all-pages")
+        .arg("--max-pages")
+        .arg("2")
+        .arg("-t")
+        .arg("domain-name")
+        .arg("foo*.example");
+
+    // THEN paging stops at the limit
+    let output = test_jig.cmd.output().expect("executing domain search");
+    assert!(output.status.success());
+    assert_eq!(search_results_len(&output.stdout), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_domain_search_all_pages_with_id_field_set() {
+    // GIVEN a server returning one result per page
+    let mut test_jig = new_paged_domains_jig().await;
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--all-pages")
+        .arg("--field-set")
+        .arg("id")
+        .arg("-t")
+        .arg("domain-name")
+        .arg("foo*.example");
+
+    // THEN the field set is kept when following the pages
+    let output = test_jig.cmd.output().expect("executing domain search");
+    assert!(output.status.success());
+    let responses: Vec<RequestResponse> =
+        serde_json::from_slice(&output.stdout).expect("parsing stdout");
+    let rdap = &responses.first().expect("response is empty").res_data.rdap;
+    let RdapResponse::DomainSearchResults(results) = rdap else {
+        panic!("not domain search results")
+    };
+    assert_eq!(results.results.len(), 3);
+    let subsetting_metadata = results
+        .subsetting_metadata
+        .as_ref()
+        .expect("subsetting metadata");
+    assert_eq!(subsetting_metadata.current_field_set(), Some("id"));
+}
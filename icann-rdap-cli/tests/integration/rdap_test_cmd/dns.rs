@@ -0,0 +1,235 @@
+#![allow(non_snake_case)]
+
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    str::FromStr,
+};
+
+use {
+    hickory_client::{
+        op::{Message, MessageType},
+        rr::{
+            rdata::{A, NS, NULL},
+            Name, RData, Record, RecordType,
+        },
+    },
+    icann_rdap_common::response::{Domain, DsDatum, Nameserver, SecureDns},
+    icann_rdap_srv::storage::StoreOps,
+    serde_json::Value,
+    tokio::net::UdpSocket,
+};
+
+use crate::test_jig::TestJig;
+
+/// Starts a DNS server that answers queries with the given records. Queries
+/// without recursion are answered with a referral, which has the NS records
+/// in the authority section and the glue for them in the additional section.
+async fn stub_dns_server(records: Vec<Record>, glue: Vec<Record>) -> SocketAddr {
+    let socket = UdpSocket::bind("127.0.0.1:0")
+        .await
+        .expect("binding stub DNS server");
+    let addr = socket.local_addr().expect("stub DNS server address");
+    tokio::spawn(async move {
+        let mut buf = [0u8; 512];
+        while let Ok((len, peer)) = socket.recv_from(&mut buf).await {
+            let Ok(request) = Message::from_vec(&buf[..len]) else {
+                continue;
+            };
+            let mut response = Message::new();
+            response
+                .set_id(request.id())
+                .set_message_type(MessageType::Response)
+                .set_op_code(request.op_code())
+                .set_recursion_desired(request.recursion_desired())
+                .set_recursion_available(true);
+            for query in request.queries() {
+                response.add_query(query.clone());
+                let matching = records
+                    .iter()
+                    .filter(|r| r.name() == query.name() && r.record_type() == query.query_type())
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if request.recursion_desired() {
+                    response.add_answers(matching);
+                } else {
+                    response.add_additionals(
+                        glue.iter()
+                            .filter(|g| {
+                                matching
+                                    .iter()
+                                    .filter_map(|r| r.data().and_then(RData::as_ns))
+                                    .any(|ns| &ns.0 == g.name())
+                            })
+                            .cloned(),
+                    );
+                    response.add_name_servers(matching);
+                }
+            }
+            let response = response.to_vec().expect("encoding DNS response");
+            let _ = socket.send_to(&response, peer).await;
+        }
+    });
+    addr
+}
+
+fn name(name: &str) -> Name {
+    Name::from_str(name).expect("DNS name")
+}
+
+fn dns_records() -> Vec<Record> {
+    vec![
+        Record::from_rdata(
+            name("foo.example."),
+            86400,
+            RData::NS(NS(name("ns1.foo.example."))),
+        ),
+        Record::from_rdata(
+            name("foo.example."),
+            3600,
+            RData::Unknown {
+                code: RecordType::DS,
+                rdata: NULL::with(vec![0x30, 0x39, 13, 2, 0xab, 0xcd, 0xef, 0x01]),
+            },
+        ),
+        // the address the nameserver gives for itself, which is not the glue
+        Record::from_rdata(
+            name("ns1.foo.example."),
+            3600,
+            RData::A(A::from(Ipv4Addr::from([192, 0, 2, 9]))),
+        ),
+    ]
+}
+
+fn glue(ns_address: [u8; 4]) -> Vec<Record> {
+    vec![Record::from_rdata(
+        name("ns1.foo.example."),
+        3600,
+        RData::A(A::from(Ipv4Addr::from(ns_address))),
+    )]
+}
+
+async fn new_domain_jig() -> TestJig {
+    let test_jig = TestJig::new_rdap_test().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::response_obj()
+            .ldh_name("foo.example")
+            .nameserver(
+                Nameserver::response_obj()
+                    .ldh_name("ns1.foo.example")
+                    .address("192.0.2.1".to_string())
+                    .build()
+                    .expect("nameserver"),
+            )
+            .secure_dns(
+                SecureDns::builder()
+                    .delegation_signed(true)
+                    .ds_data(
+                        DsDatum::builder()
+                            .key_tag(12345)
+                            .algorithm(13)
+                            .digest_type(2)
+                            .digest("ABCDEF01".to_string())
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+    test_jig
+}
+
+async fn service_checks(mut test_jig: TestJig, dns_server: SocketAddr) -> Vec<String> {
+    let url = format!("{}/domain/foo.example", test_jig.rdap_base);
+    test_jig
+        .cmd
+        .arg(url)
+        .arg("--dns-checks")
+        .arg("--dns-resolver")
+        .arg(dns_server.to_string())
+        .arg("--dns-parent-server")
+        .arg(dns_server.to_string())
+        .arg("-O")
+        .arg("json");
+    let output = test_jig.cmd.output().expect("executing rdap-test");
+    let results: Value = serde_json::from_slice(&output.stdout).expect("parsing test results");
+    results["Http"]["service_checks"]
+        .as_array()
+        .expect("service checks")
+        .iter()
+        .map(|c| c["check"].as_str().expect("check").to_string())
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dns_checks_with_matching_dns() {
+    // GIVEN
+    let test_jig = new_domain_jig().await;
+    let dns_server = stub_dns_server(dns_records(), glue([192, 0, 2, 1])).await;
+
+    // WHEN
+    let checks = service_checks(test_jig, dns_server).await;
+
+    // THEN
+    assert!(!checks.iter().any(|c| c.starts_with("dns_")));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dns_checks_with_other_glue() {
+    // GIVEN
+    let test_jig = new_domain_jig().await;
+    let dns_server = stub_dns_server(dns_records(), glue([192, 0, 2, 2])).await;
+
+    // WHEN
+    let checks = service_checks(test_jig, dns_server).await;
+
+    // THEN
+    assert!(checks.contains(&"dns_ip_addresses_mismatch".to_string()));
+    assert!(!checks.contains(&"dns_nameservers_mismatch".to_string()));
+    assert!(!checks.contains(&"dns_ds_data_mismatch".to_string()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dns_checks_without_delegation() {
+    // GIVEN a parent server that does not know the domain
+    let test_jig = new_domain_jig().await;
+    let dns_server = stub_dns_server(vec![], vec![]).await;
+
+    // WHEN
+    let checks = service_checks(test_jig, dns_server).await;
+
+    // THEN
+    assert!(checks.contains(&"dns_query_failed".to_string()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dns_checks_with_unusable_resolver() {
+    // GIVEN
+    let mut test_jig = new_domain_jig().await;
+
+    // WHEN
+    let url = format!("{}/domain/foo.example", test_jig.rdap_base);
+    test_jig
+        .cmd
+        .arg(url)
+        .arg("--dns-checks")
+        .arg("--dns-resolver")
+        .arg("not-a-resolver")
+        .arg("-O")
+        .arg("json");
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing rdap-test");
+    let results: Value = serde_json::from_slice(&output.stdout).expect("parsing test results");
+    let checks = results["Http"]["service_checks"]
+        .as_array()
+        .expect("service checks");
+    assert!(checks.iter().any(|c| c["check"] == "dns_query_failed"));
+    assert!(!results["Http"]["test_runs"]
+        .as_array()
+        .expect("test runs")
+        .is_empty());
+}
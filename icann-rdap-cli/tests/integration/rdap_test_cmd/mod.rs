@@ -1,3 +1,9 @@
+pub mod dns;
 pub mod in_file;
+pub mod negative;
+pub mod nro;
+pub mod output;
+pub mod plan;
 pub mod stdin;
+pub mod targets;
 pub mod url;
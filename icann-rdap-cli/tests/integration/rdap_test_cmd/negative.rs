@@ -0,0 +1,102 @@
+#![allow(non_snake_case)]
+
+use {icann_rdap_common::response::Network, icann_rdap_srv::storage::StoreOps, serde_json::Value};
+
+use crate::test_jig::TestJig;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_negative_tests() {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap_test().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    tx.add_network(
+        &Network::response_obj()
+            .cidr("10.0.0.0/24")
+            .build()
+            .expect("cidr parsing"),
+    )
+    .await
+    .expect("add network in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let url = format!("{}/ip/10.0.0.1", test_jig.rdap_base);
+    test_jig
+        .cmd
+        .arg(url)
+        .arg("--negative-tests")
+        .arg("-O")
+        .arg("json");
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing rdap-test");
+    let results: Value = serde_json::from_slice(&output.stdout).expect("parsing test results");
+    let probe_results = results["Http"]["probe_results"]
+        .as_array()
+        .expect("probe results");
+    assert_eq!(probe_results.len(), 8);
+    let probe = |name: &str| {
+        probe_results
+            .iter()
+            .find(|r| r["probe"] == name)
+            .expect("probe result")
+    };
+    // the server rejects a malformed autnum without an RFC 9083 body, does not
+    // tell an invalid IDN from a domain it does not have, and rejects a trailing slash
+    for (name, status_code, checks) in [
+        ("NotFound", 404, vec![]),
+        ("MalformedIp", 400, vec![]),
+        (
+            "MalformedAutnum",
+            400,
+            vec!["error_response_is_not_rfc9083"],
+        ),
+        ("InvalidIdn", 404, vec!["malformed_query_status_is_not400"]),
+        ("UnsupportedSearch", 501, vec![]),
+        ("Head", 200, vec![]),
+        (
+            "TrailingSlash",
+            400,
+            vec!["trailing_slash_not_answered_like_query"],
+        ),
+        ("JsonMediaType", 200, vec![]),
+    ] {
+        assert_eq!(probe(name)["status_code"], status_code, "{name}");
+        let found = probe(name)["checks"]
+            .as_array()
+            .expect("checks")
+            .iter()
+            .map(|c| c["check"].as_str().expect("check name"))
+            .collect::<Vec<_>>();
+        assert_eq!(found, checks, "{name}");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_negative_tests_of_unreachable_server() {
+    // GIVEN
+    let mut test_jig = TestJig::new_rdap_test().await;
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("binding port");
+    let port = listener.local_addr().expect("local address").port();
+    drop(listener);
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg(format!("http://127.0.0.1:{port}/rdap/ip/10.0.0.1"))
+        .arg("--negative-tests")
+        .arg("-O")
+        .arg("json");
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing rdap-test");
+    let results: Value = serde_json::from_slice(&output.stdout).expect("parsing test results");
+    let probe_results = results["Http"]["probe_results"]
+        .as_array()
+        .expect("probe results");
+    assert_eq!(probe_results.len(), 8);
+    for probe_result in probe_results {
+        assert!(probe_result["execution_error"].is_string());
+        assert!(probe_result["status_code"].is_null());
+    }
+}
@@ -0,0 +1,87 @@
+#![allow(non_snake_case)]
+
+use {icann_rdap_common::response::Network, icann_rdap_srv::storage::StoreOps, serde_json::Value};
+
+use crate::test_jig::TestJig;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_nro_group_checks_nro_profile() {
+    // GIVEN a network without entities
+    let mut test_jig = TestJig::new_rdap_test().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    tx.add_network(
+        &Network::response_obj()
+            .cidr("10.0.0.0/24")
+            .build()
+            .expect("cidr parsing"),
+    )
+    .await
+    .expect("add network in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let url = format!("{}/ip/10.0.0.1", test_jig.rdap_base);
+    test_jig
+        .cmd
+        .arg(url)
+        .arg("--expect-group")
+        .arg("nro")
+        .arg("-C")
+        .arg("nro-profile-error")
+        .arg("-O")
+        .arg("json");
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing rdap-test");
+    assert_eq!(output.status.code(), Some(3));
+    let results: Value = serde_json::from_slice(&output.stdout).expect("parsing test results");
+    let checks = results["Http"]["test_runs"]
+        .as_array()
+        .expect("test runs")
+        .iter()
+        .flat_map(|r| r["summaries"].as_array().cloned().unwrap_or_default())
+        .map(|s| s["item"]["check"].as_str().expect("check").to_string())
+        .collect::<Vec<_>>();
+    assert!(checks.contains(&"nro_registrant_entity_missing".to_string()));
+    assert!(checks.contains(&"nro_abuse_contact_missing".to_string()));
+    // the server does not declare rirSearch1, so the relation links are not required
+    assert!(!checks.contains(&"nro_rdap_top_link_missing".to_string()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_no_nro_checks_without_nro_group() {
+    // GIVEN a network without entities
+    let mut test_jig = TestJig::new_rdap_test().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    tx.add_network(
+        &Network::response_obj()
+            .cidr("10.0.0.0/24")
+            .build()
+            .expect("cidr parsing"),
+    )
+    .await
+    .expect("add network in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let url = format!("{}/ip/10.0.0.1", test_jig.rdap_base);
+    test_jig
+        .cmd
+        .arg(url)
+        .arg("-C")
+        .arg("nro-profile-error")
+        .arg("-O")
+        .arg("json");
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing rdap-test");
+    let results: Value = serde_json::from_slice(&output.stdout).expect("parsing test results");
+    let checks = results["Http"]["test_runs"]
+        .as_array()
+        .expect("test runs")
+        .iter()
+        .flat_map(|r| r["summaries"].as_array().cloned().unwrap_or_default())
+        .map(|s| s["item"]["check"].as_str().expect("check").to_string())
+        .collect::<Vec<_>>();
+    assert!(!checks.iter().any(|c| c.starts_with("nro_")));
+}
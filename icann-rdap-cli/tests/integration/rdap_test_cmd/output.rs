@@ -0,0 +1,88 @@
+#![allow(non_snake_case)]
+
+use {icann_rdap_common::response::Network, icann_rdap_srv::storage::StoreOps, serde_json::Value};
+
+use crate::test_jig::TestJig;
+
+async fn new_network_jig() -> TestJig {
+    let test_jig = TestJig::new_rdap_test().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    tx.add_network(
+        &Network::response_obj()
+            .cidr("10.0.0.0/24")
+            .build()
+            .expect("cidr parsing"),
+    )
+    .await
+    .expect("add network in tx");
+    tx.commit().await.expect("tx commit");
+    test_jig
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_junit_output() {
+    // GIVEN
+    let mut test_jig = new_network_jig().await;
+
+    // WHEN
+    let url = format!("{}/ip/10.0.0.1", test_jig.rdap_base);
+    test_jig.cmd.arg(url).arg("-O").arg("junit");
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing rdap-test");
+    let xml = String::from_utf8(output.stdout).expect("UTF-8 output");
+    assert!(xml.starts_with("<?xml"));
+    assert!(xml.contains(r#"<testsuites name="rdap-test""#));
+    assert!(xml.contains("<testsuite name="));
+    assert!(xml.contains("<testcase name="));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sarif_output() {
+    // GIVEN
+    let mut test_jig = new_network_jig().await;
+
+    // WHEN
+    let url = format!("{}/ip/10.0.0.1", test_jig.rdap_base);
+    test_jig.cmd.arg(&url).arg("-O").arg("sarif");
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing rdap-test");
+    let sarif: Value = serde_json::from_slice(&output.stdout).expect("parsing SARIF");
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "rdap-test");
+    let rules = run["tool"]["driver"]["rules"].as_array().expect("rules");
+    for result in run["results"].as_array().expect("results") {
+        let rule_index = result["ruleIndex"].as_u64().expect("rule index") as usize;
+        assert_eq!(rules[rule_index]["id"], result["ruleId"]);
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            url.as_str()
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_junit_output_with_targets_is_unsupported() {
+    // GIVEN
+    let mut test_jig = new_network_jig().await;
+    let targets = tempfile::NamedTempFile::new().expect("creating temp file");
+    std::fs::write(
+        targets.path(),
+        format!("{}/ip/10.0.0.1\n", test_jig.rdap_base),
+    )
+    .expect("writing targets");
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--targets")
+        .arg(targets.path())
+        .arg("-O")
+        .arg("junit");
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing rdap-test");
+    assert_eq!(output.status.code(), Some(205));
+}
@@ -0,0 +1,89 @@
+#![allow(non_snake_case)]
+
+use {icann_rdap_common::response::Network, icann_rdap_srv::storage::StoreOps, serde_json::Value};
+
+use crate::test_jig::TestJig;
+
+async fn new_plan_jig() -> TestJig {
+    let test_jig = TestJig::new_rdap_test().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    tx.add_network(
+        &Network::response_obj()
+            .cidr("10.0.0.0/24")
+            .build()
+            .expect("cidr parsing"),
+    )
+    .await
+    .expect("add network in tx");
+    tx.commit().await.expect("tx commit");
+    test_jig
+}
+
+fn write_plan(plan: &str, suffix: &str) -> tempfile::NamedTempFile {
+    let file = tempfile::Builder::new()
+        .suffix(suffix)
+        .tempfile()
+        .expect("creating temp file");
+    std::fs::write(file.path(), plan).expect("writing test plan");
+    file
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_yaml_plan_with_assertions_met() {
+    // GIVEN
+    let mut test_jig = new_plan_jig().await;
+    let plan = write_plan(
+        &format!(
+            "name: networks\ntests:\n  - query: {}/ip/10.0.0.1\n    status: 200\n    extensions: [rdap_level_0]\n    forbidden_checks: [content_type_is_not_rdap]\n",
+            test_jig.rdap_base
+        ),
+        ".yaml",
+    );
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--test-plan")
+        .arg(plan.path())
+        .arg("-O")
+        .arg("json");
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing rdap-test");
+    assert!(output.status.success());
+    let results: Value = serde_json::from_slice(&output.stdout).expect("parsing plan results");
+    assert_eq!(results["name"], "networks");
+    let assertions = results["tests"][0]["assertions"]
+        .as_array()
+        .expect("assertions");
+    assert_eq!(assertions.len(), 3);
+    assert!(assertions.iter().all(|a| a["passed"] == true));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_json_plan_with_failed_assertion() {
+    // GIVEN a plan expecting a network that does not exist
+    let mut test_jig = new_plan_jig().await;
+    let plan = write_plan(
+        &format!(
+            r#"{{"tests": [{{"query": "{}/ip/10.0.0.1", "status": 200}}, {{"query": "{}/ip/10.0.1.1", "status": 200}}]}}"#,
+            test_jig.rdap_base, test_jig.rdap_base
+        ),
+        ".json",
+    );
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--test-plan")
+        .arg(plan.path())
+        .arg("-O")
+        .arg("json");
+
+    // THEN
+    let output = test_jig.cmd.output().expect("executing rdap-test");
+    assert_eq!(output.status.code(), Some(4));
+    let results: Value = serde_json::from_slice(&output.stdout).expect("parsing plan results");
+    assert_eq!(results["tests"][0]["assertions"][0]["passed"], true);
+    assert_eq!(results["tests"][1]["assertions"][0]["passed"], false);
+}
@@ -0,0 +1,93 @@
+#![allow(non_snake_case)]
+
+use {icann_rdap_common::response::Network, icann_rdap_srv::storage::StoreOps, serde_json::Value};
+
+use crate::test_jig::TestJig;
+
+async fn new_targets_jig() -> TestJig {
+    let test_jig = TestJig::new_rdap_test().await;
+    let mut tx = test_jig.mem.new_tx().await.expect("new transaction");
+    for cidr in ["10.0.0.0/24", "10.0.1.0/24"] {
+        tx.add_network(
+            &Network::response_obj()
+                .cidr(cidr)
+                .build()
+                .expect("cidr parsing"),
+        )
+        .await
+        .expect("add network in tx");
+    }
+    tx.commit().await.expect("tx commit");
+    test_jig
+}
+
+fn matrix(stdout: &[u8]) -> Value {
+    serde_json::from_slice(stdout).expect("parsing summary matrix")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_targets_of_query_values() {
+    // GIVEN a file of URLs on the same server
+    let mut test_jig = new_targets_jig().await;
+    let targets = tempfile::NamedTempFile::new().expect("creating temp file");
+    std::fs::write(
+        targets.path(),
+        format!(
+            "# servers\n{0}/ip/10.0.0.1\n\n{0}/ip/10.0.1.1\n",
+            test_jig.rdap_base
+        ),
+    )
+    .expect("writing targets");
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--targets")
+        .arg(targets.path())
+        .arg("--concurrency")
+        .arg("2")
+        .arg("-O")
+        .arg("json");
+
+    // THEN one server with both targets tested
+    let output = test_jig.cmd.output().expect("executing rdap-test");
+    assert!(output.status.success());
+    let matrix = matrix(&output.stdout);
+    let servers = matrix["servers"].as_array().expect("servers");
+    assert_eq!(servers.len(), 1);
+    assert_eq!(servers[0]["targets"].as_array().expect("targets").len(), 2);
+    assert_eq!(servers[0]["execution_errors"], 0);
+    assert!(servers[0]["tested_runs"].as_u64().expect("tested runs") > 0);
+    assert!(servers[0]["checks"]["std95_error"]["count"].is_u64());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_targets_of_base_urls() {
+    // GIVEN a file of base URLs, one of which is not reachable
+    let mut test_jig = new_targets_jig().await;
+    let targets = tempfile::NamedTempFile::new().expect("creating temp file");
+    std::fs::write(
+        targets.path(),
+        format!("{}\nhttp://127.0.0.1:1/rdap\n", test_jig.rdap_base),
+    )
+    .expect("writing targets");
+
+    // WHEN the query is sent to each base URL
+    test_jig
+        .cmd
+        .arg("--targets")
+        .arg(targets.path())
+        .arg("-O")
+        .arg("json")
+        .arg("10.0.0.1");
+
+    // THEN a row for each server, with the unreachable one in error
+    let output = test_jig.cmd.output().expect("executing rdap-test");
+    assert_eq!(output.status.code(), Some(1));
+    let matrix = matrix(&output.stdout);
+    let servers = matrix["servers"].as_array().expect("servers");
+    assert_eq!(servers.len(), 2);
+    assert_eq!(servers[0]["execution_errors"], 0);
+    assert_eq!(servers[1]["server"], "http://127.0.0.1:1");
+    assert!(servers[1]["execution_errors"].as_u64().expect("errors") > 0);
+}
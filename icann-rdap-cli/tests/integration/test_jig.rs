@@ -1,9 +1,11 @@
 use {
     assert_cmd::Command,
     icann_rdap_srv::{
-        config::{JsContactConversion, ListenConfig},
+        config::{JsContactConversion, ListenConfig, SearchConfig},
+        health::Readiness,
         server::{AppState, Listener},
         storage::{
+            data::DataStatus,
             mem::{config::MemConfig, ops::Mem},
             CommonConfig,
         },
@@ -64,6 +66,13 @@ impl TestJig {
             storage: mem.clone(),
             bootstrap: false,
             jscontact_conversion: JsContactConversion::None,
+            search_config: SearchConfig::default(),
+            redaction_policy: None,
+            auth: None,
+            rate_limit: None,
+            client_ip_source: None,
+            readiness: Readiness::ready(),
+            data_status: DataStatus::default(),
         };
         let _ = tracing_subscriber::fmt().try_init();
         let listener = Listener::listen(&ListenConfig::default())
@@ -91,11 +100,26 @@ impl TestJig {
     }
 
     pub async fn new_common_config(common_config: CommonConfig, cmd_type: CommandType) -> Self {
+        Self::new_search_config(common_config, SearchConfig::default(), cmd_type).await
+    }
+
+    pub async fn new_search_config(
+        common_config: CommonConfig,
+        search_config: SearchConfig,
+        cmd_type: CommandType,
+    ) -> Self {
         let mem = Mem::new(MemConfig::builder().common_config(common_config).build());
         let app_state = AppState {
             storage: mem.clone(),
             bootstrap: false,
             jscontact_conversion: JsContactConversion::None,
+            search_config,
+            redaction_policy: None,
+            auth: None,
+            rate_limit: None,
+            client_ip_source: None,
+            readiness: Readiness::ready(),
+            data_status: DataStatus::default(),
         };
         let _ = tracing_subscriber::fmt().try_init();
         let listener = Listener::listen(&ListenConfig::default())
@@ -8,9 +8,12 @@ use icann_rdap_common::prelude::ExtensionId;
 pub use reqwest::{header::HeaderValue, Client as ReqwestClient, Error as ReqwestError};
 use {
     icann_rdap_common::httpdata::HttpData,
-    reqwest::header::{
-        ACCESS_CONTROL_ALLOW_ORIGIN, CACHE_CONTROL, CONTENT_TYPE, EXPIRES, LOCATION, RETRY_AFTER,
-        STRICT_TRANSPORT_SECURITY,
+    reqwest::{
+        header::{
+            ACCEPT, ACCESS_CONTROL_ALLOW_ORIGIN, CACHE_CONTROL, CONTENT_TYPE, EXPIRES, LOCATION,
+            RETRY_AFTER, STRICT_TRANSPORT_SECURITY,
+        },
+        Method, Response,
     },
 };
 
@@ -275,6 +278,40 @@ pub(crate) async fn wrapped_request(
     //let response = response.error_for_status()?;
 
     // get the response
+    let http_data = response_http_data(request_uri, &response);
+    let text = response.text().await?;
+
+    Ok(WrappedResponse { http_data, text })
+}
+
+/// The response to a [probe_request].
+pub struct ProbeResponse {
+    pub http_data: HttpData,
+    pub text: String,
+}
+
+/// Sends a request to see how a server answers it.
+///
+/// Unlike the other request functions, the method and the accept header
+/// can be given and the request is not retried, so the response is
+/// returned as-is whatever its status code.
+pub async fn probe_request(
+    request_uri: &str,
+    method: Method,
+    accept: Option<&str>,
+    client: &Client,
+) -> Result<ProbeResponse, RdapClientError> {
+    let mut request = client.reqwest_client.request(method, request_uri);
+    if let Some(accept) = accept {
+        request = request.header(ACCEPT, accept);
+    }
+    let response = request.send().await?;
+    let http_data = response_http_data(request_uri, &response);
+    let text = response.text().await?;
+    Ok(ProbeResponse { http_data, text })
+}
+
+fn response_http_data(request_uri: &str, response: &Response) -> HttpData {
     let content_type = response
         .headers()
         .get(CONTENT_TYPE)
@@ -305,10 +342,9 @@ pub(crate) async fn wrapped_request(
         .map(|value| value.to_str().unwrap().to_string());
     let content_length = response.content_length();
     let status_code = response.status().as_u16();
-    let url = response.url().to_owned();
-    let text = response.text().await?;
+    let url = response.url();
 
-    let http_data = HttpData::now()
+    HttpData::now()
         .status_code(status_code)
         .and_location(location)
         .and_content_length(content_length)
@@ -325,7 +361,5 @@ pub(crate) async fn wrapped_request(
         .and_strict_transport_security(strict_transport_security)
         .and_retry_after(retry_after)
         .request_uri(request_uri)
-        .build();
-
-    Ok(WrappedResponse { http_data, text })
+        .build()
 }
@@ -34,6 +34,10 @@ pub mod prelude {
     #[doc(inline)]
     pub use crate::rdap::rdap_url_request;
     #[doc(inline)]
+    pub use crate::rdap::rdap_url_request_all_pages;
+    #[doc(inline)]
+    pub use crate::rdap::PagingLimits;
+    #[doc(inline)]
     pub use crate::rdap::QueryType;
     #[doc(inline)]
     pub use crate::RdapClientError;
@@ -77,6 +81,9 @@ pub enum RdapClientError {
 
     #[error(transparent)]
     DomainNameError(#[from] DomainNameError),
+
+    #[error("Page {page} of the search results returned HTTP status {status_code}")]
+    PageUnavailable { page: usize, status_code: u16 },
 }
 
 impl<T> From<PoisonError<T>> for RdapClientError {
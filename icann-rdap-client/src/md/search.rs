@@ -1,5 +1,6 @@
 use icann_rdap_common::response::{
-    DomainSearchResults, EntitySearchResults, NameserverSearchResults,
+    AutnumSearchResults, DomainSearchResults, EntitySearchResults, NameserverSearchResults,
+    NetworkSearchResults,
 };
 
 use super::{MdHeaderText, MdParams, MdUtil, ToMd};
@@ -49,6 +50,36 @@ impl ToMd for EntitySearchResults {
     }
 }
 
+impl ToMd for NetworkSearchResults {
+    fn to_md(&self, params: MdParams) -> String {
+        let mut md = String::new();
+        md.push_str(&self.common.to_md(params.from_parent()));
+        self.results.iter().for_each(|result| {
+            md.push_str(&result.to_md(MdParams {
+                heading_level: params.heading_level + 1,
+                ..params
+            }))
+        });
+        md.push('\n');
+        md
+    }
+}
+
+impl ToMd for AutnumSearchResults {
+    fn to_md(&self, params: MdParams) -> String {
+        let mut md = String::new();
+        md.push_str(&self.common.to_md(params.from_parent()));
+        self.results.iter().for_each(|result| {
+            md.push_str(&result.to_md(MdParams {
+                heading_level: params.heading_level + 1,
+                ..params
+            }))
+        });
+        md.push('\n');
+        md
+    }
+}
+
 impl MdUtil for DomainSearchResults {
     fn get_header_text(&self) -> MdHeaderText {
         MdHeaderText::builder()
@@ -72,3 +103,19 @@ impl MdUtil for NameserverSearchResults {
             .build()
     }
 }
+
+impl MdUtil for NetworkSearchResults {
+    fn get_header_text(&self) -> MdHeaderText {
+        MdHeaderText::builder()
+            .header_text("IP Network Search Results")
+            .build()
+    }
+}
+
+impl MdUtil for AutnumSearchResults {
+    fn get_header_text(&self) -> MdHeaderText {
+        MdHeaderText::builder()
+            .header_text("Autnum Search Results")
+            .build()
+    }
+}
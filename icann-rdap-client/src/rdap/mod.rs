@@ -1,5 +1,7 @@
 //! Code for managing RDAP queries.
 
+#[doc(inline)]
+pub use paging::*;
 #[doc(inline)]
 pub use qtype::*;
 // #[doc(inline)]
@@ -9,6 +11,7 @@ pub use request::*;
 #[doc(inline)]
 pub use rr::*;
 
+pub(crate) mod paging;
 pub(crate) mod qtype;
 pub mod redacted;
 pub(crate) mod request;
@@ -0,0 +1,394 @@
+//! Functions to follow the pages of RFC 8977 paged search results.
+
+use {
+    icann_rdap_common::response::{
+        AutnumSearchResults, DomainSearchResults, EntitySearchResults, NameserverSearchResults,
+        NetworkSearchResults, PagingMetadata, RdapResponse,
+    },
+    reqwest::Url,
+    tracing::debug,
+};
+
+use crate::{http::Client, RdapClientError};
+
+use super::{rdap_url_request, ResponseData};
+
+/// The default maximum number of pages to follow.
+pub const DEFAULT_MAX_PAGES: usize = 10;
+
+/// Limits on following the pages of search results.
+#[derive(Debug, Clone, Copy)]
+pub struct PagingLimits {
+    /// The maximum number of pages to fetch, including the first page.
+    pub max_pages: usize,
+
+    /// If specified, no more pages are fetched once this many results have been received,
+    /// and merged results are truncated to this many.
+    pub max_results: Option<usize>,
+}
+
+#[buildstructor::buildstructor]
+impl PagingLimits {
+    /// Creates paging limits.
+    ///
+    /// If `max_pages` is not given, it defaults to [DEFAULT_MAX_PAGES].
+    #[builder(visibility = "pub")]
+    fn new(max_pages: Option<usize>, max_results: Option<usize>) -> Self {
+        Self {
+            max_pages: max_pages.unwrap_or(DEFAULT_MAX_PAGES),
+            max_results,
+        }
+    }
+}
+
+impl Default for PagingLimits {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Iterates over the pages of search results by following the RFC 8977 `next` links.
+///
+/// ```no_run
+/// use icann_rdap_client::prelude::*;
+/// use icann_rdap_client::rdap::{PagingLimits, SearchPages};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), RdapClientError> {
+///     let client = create_client(&ClientConfig::default())?;
+///     let mut pages = SearchPages::new(
+///         "https://example.com/rdap/domains?name=foo*.example",
+///         &client,
+///         PagingLimits::default(),
+///     );
+///     while let Some(page) = pages.next_page().await {
+///         let page = page?;
+///         println!("{}", page.rdap_type);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct SearchPages<'a> {
+    client: &'a Client,
+    limits: PagingLimits,
+    next_url: Option<String>,
+    pages: usize,
+    results: usize,
+}
+
+impl<'a> SearchPages<'a> {
+    /// Creates an iterator starting with the query for the first page.
+    pub fn new(url: &str, client: &'a Client, limits: PagingLimits) -> Self {
+        Self {
+            client,
+            limits,
+            next_url: Some(url.to_string()),
+            pages: 0,
+            results: 0,
+        }
+    }
+
+    /// Creates an iterator over the pages after a first page that has already been fetched.
+    pub fn after(first: &ResponseData, client: &'a Client, limits: PagingLimits) -> Self {
+        let mut pages = Self {
+            client,
+            limits,
+            next_url: None,
+            pages: 0,
+            results: 0,
+        };
+        pages.update(first);
+        pages
+    }
+
+    /// Fetches the next page, if there is one and the limits have not been reached.
+    pub async fn next_page(&mut self) -> Option<Result<ResponseData, RdapClientError>> {
+        if self.limits_reached() {
+            return None;
+        }
+        let url = self.next_url.take()?;
+        debug!(
+            "Fetching page {} of search results from {url}",
+            self.pages + 1
+        );
+        let response = rdap_url_request(&url, self.client).await;
+        if let Ok(response) = &response {
+            self.update(response);
+        }
+        Some(response)
+    }
+
+    fn limits_reached(&self) -> bool {
+        self.pages >= self.limits.max_pages
+            || self
+                .limits
+                .max_results
+                .is_some_and(|max_results| self.results >= max_results)
+    }
+
+    fn update(&mut self, response: &ResponseData) {
+        self.pages += 1;
+        self.results += search_results_len(&response.rdap);
+        self.next_url = next_page_url(response);
+    }
+}
+
+/// Makes an RDAP request with a full RDAP URL and follows the `next` links
+/// of paged search results, merging the pages into one set of results.
+///
+/// The merged results carry the paging metadata of the last page fetched,
+/// so a `next` link remains if the limits stopped the paging early.
+pub async fn rdap_url_request_all_pages(
+    url: &str,
+    client: &Client,
+    limits: PagingLimits,
+) -> Result<ResponseData, RdapClientError> {
+    let first = rdap_url_request(url, client).await?;
+    rdap_follow_pages(first, client, limits).await
+}
+
+/// Follows the `next` links of a response with paged search results,
+/// merging the pages into one set of results.
+///
+/// Responses that are not search results or do not have a `next` link
+/// are returned as is. If a later page is not a 200 response, an
+/// [RdapClientError::PageUnavailable] error is returned rather than
+/// partial results.
+pub async fn rdap_follow_pages(
+    first: ResponseData,
+    client: &Client,
+    limits: PagingLimits,
+) -> Result<ResponseData, RdapClientError> {
+    let mut pages = SearchPages::after(&first, client, limits);
+    let mut merged = first;
+    while let Some(page) = pages.next_page().await {
+        let page = page?;
+        let status_code = page.http_data.status_code();
+        if status_code != 200 {
+            return Err(RdapClientError::PageUnavailable {
+                page: pages.pages,
+                status_code,
+            });
+        }
+        merged.rdap = merge_page(merged.rdap, page.rdap);
+    }
+    if let Some(max_results) = limits.max_results {
+        merged.rdap = truncate_results(merged.rdap, max_results);
+    }
+    Ok(merged)
+}
+
+/// Gets the absolute URL of the next page, resolving relative links
+/// against the URL of the response.
+fn next_page_url(response: &ResponseData) -> Option<String> {
+    let href = paging_metadata(&response.rdap)?
+        .next_link()?
+        .href
+        .as_deref()?;
+    match response.http_data.request_uri() {
+        Some(request_uri) => Url::parse(request_uri)
+            .and_then(|base| base.join(href))
+            .ok()
+            .map(|url| url.to_string()),
+        None => Url::parse(href).ok().map(|url| url.to_string()),
+    }
+}
+
+fn paging_metadata(rdap: &RdapResponse) -> Option<&PagingMetadata> {
+    match rdap {
+        RdapResponse::DomainSearchResults(r) => r.paging_metadata.as_ref(),
+        RdapResponse::NameserverSearchResults(r) => r.paging_metadata.as_ref(),
+        RdapResponse::EntitySearchResults(r) => r.paging_metadata.as_ref(),
+        RdapResponse::NetworkSearchResults(r) => r.paging_metadata.as_ref(),
+        RdapResponse::AutnumSearchResults(r) => r.paging_metadata.as_ref(),
+        _ => None,
+    }
+}
+
+fn search_results_len(rdap: &RdapResponse) -> usize {
+    match rdap {
+        RdapResponse::DomainSearchResults(r) => r.results.len(),
+        RdapResponse::NameserverSearchResults(r) => r.results.len(),
+        RdapResponse::EntitySearchResults(r) => r.results.len(),
+        RdapResponse::NetworkSearchResults(r) => r.results.len(),
+        RdapResponse::AutnumSearchResults(r) => r.results.len(),
+        _ => 0,
+    }
+}
+
+/// Appends the results of a page to the results already merged.
+/// Pages of a different type are ignored.
+fn merge_page(merged: RdapResponse, page: RdapResponse) -> RdapResponse {
+    match (merged, page) {
+        (
+            RdapResponse::DomainSearchResults(mut merged),
+            RdapResponse::DomainSearchResults(page),
+        ) => {
+            let page = *page;
+            merged.results.extend(page.results);
+            RdapResponse::DomainSearchResults(Box::new(DomainSearchResults {
+                paging_metadata: page.paging_metadata,
+                ..*merged
+            }))
+        }
+        (
+            RdapResponse::NameserverSearchResults(mut merged),
+            RdapResponse::NameserverSearchResults(page),
+        ) => {
+            let page = *page;
+            merged.results.extend(page.results);
+            RdapResponse::NameserverSearchResults(Box::new(NameserverSearchResults {
+                paging_metadata: page.paging_metadata,
+                ..*merged
+            }))
+        }
+        (
+            RdapResponse::EntitySearchResults(mut merged),
+            RdapResponse::EntitySearchResults(page),
+        ) => {
+            let page = *page;
+            merged.results.extend(page.results);
+            RdapResponse::EntitySearchResults(Box::new(EntitySearchResults {
+                paging_metadata: page.paging_metadata,
+                ..*merged
+            }))
+        }
+        (
+            RdapResponse::NetworkSearchResults(mut merged),
+            RdapResponse::NetworkSearchResults(page),
+        ) => {
+            let page = *page;
+            merged.results.extend(page.results);
+            RdapResponse::NetworkSearchResults(Box::new(NetworkSearchResults {
+                paging_metadata: page.paging_metadata,
+                ..*merged
+            }))
+        }
+        (
+            RdapResponse::AutnumSearchResults(mut merged),
+            RdapResponse::AutnumSearchResults(page),
+        ) => {
+            let page = *page;
+            merged.results.extend(page.results);
+            RdapResponse::AutnumSearchResults(Box::new(AutnumSearchResults {
+                paging_metadata: page.paging_metadata,
+                ..*merged
+            }))
+        }
+        (merged, _) => merged,
+    }
+}
+
+fn truncate_results(mut rdap: RdapResponse, max_results: usize) -> RdapResponse {
+    match &mut rdap {
+        RdapResponse::DomainSearchResults(r) => r.results.truncate(max_results),
+        RdapResponse::NameserverSearchResults(r) => r.results.truncate(max_results),
+        RdapResponse::EntitySearchResults(r) => r.results.truncate(max_results),
+        RdapResponse::NetworkSearchResults(r) => r.results.truncate(max_results),
+        RdapResponse::AutnumSearchResults(r) => r.results.truncate(max_results),
+        _ => {}
+    }
+    rdap
+}
+
+#[cfg(test)]
+mod tests {
+    use icann_rdap_common::{
+        httpdata::HttpData,
+        prelude::{Domain, DomainSearchResults, Link, PagingMetadata, RdapResponse, ToResponse},
+    };
+
+    use crate::rdap::ResponseData;
+
+    use super::{merge_page, next_page_url, truncate_results};
+
+    fn search_results(names: &[&str], next: Option<&str>) -> RdapResponse {
+        let links = next
+            .map(|href| {
+                vec![Link::builder()
+                    .value("https://example.com/rdap/domains?name=foo*")
+                    .rel("next")
+                    .href(href)
+                    .build()]
+            })
+            .unwrap_or_default();
+        DomainSearchResults::response_obj()
+            .results(
+                names
+                    .iter()
+                    .map(|name| Domain::builder().ldh_name(*name).build())
+                    .collect(),
+            )
+            .paging_metadata(PagingMetadata::builder().links(links).build())
+            .build()
+            .to_response()
+    }
+
+    fn names(rdap: &RdapResponse) -> Vec<&str> {
+        let RdapResponse::DomainSearchResults(results) = rdap else {
+            panic!("not domain search results")
+        };
+        results
+            .results
+            .iter()
+            .filter_map(|d| d.ldh_name())
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_page_appends_results_and_takes_paging_metadata() {
+        // GIVEN a first page with a next link and a last page without one
+        let first = search_results(&["foo1.example", "foo2.example"], Some("/rdap/next"));
+        let last = search_results(&["foo3.example"], None);
+
+        // WHEN merged
+        let actual = merge_page(first, last);
+
+        // THEN the results are appended and there is no more next link
+        assert_eq!(
+            names(&actual),
+            ["foo1.example", "foo2.example", "foo3.example"]
+        );
+        let RdapResponse::DomainSearchResults(results) = actual else {
+            panic!("not domain search results")
+        };
+        assert!(results
+            .paging_metadata
+            .expect("paging metadata")
+            .next_link()
+            .is_none());
+    }
+
+    #[test]
+    fn test_truncate_results() {
+        // GIVEN search results
+        let rdap = search_results(&["foo1.example", "foo2.example"], None);
+
+        // WHEN truncated
+        let actual = truncate_results(rdap, 1);
+
+        // THEN
+        assert_eq!(names(&actual), ["foo1.example"]);
+    }
+
+    #[test]
+    fn test_relative_next_link_resolved_against_request_uri() {
+        // GIVEN a response with a relative next link
+        let response = ResponseData {
+            rdap: search_results(&["foo1.example"], Some("/rdap/domains?name=foo*&cursor=MQ")),
+            rdap_type: "Domain Search Results".to_string(),
+            http_data: HttpData::example()
+                .request_uri("https://example.com/rdap/domains?name=foo*")
+                .build(),
+        };
+
+        // WHEN
+        let actual = next_page_url(&response);
+
+        // THEN
+        assert_eq!(
+            actual.as_deref(),
+            Some("https://example.com/rdap/domains?name=foo*&cursor=MQ")
+        );
+    }
+}
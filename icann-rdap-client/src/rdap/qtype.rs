@@ -9,7 +9,7 @@ use icann_rdap_common::rdns::{ip_to_reverse_dns, reverse_dns_to_ip};
 
 use {
     cidr::{IpCidr, Ipv4Cidr, Ipv6Cidr},
-    icann_rdap_common::{check::StringCheck, dns_types::DomainName},
+    icann_rdap_common::{check::StringCheck, dns_types::DomainName, response::FieldSet},
     pct_str::{PctString, UriReserved},
     regex::Regex,
     strum_macros::Display,
@@ -71,6 +71,33 @@ pub enum QueryType {
     #[strum(serialize = "Nameserver IP Address Search")]
     NameserverIpSearch(IpAddr),
 
+    #[strum(serialize = "IP Network Handle Search")]
+    NetworkHandleSearch(String),
+
+    #[strum(serialize = "IP Network Name Search")]
+    NetworkNameSearch(String),
+
+    #[strum(serialize = "Autnum Handle Search")]
+    AutnumHandleSearch(String),
+
+    #[strum(serialize = "Autnum Name Search")]
+    AutnumNameSearch(String),
+
+    #[strum(serialize = "Domain Reverse Search")]
+    DomainReverseSearch(RelatedEntitySearch),
+
+    #[strum(serialize = "Nameserver Reverse Search")]
+    NameserverReverseSearch(RelatedEntitySearch),
+
+    #[strum(serialize = "Entity Reverse Search")]
+    EntityReverseSearch(RelatedEntitySearch),
+
+    #[strum(serialize = "IP Network Reverse Search")]
+    NetworkReverseSearch(RelatedEntitySearch),
+
+    #[strum(serialize = "Autnum Reverse Search")]
+    AutnumReverseSearch(RelatedEntitySearch),
+
     #[strum(serialize = "Server Help Lookup")]
     Help,
 
@@ -141,11 +168,65 @@ impl QueryType {
             Self::NameserverIpSearch(value) => {
                 search_query(&value.to_string(), "nameservers?ip", base_url)
             }
+            Self::NetworkHandleSearch(value) => search_query(value, "ips?handle", base_url),
+            Self::NetworkNameSearch(value) => search_query(value, "ips?name", base_url),
+            Self::AutnumHandleSearch(value) => search_query(value, "autnums?handle", base_url),
+            Self::AutnumNameSearch(value) => search_query(value, "autnums?name", base_url),
+            Self::DomainReverseSearch(search) => search.query_url("domains", base_url),
+            Self::NameserverReverseSearch(search) => search.query_url("nameservers", base_url),
+            Self::EntityReverseSearch(search) => search.query_url("entities", base_url),
+            Self::NetworkReverseSearch(search) => search.query_url("ips", base_url),
+            Self::AutnumReverseSearch(search) => search.query_url("autnums", base_url),
             Self::Help => Ok(format!("{base_url}/help")),
             Self::Url(url) => Ok(url.to_owned()),
         }
     }
 
+    /// Gets the query URL requesting an RFC 8982 field set.
+    ///
+    /// Field sets only apply to searches, so the field set is ignored for other
+    /// types of queries. An explicit URL is a search if its path ends with
+    /// the path segment of a search, such as `domains`.
+    pub fn query_url_with_field_set(
+        &self,
+        base_url: &str,
+        field_set: FieldSet,
+    ) -> Result<String, RdapClientError> {
+        let url = self.query_url(base_url)?;
+        let is_search = match self {
+            Self::Url(url) => is_search_url(url),
+            _ => self.is_search(),
+        };
+        if !is_search {
+            return Ok(url);
+        }
+        let separator = if url.contains('?') { '&' } else { '?' };
+        Ok(format!("{url}{separator}fieldSet={field_set}"))
+    }
+
+    /// Returns true if the query is a search.
+    pub fn is_search(&self) -> bool {
+        matches!(
+            self,
+            Self::EntityNameSearch(_)
+                | Self::EntityHandleSearch(_)
+                | Self::DomainNameSearch(_)
+                | Self::DomainNsNameSearch(_)
+                | Self::DomainNsIpSearch(_)
+                | Self::NameserverNameSearch(_)
+                | Self::NameserverIpSearch(_)
+                | Self::NetworkHandleSearch(_)
+                | Self::NetworkNameSearch(_)
+                | Self::AutnumHandleSearch(_)
+                | Self::AutnumNameSearch(_)
+                | Self::DomainReverseSearch(_)
+                | Self::NameserverReverseSearch(_)
+                | Self::EntityReverseSearch(_)
+                | Self::NetworkReverseSearch(_)
+                | Self::AutnumReverseSearch(_)
+        )
+    }
+
     pub fn domain(domain_name: &str) -> Result<Self, RdapClientError> {
         Ok(Self::Domain(DomainName::from_str(domain_name)?))
     }
@@ -224,6 +305,17 @@ impl QueryType {
     }
 }
 
+/// The last path segments of search URLs.
+const SEARCH_SEGMENTS: [&str; 5] = ["domains", "nameservers", "entities", "ips", "autnums"];
+
+fn is_search_url(url: &str) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    path.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .is_some_and(|segment| SEARCH_SEGMENTS.contains(&segment))
+}
+
 fn search_query(value: &str, path_query: &str, base_url: &str) -> Result<String, RdapClientError> {
     Ok(format!(
         "{base_url}/{path_query}={}",
@@ -231,6 +323,82 @@ fn search_query(value: &str, path_query: &str, base_url: &str) -> Result<String,
     ))
 }
 
+/// The conditions on the related entity of an RFC 9536 reverse search.
+///
+/// ```rust
+/// use icann_rdap_client::rdap::{QueryType, RelatedEntitySearch};
+///
+/// let query = QueryType::DomainReverseSearch(
+///     RelatedEntitySearch::builder()
+///         .full_name("Bob*")
+///         .role("registrant")
+///         .build(),
+/// );
+/// let url = query.query_url("https://example.com/rdap").unwrap();
+/// assert_eq!(
+///     url,
+///     "https://example.com/rdap/domains/reverse_search/entity?fn=Bob%2A&role=registrant"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelatedEntitySearch {
+    /// The handle of the related entity.
+    pub handle: Option<String>,
+
+    /// The full name (vCard `fn`) of the related entity.
+    pub full_name: Option<String>,
+
+    /// An email address of the related entity.
+    pub email: Option<String>,
+
+    /// The role of the related entity.
+    pub role: Option<String>,
+}
+
+#[buildstructor::buildstructor]
+impl RelatedEntitySearch {
+    #[builder(visibility = "pub")]
+    fn new(
+        handle: Option<String>,
+        full_name: Option<String>,
+        email: Option<String>,
+        role: Option<String>,
+    ) -> Self {
+        Self {
+            handle,
+            full_name,
+            email,
+            role,
+        }
+    }
+
+    fn query_url(&self, searchable: &str, base_url: &str) -> Result<String, RdapClientError> {
+        let query = [
+            ("handle", &self.handle),
+            ("fn", &self.full_name),
+            ("email", &self.email),
+            ("role", &self.role),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| {
+            value.as_ref().map(|value| {
+                format!(
+                    "{name}={}",
+                    PctString::encode(value.chars(), UriReserved::Any)
+                )
+            })
+        })
+        .collect::<Vec<String>>();
+        if query.is_empty() || (query.len() == 1 && self.role.is_some()) {
+            return Err(RdapClientError::InvalidQueryValue);
+        }
+        Ok(format!(
+            "{base_url}/{searchable}/reverse_search/entity?{}",
+            query.join("&")
+        ))
+    }
+}
+
 impl FromStr for QueryType {
     type Err = RdapClientError;
 
@@ -623,6 +791,59 @@ mod tests {
         assert_eq!(actual, "https://example.com/domains?name=foo")
     }
 
+    #[test]
+    fn test_domain_name_search_query_url_with_field_set() {
+        // GIVEN
+        let q = QueryType::DomainNameSearch("foo".to_string());
+
+        // WHEN
+        let actual = q
+            .query_url_with_field_set("https://example.com", FieldSet::Brief)
+            .expect("query url");
+
+        // THEN
+        assert_eq!(
+            actual,
+            "https://example.com/domains?name=foo&fieldSet=brief"
+        )
+    }
+
+    #[test]
+    fn test_domain_lookup_query_url_with_field_set_ignored() {
+        // GIVEN
+        let q = QueryType::domain("foo.example").expect("domain");
+
+        // WHEN
+        let actual = q
+            .query_url_with_field_set("https://example.com", FieldSet::Id)
+            .expect("query url");
+
+        // THEN
+        assert_eq!(actual, "https://example.com/domain/foo.example")
+    }
+
+    #[test]
+    fn test_url_query_url_with_field_set() {
+        // GIVEN a search URL and a lookup URL
+        let search = QueryType::Url("https://example.com/domains?name=foo*".to_string());
+        let lookup = QueryType::Url("https://example.com/domain/foo.example".to_string());
+
+        // WHEN
+        let search = search
+            .query_url_with_field_set("https://example.com", FieldSet::Brief)
+            .expect("query url");
+        let lookup = lookup
+            .query_url_with_field_set("https://example.com", FieldSet::Brief)
+            .expect("query url");
+
+        // THEN the field set is only added to the search
+        assert_eq!(
+            search,
+            "https://example.com/domains?name=foo*&fieldSet=brief"
+        );
+        assert_eq!(lookup, "https://example.com/domain/foo.example");
+    }
+
     #[test]
     fn test_domain_ns_name_search_query_url() {
         // GIVEN
@@ -670,4 +891,83 @@ mod tests {
         // THEN
         assert_eq!(actual, "https://example.com/nameservers?ip=1.1.1.1")
     }
+
+    #[test]
+    fn test_network_handle_search_query_url() {
+        // GIVEN
+        let q = QueryType::NetworkHandleSearch("NET-10-*".to_string());
+
+        // WHEN
+        let actual = q.query_url("https://example.com").expect("query url");
+
+        // THEN
+        assert_eq!(actual, "https://example.com/ips?handle=NET-10-%2A")
+    }
+
+    #[test]
+    fn test_autnum_name_search_query_url() {
+        // GIVEN
+        let q = QueryType::AutnumNameSearch("EXAMPLE-AS".to_string());
+
+        // WHEN
+        let actual = q.query_url("https://example.com").expect("query url");
+
+        // THEN
+        assert_eq!(actual, "https://example.com/autnums?name=EXAMPLE-AS");
+        assert!(q.is_search());
+    }
+
+    #[test]
+    fn test_domain_reverse_search_query_url() {
+        // GIVEN
+        let q = QueryType::DomainReverseSearch(
+            RelatedEntitySearch::builder()
+                .handle("BOB-1")
+                .role("registrant")
+                .build(),
+        );
+
+        // WHEN
+        let actual = q.query_url("https://example.com").expect("query url");
+
+        // THEN
+        assert_eq!(
+            actual,
+            "https://example.com/domains/reverse_search/entity?handle=BOB-1&role=registrant"
+        );
+        assert!(q.is_search());
+    }
+
+    #[test]
+    fn test_network_reverse_search_query_url() {
+        // GIVEN
+        let q = QueryType::NetworkReverseSearch(
+            RelatedEntitySearch::builder()
+                .full_name("Bob Smurd")
+                .email("bob@example.com")
+                .build(),
+        );
+
+        // WHEN
+        let actual = q.query_url("https://example.com").expect("query url");
+
+        // THEN
+        assert_eq!(
+            actual,
+            "https://example.com/ips/reverse_search/entity?fn=Bob%20Smurd&email=bob%40example.com"
+        )
+    }
+
+    #[test]
+    fn test_reverse_search_with_only_role_is_invalid() {
+        // GIVEN
+        let q =
+            QueryType::AutnumReverseSearch(RelatedEntitySearch::builder().role("abuse").build());
+
+        // WHEN
+        let actual = q.query_url("https://example.com");
+
+        // THEN
+        assert!(matches!(actual, Err(RdapClientError::InvalidQueryValue)));
+    }
 }
@@ -5,11 +5,6 @@ pub use registered_redactions::*;
 #[doc(inline)]
 pub use simplify::*;
 
+pub mod paths;
 pub(crate) mod registered_redactions;
 pub(crate) mod simplify;
-mod simplify_addr;
-mod simplify_email;
-mod simplify_ids;
-mod simplify_names;
-mod simplify_org;
-mod simplify_phones;
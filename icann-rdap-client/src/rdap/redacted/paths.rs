@@ -0,0 +1,270 @@
+//! Locates the values found by the JSONPath expressions of redactions.
+
+use {
+    jsonpath_rust::{parser::model::JpQuery, query::js_path_process},
+    serde_json::Value,
+};
+
+use crate::rdap::redacted::RedactedName;
+
+/// Gets the JSON pointers of the values found by a JSONPath query.
+pub fn matched_pointers(rdap: &Value, query: &JpQuery) -> Vec<String> {
+    js_path_process(query, rdap)
+        .map(|found| {
+            found
+                .into_iter()
+                .filter_map(|found| to_pointer(&found.path()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Converts a normalized JSONPath, such as `$['entities'][0]['handle']`,
+/// to a JSON pointer, such as `/entities/0/handle`.
+pub fn to_pointer(path: &str) -> Option<String> {
+    let mut pointer = String::new();
+    let mut chars = path.strip_prefix('$')?.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '[' {
+            return None;
+        }
+        pointer.push('/');
+        if chars.next_if_eq(&'\'').is_some() {
+            loop {
+                match chars.next()? {
+                    '\\' => pointer.push(chars.next()?),
+                    '\'' => break,
+                    '~' => pointer.push_str("~0"),
+                    '/' => pointer.push_str("~1"),
+                    c => pointer.push(c),
+                }
+            }
+        } else {
+            while let Some(digit) = chars.next_if(|c| c.is_ascii_digit()) {
+                pointer.push(digit);
+            }
+        }
+        if chars.next()? != ']' {
+            return None;
+        }
+    }
+    Some(pointer)
+}
+
+/// Gets the JSON pointer of the RDAP object holding the value at the pointer,
+/// which is the closest object with an `objectClassName`, or the root.
+pub fn enclosing_object(rdap: &Value, pointer: &str) -> String {
+    let mut object = pointer;
+    loop {
+        if let Some(Value::Object(map)) = rdap.pointer(object) {
+            if map.contains_key("objectClassName") {
+                return object.to_string();
+            }
+        }
+        match object.rfind('/') {
+            Some(i) => object = &object[..i],
+            None => return String::new(),
+        }
+    }
+}
+
+/// The paths of the registered redactions, as given by the gTLD RDAP profile.
+///
+/// Entities are matched by any of their roles, not only by the first one.
+pub fn registered_path(name: RedactedName) -> &'static str {
+    match name {
+        RedactedName::RegistryDomainId => "$.handle",
+        RedactedName::RegistryRegistrantId => "$.entities[?(@.roles[?(@=='registrant')])].handle",
+        RedactedName::RegistrantName => {
+            "$.entities[?(@.roles[?(@=='registrant')])].vcardArray[1][?(@[0]=='fn')][3]"
+        }
+        RedactedName::RegistrantOrganization => {
+            "$.entities[?(@.roles[?(@=='registrant')])].vcardArray[1][?(@[0]=='org')][3]"
+        }
+        RedactedName::RegistrantStreet => {
+            "$.entities[?(@.roles[?(@=='registrant')])].vcardArray[1][?(@[0]=='adr')][3][2]"
+        }
+        RedactedName::RegistrantCity => {
+            "$.entities[?(@.roles[?(@=='registrant')])].vcardArray[1][?(@[0]=='adr')][3][3]"
+        }
+        RedactedName::RegistrantPostalCode => {
+            "$.entities[?(@.roles[?(@=='registrant')])].vcardArray[1][?(@[0]=='adr')][3][5]"
+        }
+        RedactedName::RegistrantPhone | RedactedName::RegistrantPhoneExt => {
+            "$.entities[?(@.roles[?(@=='registrant')])].vcardArray[1][?(@[1].type=='voice')][3]"
+        }
+        RedactedName::RegistrantFax | RedactedName::RegistrantFaxExt => {
+            "$.entities[?(@.roles[?(@=='registrant')])].vcardArray[1][?(@[1].type=='fax')][3]"
+        }
+        RedactedName::RegistrantEmail => {
+            "$.entities[?(@.roles[?(@=='registrant')])].vcardArray[1][?(@[0]=='email')][3]"
+        }
+        RedactedName::RegistryTechId => "$.entities[?(@.roles[?(@=='technical')])].handle",
+        RedactedName::TechName => {
+            "$.entities[?(@.roles[?(@=='technical')])].vcardArray[1][?(@[0]=='fn')][3]"
+        }
+        RedactedName::TechPhone | RedactedName::TechPhoneExt => {
+            "$.entities[?(@.roles[?(@=='technical')])].vcardArray[1][?(@[1].type=='voice')][3]"
+        }
+        RedactedName::TechEmail => {
+            "$.entities[?(@.roles[?(@=='technical')])].vcardArray[1][?(@[0]=='email')][3]"
+        }
+    }
+}
+
+/// The paths of the registered redactions in the JSContact card of an entity,
+/// including its localizations, which are kept in sync with the jCard.
+pub fn registered_jscontact_path(name: &RedactedName) -> Option<&'static str> {
+    match name {
+        RedactedName::RegistryDomainId
+        | RedactedName::RegistryRegistrantId
+        | RedactedName::RegistryTechId => None,
+        RedactedName::RegistrantName | RedactedName::TechName => {
+            Some("$.jscontact_card..name.full")
+        }
+        RedactedName::RegistrantOrganization => Some("$.jscontact_card..organizations.org.name"),
+        RedactedName::RegistrantStreet => {
+            Some("$.jscontact_card..addresses.*.components[?(@.kind=='name')].value")
+        }
+        RedactedName::RegistrantCity => {
+            Some("$.jscontact_card..addresses.*.components[?(@.kind=='locality')].value")
+        }
+        RedactedName::RegistrantPostalCode => {
+            Some("$.jscontact_card..addresses.*.components[?(@.kind=='postalcode')].value")
+        }
+        RedactedName::RegistrantPhone
+        | RedactedName::RegistrantPhoneExt
+        | RedactedName::TechPhone
+        | RedactedName::TechPhoneExt => Some("$.jscontact_card.phones.voice.number"),
+        RedactedName::RegistrantFax | RedactedName::RegistrantFaxExt => {
+            Some("$.jscontact_card.phones.fax.number")
+        }
+        RedactedName::RegistrantEmail | RedactedName::TechEmail => {
+            Some("$.jscontact_card.emails.email.address")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {jsonpath_rust::parser::parse_json_path, serde_json::json};
+
+    use crate::rdap::redacted::RedactedName;
+
+    use super::{
+        enclosing_object, matched_pointers, registered_jscontact_path, registered_path, to_pointer,
+    };
+
+    #[test]
+    fn test_to_pointer() {
+        // GIVEN normalized paths
+        let paths = [
+            "$",
+            "$['handle']",
+            "$['entities'][0]['vcardArray'][1][2][3]",
+            "$['a/b~c']['it\\'s']",
+        ];
+
+        // WHEN converted
+        let actual = paths.map(to_pointer);
+
+        // THEN
+        assert_eq!(
+            actual,
+            [
+                Some("".to_string()),
+                Some("/handle".to_string()),
+                Some("/entities/0/vcardArray/1/2/3".to_string()),
+                Some("/a~1b~0c/it's".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_pointer_not_normalized() {
+        // GIVEN a path that is not normalized
+        let path = "$.handle";
+
+        // WHEN converted
+        let actual = to_pointer(path);
+
+        // THEN
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn test_enclosing_object() {
+        // GIVEN a domain with an entity
+        let rdap = json!({
+            "objectClassName": "domain",
+            "entities": [{
+                "objectClassName": "entity",
+                "vcardArray": ["vcard", [["fn", {}, "text", "Alice"]]]
+            }]
+        });
+
+        // WHEN the enclosing objects are found
+        let of_fn = enclosing_object(&rdap, "/entities/0/vcardArray/1/0/3");
+        let of_entities = enclosing_object(&rdap, "/entities");
+
+        // THEN
+        assert_eq!(of_fn, "/entities/0");
+        assert_eq!(of_entities, "");
+    }
+
+    #[test]
+    fn test_registered_path_matches_any_role() {
+        // GIVEN a registrant whose first role is not registrant
+        let rdap = json!({
+            "objectClassName": "domain",
+            "entities": [
+                { "objectClassName": "entity", "handle": "TECH", "roles": ["technical"] },
+                { "objectClassName": "entity", "handle": "REG", "roles": ["technical", "registrant"] }
+            ]
+        });
+        let query = parse_json_path(registered_path(RedactedName::RegistryRegistrantId))
+            .expect("parsing registered path");
+
+        // WHEN the registrant ID is found
+        let actual = matched_pointers(&rdap, &query);
+
+        // THEN
+        assert_eq!(actual, vec!["/entities/1/handle".to_string()]);
+    }
+
+    #[test]
+    fn test_registered_jscontact_path_matches_localizations() {
+        // GIVEN an entity with a JSContact card with a localized name
+        let rdap = json!({
+            "objectClassName": "entity",
+            "jscontact_card": {
+                "@type": "Card",
+                "version": "2.0",
+                "name": { "full": "Alice" },
+                "addresses": {
+                    "addr": { "full": "Alice", "components": [{ "kind": "locality", "value": "Quebec" }] }
+                },
+                "localizations": {
+                    "fr": { "name": { "full": "Alice" } }
+                }
+            }
+        });
+        let query = parse_json_path(
+            registered_jscontact_path(&RedactedName::RegistrantName).expect("JSContact path"),
+        )
+        .expect("parsing JSContact path");
+
+        // WHEN the names are found
+        let mut actual = matched_pointers(&rdap, &query);
+        actual.sort();
+
+        // THEN the name and its localization are found but not the address
+        assert_eq!(
+            actual,
+            vec![
+                "/jscontact_card/localizations/fr/name/full".to_string(),
+                "/jscontact_card/name/full".to_string(),
+            ]
+        );
+    }
+}
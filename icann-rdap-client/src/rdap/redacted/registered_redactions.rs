@@ -1,48 +1,9 @@
 //! Determines of an RFC 9537 registered redaction is present.
 
-use {
-    icann_rdap_common::response::{Entity, EntityRole, RdapResponse},
-    strum_macros::{Display, EnumString},
-};
-
-/// Redacted types in the IANA registry
-#[derive(Debug, PartialEq, Eq, EnumString, Display)]
-pub enum RedactedName {
-    #[strum(serialize = "Registry Domain ID")]
-    RegistryDomainId,
-    #[strum(serialize = "Registry Registrant ID")]
-    RegistryRegistrantId,
-    #[strum(serialize = "Registrant Name")]
-    RegistrantName,
-    #[strum(serialize = "Registrant Organization")]
-    RegistrantOrganization,
-    #[strum(serialize = "Registrant Street")]
-    RegistrantStreet,
-    #[strum(serialize = "Registrant City")]
-    RegistrantCity,
-    #[strum(serialize = "Registrant Postal Code")]
-    RegistrantPostalCode,
-    #[strum(serialize = "Registrant Phone")]
-    RegistrantPhone,
-    #[strum(serialize = "Registrant Phone Ext")]
-    RegistrantPhoneExt,
-    #[strum(serialize = "Registrant Fax")]
-    RegistrantFax,
-    #[strum(serialize = "Registrant Fax Ext")]
-    RegistrantFaxExt,
-    #[strum(serialize = "Registrant Email")]
-    RegistrantEmail,
-    #[strum(serialize = "Registry Tech ID")]
-    RegistryTechId,
-    #[strum(serialize = "Tech Name")]
-    TechName,
-    #[strum(serialize = "Tech Phone")]
-    TechPhone,
-    #[strum(serialize = "Tech Phone Ext")]
-    TechPhoneExt,
-    #[strum(serialize = "Tech Email")]
-    TechEmail,
-}
+use icann_rdap_common::response::{Entity, EntityRole, RdapResponse};
+
+#[doc(inline)]
+pub use icann_rdap_common::response::redacted::RedactedName;
 
 /// This function looks at the RDAP response to see if a
 /// redaction is present where the type of redaction is registered
@@ -1,108 +1,394 @@
 //! Changes RFC 9537 redactions to simple redactions
 
-use crate::rdap::redacted::simplify_addr::simplify_registrant_city;
-use crate::rdap::redacted::simplify_addr::simplify_registrant_postal_code;
-use crate::rdap::redacted::simplify_addr::simplify_registrant_street;
-use crate::rdap::redacted::simplify_email::simplify_registrant_email;
-use crate::rdap::redacted::simplify_email::simplify_tech_email;
-use crate::rdap::redacted::simplify_ids::simplify_registry_domain_id;
-use crate::rdap::redacted::simplify_ids::simplify_registry_registrant_id;
-use crate::rdap::redacted::simplify_ids::simplify_registry_tech_id;
-use crate::rdap::redacted::simplify_names::simplify_registrant_name;
-use crate::rdap::redacted::simplify_names::simplify_tech_name;
-use crate::rdap::redacted::simplify_org::simplify_registrant_org;
-use crate::rdap::redacted::simplify_phones::simplify_registrant_fax;
-use crate::rdap::redacted::simplify_phones::simplify_registrant_fax_ext;
-use crate::rdap::redacted::simplify_phones::simplify_registrant_phone;
-use crate::rdap::redacted::simplify_phones::simplify_registrant_phone_ext;
-use crate::rdap::redacted::simplify_phones::simplify_tech_phone;
-use crate::rdap::redacted::simplify_phones::simplify_tech_phone_ext;
-use std::str::FromStr;
-
-use icann_rdap_common::prelude::{redacted::Redacted, Domain, RdapResponse, Remark, ToResponse};
-
-use crate::rdap::redacted::RedactedName;
+use std::{str::FromStr, sync::LazyLock};
+
+use {
+    icann_rdap_common::prelude::{
+        redacted::{Method, Redacted},
+        RdapResponse, Remark,
+    },
+    jsonpath_rust::parser::{
+        model::{JpQuery, Segment, Selector},
+        parse_json_path,
+    },
+    regex::Regex,
+    serde_json::Value,
+    tracing::warn,
+};
+
+use crate::rdap::redacted::{
+    paths::{enclosing_object, matched_pointers, registered_jscontact_path, registered_path},
+    RedactedName,
+};
+
+/// The members of search results holding the array of results.
+const SEARCH_RESULTS: [&str; 5] = [
+    "domainSearchResults",
+    "entitySearchResults",
+    "nameserverSearchResults",
+    "ipSearchResults",
+    "autnumSearchResults",
+];
 
 /// Takes in an RDAP response and creates Simple Redactions
 /// from the RFC 9537 redactions.
 ///
+/// The prePath, postPath, and replacementPath expressions of each redaction
+/// are evaluated against the response, and the fields they point to are marked
+/// with a simple redaction in the object containing the field. Replacement
+/// values are not changed. Redactions with registered names but no paths are
+/// evaluated with the paths of the registration, which match entities by any of
+/// their roles. The JSContact cards of the entities with registered redactions
+/// are marked along with their jCards. The redactions of each search result are
+/// evaluated against the search result.
+///
 /// # Arguments
 ///
 /// * `rdap` is the [RdapResponse] which is altered.
 /// * `only_pre_path` only create Simple Redactions when no path expression is given or the prePath expression is present.
 pub fn simplify_redactions(rdap: RdapResponse, only_pre_path: bool) -> RdapResponse {
-    match rdap {
-        RdapResponse::Entity(entity) => {
-            // no registered redactions are on plain entities. They must all
-            // have roles.
-            entity.to_response()
+    let Ok(mut json) = serde_json::to_value(&rdap) else {
+        return rdap;
+    };
+    let mut simplified = simplify_object(&mut json, only_pre_path);
+    for member in SEARCH_RESULTS {
+        if let Some(Value::Array(results)) = json.get_mut(member) {
+            for result in results {
+                simplified |= simplify_object(result, only_pre_path);
+            }
         }
-        RdapResponse::Domain(domain) => simplify_domain_redactions(domain, only_pre_path),
-        RdapResponse::Nameserver(nameserver) => {
-            // no registered redactions on nameservers.
-            nameserver.to_response()
+    }
+    if !simplified {
+        return rdap;
+    }
+    match serde_json::from_value(json) {
+        Ok(simplified) => simplified,
+        Err(e) => {
+            warn!("redactions not simplified, simplified response is not RDAP: {e}");
+            rdap
         }
-        RdapResponse::Autnum(autnum) => {
-            // no registered redactions on autnums.
-            autnum.to_response()
+    }
+}
+
+/// Simplifies the redactions of an object, returning true if anything changed.
+///
+/// A redaction that would change the object into something that is no longer
+/// an RDAP object, such as by putting a string in place of an array, is not
+/// simplified.
+fn simplify_object(rdap: &mut Value, only_pre_path: bool) -> bool {
+    let redactions = rdap
+        .get("redacted")
+        .cloned()
+        .and_then(|redacted| serde_json::from_value::<Vec<Redacted>>(redacted).ok())
+        .unwrap_or_default();
+    let is_rdap = is_rdap_object(rdap);
+    let mut simplified = false;
+    for redaction in &redactions {
+        if is_only_pre_path(only_pre_path, redaction) {
+            continue;
         }
-        RdapResponse::Network(network) => {
-            // no registered redactions on networks
-            network.to_response()
+        let registered = redaction
+            .name()
+            .type_field()
+            .and_then(|name| RedactedName::from_str(name).ok());
+        let jscontact = registered
+            .as_ref()
+            .and_then(registered_jscontact_path)
+            .and_then(|path| parse_json_path(path).ok());
+
+        let is_jsonpath = redaction
+            .path_lang()
+            .is_none_or(|lang| lang.eq_ignore_ascii_case("jsonpath"));
+
+        // the server's paths are used when given, and the paths of the
+        // registration only when they are not
+        let path = match redacted_path(redaction) {
+            Some(path) if is_jsonpath => path,
+            Some(_) => continue,
+            None => match registered {
+                Some(name) => registered_path(name),
+                None => continue,
+            },
+        };
+        let Ok(query) = parse_json_path(path) else {
+            continue;
+        };
+        let unsimplified = rdap.clone();
+        let marker = Marker::new(redaction);
+        let objects = simplify_redaction(rdap, &query, &marker, redaction);
+        let mut changed = !objects.is_empty();
+
+        // the JSContact cards of the objects are kept in sync with their jCards
+        if let Some(jscontact) = jscontact.filter(|_| is_replaced(redaction)) {
+            for object in &objects {
+                mark_object(rdap, object, &jscontact, &marker);
+            }
         }
-        _ => {
-            // do nothing as RFC 9537 does not explain how or if its redacted
-            // directives work against search results or other, non-object class responses.
-            rdap
+
+        // replacement values are kept, with the objects holding them noted
+        if let Some(Ok(replacement)) = redaction
+            .replacement_path()
+            .filter(|_| is_jsonpath)
+            .map(parse_json_path)
+        {
+            for pointer in matched_pointers(rdap, &replacement) {
+                add_remark_to_object(rdap, &pointer, &marker, redaction);
+                changed = true;
+            }
+        }
+
+        if changed && is_rdap && !is_rdap_object(rdap) {
+            warn!("redaction of {path} not simplified, the value at the path cannot be redacted");
+            *rdap = unsimplified;
+            continue;
         }
+        simplified |= changed;
     }
+    simplified
 }
 
-fn simplify_domain_redactions(mut domain: Box<Domain>, only_pre_path: bool) -> RdapResponse {
-    let binding = domain.object_common.redacted.clone();
-    let redactions = binding.as_deref().unwrap_or_default();
-    for redaction in redactions {
-        if is_only_pre_path(only_pre_path, redaction) {
+/// True if the redacted value is replaced by the simple redaction. Replacement
+/// values are not redacted values, so they are only noted.
+fn is_replaced(redaction: &Redacted) -> bool {
+    !matches!(redaction.method(), Some(Method::ReplacementValue))
+}
+
+/// Marks the values found by a query against the object at the pointer.
+fn mark_object(rdap: &mut Value, object_pointer: &str, query: &JpQuery, marker: &Marker) {
+    let Some(object) = rdap.pointer_mut(object_pointer) else {
+        return;
+    };
+    for pointer in matched_pointers(object, query) {
+        if let Some(value) = object.pointer_mut(&pointer) {
+            marker.mark(value);
+        }
+    }
+}
+
+/// True if the value is an RDAP object of a known class.
+fn is_rdap_object(rdap: &Value) -> bool {
+    serde_json::from_value::<RdapResponse>(rdap.clone()).is_ok()
+}
+
+/// Gets the path to the redacted field, which is the postPath of redacted values
+/// or the prePath of removed values.
+fn redacted_path(redaction: &Redacted) -> Option<&str> {
+    redaction.post_path().or(redaction.pre_path())
+}
+
+/// Simplifies a redaction, returning the JSON pointers of the RDAP objects
+/// given the simple redaction.
+fn simplify_redaction(
+    rdap: &mut Value,
+    query: &JpQuery,
+    marker: &Marker,
+    redaction: &Redacted,
+) -> Vec<String> {
+    let replace = is_replaced(redaction);
+
+    let pointers = matched_pointers(rdap, query);
+    if !pointers.is_empty() {
+        let mut objects = vec![];
+        for pointer in &pointers {
+            if replace {
+                if let Some(value) = rdap.pointer_mut(pointer) {
+                    marker.mark(value);
+                }
+            }
+            objects.push(add_remark_to_object(rdap, pointer, marker, redaction));
+        }
+        objects.dedup();
+        return objects;
+    }
+
+    // nothing was found, as is the case for removed fields. the closest
+    // parent found gets the remark, and if it is the object holding
+    // the field then the field is restored.
+    for len in (0..query.segments.len()).rev() {
+        let parent = JpQuery::new(query.segments[..len].to_vec());
+        let pointers = matched_pointers(rdap, &parent);
+        if pointers.is_empty() {
             continue;
         }
-        if let Some(r_type) = redaction.name().type_field() {
-            let r_name = RedactedName::from_str(r_type);
-            if let Ok(registered_redaction) = r_name {
-                domain = match registered_redaction {
-                    RedactedName::RegistryDomainId => {
-                        simplify_registry_domain_id(domain, redaction)
-                    }
-                    RedactedName::RegistryRegistrantId => {
-                        simplify_registry_registrant_id(domain, redaction)
-                    }
-                    RedactedName::RegistrantName => simplify_registrant_name(domain, redaction),
-                    RedactedName::RegistrantOrganization => {
-                        simplify_registrant_org(domain, redaction)
-                    }
-                    RedactedName::RegistrantStreet => simplify_registrant_street(domain, redaction),
-                    RedactedName::RegistrantCity => simplify_registrant_city(domain, redaction),
-                    RedactedName::RegistrantPostalCode => {
-                        simplify_registrant_postal_code(domain, redaction)
-                    }
-                    RedactedName::RegistrantPhone => simplify_registrant_phone(domain, redaction),
-                    RedactedName::RegistrantPhoneExt => {
-                        simplify_registrant_phone_ext(domain, redaction)
-                    }
-                    RedactedName::RegistrantFax => simplify_registrant_fax(domain, redaction),
-                    RedactedName::RegistrantFaxExt => {
-                        simplify_registrant_fax_ext(domain, redaction)
-                    }
-                    RedactedName::RegistrantEmail => simplify_registrant_email(domain, redaction),
-                    RedactedName::RegistryTechId => simplify_registry_tech_id(domain, redaction),
-                    RedactedName::TechName => simplify_tech_name(domain, redaction),
-                    RedactedName::TechPhone => simplify_tech_phone(domain, redaction),
-                    RedactedName::TechPhoneExt => simplify_tech_phone_ext(domain, redaction),
-                    RedactedName::TechEmail => simplify_tech_email(domain, redaction),
+        let member = if len + 1 == query.segments.len() {
+            member_name(&query.segments[len])
+        } else {
+            None
+        };
+        let mut objects = vec![];
+        for pointer in &pointers {
+            if let Some(member) = &member {
+                restore_member(rdap, pointer, member, marker);
+            }
+            objects.push(add_remark_to_object(rdap, pointer, marker, redaction));
+        }
+        objects.dedup();
+        return objects;
+    }
+    vec![]
+}
+
+/// Puts the marker in place of a removed member of an object, unless the
+/// member cannot hold a string.
+fn restore_member(rdap: &mut Value, pointer: &str, member: &str, marker: &Marker) {
+    let object_pointer = enclosing_object(rdap, pointer);
+    let was_rdap = rdap.pointer(&object_pointer).is_some_and(is_rdap_object);
+    let Some(Value::Object(object)) = rdap.pointer_mut(pointer) else {
+        return;
+    };
+    if object.contains_key(member) {
+        return;
+    }
+    object.insert(member.to_string(), Value::String(marker.key.to_string()));
+    if was_rdap && !rdap.pointer(&object_pointer).is_some_and(is_rdap_object) {
+        if let Some(Value::Object(object)) = rdap.pointer_mut(pointer) {
+            object.remove(member);
+        }
+    }
+}
+
+/// Gets the name of a member selected by a segment, if that is what it selects.
+fn member_name(segment: &Segment) -> Option<String> {
+    match segment {
+        Segment::Selector(Selector::Name(name)) => {
+            Some(name.trim_matches(|c| c == '\'' || c == '"').to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Adds the remark of a simple redaction to the RDAP object holding the field,
+/// returning the JSON pointer of the object.
+fn add_remark_to_object(
+    rdap: &mut Value,
+    pointer: &str,
+    marker: &Marker,
+    redaction: &Redacted,
+) -> String {
+    let object_pointer = enclosing_object(rdap, pointer);
+    let Some(Value::Object(object)) = rdap.pointer_mut(&object_pointer) else {
+        return object_pointer;
+    };
+    let remarks = object
+        .get("remarks")
+        .cloned()
+        .and_then(|remarks| serde_json::from_value::<Vec<Remark>>(remarks).ok());
+    let remarks = add_remark(marker.key, &marker.desc, redaction, remarks);
+    if let Ok(remarks) = serde_json::to_value(remarks) {
+        object.insert("remarks".to_string(), remarks);
+    }
+    object_pointer
+}
+
+static REDACTED: &str = "////REDACTED////";
+static REDACTED_ID: &str = "////REDACTED_ID////";
+static REDACTED_NAME: &str = "////REDACTED_NAME////";
+static REDACTED_ORG: &str = "////REDACTED_ORGANIZATION////";
+static REDACTED_STREET: &str = "////REDACTED_STREET////";
+static REDACTED_CITY: &str = "////REDACTED_CITY////";
+static REDACTED_POSTAL_CODE: &str = "////REDACTED_POSTAL_CODE////";
+static REDACTED_PHONE: &str = "////REDACTED_PHONE////";
+static REDACTED_PHONE_EXT: &str = "////REDACTED_PHONE_EXT////";
+static REDACTED_FAX: &str = "////REDACTED_FAX////";
+static REDACTED_FAX_EXT: &str = "////REDACTED_FAX_EXT////";
+static REDACTED_EMAIL: &str = "redacted_email@redacted.invalid";
+
+/// The value put in place of a redacted value, which is also the
+/// key of the simple redaction.
+struct Marker {
+    key: &'static str,
+    desc: String,
+
+    /// Only the extension of a phone number is redacted.
+    ext: bool,
+}
+
+impl Marker {
+    fn new(redaction: &Redacted) -> Self {
+        let registered = redaction
+            .name()
+            .type_field()
+            .and_then(|name| RedactedName::from_str(name).ok());
+        let (key, desc, ext) = match registered {
+            Some(RedactedName::RegistryDomainId)
+            | Some(RedactedName::RegistryRegistrantId)
+            | Some(RedactedName::RegistryTechId) => (REDACTED_ID, "ID redacted.", false),
+            Some(RedactedName::RegistrantName) | Some(RedactedName::TechName) => {
+                (REDACTED_NAME, "Name redacted.", false)
+            }
+            Some(RedactedName::RegistrantOrganization) => {
+                (REDACTED_ORG, "Organization redacted.", false)
+            }
+            Some(RedactedName::RegistrantStreet) => (REDACTED_STREET, "Street redacted.", false),
+            Some(RedactedName::RegistrantCity) => (REDACTED_CITY, "City redacted.", false),
+            Some(RedactedName::RegistrantPostalCode) => {
+                (REDACTED_POSTAL_CODE, "Postal code redacted.", false)
+            }
+            Some(RedactedName::RegistrantPhone) | Some(RedactedName::TechPhone) => {
+                (REDACTED_PHONE, "Phone redacted.", false)
+            }
+            Some(RedactedName::RegistrantPhoneExt) | Some(RedactedName::TechPhoneExt) => {
+                (REDACTED_PHONE_EXT, "Phone extension redacted.", true)
+            }
+            Some(RedactedName::RegistrantFax) => (REDACTED_FAX, "Fax redacted.", false),
+            Some(RedactedName::RegistrantFaxExt) => {
+                (REDACTED_FAX_EXT, "Fax extension redacted.", true)
+            }
+            Some(RedactedName::RegistrantEmail) | Some(RedactedName::TechEmail) => {
+                (REDACTED_EMAIL, "Email redacted.", false)
+            }
+            None => {
+                // unregistered redactions are described by their name
+                let name = redaction
+                    .name()
+                    .type_field()
+                    .or(redaction.name().description())
+                    .unwrap_or("Value");
+                return Self {
+                    key: REDACTED,
+                    desc: format!("{name} redacted."),
+                    ext: false,
                 };
             }
+        };
+        Self {
+            key,
+            desc: desc.to_string(),
+            ext,
+        }
+    }
+
+    /// Puts the marker in place of the strings of a value. Arrays of jCard
+    /// properties only have the property values marked.
+    fn mark(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => *s = self.marked(s),
+            Value::Array(items) => {
+                let is_jcard_property = items.len() > 3
+                    && items[0].is_string()
+                    && items[1].is_object()
+                    && items[2].is_string();
+                let skip = if is_jcard_property { 3 } else { 0 };
+                for item in items.iter_mut().skip(skip) {
+                    self.mark(item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn marked(&self, s: &str) -> String {
+        if !self.ext {
+            self.key.to_string()
+        } else if s.contains(";ext=") {
+            static EXT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r";ext=[^;]*").unwrap());
+            EXT_RE
+                .replace_all(s, format!(";ext={}", self.key))
+                .to_string()
+        } else {
+            format!("{s} {}", self.key)
         }
     }
-    domain.to_response()
 }
 
 fn is_only_pre_path(only_pre_path: bool, redaction: &Redacted) -> bool {
@@ -172,9 +458,18 @@ pub(crate) fn add_remark(
 
 #[cfg(test)]
 mod tests {
-    use super::{add_remark, is_only_pre_path};
+    use serde_json::{json, Value};
+
+    use super::{
+        add_remark, is_only_pre_path, simplify_redactions, REDACTED, REDACTED_CITY, REDACTED_EMAIL,
+        REDACTED_FAX, REDACTED_FAX_EXT, REDACTED_ID, REDACTED_NAME, REDACTED_ORG,
+        REDACTED_PHONE_EXT, REDACTED_POSTAL_CODE, REDACTED_STREET,
+    };
 
-    use icann_rdap_common::response::redacted::{Name, Redacted};
+    use icann_rdap_common::{
+        prelude::RdapResponse,
+        response::redacted::{Name, Redacted},
+    };
 
     fn get_test_redacted() -> Redacted {
         Redacted::builder()
@@ -548,4 +843,802 @@ mod tests {
             Some(&desc.to_string())
         );
     }
+
+    fn simplify(rdap: Value, only_pre_path: bool) -> Value {
+        let rdap: RdapResponse = serde_json::from_value(rdap).expect("parsing RDAP");
+        serde_json::to_value(simplify_redactions(rdap, only_pre_path)).expect("serializing RDAP")
+    }
+
+    fn registrant() -> Value {
+        json!({
+            "objectClassName": "entity",
+            "handle": "REG-1",
+            "roles": ["registrant"],
+            "vcardArray": ["vcard", [
+                ["version", {}, "text", "4.0"],
+                ["fn", {}, "text", ""],
+                ["tel", {"type": "voice"}, "uri", "tel:+1.5555555555;ext=123"],
+                ["email", {}, "text", ""]
+            ]]
+        })
+    }
+
+    fn has_simple_redaction(object: &Value, key: &str) -> bool {
+        object["remarks"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .any(|remark| {
+                remark["simpleRedaction_keys"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .any(|k| k == key)
+            })
+    }
+
+    fn autnum(entities: Vec<Value>, redacted: Value) -> Value {
+        json!({
+            "objectClassName": "autnum",
+            "handle": "AS64496",
+            "startAutnum": 64496,
+            "endAutnum": 64496,
+            "entities": entities,
+            "redacted": redacted
+        })
+    }
+
+    #[test]
+    fn test_simplify_post_path_empty_value() {
+        // GIVEN an autnum with a registrant email redacted by an empty value
+        let rdap = autnum(
+            vec![registrant()],
+            json!([{
+                "name": {"type": "Registrant Email"},
+                "postPath": "$.entities[?(@.roles[0]=='registrant')].vcardArray[1][?(@[0]=='email')][3]",
+                "method": "emptyValue"
+            }]),
+        );
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the email is marked in the registrant
+        let registrant = &actual["entities"][0];
+        assert_eq!(registrant["vcardArray"][1][3][3], REDACTED_EMAIL);
+        assert!(has_simple_redaction(registrant, REDACTED_EMAIL));
+        assert!(!has_simple_redaction(&actual, REDACTED_EMAIL));
+    }
+
+    #[test]
+    fn test_simplify_pre_path_removal_restores_member() {
+        // GIVEN an entity with its handle removed
+        let rdap = json!({
+            "objectClassName": "entity",
+            "redacted": [{
+                "name": {"type": "Registry Registrant ID"},
+                "prePath": "$.handle",
+                "method": "removal"
+            }]
+        });
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the handle is put back as a simple redaction
+        assert_eq!(actual["handle"], REDACTED_ID);
+        assert!(has_simple_redaction(&actual, REDACTED_ID));
+    }
+
+    #[test]
+    fn test_simplify_removal_of_array_not_restored() {
+        // GIVEN a domain with its nameservers and handle removed
+        let rdap = json!({
+            "objectClassName": "domain",
+            "ldhName": "example.com",
+            "redacted": [
+                {
+                    "name": {"description": "Nameservers"},
+                    "prePath": "$.nameservers",
+                    "method": "removal"
+                },
+                {
+                    "name": {"description": "Domain Handle"},
+                    "prePath": "$.handle",
+                    "method": "removal"
+                }
+            ]
+        });
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the nameservers are noted but not put back as a string
+        assert!(actual.get("nameservers").is_none());
+        assert!(has_simple_redaction(&actual, REDACTED));
+        let description = &actual["remarks"][0]["description"];
+        assert_eq!(description[0], "Nameservers redacted.");
+
+        // AND the handle is still simplified
+        assert_eq!(actual["handle"], REDACTED);
+    }
+
+    #[test]
+    fn test_simplify_registered_name_without_paths() {
+        // GIVEN an autnum with a registrant name redacted without paths
+        let rdap = autnum(
+            vec![registrant()],
+            json!([{
+                "name": {"type": "Registrant Name"}
+            }]),
+        );
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the registered path is used
+        let registrant = &actual["entities"][0];
+        assert_eq!(registrant["vcardArray"][1][1][3], REDACTED_NAME);
+        assert!(has_simple_redaction(registrant, REDACTED_NAME));
+    }
+
+    #[test]
+    fn test_simplify_domain_registrant_with_many_roles() {
+        // GIVEN a domain with an entity that is the technical contact and the registrant
+        let mut contact = registrant();
+        contact["roles"] = json!(["technical", "registrant"]);
+        contact["vcardArray"][1][1][3] = json!("Alice");
+        let rdap = json!({
+            "objectClassName": "domain",
+            "ldhName": "example.com",
+            "entities": [contact],
+            "redacted": [{
+                "name": {"type": "Registrant Name"},
+                "method": "emptyValue"
+            }]
+        });
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the registrant name is simplified with the path of the registration
+        let registrant = &actual["entities"][0];
+        assert_eq!(registrant["vcardArray"][1][1][3], REDACTED_NAME);
+        assert!(has_simple_redaction(registrant, REDACTED_NAME));
+    }
+
+    #[test]
+    fn test_simplify_domain_registered_name_keeps_jscontact_in_sync() {
+        // GIVEN a domain with a registrant having a jCard and a JSContact card
+        let mut contact = registrant();
+        contact["vcardArray"][1][1][3] = json!("Alice");
+        contact["jscontact_card"] = json!({
+            "@type": "Card",
+            "version": "2.0",
+            "name": {"full": "Alice"},
+            "localizations": {"fr": {"name": {"full": "Alice"}}}
+        });
+        let rdap = json!({
+            "objectClassName": "domain",
+            "ldhName": "example.com",
+            "entities": [contact],
+            "redacted": [{
+                "name": {"type": "Registrant Name"},
+                "method": "removal"
+            }]
+        });
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the name is marked in both
+        let registrant = &actual["entities"][0];
+        assert_eq!(registrant["vcardArray"][1][1][3], REDACTED_NAME);
+        let card = &registrant["jscontact_card"];
+        assert_eq!(card["name"]["full"], REDACTED_NAME);
+        assert_eq!(card["localizations"]["fr"]["name"]["full"], REDACTED_NAME);
+        assert!(has_simple_redaction(registrant, REDACTED_NAME));
+    }
+
+    #[test]
+    fn test_simplify_domain_registered_name_in_jscontact_only() {
+        // GIVEN a domain with a technical contact having only a JSContact card
+        let rdap = json!({
+            "objectClassName": "domain",
+            "ldhName": "example.com",
+            "entities": [{
+                "objectClassName": "entity",
+                "roles": ["administrative", "technical"],
+                "jscontact_card": {
+                    "@type": "Card",
+                    "version": "2.0",
+                    "emails": {"email": {"address": "tech@example.com"}}
+                }
+            }],
+            "redacted": [{
+                "name": {"type": "Tech Email"},
+                "method": "emptyValue"
+            }]
+        });
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the email of the JSContact card is marked
+        let tech = &actual["entities"][0];
+        assert_eq!(
+            tech["jscontact_card"]["emails"]["email"]["address"],
+            REDACTED_EMAIL
+        );
+        assert!(has_simple_redaction(tech, REDACTED_EMAIL));
+    }
+
+    #[test]
+    fn test_simplify_domain_registered_name_with_server_post_path() {
+        // GIVEN a domain with a registrant email at a path other than the registered one
+        let mut contact = registrant();
+        contact["roles"] = json!(["administrative"]);
+        let rdap = json!({
+            "objectClassName": "domain",
+            "ldhName": "example.com",
+            "entities": [contact],
+            "redacted": [{
+                "name": {"type": "Registrant Email"},
+                "postPath": "$.entities[?(@.handle=='REG-1')].vcardArray[1][?(@[0]=='email')][3]",
+                "method": "emptyValue"
+            }]
+        });
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the postPath of the server is used
+        let registrant = &actual["entities"][0];
+        assert_eq!(registrant["vcardArray"][1][3][3], REDACTED_EMAIL);
+        assert!(has_simple_redaction(registrant, REDACTED_EMAIL));
+    }
+
+    #[test]
+    fn test_simplify_domain_registered_name_with_server_pre_path() {
+        // GIVEN a domain with its handle removed from a path given by the server
+        let rdap = json!({
+            "objectClassName": "domain",
+            "ldhName": "example.com",
+            "redacted": [{
+                "name": {"type": "Registry Domain ID"},
+                "prePath": "$.handle",
+                "method": "removal"
+            }]
+        });
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the handle is put back
+        assert_eq!(actual["handle"], REDACTED_ID);
+        assert!(has_simple_redaction(&actual, REDACTED_ID));
+    }
+
+    #[test]
+    fn test_simplify_domain_registered_name_not_jsonpath() {
+        // GIVEN a domain with its handle removed by a path that is not JSONPath
+        let rdap = json!({
+            "objectClassName": "domain",
+            "ldhName": "example.com",
+            "redacted": [{
+                "name": {"type": "Registry Domain ID"},
+                "prePath": "/handle",
+                "pathLang": "jsonpointer",
+                "method": "removal"
+            }]
+        });
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN nothing is simplified
+        assert!(actual.get("handle").is_none());
+    }
+
+    #[test]
+    fn test_simplify_phone_ext() {
+        // GIVEN an autnum with the registrant phone extension redacted
+        let rdap = autnum(
+            vec![registrant()],
+            json!([{
+                "name": {"type": "Registrant Phone Ext"},
+                "postPath": "$.entities[?(@.roles[0]=='registrant')].vcardArray[1][?(@[1].type=='voice')][3]",
+                "method": "partialValue"
+            }]),
+        );
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN only the extension is marked
+        assert_eq!(
+            actual["entities"][0]["vcardArray"][1][2][3],
+            format!("tel:+1.5555555555;ext={REDACTED_PHONE_EXT}")
+        );
+    }
+
+    fn domain(entities: Vec<Value>, redacted: Value) -> Value {
+        json!({
+            "objectClassName": "domain",
+            "ldhName": "example.com",
+            "entities": entities,
+            "redacted": redacted
+        })
+    }
+
+    fn registrant_with_address(adr: Value) -> Value {
+        let mut contact = registrant();
+        contact["vcardArray"][1]
+            .as_array_mut()
+            .expect("jCard properties")
+            .push(json!(["adr", {}, "text", adr]));
+        contact
+    }
+
+    #[test]
+    fn test_simplify_domain_registrant_fax() {
+        // GIVEN a domain with a registrant having a voice and a fax number
+        let mut contact = registrant();
+        contact["vcardArray"][1]
+            .as_array_mut()
+            .expect("jCard properties")
+            .push(json!(["tel", {"type": "fax"}, "uri", "tel:+1.5555555556"]));
+        let rdap = domain(
+            vec![contact],
+            json!([{
+                "name": {"type": "Registrant Fax"},
+                "method": "removal"
+            }]),
+        );
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN only the fax number is marked
+        let registrant = &actual["entities"][0];
+        assert_eq!(registrant["vcardArray"][1][4][3], REDACTED_FAX);
+        assert_eq!(
+            registrant["vcardArray"][1][2][3],
+            "tel:+1.5555555555;ext=123"
+        );
+        assert!(has_simple_redaction(registrant, REDACTED_FAX));
+    }
+
+    #[test]
+    fn test_simplify_domain_registrant_fax_ext() {
+        // GIVEN a domain with a registrant having a fax number with an extension
+        let mut contact = registrant();
+        contact["vcardArray"][1]
+            .as_array_mut()
+            .expect("jCard properties")
+            .push(json!(["tel", {"type": "fax"}, "uri", "tel:+1.5555555556;ext=456"]));
+        let rdap = domain(
+            vec![contact],
+            json!([{
+                "name": {"type": "Registrant Fax Ext"},
+                "method": "removal"
+            }]),
+        );
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN only the extension of the fax number is marked
+        let registrant = &actual["entities"][0];
+        assert_eq!(
+            registrant["vcardArray"][1][4][3],
+            format!("tel:+1.5555555556;ext={REDACTED_FAX_EXT}")
+        );
+        assert!(has_simple_redaction(registrant, REDACTED_FAX_EXT));
+    }
+
+    #[test]
+    fn test_simplify_domain_tech_email_and_id() {
+        // GIVEN a domain with a registrant and a technical contact
+        let mut tech = registrant();
+        tech["handle"] = json!("TECH-1");
+        tech["roles"] = json!(["technical"]);
+        tech["vcardArray"][1][3][3] = json!("tech@example.com");
+        let rdap = domain(
+            vec![registrant(), tech],
+            json!([
+                {
+                    "name": {"type": "Tech Email"},
+                    "method": "removal"
+                },
+                {
+                    "name": {"type": "Registry Tech ID"},
+                    "method": "removal"
+                }
+            ]),
+        );
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the email and handle of the technical contact are marked
+        let tech = &actual["entities"][1];
+        assert_eq!(tech["vcardArray"][1][3][3], REDACTED_EMAIL);
+        assert_eq!(tech["handle"], REDACTED_ID);
+        assert!(has_simple_redaction(tech, REDACTED_EMAIL));
+        assert!(has_simple_redaction(tech, REDACTED_ID));
+
+        // AND the registrant is left alone
+        let registrant = &actual["entities"][0];
+        assert_eq!(registrant["handle"], "REG-1");
+        assert_eq!(registrant["vcardArray"][1][3][3], "");
+        assert!(registrant.get("remarks").is_none());
+    }
+
+    #[test]
+    fn test_simplify_domain_registrant_address() {
+        // GIVEN a domain with a registrant having a postal address
+        let rdap = domain(
+            vec![registrant_with_address(json!([
+                "",
+                "",
+                "123 Main St",
+                "Anytown",
+                "",
+                "12345",
+                ""
+            ]))],
+            json!([
+                {
+                    "name": {"type": "Registrant Street"},
+                    "method": "removal"
+                },
+                {
+                    "name": {"type": "Registrant City"},
+                    "method": "removal"
+                },
+                {
+                    "name": {"type": "Registrant Postal Code"},
+                    "method": "removal"
+                }
+            ]),
+        );
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the street, city, and postal code are marked
+        let registrant = &actual["entities"][0];
+        let adr = &registrant["vcardArray"][1][4][3];
+        assert_eq!(adr[2], REDACTED_STREET);
+        assert_eq!(adr[3], REDACTED_CITY);
+        assert_eq!(adr[5], REDACTED_POSTAL_CODE);
+        for key in [REDACTED_STREET, REDACTED_CITY, REDACTED_POSTAL_CODE] {
+            assert!(has_simple_redaction(registrant, key));
+        }
+    }
+
+    #[test]
+    fn test_simplify_domain_registrant_without_city() {
+        // GIVEN a domain with a registrant having a postal address without a city
+        let rdap = domain(
+            vec![registrant_with_address(json!([
+                "",
+                "",
+                "123 Main St",
+                "",
+                "",
+                "12345",
+                ""
+            ]))],
+            json!([{
+                "name": {"type": "Registrant City"},
+                "method": "removal"
+            }]),
+        );
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the city is set to the simple redaction
+        let registrant = &actual["entities"][0];
+        let adr = &registrant["vcardArray"][1][4][3];
+        assert_eq!(adr[3], REDACTED_CITY);
+        assert_eq!(adr[2], "123 Main St");
+        assert_eq!(adr[5], "12345");
+        assert!(has_simple_redaction(registrant, REDACTED_CITY));
+    }
+
+    #[test]
+    fn test_simplify_domain_registrant_org_with_localizations() {
+        // GIVEN a domain with a registrant having localized organization names
+        let mut contact = registrant();
+        contact["vcardArray"][1]
+            .as_array_mut()
+            .expect("jCard properties")
+            .push(json!(["org", {}, "text", "Original Org"]));
+        contact["jscontact_card"] = json!({
+            "@type": "Card",
+            "version": "2.0",
+            "organizations": {"org": {"name": "Original Org"}},
+            "localizations": {
+                "fr": {"organizations": {"org": {"name": "Organisation Française"}}},
+                "es": {"organizations": {"org": {"name": "Organización Española"}}}
+            }
+        });
+        let rdap = domain(
+            vec![contact],
+            json!([{
+                "name": {"type": "Registrant Organization"},
+                "method": "removal"
+            }]),
+        );
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the organization is marked in the jCard, the card, and its localizations
+        let registrant = &actual["entities"][0];
+        assert_eq!(registrant["vcardArray"][1][4][3], REDACTED_ORG);
+        let card = &registrant["jscontact_card"];
+        assert_eq!(card["organizations"]["org"]["name"], REDACTED_ORG);
+        for lang in ["fr", "es"] {
+            assert_eq!(
+                card["localizations"][lang]["organizations"]["org"]["name"],
+                REDACTED_ORG
+            );
+        }
+        assert!(has_simple_redaction(registrant, REDACTED_ORG));
+    }
+
+    #[test]
+    fn test_simplify_domain_only_registrant_redacted() {
+        // GIVEN a domain with a registrant followed by an administrative contact
+        let mut admin = registrant_with_address(json!(["", "", "456 Admin St", "", "", "", ""]));
+        admin["handle"] = json!("ADMIN-1");
+        admin["roles"] = json!(["administrative"]);
+        let rdap = domain(
+            vec![
+                registrant_with_address(json!(["", "", "123 Registrant St", "", "", "", ""])),
+                admin,
+            ],
+            json!([{
+                "name": {"type": "Registrant Street"},
+                "method": "removal"
+            }]),
+        );
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN only the street of the registrant is marked
+        let registrant = &actual["entities"][0];
+        assert_eq!(registrant["vcardArray"][1][4][3][2], REDACTED_STREET);
+        assert!(has_simple_redaction(registrant, REDACTED_STREET));
+        let admin = &actual["entities"][1];
+        assert_eq!(admin["vcardArray"][1][4][3][2], "456 Admin St");
+        assert!(admin.get("remarks").is_none());
+    }
+
+    #[test]
+    fn test_simplify_same_remark_not_repeated() {
+        // GIVEN a registrant already having the remark of the simple redaction
+        let mut contact = registrant();
+        contact["remarks"] = json!([{
+            "title": "RFC9537 to Simple Redactions",
+            "simpleRedaction_keys": [REDACTED_EMAIL],
+            "description": ["Email redacted."]
+        }]);
+        let rdap = domain(
+            vec![contact],
+            json!([{
+                "name": {"type": "Registrant Email"},
+                "method": "removal"
+            }]),
+        );
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the remark is not repeated
+        let registrant = &actual["entities"][0];
+        assert_eq!(registrant["vcardArray"][1][3][3], REDACTED_EMAIL);
+        let remarks = registrant["remarks"].as_array().expect("remarks");
+        assert_eq!(remarks.len(), 1);
+        assert_eq!(remarks[0]["simpleRedaction_keys"], json!([REDACTED_EMAIL]));
+        assert_eq!(remarks[0]["description"], json!(["Email redacted."]));
+    }
+
+    #[test]
+    fn test_simplify_unregistered_name_on_network() {
+        // GIVEN a network with an abuse contact email redacted with an unregistered name
+        let rdap = json!({
+            "objectClassName": "ip network",
+            "handle": "NET-192-0-2-0-1",
+            "startAddress": "192.0.2.0",
+            "endAddress": "192.0.2.255",
+            "ipVersion": "v4",
+            "entities": [{
+                "objectClassName": "entity",
+                "roles": ["abuse"],
+                "vcardArray": ["vcard", [
+                    ["version", {}, "text", "4.0"],
+                    ["email", {}, "text", ""]
+                ]]
+            }],
+            "redacted": [{
+                "name": {"description": "Abuse Email"},
+                "postPath": "$.entities[?(@.roles[0]=='abuse')].vcardArray[1][?(@[0]=='email')]",
+                "method": "emptyValue"
+            }]
+        });
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the value of the jCard property is marked
+        let abuse = &actual["entities"][0];
+        assert_eq!(
+            abuse["vcardArray"][1][1],
+            json!(["email", {}, "text", REDACTED])
+        );
+        assert!(has_simple_redaction(abuse, REDACTED));
+        let remark = &abuse["remarks"][0]["description"][0];
+        assert_eq!(remark, "Abuse Email redacted.");
+    }
+
+    #[test]
+    fn test_simplify_search_results() {
+        // GIVEN entity search results where one result has a redaction
+        let rdap = json!({
+            "entitySearchResults": [
+                {
+                    "objectClassName": "entity",
+                    "handle": "FOO-1"
+                },
+                {
+                    "objectClassName": "entity",
+                    "redacted": [{
+                        "name": {"type": "Registry Registrant ID"},
+                        "prePath": "$.handle",
+                        "method": "removal"
+                    }]
+                }
+            ]
+        });
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the redaction is evaluated against its own search result
+        let results = &actual["entitySearchResults"];
+        assert_eq!(results[0]["handle"], "FOO-1");
+        assert_eq!(results[1]["handle"], REDACTED_ID);
+    }
+
+    #[test]
+    fn test_simplify_domain_search_results_registered_names() {
+        // GIVEN domain search results with a registered redaction
+        let mut contact = registrant();
+        contact["vcardArray"][1][3][3] = json!("alice@example.com");
+        let rdap = json!({
+            "domainSearchResults": [{
+                "objectClassName": "domain",
+                "ldhName": "example.com",
+                "entities": [contact],
+                "redacted": [{
+                    "name": {"type": "Registrant Email"},
+                    "method": "removal"
+                }]
+            }]
+        });
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the email of the registrant is simplified
+        let registrant = &actual["domainSearchResults"][0]["entities"][0];
+        let email = registrant["vcardArray"][1]
+            .as_array()
+            .expect("jCard properties")
+            .iter()
+            .find(|property| property[0] == "email")
+            .expect("email property");
+        assert_eq!(email[3], REDACTED_EMAIL);
+    }
+
+    #[test]
+    fn test_simplify_replacement_value_only_noted() {
+        // GIVEN a redaction by replacement value
+        let rdap = autnum(
+            vec![registrant()],
+            json!([{
+                "name": {"type": "Registrant Email"},
+                "postPath": "$.entities[?(@.roles[0]=='registrant')].vcardArray[1][?(@[0]=='version')][3]",
+                "method": "replacementValue"
+            }]),
+        );
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the replacement value is kept but noted
+        let registrant = &actual["entities"][0];
+        assert_eq!(registrant["vcardArray"][1][0][3], "4.0");
+        assert!(has_simple_redaction(registrant, REDACTED_EMAIL));
+    }
+
+    #[test]
+    fn test_simplify_replacement_path_noted() {
+        // GIVEN a registrant email removed and replaced by a web form
+        let mut contact = registrant();
+        contact["vcardArray"][1]
+            .as_array_mut()
+            .expect("jCard properties")
+            .push(json!([
+                "contact-uri",
+                {},
+                "uri",
+                "https://example.com/contact"
+            ]));
+        let rdap = autnum(
+            vec![contact],
+            json!([{
+                "name": {"type": "Registrant Email"},
+                "prePath": "$.entities[?(@.roles[0]=='registrant')].vcardArray[1][?(@[0]=='email')]",
+                "replacementPath": "$.entities[?(@.roles[0]=='registrant')].vcardArray[1][?(@[0]=='contact-uri')]",
+                "method": "replacementValue"
+            }]),
+        );
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN the replacement is kept and noted
+        let registrant = &actual["entities"][0];
+        assert_eq!(
+            registrant["vcardArray"][1][4][3],
+            "https://example.com/contact"
+        );
+        assert!(has_simple_redaction(registrant, REDACTED_EMAIL));
+    }
+
+    #[test]
+    fn test_simplify_not_jsonpath() {
+        // GIVEN a redaction with a path language other than JSONPath
+        let rdap = json!({
+            "objectClassName": "entity",
+            "redacted": [{
+                "name": {"type": "Registry Registrant ID"},
+                "prePath": "/handle",
+                "pathLang": "jsonpointer",
+                "method": "removal"
+            }]
+        });
+
+        // WHEN
+        let actual = simplify(rdap, false);
+
+        // THEN nothing is simplified
+        assert!(actual.get("handle").is_none());
+    }
+
+    #[test]
+    fn test_simplify_only_pre_path_skips_removals() {
+        // GIVEN a removal
+        let rdap = json!({
+            "objectClassName": "entity",
+            "redacted": [{
+                "name": {"type": "Registry Registrant ID"},
+                "prePath": "$.handle",
+                "method": "removal"
+            }]
+        });
+
+        // WHEN
+        let actual = simplify(rdap, true);
+
+        // THEN nothing is simplified
+        assert!(actual.get("handle").is_none());
+    }
 }
@@ -1,5 +1,6 @@
 use icann_rdap_common::prelude::{
-    CommonFields, DomainSearchResults, EntitySearchResults, NameserverSearchResults,
+    AutnumSearchResults, CommonFields, DomainSearchResults, EntitySearchResults,
+    NameserverSearchResults, NetworkSearchResults,
 };
 
 use crate::rpsl::{RpslParams, ToRpsl};
@@ -59,3 +60,39 @@ impl ToRpsl for EntitySearchResults {
         rpsl
     }
 }
+
+impl ToRpsl for NetworkSearchResults {
+    fn to_rpsl(&self, params: RpslParams) -> String {
+        let mut rpsl = String::new();
+
+        // notices are comments before the objects
+        rpsl = push_notices(rpsl, self.notices());
+
+        for network in self.results() {
+            rpsl.push_str(&network.to_rpsl(params));
+        }
+
+        //end
+        rpsl.push('\n');
+
+        rpsl
+    }
+}
+
+impl ToRpsl for AutnumSearchResults {
+    fn to_rpsl(&self, params: RpslParams) -> String {
+        let mut rpsl = String::new();
+
+        // notices are comments before the objects
+        rpsl = push_notices(rpsl, self.notices());
+
+        for autnum in self.results() {
+            rpsl.push_str(&autnum.to_rpsl(params));
+        }
+
+        //end
+        rpsl.push('\n');
+
+        rpsl
+    }
+}
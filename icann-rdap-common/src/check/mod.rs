@@ -21,7 +21,9 @@ mod help;
 mod httpdata;
 mod nameserver;
 mod network;
+mod nro;
 pub mod process;
+mod redacted;
 mod search;
 mod string;
 mod types;
@@ -89,6 +91,24 @@ pub enum CheckClass {
     /// This class represents errors with respect to the gTLD RDAP profile.
     #[strum(serialize = "GtldProfileErr")]
     GtldProfileError,
+
+    /// RFC 9537 Errors
+    ///
+    /// This class represents errors with respect to RFC 9537 redactions.
+    #[strum(serialize = "Rfc9537Err")]
+    Rfc9537Error,
+
+    /// DNS Consistency Errors
+    ///
+    /// This class represents differences between the RDAP data and the DNS.
+    #[strum(serialize = "DnsErr")]
+    DnsConsistencyError,
+
+    /// NRO Profile Errors
+    ///
+    /// This class represents errors with respect to the NRO RDAP profile.
+    #[strum(serialize = "NroProfileErr")]
+    NroProfileError,
 }
 
 /// All check classes
@@ -99,12 +119,18 @@ pub static WARNING_CHECK_CLASSES: &[CheckClass] = &[
     CheckClass::Std95Error,
     CheckClass::Cidr0Error,
     CheckClass::GtldProfileError,
+    CheckClass::Rfc9537Error,
+    CheckClass::DnsConsistencyError,
+    CheckClass::NroProfileError,
 ];
 /// All error check classes.
 pub static ERROR_CHECK_CLASSES: &[CheckClass] = &[
     CheckClass::Std95Error,
     CheckClass::Cidr0Error,
     CheckClass::GtldProfileError,
+    CheckClass::Rfc9537Error,
+    CheckClass::DnsConsistencyError,
+    CheckClass::NroProfileError,
 ];
 
 /// Represents the name of an RDAP structure for which a check appears.
@@ -119,6 +145,7 @@ pub static ERROR_CHECK_CLASSES: &[CheckClass] = &[
 #[serde(rename_all = "snake_case")]
 pub enum RdapStructure {
     Autnum,
+    AutnumSearchResults,
     Cidr0,
     Domain,
     DomainSearchResults,
@@ -138,6 +165,7 @@ pub enum RdapStructure {
     Links,
     Nameserver,
     NameserverSearchResults,
+    NetworkSearchResults,
     NoticeOrRemark,
     Notice,
     Notices,
@@ -260,6 +288,8 @@ impl GetChecks for RdapResponse {
             Self::DomainSearchResults(r) => r.get_checks(index, params),
             Self::EntitySearchResults(r) => r.get_checks(index, params),
             Self::NameserverSearchResults(r) => r.get_checks(index, params),
+            Self::NetworkSearchResults(r) => r.get_checks(index, params),
+            Self::AutnumSearchResults(r) => r.get_checks(index, params),
             Self::ErrorResponse(e) => e.get_checks(index, params),
             Self::Help(h) => h.get_checks(index, params),
         }
@@ -527,6 +557,22 @@ pub enum Check {
     ContentTypeIsAbsent = 1803,
     #[strum(message = "Content-type is not application/rdap+json.")]
     ContentTypeIsNotRdap = 1804,
+    #[strum(message = "Query for an object that does not exist did not return 404.")]
+    NotFoundStatusIsNot404 = 1805,
+    #[strum(message = "Error response does not have an RFC 9083 error body.")]
+    ErrorResponseIsNotRfc9083 = 1806,
+    #[strum(message = "Malformed query did not return 400.")]
+    MalformedQueryStatusIsNot400 = 1807,
+    #[strum(message = "Unsupported query did not return 501.")]
+    UnsupportedQueryStatusIsNot501 = 1808,
+    #[strum(message = "HEAD request was not answered like the GET request.")]
+    HeadNotAnsweredLikeGet = 1809,
+    #[strum(message = "Query with a trailing slash was not answered like the query.")]
+    TrailingSlashNotAnsweredLikeQuery = 1810,
+    #[strum(message = "Query accepting application/json was not answered with JSON.")]
+    JsonMediaTypeNotAccepted = 1811,
+    #[strum(message = "Use of access-control-allow-origin on error responses is recommended.")]
+    CorsAllowOriginOnErrorRecommended = 1812,
 
     // Cidr0 1900 - 1999
     #[strum(message = "Cidr0 v4 prefix is absent")]
@@ -606,6 +652,64 @@ pub enum Check {
     // Entities 2400 - 2499
     #[strum(message = "entity array is empty")]
     EntityArrayIsEmpty = 2400,
+
+    // Redactions 2500 - 2599
+    #[strum(message = "redaction name is not in the IANA registry.")]
+    RedactedNameUnregistered = 2500,
+    #[strum(message = "redaction method contradicts the paths given.")]
+    RedactedMethodContradictsPath = 2501,
+    #[strum(message = "prePath is not a valid JSONPath expression.")]
+    RedactedPrePathInvalid = 2502,
+    #[strum(message = "prePath matches a value that should have been removed.")]
+    RedactedPrePathMatchesValue = 2503,
+    #[strum(message = "postPath is not a valid JSONPath expression.")]
+    RedactedPostPathInvalid = 2504,
+    #[strum(message = "postPath does not match any value.")]
+    RedactedPostPathMatchesNothing = 2505,
+    #[strum(message = "replacementPath is not a valid JSONPath expression.")]
+    RedactedReplacementPathInvalid = 2506,
+    #[strum(message = "replacementPath does not match any value.")]
+    RedactedReplacementPathMatchesNothing = 2507,
+    #[strum(message = "'redacted' is not in 'rdapConformance' of response with redactions.")]
+    RedactedConformanceMissing = 2508,
+
+    // DNS Consistency 2600 - 2699
+    #[strum(message = "nameservers do not match the NS records of the delegation in DNS.")]
+    DnsNameserversMismatch = 2600,
+    #[strum(
+        message = "nameserver ipAddresses do not match the glue records of the delegation in DNS."
+    )]
+    DnsIpAddressesMismatch = 2601,
+    #[strum(message = "dsData does not match the DS records in DNS.")]
+    DnsDsDataMismatch = 2602,
+    #[strum(message = "ttl0 value is less than the TTL in DNS.")]
+    DnsTtlGreaterThanTtl0 = 2603,
+    #[strum(message = "DNS could not be queried to check the domain.")]
+    DnsQueryFailed = 2604,
+
+    // NRO Profile 2700 - 2799
+    #[strum(message = "'nro_rdap_profile_0' is not in 'rdapConformance'.")]
+    NroProfileConformanceMissing = 2700,
+    #[strum(message = "'cidr0' is not in 'rdapConformance' of network response.")]
+    NroCidr0ConformanceMissing = 2701,
+    #[strum(message = "neither NRO flat nor hierarchical ASN profile is in 'rdapConformance'.")]
+    NroAsnProfileConformanceMissing = 2702,
+    #[strum(message = "both NRO flat and hierarchical ASN profiles are in 'rdapConformance'.")]
+    NroAsnProfileConformanceAmbiguous = 2703,
+    #[strum(message = "no entity has the registrant role.")]
+    NroRegistrantEntityMissing = 2704,
+    #[strum(message = "no entity has the abuse role.")]
+    NroAbuseContactMissing = 2705,
+    #[strum(message = "abuse contact has no email address.")]
+    NroAbuseContactEmailMissing = 2706,
+    #[strum(message = "network has no cidr0_cidrs.")]
+    NroCidr0CidrsMissing = 2707,
+    #[strum(message = "network with a parent has no 'rdap-up' link.")]
+    NroRdapUpLinkMissing = 2708,
+    #[strum(message = "no 'rdap-top' link.")]
+    NroRdapTopLinkMissing = 2709,
+    #[strum(message = "autnum of the flat ASN profile has an 'rdap-up' link.")]
+    NroAsnFlatHasRdapUpLink = 2710,
 }
 
 impl Check {
@@ -690,6 +794,14 @@ impl Check {
             | Self::CorsAllowOriginStarRecommended
             | Self::CorsAllowCredentialsNotRecommended => CheckClass::Std95Warning,
             Self::ContentTypeIsAbsent | Self::ContentTypeIsNotRdap => CheckClass::Std95Error,
+            Self::NotFoundStatusIsNot404 => CheckClass::Std95Error,
+            Self::ErrorResponseIsNotRfc9083
+            | Self::MalformedQueryStatusIsNot400
+            | Self::UnsupportedQueryStatusIsNot501
+            | Self::HeadNotAnsweredLikeGet
+            | Self::TrailingSlashNotAnsweredLikeQuery
+            | Self::JsonMediaTypeNotAccepted
+            | Self::CorsAllowOriginOnErrorRecommended => CheckClass::Std95Warning,
 
             Self::Cidr0V4PrefixIsAbsent
             | Self::Cidr0V4LengthIsAbsent
@@ -724,6 +836,34 @@ impl Check {
             Self::NetworkOrAutnumCountryIsNotString => CheckClass::Std95Error,
 
             Self::EntityArrayIsEmpty => CheckClass::Std95Warning,
+
+            Self::RedactedNameUnregistered => CheckClass::SpecificationNote,
+            Self::RedactedMethodContradictsPath
+            | Self::RedactedPrePathInvalid
+            | Self::RedactedPrePathMatchesValue
+            | Self::RedactedPostPathInvalid
+            | Self::RedactedPostPathMatchesNothing
+            | Self::RedactedReplacementPathInvalid
+            | Self::RedactedReplacementPathMatchesNothing
+            | Self::RedactedConformanceMissing => CheckClass::Rfc9537Error,
+
+            Self::DnsNameserversMismatch
+            | Self::DnsIpAddressesMismatch
+            | Self::DnsDsDataMismatch
+            | Self::DnsTtlGreaterThanTtl0
+            | Self::DnsQueryFailed => CheckClass::DnsConsistencyError,
+
+            Self::NroProfileConformanceMissing
+            | Self::NroCidr0ConformanceMissing
+            | Self::NroAsnProfileConformanceMissing
+            | Self::NroAsnProfileConformanceAmbiguous
+            | Self::NroRegistrantEntityMissing
+            | Self::NroAbuseContactMissing
+            | Self::NroAbuseContactEmailMissing
+            | Self::NroCidr0CidrsMissing
+            | Self::NroRdapUpLinkMissing
+            | Self::NroRdapTopLinkMissing
+            | Self::NroAsnFlatHasRdapUpLink => CheckClass::NroProfileError,
         };
         CheckItem {
             check_class,
@@ -0,0 +1,304 @@
+//! Checks of the NRO RDAP profile.
+
+use crate::response::{Entity, ExtensionId, Links, RdapResponse};
+
+use super::{Check, CheckItem};
+
+/// Gets the NRO RDAP profile checks of a network or autnum response.
+///
+/// Other responses have no checks.
+pub(crate) fn nro_profile_checks(rdap: &RdapResponse) -> Vec<CheckItem> {
+    let mut items = vec![];
+    let entities = match rdap {
+        RdapResponse::Network(network) => {
+            if !rdap.has_extension_id(ExtensionId::Cidr0) {
+                items.push(Check::NroCidr0ConformanceMissing.check_item());
+            }
+            if network.cidr0_cidrs.as_ref().is_none_or(|c| c.is_empty()) {
+                items.push(Check::NroCidr0CidrsMissing.check_item());
+            }
+            // the relation links are only required of servers with RIR search
+            if rdap.has_extension_id(ExtensionId::RirSearch1) {
+                let links = network.object_common.links.as_ref();
+                if network.parent_handle.is_some() && !has_link_rel(links, "rdap-up") {
+                    items.push(Check::NroRdapUpLinkMissing.check_item());
+                }
+                if !has_link_rel(links, "rdap-top") {
+                    items.push(Check::NroRdapTopLinkMissing.check_item());
+                }
+            }
+            network.object_common.entities.as_deref()
+        }
+        RdapResponse::Autnum(autnum) => {
+            let links = autnum.object_common.links.as_ref();
+            let flat = rdap.has_extension_id(ExtensionId::NroRdapProfileAsnFlat0);
+            let hierarchical = rdap.has_extension_id(ExtensionId::NroRdapProfileAsnHierarchical0);
+            match (flat, hierarchical) {
+                (false, false) => items.push(Check::NroAsnProfileConformanceMissing.check_item()),
+                (true, true) => items.push(Check::NroAsnProfileConformanceAmbiguous.check_item()),
+                (true, false) => {
+                    if has_link_rel(links, "rdap-up") {
+                        items.push(Check::NroAsnFlatHasRdapUpLink.check_item());
+                    }
+                }
+                (false, true) => {
+                    if !has_link_rel(links, "rdap-top") {
+                        items.push(Check::NroRdapTopLinkMissing.check_item());
+                    }
+                }
+            }
+            autnum.object_common.entities.as_deref()
+        }
+        _ => return items,
+    };
+
+    if !rdap.has_extension_id(ExtensionId::NroRdapProfile0) {
+        items.push(Check::NroProfileConformanceMissing.check_item());
+    }
+
+    let entities = entities.unwrap_or_default();
+    if !entities.iter().any(|e| e.is_entity_role("registrant")) {
+        items.push(Check::NroRegistrantEntityMissing.check_item());
+    }
+
+    // abuse contacts are often entities of the registrant
+    let abuse_contacts = entities
+        .iter()
+        .chain(
+            entities
+                .iter()
+                .flat_map(|e| e.object_common.entities.as_deref().unwrap_or_default()),
+        )
+        .filter(|e| e.is_entity_role("abuse"))
+        .collect::<Vec<&Entity>>();
+    if abuse_contacts.is_empty() {
+        items.push(Check::NroAbuseContactMissing.check_item());
+    } else if !abuse_contacts
+        .iter()
+        .any(|e| e.contact().is_some_and(|c| !c.emails().is_empty()))
+    {
+        items.push(Check::NroAbuseContactEmailMissing.check_item());
+    }
+
+    items
+}
+
+/// True if one of the links has the relation, as one of its space separated relations.
+fn has_link_rel(links: Option<&Links>, rel: &str) -> bool {
+    links.is_some_and(|links| {
+        links.iter().any(|l| {
+            l.rel()
+                .is_some_and(|r| r.split_whitespace().any(|r| r == rel))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        check::{Check, CheckItem},
+        response::RdapResponse,
+    };
+
+    use super::nro_profile_checks;
+
+    fn checks_of(json: &str) -> Vec<Check> {
+        let rdap = serde_json::from_str::<RdapResponse>(json).expect("parsing JSON");
+        nro_profile_checks(&rdap)
+            .into_iter()
+            .map(|i: CheckItem| i.check)
+            .collect()
+    }
+
+    const ABUSE_ENTITY: &str = r#"
+        {
+          "objectClassName" : "entity",
+          "handle" : "ABUSE-RIR",
+          "roles" : [ "abuse" ],
+          "vcardArray" : [ "vcard", [
+            [ "version", {}, "text", "4.0" ],
+            [ "fn", {}, "text", "Abuse" ],
+            [ "email", {}, "text", "abuse@example.net" ]
+          ] ]
+        }
+    "#;
+
+    #[test]
+    fn check_network_following_profile() {
+        // GIVEN
+        let json = format!(
+            r#"
+            {{
+              "rdapConformance" : [ "rdap_level_0", "nro_rdap_profile_0", "cidr0", "rirSearch1" ],
+              "objectClassName" : "ip network",
+              "handle" : "XXXX-RIR",
+              "startAddress" : "192.0.2.0",
+              "endAddress" : "192.0.2.255",
+              "ipVersion" : "v4",
+              "parentHandle" : "YYYY-RIR",
+              "cidr0_cidrs" : [ {{ "v4prefix" : "192.0.2.0", "length" : 24 }} ],
+              "links" : [
+                {{ "value" : "https://rdap.example.net/ip/192.0.2.0", "rel" : "rdap-up rdap-active", "href" : "https://rdap.example.net/ips/rirSearch1/rdap-up/192.0.2.0" }},
+                {{ "value" : "https://rdap.example.net/ip/192.0.2.0", "rel" : "rdap-top", "href" : "https://rdap.example.net/ips/rirSearch1/rdap-top/192.0.2.0" }}
+              ],
+              "entities" : [
+                {{
+                  "objectClassName" : "entity",
+                  "handle" : "ORG-RIR",
+                  "roles" : [ "registrant" ],
+                  "entities" : [ {ABUSE_ENTITY} ]
+                }}
+              ]
+            }}
+        "#
+        );
+
+        // WHEN
+        let checks = checks_of(&json);
+
+        // THEN
+        assert!(checks.is_empty(), "{checks:?}");
+    }
+
+    #[test]
+    fn check_network_not_following_profile() {
+        // GIVEN
+        let json = r#"
+            {
+              "rdapConformance" : [ "rdap_level_0" ],
+              "objectClassName" : "ip network",
+              "handle" : "XXXX-RIR",
+              "startAddress" : "192.0.2.0",
+              "endAddress" : "192.0.2.255",
+              "ipVersion" : "v4",
+              "parentHandle" : "YYYY-RIR"
+            }
+        "#;
+
+        // WHEN
+        let checks = checks_of(json);
+
+        // THEN
+        assert_eq!(
+            checks,
+            vec![
+                Check::NroCidr0ConformanceMissing,
+                Check::NroCidr0CidrsMissing,
+                Check::NroProfileConformanceMissing,
+                Check::NroRegistrantEntityMissing,
+                Check::NroAbuseContactMissing,
+            ]
+        );
+    }
+
+    #[test]
+    fn check_network_with_rir_search_missing_links() {
+        // GIVEN
+        let json = format!(
+            r#"
+            {{
+              "rdapConformance" : [ "rdap_level_0", "nro_rdap_profile_0", "cidr0", "rirSearch1" ],
+              "objectClassName" : "ip network",
+              "handle" : "XXXX-RIR",
+              "startAddress" : "192.0.2.0",
+              "endAddress" : "192.0.2.255",
+              "ipVersion" : "v4",
+              "parentHandle" : "YYYY-RIR",
+              "cidr0_cidrs" : [ {{ "v4prefix" : "192.0.2.0", "length" : 24 }} ],
+              "entities" : [
+                {{
+                  "objectClassName" : "entity",
+                  "handle" : "ORG-RIR",
+                  "roles" : [ "registrant" ],
+                  "entities" : [ {ABUSE_ENTITY} ]
+                }}
+              ]
+            }}
+        "#
+        );
+
+        // WHEN
+        let checks = checks_of(&json);
+
+        // THEN
+        assert_eq!(
+            checks,
+            vec![Check::NroRdapUpLinkMissing, Check::NroRdapTopLinkMissing]
+        );
+    }
+
+    #[test]
+    fn check_flat_autnum_with_rdap_up_link() {
+        // GIVEN
+        let json = format!(
+            r#"
+            {{
+              "rdapConformance" : [ "rdap_level_0", "nro_rdap_profile_0", "nro_rdap_profile_asn_flat_0" ],
+              "objectClassName" : "autnum",
+              "handle" : "AS64496-RIR",
+              "startAutnum" : 64496,
+              "endAutnum" : 64496,
+              "links" : [
+                {{ "value" : "https://rdap.example.net/autnum/64496", "rel" : "rdap-up", "href" : "https://rdap.example.net/autnum/64000" }}
+              ],
+              "entities" : [
+                {{ "objectClassName" : "entity", "handle" : "ORG-RIR", "roles" : [ "registrant" ] }},
+                {ABUSE_ENTITY}
+              ]
+            }}
+        "#
+        );
+
+        // WHEN
+        let checks = checks_of(&json);
+
+        // THEN
+        assert_eq!(checks, vec![Check::NroAsnFlatHasRdapUpLink]);
+    }
+
+    #[test]
+    fn check_autnum_with_both_asn_profiles_and_abuse_without_email() {
+        // GIVEN
+        let json = r#"
+            {
+              "rdapConformance" : [ "rdap_level_0", "nro_rdap_profile_0", "nro_rdap_profile_asn_flat_0", "nro_rdap_profile_asn_hierarchical_0" ],
+              "objectClassName" : "autnum",
+              "handle" : "AS64496-RIR",
+              "startAutnum" : 64496,
+              "endAutnum" : 64496,
+              "entities" : [
+                { "objectClassName" : "entity", "handle" : "ORG-RIR", "roles" : [ "registrant", "abuse" ] }
+              ]
+            }
+        "#;
+
+        // WHEN
+        let checks = checks_of(json);
+
+        // THEN
+        assert_eq!(
+            checks,
+            vec![
+                Check::NroAsnProfileConformanceAmbiguous,
+                Check::NroAbuseContactEmailMissing,
+            ]
+        );
+    }
+
+    #[test]
+    fn check_domain_has_no_nro_checks() {
+        // GIVEN
+        let json = r#"
+            {
+              "objectClassName" : "domain",
+              "ldhName" : "example.net"
+            }
+        "#;
+
+        // WHEN
+        let checks = checks_of(json);
+
+        // THEN
+        assert!(checks.is_empty());
+    }
+}
@@ -2,9 +2,15 @@
 
 use strum::{EnumMessage, VariantArray};
 
-use crate::{httpdata::HttpData, prelude::RdapResponse};
+use crate::{
+    httpdata::HttpData,
+    prelude::{ExtensionId, RdapResponse},
+};
 
-use super::{traverse_checks, Check, CheckClass, CheckParams, CheckSummary, Checks, GetChecks};
+use super::{
+    nro::nro_profile_checks, traverse_checks, Check, CheckClass, CheckParams, CheckSummary, Checks,
+    GetChecks,
+};
 
 pub fn do_check_processing(
     rdap: &RdapResponse,
@@ -38,6 +44,17 @@ pub fn do_check_processing(
         }
     }
 
+    // add NRO profile checks if the profile is expected
+    let nro_profile = ExtensionId::NroRdapProfile0.to_string();
+    let nro_expected = expect_exts.is_some_and(|exts| {
+        exts.iter()
+            .any(|ext| ext.split('|').any(|s| s == nro_profile))
+    });
+    if nro_expected {
+        // add these to the root check structure
+        checks.items.append(&mut nro_profile_checks(rdap));
+    }
+
     checks
 }
 
@@ -69,8 +86,11 @@ pub fn get_summaries(checks: &Checks, classes: Option<&[CheckClass]>) -> Vec<Che
 mod tests {
 
     use crate::{
-        check::process::rdap_has_expected_extension,
-        prelude::{Domain, Extension, ToResponse},
+        check::{
+            process::{do_check_processing, rdap_has_expected_extension},
+            Check,
+        },
+        prelude::{Domain, Extension, ExtensionId, Network, ToResponse},
     };
 
     #[test]
@@ -120,4 +140,46 @@ mod tests {
         // THEN
         assert!(actual);
     }
+
+    #[test]
+    fn test_nro_checks_when_nro_profile_expected() {
+        // GIVEN
+        let network = Network::response_obj()
+            .cidr("10.0.0.0/24")
+            .build()
+            .expect("cidr parsing");
+        let rdap = network.to_response();
+        let expected = vec![ExtensionId::NroRdapProfile0.to_string()];
+
+        // WHEN
+        let checks = do_check_processing(&rdap, None, Some(&expected), false);
+
+        // THEN
+        assert!(checks
+            .items
+            .iter()
+            .any(|c| c.check == Check::NroProfileConformanceMissing));
+    }
+
+    #[test]
+    fn test_no_nro_checks_when_nro_profile_declared_but_not_expected() {
+        // GIVEN
+        let network = Network::response_obj()
+            .extension(Extension::from(
+                ExtensionId::NroRdapProfile0.to_string().as_str(),
+            ))
+            .cidr("10.0.0.0/24")
+            .build()
+            .expect("cidr parsing");
+        let rdap = network.to_response();
+
+        // WHEN
+        let checks = do_check_processing(&rdap, None, None, false);
+
+        // THEN
+        assert!(!checks
+            .items
+            .iter()
+            .any(|c| c.check == Check::NroProfileConformanceMissing));
+    }
 }
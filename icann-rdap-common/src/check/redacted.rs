@@ -0,0 +1,408 @@
+use std::str::FromStr;
+
+use {jsonpath_rust::JsonPath, serde_json::Value};
+
+use crate::response::redacted::{Method, Redacted, RedactedName};
+
+use super::{Check, CheckItem, CheckParams, Checks, GetChecks, RdapStructure};
+
+impl GetChecks for Redacted {
+    fn get_checks(&self, index: Option<usize>, params: CheckParams) -> Checks {
+        let mut items = vec![];
+
+        // names
+        if let Some(name_type) = self.name().type_field() {
+            if RedactedName::from_str(name_type).is_err() {
+                items.push(Check::RedactedNameUnregistered.check_item());
+            }
+        }
+
+        // method and paths
+        let method = self.method().cloned().unwrap_or_default();
+        let contradicts = match method {
+            Method::Removal => self.pre_path().is_none() || self.post_path().is_some(),
+            Method::EmptyValue | Method::PartialValue => self.post_path().is_none(),
+            Method::ReplacementValue => {
+                self.post_path().is_none() && self.replacement_path().is_none()
+            }
+        } || (self.replacement_path().is_some()
+            && !matches!(method, Method::ReplacementValue));
+        if contradicts {
+            items.push(Check::RedactedMethodContradictsPath.check_item());
+        }
+
+        // the paths can only be evaluated if they are JSONPath, which is the default
+        let is_jsonpath = self
+            .path_lang()
+            .is_none_or(|lang| lang.eq_ignore_ascii_case("jsonpath"));
+        if is_jsonpath {
+            items.append(&mut self.path_checks(params));
+        }
+
+        Checks {
+            rdap_struct: RdapStructure::Redacted,
+            index,
+            items,
+            sub_checks: vec![],
+        }
+    }
+}
+
+impl Redacted {
+    fn path_checks(&self, params: CheckParams) -> Vec<CheckItem> {
+        let mut items = vec![];
+        let (Ok(root), Ok(redaction)) = (
+            serde_json::to_value(params.root),
+            serde_json::to_value(self),
+        ) else {
+            return items;
+        };
+        let rdap = redaction_owner(&root, &redaction).unwrap_or(&root);
+
+        // a removed field must not be found, but other methods may leave it in place
+        if let Some(pre_path) = self.pre_path() {
+            let removed = matches!(self.method(), None | Some(Method::Removal));
+            match matches(rdap, pre_path) {
+                None => items.push(Check::RedactedPrePathInvalid.check_item()),
+                Some(true) if removed => {
+                    items.push(Check::RedactedPrePathMatchesValue.check_item())
+                }
+                Some(_) => {}
+            }
+        }
+
+        // other redacted fields must be found
+        if let Some(post_path) = self.post_path() {
+            match matches(rdap, post_path) {
+                None => items.push(Check::RedactedPostPathInvalid.check_item()),
+                Some(false) => items.push(Check::RedactedPostPathMatchesNothing.check_item()),
+                Some(true) => {}
+            }
+        }
+        if let Some(replacement_path) = self.replacement_path() {
+            match matches(rdap, replacement_path) {
+                None => items.push(Check::RedactedReplacementPathInvalid.check_item()),
+                Some(false) => {
+                    items.push(Check::RedactedReplacementPathMatchesNothing.check_item())
+                }
+                Some(true) => {}
+            }
+        }
+        items
+    }
+}
+
+/// Finds the object whose `redacted` array holds the redaction, as its paths
+/// are relative to that object and not to search results wrapping it.
+fn redaction_owner<'a>(value: &'a Value, redaction: &Value) -> Option<&'a Value> {
+    match value {
+        Value::Object(object) => {
+            let owns = object
+                .get("redacted")
+                .and_then(Value::as_array)
+                .is_some_and(|redacted| redacted.contains(redaction));
+            if owns {
+                Some(value)
+            } else {
+                object
+                    .values()
+                    .find_map(|value| redaction_owner(value, redaction))
+            }
+        }
+        Value::Array(array) => array
+            .iter()
+            .find_map(|value| redaction_owner(value, redaction)),
+        _ => None,
+    }
+}
+
+/// Evaluates a JSONPath, returning `None` if it is not valid.
+fn matches(rdap: &Value, path: &str) -> Option<bool> {
+    rdap.query(path).ok().map(|found| !found.is_empty())
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use crate::{
+        check::{contains_check, Check, CheckParams, Checks, GetChecks},
+        prelude::{ExtensionId, ToResponse},
+        response::{
+            domain::Domain,
+            redacted::{Method, Name, Redacted},
+            search::DomainSearchResults,
+            RdapResponse,
+        },
+    };
+
+    fn domain_with(redacted: Redacted, with_conformance: bool) -> RdapResponse {
+        let extensions = if with_conformance {
+            vec![ExtensionId::Redacted.to_extension()]
+        } else {
+            vec![]
+        };
+        Domain::response_obj()
+            .ldh_name("example.com")
+            .handle("EXAMPLE-1")
+            .extensions(extensions)
+            .redacted(vec![redacted])
+            .build()
+            .to_response()
+    }
+
+    fn checks_of(rdap: &RdapResponse) -> Checks {
+        rdap.get_checks(None, CheckParams::for_rdap(rdap))
+    }
+
+    fn registry_domain_id() -> Name {
+        Name::builder().type_field("Registry Domain ID").build()
+    }
+
+    #[test]
+    fn GIVEN_valid_removal_WHEN_checked_THEN_no_redacted_checks() {
+        // GIVEN
+        let redacted = Redacted::builder()
+            .name(Name::builder().type_field("Registrant Email").build())
+            .pre_path("$.entities[?(@.roles[0]=='registrant')]".to_string())
+            .method(Method::Removal)
+            .build();
+        let rdap = domain_with(redacted, true);
+
+        // WHEN
+        let checks = checks_of(&rdap);
+
+        // THEN
+        for check in [
+            Check::RedactedNameUnregistered,
+            Check::RedactedMethodContradictsPath,
+            Check::RedactedPrePathInvalid,
+            Check::RedactedPrePathMatchesValue,
+            Check::RedactedConformanceMissing,
+        ] {
+            assert!(!contains_check(check, &checks), "{check}");
+        }
+    }
+
+    #[test]
+    fn GIVEN_pre_path_not_parsable_WHEN_checked_THEN_invalid() {
+        // GIVEN
+        let redacted = Redacted::builder()
+            .name(registry_domain_id())
+            .pre_path("$.entities[?(@.roles[0]=='registrant'".to_string())
+            .build();
+        let rdap = domain_with(redacted, true);
+
+        // WHEN
+        let checks = checks_of(&rdap);
+
+        // THEN
+        assert!(contains_check(Check::RedactedPrePathInvalid, &checks));
+    }
+
+    #[test]
+    fn GIVEN_removed_field_present_WHEN_checked_THEN_pre_path_matches_value() {
+        // GIVEN
+        let redacted = Redacted::builder()
+            .name(registry_domain_id())
+            .pre_path("$.handle".to_string())
+            .method(Method::Removal)
+            .build();
+        let rdap = domain_with(redacted, true);
+
+        // WHEN
+        let checks = checks_of(&rdap);
+
+        // THEN
+        assert!(contains_check(Check::RedactedPrePathMatchesValue, &checks));
+    }
+
+    #[test]
+    fn GIVEN_replaced_field_present_WHEN_checked_THEN_pre_path_does_not_match_value() {
+        // GIVEN
+        let redacted = Redacted::builder()
+            .name(registry_domain_id())
+            .pre_path("$.handle".to_string())
+            .replacement_path("$.ldhName".to_string())
+            .method(Method::ReplacementValue)
+            .build();
+        let rdap = domain_with(redacted, true);
+
+        // WHEN
+        let checks = checks_of(&rdap);
+
+        // THEN
+        assert!(!contains_check(Check::RedactedPrePathMatchesValue, &checks));
+        assert!(!contains_check(Check::RedactedPrePathInvalid, &checks));
+    }
+
+    #[test]
+    fn GIVEN_redacted_WHEN_inherent_get_checks_THEN_same_as_trait() {
+        // GIVEN
+        let redacted = Redacted::builder()
+            .name(registry_domain_id())
+            .pre_path("$.handle".to_string())
+            .method(Method::Removal)
+            .build();
+        let rdap = domain_with(redacted.clone(), true);
+
+        // WHEN
+        let checks = redacted.get_checks(CheckParams::for_rdap(&rdap));
+
+        // THEN
+        assert!(checks.index.is_none());
+        assert!(contains_check(Check::RedactedPrePathMatchesValue, &checks));
+    }
+
+    #[test]
+    fn GIVEN_post_path_to_nothing_WHEN_checked_THEN_matches_nothing() {
+        // GIVEN
+        let redacted = Redacted::builder()
+            .name(registry_domain_id())
+            .post_path("$.port43".to_string())
+            .method(Method::EmptyValue)
+            .build();
+        let rdap = domain_with(redacted, true);
+
+        // WHEN
+        let checks = checks_of(&rdap);
+
+        // THEN
+        assert!(contains_check(
+            Check::RedactedPostPathMatchesNothing,
+            &checks
+        ));
+        assert!(!contains_check(
+            Check::RedactedMethodContradictsPath,
+            &checks
+        ));
+    }
+
+    #[test]
+    fn GIVEN_replacement_path_to_nothing_WHEN_checked_THEN_matches_nothing() {
+        // GIVEN
+        let redacted = Redacted::builder()
+            .name(registry_domain_id())
+            .post_path("$.handle".to_string())
+            .replacement_path("$.nothing".to_string())
+            .method(Method::ReplacementValue)
+            .build();
+        let rdap = domain_with(redacted, true);
+
+        // WHEN
+        let checks = checks_of(&rdap);
+
+        // THEN
+        assert!(contains_check(
+            Check::RedactedReplacementPathMatchesNothing,
+            &checks
+        ));
+        assert!(!contains_check(
+            Check::RedactedPostPathMatchesNothing,
+            &checks
+        ));
+    }
+
+    #[test]
+    fn GIVEN_search_result_paths_WHEN_checked_THEN_paths_relative_to_result() {
+        // GIVEN
+        let redacted = Redacted::builder()
+            .name(registry_domain_id())
+            .post_path("$.port43".to_string())
+            .replacement_path("$.handle".to_string())
+            .method(Method::ReplacementValue)
+            .build();
+        let domain = Domain::builder()
+            .ldh_name("example.com")
+            .handle("EXAMPLE-1")
+            .port_43("whois.example.com".to_string())
+            .redacted(vec![redacted])
+            .build();
+        let rdap = DomainSearchResults::response_obj()
+            .results(vec![domain])
+            .extensions(vec![ExtensionId::Redacted.to_extension()])
+            .build()
+            .to_response();
+
+        // WHEN
+        let checks = checks_of(&rdap);
+
+        // THEN
+        assert!(!contains_check(
+            Check::RedactedPostPathMatchesNothing,
+            &checks
+        ));
+        assert!(!contains_check(
+            Check::RedactedReplacementPathMatchesNothing,
+            &checks
+        ));
+    }
+
+    #[test]
+    fn GIVEN_empty_value_with_pre_path_WHEN_checked_THEN_method_contradicts_path() {
+        // GIVEN
+        let redacted = Redacted::builder()
+            .name(registry_domain_id())
+            .pre_path("$.port43".to_string())
+            .method(Method::EmptyValue)
+            .build();
+        let rdap = domain_with(redacted, true);
+
+        // WHEN
+        let checks = checks_of(&rdap);
+
+        // THEN
+        assert!(contains_check(
+            Check::RedactedMethodContradictsPath,
+            &checks
+        ));
+    }
+
+    #[test]
+    fn GIVEN_no_redacted_conformance_WHEN_checked_THEN_conformance_missing() {
+        // GIVEN
+        let redacted = Redacted::builder()
+            .name(registry_domain_id())
+            .pre_path("$.port43".to_string())
+            .build();
+        let rdap = domain_with(redacted, false);
+
+        // WHEN
+        let checks = checks_of(&rdap);
+
+        // THEN
+        assert!(contains_check(Check::RedactedConformanceMissing, &checks));
+    }
+
+    #[test]
+    fn GIVEN_unregistered_name_WHEN_checked_THEN_name_unregistered() {
+        // GIVEN
+        let redacted = Redacted::builder()
+            .name(Name::builder().type_field("Abuse Fax").build())
+            .pre_path("$.port43".to_string())
+            .build();
+        let rdap = domain_with(redacted, true);
+
+        // WHEN
+        let checks = checks_of(&rdap);
+
+        // THEN
+        assert!(contains_check(Check::RedactedNameUnregistered, &checks));
+    }
+
+    #[test]
+    fn GIVEN_other_path_lang_WHEN_checked_THEN_paths_not_evaluated() {
+        // GIVEN
+        let redacted = Redacted::builder()
+            .name(registry_domain_id())
+            .pre_path("not jsonpath".to_string())
+            .path_lang("xpath".to_string())
+            .build();
+        let rdap = domain_with(redacted, true);
+
+        // WHEN
+        let checks = checks_of(&rdap);
+
+        // THEN
+        assert!(!contains_check(Check::RedactedPrePathInvalid, &checks));
+    }
+}
@@ -1,6 +1,9 @@
 use std::any::TypeId;
 
-use crate::response::search::{DomainSearchResults, EntitySearchResults, NameserverSearchResults};
+use crate::response::search::{
+    AutnumSearchResults, DomainSearchResults, EntitySearchResults, NameserverSearchResults,
+    NetworkSearchResults,
+};
 
 use super::{CheckParams, Checks, GetChecks, GetGroupChecks};
 
@@ -66,3 +69,45 @@ impl GetChecks for EntitySearchResults {
         }
     }
 }
+
+impl GetChecks for NetworkSearchResults {
+    fn get_checks(&self, index: Option<usize>, params: CheckParams) -> super::Checks {
+        let sub_checks: Vec<Checks> = {
+            let mut sub_checks: Vec<Checks> = self
+                .common
+                .get_group_checks(params.from_parent(TypeId::of::<Self>()));
+            self.results.iter().enumerate().for_each(|(i, result)| {
+                sub_checks
+                    .push(result.get_checks(Some(i), params.from_parent(TypeId::of::<Self>())))
+            });
+            sub_checks
+        };
+        Checks {
+            rdap_struct: super::RdapStructure::NetworkSearchResults,
+            index,
+            items: vec![],
+            sub_checks,
+        }
+    }
+}
+
+impl GetChecks for AutnumSearchResults {
+    fn get_checks(&self, index: Option<usize>, params: CheckParams) -> super::Checks {
+        let sub_checks: Vec<Checks> = {
+            let mut sub_checks: Vec<Checks> = self
+                .common
+                .get_group_checks(params.from_parent(TypeId::of::<Self>()));
+            self.results.iter().enumerate().for_each(|(i, result)| {
+                sub_checks
+                    .push(result.get_checks(Some(i), params.from_parent(TypeId::of::<Self>())))
+            });
+            sub_checks
+        };
+        Checks {
+            rdap_struct: super::RdapStructure::AutnumSearchResults,
+            index,
+            items: vec![],
+            sub_checks,
+        }
+    }
+}
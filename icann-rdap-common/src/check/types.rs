@@ -375,6 +375,21 @@ impl GetGroupChecks for ObjectCommon {
             }
         }
 
+        // redactions
+        if let Some(redacted) = &self.redacted {
+            if !redacted.is_empty() && !params.root.has_extension_id(ExtensionId::Redacted) {
+                sub_checks.push(Checks {
+                    rdap_struct: super::RdapStructure::Redacted,
+                    index: None,
+                    items: vec![Check::RedactedConformanceMissing.check_item()],
+                    sub_checks: vec![],
+                })
+            }
+            for (i, redaction) in redacted.iter().enumerate() {
+                sub_checks.push(GetChecks::get_checks(redaction, Some(i), params));
+            }
+        }
+
         sub_checks
     }
 }
@@ -6,8 +6,9 @@
 use crate::{
     contact::Contact,
     prelude::{
-        Autnum, Domain, DomainSearchResults, Entity, EntitySearchResults, Nameserver,
-        NameserverSearchResults, Network, RdapResponse, ToResponse,
+        Autnum, AutnumSearchResults, Domain, DomainSearchResults, Entity, EntitySearchResults,
+        Nameserver, NameserverSearchResults, Network, NetworkSearchResults, RdapResponse,
+        ToResponse,
     },
 };
 
@@ -36,6 +37,12 @@ impl JsContactConvert for RdapResponse {
             RdapResponse::NameserverSearchResults(nameserver_search_results) => {
                 nameserver_search_results.to_jscontact().to_response()
             }
+            RdapResponse::NetworkSearchResults(network_search_results) => {
+                network_search_results.to_jscontact().to_response()
+            }
+            RdapResponse::AutnumSearchResults(autnum_search_results) => {
+                autnum_search_results.to_jscontact().to_response()
+            }
             RdapResponse::ErrorResponse(rfc9083_error) => rfc9083_error.to_response(),
             RdapResponse::Help(help) => help.to_response(),
         }
@@ -57,6 +64,12 @@ impl JsContactConvert for RdapResponse {
             RdapResponse::NameserverSearchResults(nameserver_search_results) => {
                 nameserver_search_results.only_jscontact().to_response()
             }
+            RdapResponse::NetworkSearchResults(network_search_results) => {
+                network_search_results.only_jscontact().to_response()
+            }
+            RdapResponse::AutnumSearchResults(autnum_search_results) => {
+                autnum_search_results.only_jscontact().to_response()
+            }
             RdapResponse::ErrorResponse(rfc9083_error) => rfc9083_error.to_response(),
             RdapResponse::Help(help) => help.to_response(),
         }
@@ -250,6 +263,46 @@ impl JsContactConvert for EntitySearchResults {
     }
 }
 
+impl JsContactConvert for NetworkSearchResults {
+    fn to_jscontact(self) -> Self {
+        Self {
+            results: self.results.into_iter().map(|i| i.to_jscontact()).collect(),
+            ..self
+        }
+    }
+
+    fn only_jscontact(self) -> Self {
+        Self {
+            results: self
+                .results
+                .into_iter()
+                .map(|i| i.only_jscontact())
+                .collect(),
+            ..self
+        }
+    }
+}
+
+impl JsContactConvert for AutnumSearchResults {
+    fn to_jscontact(self) -> Self {
+        Self {
+            results: self.results.into_iter().map(|i| i.to_jscontact()).collect(),
+            ..self
+        }
+    }
+
+    fn only_jscontact(self) -> Self {
+        Self {
+            results: self
+                .results
+                .into_iter()
+                .map(|i| i.only_jscontact())
+                .collect(),
+            ..self
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
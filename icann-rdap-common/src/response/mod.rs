@@ -32,8 +32,12 @@ pub use network::*;
 #[doc(inline)]
 pub use obj_common::*;
 #[doc(inline)]
+pub use paging::*;
+#[doc(inline)]
 pub use search::*;
 #[doc(inline)]
+pub use subsetting::*;
+#[doc(inline)]
 pub use types::*;
 #[doc(inline)]
 pub use values::*;
@@ -49,8 +53,10 @@ pub(crate) mod lenient;
 pub(crate) mod nameserver;
 pub(crate) mod network;
 pub(crate) mod obj_common;
+pub(crate) mod paging;
 pub mod redacted; // RFC 9537 is not a mainstream extension.
 pub(crate) mod search;
+pub(crate) mod subsetting;
 pub mod ttl; // ttl0 extension
 pub(crate) mod types;
 pub(crate) mod values;
@@ -139,6 +145,8 @@ pub enum RdapResponse {
     DomainSearchResults(Box<DomainSearchResults>),
     EntitySearchResults(Box<EntitySearchResults>),
     NameserverSearchResults(Box<NameserverSearchResults>),
+    NetworkSearchResults(Box<NetworkSearchResults>),
+    AutnumSearchResults(Box<AutnumSearchResults>),
 
     // Error
     ErrorResponse(Box<Rfc9083Error>),
@@ -211,6 +219,26 @@ impl TryFrom<Value> for RdapResponse {
                 ));
             }
         }
+        // else if it is an IP network search result
+        if let Some(result) = response.get("ipSearchResults") {
+            if result.is_array() {
+                return Ok(serde_json::from_value::<NetworkSearchResults>(value)?.to_response());
+            } else {
+                return Err(RdapResponseError::WrongJsonType(
+                    "'ipSearchResults' is not an array".to_string(),
+                ));
+            }
+        }
+        // else if it is an autnum search result
+        if let Some(result) = response.get("autnumSearchResults") {
+            if result.is_array() {
+                return Ok(serde_json::from_value::<AutnumSearchResults>(value)?.to_response());
+            } else {
+                return Err(RdapResponseError::WrongJsonType(
+                    "'autnumSearchResults' is not an array".to_string(),
+                ));
+            }
+        }
 
         // else if it has an errorCode
         if let Some(result) = response.get("errorCode") {
@@ -248,6 +276,8 @@ impl RdapResponse {
             Self::DomainSearchResults(_) => TypeId::of::<DomainSearchResults>(),
             Self::EntitySearchResults(_) => TypeId::of::<EntitySearchResults>(),
             Self::NameserverSearchResults(_) => TypeId::of::<NameserverSearchResults>(),
+            Self::NetworkSearchResults(_) => TypeId::of::<NetworkSearchResults>(),
+            Self::AutnumSearchResults(_) => TypeId::of::<AutnumSearchResults>(),
             Self::ErrorResponse(_) => TypeId::of::<crate::response::Rfc9083Error>(),
             Self::Help(_) => TypeId::of::<Help>(),
         }
@@ -263,6 +293,8 @@ impl RdapResponse {
             Self::DomainSearchResults(_)
             | Self::EntitySearchResults(_)
             | Self::NameserverSearchResults(_)
+            | Self::NetworkSearchResults(_)
+            | Self::AutnumSearchResults(_)
             | Self::ErrorResponse(_)
             | Self::Help(_) => None,
         }
@@ -278,6 +310,8 @@ impl RdapResponse {
             Self::DomainSearchResults(s) => s.common.rdap_conformance.as_ref(),
             Self::EntitySearchResults(s) => s.common.rdap_conformance.as_ref(),
             Self::NameserverSearchResults(s) => s.common.rdap_conformance.as_ref(),
+            Self::NetworkSearchResults(s) => s.common.rdap_conformance.as_ref(),
+            Self::AutnumSearchResults(s) => s.common.rdap_conformance.as_ref(),
             Self::ErrorResponse(e) => e.common.rdap_conformance.as_ref(),
             Self::Help(h) => h.common.rdap_conformance.as_ref(),
         }
@@ -442,6 +476,8 @@ impl ContentExtensions for RdapResponse {
             Self::DomainSearchResults(r) => r.content_extensions(),
             Self::EntitySearchResults(r) => r.content_extensions(),
             Self::NameserverSearchResults(r) => r.content_extensions(),
+            Self::NetworkSearchResults(r) => r.content_extensions(),
+            Self::AutnumSearchResults(r) => r.content_extensions(),
             Self::ErrorResponse(e) => e.content_extensions(),
             Self::Help(h) => h.content_extensions(),
         }
@@ -521,6 +557,22 @@ pub fn normalize_extensions(rdap: RdapResponse) -> RdapResponse {
             ..*r
         }
         .to_response(),
+        RdapResponse::NetworkSearchResults(r) => NetworkSearchResults {
+            common: Common {
+                rdap_conformance: Some(rdap_conformance),
+                ..r.common
+            },
+            ..*r
+        }
+        .to_response(),
+        RdapResponse::AutnumSearchResults(r) => AutnumSearchResults {
+            common: Common {
+                rdap_conformance: Some(rdap_conformance),
+                ..r.common
+            },
+            ..*r
+        }
+        .to_response(),
         RdapResponse::ErrorResponse(e) => Rfc9083Error {
             common: Common {
                 rdap_conformance: Some(rdap_conformance),
@@ -0,0 +1,262 @@
+//! RFC 8977 Sorting and Paging Metadata.
+use serde::{Deserialize, Serialize};
+
+use super::{to_opt_vec, Boolish, Link, Links, Numberish};
+
+/// Represents the RFC 8977 `paging_metadata` of search results.
+///
+/// ```rust
+/// use icann_rdap_common::prelude::*;
+///
+/// let paging_metadata = PagingMetadata::builder()
+///   .total_count(73)
+///   .page_size(10)
+///   .page_number(1)
+///   .link(Link::builder()
+///     .value("https://example.com/domains?name=foo*.example")
+///     .rel("next")
+///     .href("https://example.com/domains?name=foo*.example&cursor=MTA")
+///     .media_type("application/rdap+json")
+///     .build())
+///   .build();
+/// let next = paging_metadata.next_link();
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PagingMetadata {
+    #[serde(rename = "totalCount")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<Numberish<u64>>,
+
+    #[serde(rename = "pageSize")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<Numberish<u64>>,
+
+    #[serde(rename = "pageNumber")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_number: Option<Numberish<u64>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<Links>,
+}
+
+#[buildstructor::buildstructor]
+impl PagingMetadata {
+    /// Builds paging metadata.
+    #[builder(visibility = "pub")]
+    fn new(
+        total_count: Option<u64>,
+        page_size: Option<u64>,
+        page_number: Option<u64>,
+        links: Vec<Link>,
+    ) -> Self {
+        Self {
+            total_count: total_count.map(Numberish::<u64>::from),
+            page_size: page_size.map(Numberish::<u64>::from),
+            page_number: page_number.map(Numberish::<u64>::from),
+            links: to_opt_vec(links),
+        }
+    }
+
+    /// Returns the total number of results of the search, if given.
+    pub fn total_count(&self) -> Option<u64> {
+        self.total_count.as_ref().and_then(|n| n.as_u64())
+    }
+
+    /// Returns the number of results in a page, if given.
+    pub fn page_size(&self) -> Option<u64> {
+        self.page_size.as_ref().and_then(|n| n.as_u64())
+    }
+
+    /// Returns the number of this page, if given.
+    pub fn page_number(&self) -> Option<u64> {
+        self.page_number.as_ref().and_then(|n| n.as_u64())
+    }
+
+    /// Returns the paging links.
+    pub fn links(&self) -> &[Link] {
+        self.links.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the link to the next page of results, if any.
+    pub fn next_link(&self) -> Option<&Link> {
+        self.links().iter().find(|link| link.is_relation("next"))
+    }
+
+    /// Returns the link to the previous page of results, if any.
+    pub fn prev_link(&self) -> Option<&Link> {
+        self.links().iter().find(|link| link.is_relation("prev"))
+    }
+}
+
+/// Represents the RFC 8977 `sorting_metadata` of search results.
+///
+/// ```rust
+/// use icann_rdap_common::prelude::*;
+///
+/// let sorting_metadata = SortingMetadata::builder()
+///   .current_sort("name:d")
+///   .available_sort(AvailableSort::builder()
+///     .property("name")
+///     .json_path("$.domainSearchResults[*].ldhName")
+///     .default(true)
+///     .build())
+///   .build();
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SortingMetadata {
+    #[serde(rename = "currentSort")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_sort: Option<String>,
+
+    #[serde(rename = "availableSorts")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_sorts: Option<Vec<AvailableSort>>,
+}
+
+#[buildstructor::buildstructor]
+impl SortingMetadata {
+    /// Builds sorting metadata.
+    #[builder(visibility = "pub")]
+    fn new(current_sort: Option<String>, available_sorts: Vec<AvailableSort>) -> Self {
+        Self {
+            current_sort,
+            available_sorts: to_opt_vec(available_sorts),
+        }
+    }
+
+    /// Returns the sort applied to the results, if given.
+    pub fn current_sort(&self) -> Option<&str> {
+        self.current_sort.as_deref()
+    }
+
+    /// Returns the sorts supported by the server.
+    pub fn available_sorts(&self) -> &[AvailableSort] {
+        self.available_sorts.as_deref().unwrap_or_default()
+    }
+}
+
+/// A sort property supported by a server, as described in RFC 8977.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AvailableSort {
+    /// This value is required by RFC 8977.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property: Option<String>,
+
+    #[serde(rename = "jsonPath")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_path: Option<String>,
+
+    /// This value is required by RFC 8977.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<Boolish>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<Links>,
+}
+
+#[buildstructor::buildstructor]
+impl AvailableSort {
+    /// Builds an available sort.
+    #[builder(visibility = "pub")]
+    fn new(property: String, default: bool, json_path: Option<String>, links: Vec<Link>) -> Self {
+        Self {
+            property: Some(property),
+            json_path,
+            default: Some(Boolish::from(default)),
+            links: to_opt_vec(links),
+        }
+    }
+
+    /// Returns the name of the sort property.
+    pub fn property(&self) -> Option<&str> {
+        self.property.as_deref()
+    }
+
+    /// Returns the JSONPath of the sort property.
+    pub fn json_path(&self) -> Option<&str> {
+        self.json_path.as_deref()
+    }
+
+    /// Returns true if this is the default sort of the server.
+    pub fn is_default(&self) -> bool {
+        self.default.as_ref().is_some_and(|b| b.into_bool())
+    }
+
+    /// Returns the links of the sort.
+    pub fn links(&self) -> &[Link] {
+        self.links.as_deref().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use crate::prelude::Link;
+
+    use super::{AvailableSort, PagingMetadata, SortingMetadata};
+
+    #[test]
+    fn test_paging_metadata_deserialize() {
+        // GIVEN paging metadata from RFC 8977
+        let json = r#"
+            {
+              "totalCount": 73,
+              "pageSize": 10,
+              "pageNumber": 1,
+              "links": [
+                {
+                  "value": "https://example.com/rdap/domains?name=example*.com",
+                  "rel": "next",
+                  "href": "https://example.com/rdap/domains?name=example*.com&cursor=wJlCDLIl6KTWypN7T6vc6nWEmEYe99Hjf1XY1xmqV-M=",
+                  "type": "application/rdap+json"
+                }
+              ]
+            }
+        "#;
+
+        // WHEN deserialized
+        let actual = serde_json::from_str::<PagingMetadata>(json).expect("parsing paging metadata");
+
+        // THEN the values are present
+        assert_eq!(actual.total_count(), Some(73));
+        assert_eq!(actual.page_size(), Some(10));
+        assert_eq!(actual.page_number(), Some(1));
+        assert!(actual.next_link().is_some());
+        assert!(actual.prev_link().is_none());
+    }
+
+    #[test]
+    fn test_sorting_metadata_serialize() {
+        // GIVEN sorting metadata
+        let sorting_metadata = SortingMetadata::builder()
+            .current_sort("name")
+            .available_sort(
+                AvailableSort::builder()
+                    .property("name")
+                    .json_path("$.domainSearchResults[*].ldhName")
+                    .default(true)
+                    .link(
+                        Link::builder()
+                            .value("https://example.com/rdap/domains?name=example*.com")
+                            .rel("alternate")
+                            .href("https://example.com/rdap/domains?name=example*.com&sort=name")
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build();
+
+        // WHEN serialized
+        let actual = serde_json::to_value(&sorting_metadata).expect("serializing");
+
+        // THEN the RFC 8977 member names are used
+        assert_eq!(actual["currentSort"], Value::from("name"));
+        assert_eq!(actual["availableSorts"][0]["property"], Value::from("name"));
+        assert_eq!(actual["availableSorts"][0]["default"], Value::from(true));
+        assert_eq!(
+            actual["availableSorts"][0]["jsonPath"],
+            Value::from("$.domainSearchResults[*].ldhName")
+        );
+    }
+}
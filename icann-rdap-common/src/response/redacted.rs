@@ -37,9 +37,47 @@ use {
     buildstructor::Builder,
     serde::{Deserialize, Serialize},
     std::{any::TypeId, fmt},
+    strum_macros::{Display, EnumString},
 };
 
-use crate::check::Checks;
+/// Redacted types in the IANA registry
+#[derive(Debug, PartialEq, Eq, EnumString, Display)]
+pub enum RedactedName {
+    #[strum(serialize = "Registry Domain ID")]
+    RegistryDomainId,
+    #[strum(serialize = "Registry Registrant ID")]
+    RegistryRegistrantId,
+    #[strum(serialize = "Registrant Name")]
+    RegistrantName,
+    #[strum(serialize = "Registrant Organization")]
+    RegistrantOrganization,
+    #[strum(serialize = "Registrant Street")]
+    RegistrantStreet,
+    #[strum(serialize = "Registrant City")]
+    RegistrantCity,
+    #[strum(serialize = "Registrant Postal Code")]
+    RegistrantPostalCode,
+    #[strum(serialize = "Registrant Phone")]
+    RegistrantPhone,
+    #[strum(serialize = "Registrant Phone Ext")]
+    RegistrantPhoneExt,
+    #[strum(serialize = "Registrant Fax")]
+    RegistrantFax,
+    #[strum(serialize = "Registrant Fax Ext")]
+    RegistrantFaxExt,
+    #[strum(serialize = "Registrant Email")]
+    RegistrantEmail,
+    #[strum(serialize = "Registry Tech ID")]
+    RegistryTechId,
+    #[strum(serialize = "Tech Name")]
+    TechName,
+    #[strum(serialize = "Tech Phone")]
+    TechPhone,
+    #[strum(serialize = "Tech Phone Ext")]
+    TechPhoneExt,
+    #[strum(serialize = "Tech Email")]
+    TechEmail,
+}
 
 /// Redacted registered name.
 #[derive(Builder, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -195,13 +233,8 @@ impl Redacted {
     }
 
     /// Get the checks from Redactions.
-    pub fn get_checks(&self, _check_params: crate::check::CheckParams<'_>) -> crate::check::Checks {
-        Checks {
-            rdap_struct: crate::check::RdapStructure::Redacted,
-            index: None,
-            items: vec![],
-            sub_checks: vec![],
-        }
+    pub fn get_checks(&self, check_params: crate::check::CheckParams<'_>) -> crate::check::Checks {
+        crate::check::GetChecks::get_checks(self, None, check_params)
     }
 
     /// Get the type.
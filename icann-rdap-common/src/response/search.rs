@@ -1,14 +1,17 @@
 //! RDAP Search Results.
 use std::collections::HashSet;
 
-use crate::prelude::ContentExtensions;
+use crate::prelude::{to_opt_vec, ContentExtensions, ExtensionId};
 
 use {
-    crate::prelude::{Common, Extension},
+    crate::prelude::{Common, Extension, Notice},
     serde::{Deserialize, Serialize},
 };
 
-use super::{domain::Domain, entity::Entity, nameserver::Nameserver, CommonFields, ToResponse};
+use super::{
+    autnum::Autnum, domain::Domain, entity::Entity, nameserver::Nameserver, network::Network,
+    CommonFields, PagingMetadata, SortingMetadata, SubsettingMetadata, ToResponse,
+};
 
 /// Represents RDAP domain search results.
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Eq)]
@@ -18,16 +21,41 @@ pub struct DomainSearchResults {
 
     #[serde(rename = "domainSearchResults")]
     pub results: Vec<Domain>,
+
+    #[serde(rename = "paging_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paging_metadata: Option<PagingMetadata>,
+
+    #[serde(rename = "sorting_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sorting_metadata: Option<SortingMetadata>,
+
+    #[serde(rename = "subsetting_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subsetting_metadata: Option<SubsettingMetadata>,
 }
 
 #[buildstructor::buildstructor]
 impl DomainSearchResults {
     /// Builds a domain search result.
     #[builder(entry = "response_obj", visibility = "pub")]
-    fn new_response_obj(results: Vec<Domain>, extensions: Vec<Extension>) -> Self {
+    fn new_response_obj(
+        results: Vec<Domain>,
+        extensions: Vec<Extension>,
+        notices: Vec<Notice>,
+        paging_metadata: Option<PagingMetadata>,
+        sorting_metadata: Option<SortingMetadata>,
+        subsetting_metadata: Option<SubsettingMetadata>,
+    ) -> Self {
         Self {
-            common: Common::level0().extensions(extensions).build(),
+            common: Common::level0()
+                .extensions(extensions)
+                .and_notices(to_opt_vec(notices))
+                .build(),
             results,
+            paging_metadata,
+            sorting_metadata,
+            subsetting_metadata,
         }
     }
 
@@ -56,6 +84,15 @@ impl ContentExtensions for DomainSearchResults {
             .iter()
             .for_each(|d| exts.extend(d.content_extensions()));
         exts.extend(self.common().content_extensions());
+        if self.paging_metadata.is_some() {
+            exts.insert(ExtensionId::Paging);
+        }
+        if self.sorting_metadata.is_some() {
+            exts.insert(ExtensionId::Sorting);
+        }
+        if self.subsetting_metadata.is_some() {
+            exts.insert(ExtensionId::Subsetting);
+        }
         exts
     }
 }
@@ -68,16 +105,41 @@ pub struct NameserverSearchResults {
 
     #[serde(rename = "nameserverSearchResults")]
     pub results: Vec<Nameserver>,
+
+    #[serde(rename = "paging_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paging_metadata: Option<PagingMetadata>,
+
+    #[serde(rename = "sorting_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sorting_metadata: Option<SortingMetadata>,
+
+    #[serde(rename = "subsetting_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subsetting_metadata: Option<SubsettingMetadata>,
 }
 
 #[buildstructor::buildstructor]
 impl NameserverSearchResults {
     /// Builds a nameserver search result.
     #[builder(entry = "response_obj", visibility = "pub")]
-    fn new_response_obj(results: Vec<Nameserver>, extensions: Vec<Extension>) -> Self {
+    fn new_response_obj(
+        results: Vec<Nameserver>,
+        extensions: Vec<Extension>,
+        notices: Vec<Notice>,
+        paging_metadata: Option<PagingMetadata>,
+        sorting_metadata: Option<SortingMetadata>,
+        subsetting_metadata: Option<SubsettingMetadata>,
+    ) -> Self {
         Self {
-            common: Common::level0().extensions(extensions).build(),
+            common: Common::level0()
+                .extensions(extensions)
+                .and_notices(to_opt_vec(notices))
+                .build(),
             results,
+            paging_metadata,
+            sorting_metadata,
+            subsetting_metadata,
         }
     }
 
@@ -106,6 +168,15 @@ impl ContentExtensions for NameserverSearchResults {
             .iter()
             .for_each(|n| exts.extend(n.content_extensions()));
         exts.extend(self.common().content_extensions());
+        if self.paging_metadata.is_some() {
+            exts.insert(ExtensionId::Paging);
+        }
+        if self.sorting_metadata.is_some() {
+            exts.insert(ExtensionId::Sorting);
+        }
+        if self.subsetting_metadata.is_some() {
+            exts.insert(ExtensionId::Subsetting);
+        }
         exts
     }
 }
@@ -118,16 +189,41 @@ pub struct EntitySearchResults {
 
     #[serde(rename = "entitySearchResults")]
     pub results: Vec<Entity>,
+
+    #[serde(rename = "paging_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paging_metadata: Option<PagingMetadata>,
+
+    #[serde(rename = "sorting_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sorting_metadata: Option<SortingMetadata>,
+
+    #[serde(rename = "subsetting_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subsetting_metadata: Option<SubsettingMetadata>,
 }
 
 #[buildstructor::buildstructor]
 impl EntitySearchResults {
     /// Builds an entity search result.
     #[builder(entry = "response_obj", visibility = "pub")]
-    fn new_response_obj(results: Vec<Entity>, extensions: Vec<Extension>) -> Self {
+    fn new_response_obj(
+        results: Vec<Entity>,
+        extensions: Vec<Extension>,
+        notices: Vec<Notice>,
+        paging_metadata: Option<PagingMetadata>,
+        sorting_metadata: Option<SortingMetadata>,
+        subsetting_metadata: Option<SubsettingMetadata>,
+    ) -> Self {
         Self {
-            common: Common::level0().extensions(extensions).build(),
+            common: Common::level0()
+                .extensions(extensions)
+                .and_notices(to_opt_vec(notices))
+                .build(),
             results,
+            paging_metadata,
+            sorting_metadata,
+            subsetting_metadata,
         }
     }
 
@@ -156,6 +252,183 @@ impl ContentExtensions for EntitySearchResults {
             .iter()
             .for_each(|e| exts.extend(e.content_extensions()));
         exts.extend(self.common().content_extensions());
+        if self.paging_metadata.is_some() {
+            exts.insert(ExtensionId::Paging);
+        }
+        if self.sorting_metadata.is_some() {
+            exts.insert(ExtensionId::Sorting);
+        }
+        if self.subsetting_metadata.is_some() {
+            exts.insert(ExtensionId::Subsetting);
+        }
+        exts
+    }
+}
+
+/// Represents RDAP IP network search results.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Eq)]
+pub struct NetworkSearchResults {
+    #[serde(flatten)]
+    pub common: Common,
+
+    #[serde(rename = "ipSearchResults")]
+    pub results: Vec<Network>,
+
+    #[serde(rename = "paging_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paging_metadata: Option<PagingMetadata>,
+
+    #[serde(rename = "sorting_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sorting_metadata: Option<SortingMetadata>,
+
+    #[serde(rename = "subsetting_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subsetting_metadata: Option<SubsettingMetadata>,
+}
+
+#[buildstructor::buildstructor]
+impl NetworkSearchResults {
+    /// Builds an IP network search result.
+    #[builder(entry = "response_obj", visibility = "pub")]
+    fn new_response_obj(
+        results: Vec<Network>,
+        extensions: Vec<Extension>,
+        notices: Vec<Notice>,
+        paging_metadata: Option<PagingMetadata>,
+        sorting_metadata: Option<SortingMetadata>,
+        subsetting_metadata: Option<SubsettingMetadata>,
+    ) -> Self {
+        Self {
+            common: Common::level0()
+                .extensions(extensions)
+                .and_notices(to_opt_vec(notices))
+                .build(),
+            results,
+            paging_metadata,
+            sorting_metadata,
+            subsetting_metadata,
+        }
+    }
+
+    /// Get the IP networks in the search.
+    pub fn results(&self) -> &[Network] {
+        self.results.as_ref()
+    }
+}
+
+impl CommonFields for NetworkSearchResults {
+    fn common(&self) -> &Common {
+        &self.common
+    }
+}
+
+impl ToResponse for NetworkSearchResults {
+    fn to_response(self) -> super::RdapResponse {
+        super::RdapResponse::NetworkSearchResults(Box::new(self))
+    }
+}
+
+impl ContentExtensions for NetworkSearchResults {
+    fn content_extensions(&self) -> std::collections::HashSet<super::ExtensionId> {
+        let mut exts = HashSet::new();
+        self.results()
+            .iter()
+            .for_each(|e| exts.extend(e.content_extensions()));
+        exts.extend(self.common().content_extensions());
+        if self.paging_metadata.is_some() {
+            exts.insert(ExtensionId::Paging);
+        }
+        if self.sorting_metadata.is_some() {
+            exts.insert(ExtensionId::Sorting);
+        }
+        if self.subsetting_metadata.is_some() {
+            exts.insert(ExtensionId::Subsetting);
+        }
+        exts
+    }
+}
+
+/// Represents RDAP autnum search results.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Eq)]
+pub struct AutnumSearchResults {
+    #[serde(flatten)]
+    pub common: Common,
+
+    #[serde(rename = "autnumSearchResults")]
+    pub results: Vec<Autnum>,
+
+    #[serde(rename = "paging_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paging_metadata: Option<PagingMetadata>,
+
+    #[serde(rename = "sorting_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sorting_metadata: Option<SortingMetadata>,
+
+    #[serde(rename = "subsetting_metadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subsetting_metadata: Option<SubsettingMetadata>,
+}
+
+#[buildstructor::buildstructor]
+impl AutnumSearchResults {
+    /// Builds an autnum search result.
+    #[builder(entry = "response_obj", visibility = "pub")]
+    fn new_response_obj(
+        results: Vec<Autnum>,
+        extensions: Vec<Extension>,
+        notices: Vec<Notice>,
+        paging_metadata: Option<PagingMetadata>,
+        sorting_metadata: Option<SortingMetadata>,
+        subsetting_metadata: Option<SubsettingMetadata>,
+    ) -> Self {
+        Self {
+            common: Common::level0()
+                .extensions(extensions)
+                .and_notices(to_opt_vec(notices))
+                .build(),
+            results,
+            paging_metadata,
+            sorting_metadata,
+            subsetting_metadata,
+        }
+    }
+
+    /// Get the autnums in the search.
+    pub fn results(&self) -> &[Autnum] {
+        self.results.as_ref()
+    }
+}
+
+impl CommonFields for AutnumSearchResults {
+    fn common(&self) -> &Common {
+        &self.common
+    }
+}
+
+impl ToResponse for AutnumSearchResults {
+    fn to_response(self) -> super::RdapResponse {
+        super::RdapResponse::AutnumSearchResults(Box::new(self))
+    }
+}
+
+impl ContentExtensions for AutnumSearchResults {
+    fn content_extensions(&self) -> std::collections::HashSet<super::ExtensionId> {
+        let mut exts = HashSet::new();
+        self.results()
+            .iter()
+            .for_each(|e| exts.extend(e.content_extensions()));
+        exts.extend(self.common().content_extensions());
+        if self.paging_metadata.is_some() {
+            exts.insert(ExtensionId::Paging);
+        }
+        if self.sorting_metadata.is_some() {
+            exts.insert(ExtensionId::Sorting);
+        }
+        if self.subsetting_metadata.is_some() {
+            exts.insert(ExtensionId::Subsetting);
+        }
         exts
     }
 }
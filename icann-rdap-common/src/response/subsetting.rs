@@ -0,0 +1,189 @@
+//! RFC 8982 Partial Response (Subsetting) Metadata.
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+use super::{to_opt_vec, Boolish, Link, Links};
+
+/// The field sets defined by RFC 8982.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum FieldSet {
+    /// Only the key fields of the objects, such as the handle of an entity.
+    Id,
+
+    /// The key fields plus a few fields summarizing the objects.
+    Brief,
+
+    /// All the fields of the objects.
+    Full,
+}
+
+/// Represents the RFC 8982 `subsetting_metadata` of search results.
+///
+/// ```rust
+/// use icann_rdap_common::prelude::*;
+///
+/// let subsetting_metadata = SubsettingMetadata::builder()
+///   .current_field_set("brief")
+///   .available_field_set(AvailableFieldSet::builder()
+///     .name("id")
+///     .default(false)
+///     .description("Only the key fields.")
+///     .build())
+///   .build();
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SubsettingMetadata {
+    /// This value is required by RFC 8982.
+    #[serde(rename = "currentFieldSet")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_field_set: Option<String>,
+
+    #[serde(rename = "availableFieldSets")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_field_sets: Option<Vec<AvailableFieldSet>>,
+}
+
+#[buildstructor::buildstructor]
+impl SubsettingMetadata {
+    /// Builds subsetting metadata.
+    #[builder(visibility = "pub")]
+    fn new(current_field_set: String, available_field_sets: Vec<AvailableFieldSet>) -> Self {
+        Self {
+            current_field_set: Some(current_field_set),
+            available_field_sets: to_opt_vec(available_field_sets),
+        }
+    }
+
+    /// Returns the name of the field set of the response.
+    pub fn current_field_set(&self) -> Option<&str> {
+        self.current_field_set.as_deref()
+    }
+
+    /// Returns the field sets supported by the server.
+    pub fn available_field_sets(&self) -> &[AvailableFieldSet] {
+        self.available_field_sets.as_deref().unwrap_or_default()
+    }
+}
+
+/// A field set supported by a server, as described in RFC 8982.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AvailableFieldSet {
+    /// This value is required by RFC 8982.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// This value is required by RFC 8982.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<Boolish>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<Links>,
+}
+
+#[buildstructor::buildstructor]
+impl AvailableFieldSet {
+    /// Builds an available field set.
+    #[builder(visibility = "pub")]
+    fn new(name: String, default: bool, description: Option<String>, links: Vec<Link>) -> Self {
+        Self {
+            name: Some(name),
+            default: Some(Boolish::from(default)),
+            description,
+            links: to_opt_vec(links),
+        }
+    }
+
+    /// Returns the name of the field set.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns true if this is the default field set of the server.
+    pub fn is_default(&self) -> bool {
+        self.default.as_ref().is_some_and(|b| b.into_bool())
+    }
+
+    /// Returns the description of the field set.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns the links of the field set.
+    pub fn links(&self) -> &[Link] {
+        self.links.as_deref().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{FieldSet, SubsettingMetadata};
+
+    #[test]
+    fn test_subsetting_metadata_deserialize() {
+        // GIVEN subsetting metadata from RFC 8982
+        let json = r#"
+            {
+              "currentFieldSet": "brief",
+              "availableFieldSets": [
+                {
+                  "name": "id",
+                  "description": "Contains the domain name only.",
+                  "default": false,
+                  "links": [
+                    {
+                      "value": "https://example.com/rdap/domains?name=example*.com&fieldSet=brief",
+                      "rel": "alternate",
+                      "href": "https://example.com/rdap/domains?name=example*.com&fieldSet=id",
+                      "title": "Result Ids Only",
+                      "type": "application/rdap+json"
+                    }
+                  ]
+                },
+                {
+                  "name": "brief",
+                  "description": "All the fields in the id field set plus status.",
+                  "default": true
+                }
+              ]
+            }
+        "#;
+
+        // WHEN deserialized
+        let actual =
+            serde_json::from_str::<SubsettingMetadata>(json).expect("parsing subsetting metadata");
+
+        // THEN the values are present
+        assert_eq!(actual.current_field_set(), Some("brief"));
+        assert_eq!(actual.available_field_sets().len(), 2);
+        assert_eq!(actual.available_field_sets()[0].name(), Some("id"));
+        assert!(!actual.available_field_sets()[0].is_default());
+        assert_eq!(actual.available_field_sets()[0].links().len(), 1);
+        assert!(actual.available_field_sets()[1].is_default());
+    }
+
+    #[test]
+    fn test_field_set_names() {
+        // GIVEN the field set names of RFC 8982
+        let names = ["id", "brief", "full"];
+
+        // WHEN parsed and displayed
+        let actual = names
+            .iter()
+            .map(|name| {
+                FieldSet::from_str(name)
+                    .expect("parsing field set")
+                    .to_string()
+            })
+            .collect::<Vec<String>>();
+
+        // THEN they are the same
+        assert_eq!(actual, names);
+        assert!(FieldSet::from_str("partial").is_err());
+    }
+}
@@ -47,6 +47,8 @@ pub enum ExtensionId {
     ArinOriginAs0,
     #[strum(serialize = "artRecord")]
     ArtRecord,
+    #[strum(serialize = "autnums")]
+    Autnums,
     #[strum(serialize = "cidr0")]
     Cidr0,
     #[strum(serialize = "exts")]
@@ -63,6 +65,8 @@ pub enum ExtensionId {
     IcannRdapTechnicalImplementationGuide0,
     #[strum(serialize = "icann_rdap_technical_implementation_guide_1")]
     IcannRdapTechnicalImplementationGuide1,
+    #[strum(serialize = "ips")]
+    Ips,
     #[strum(serialize = "jscontact")]
     JsContact,
     #[strum(serialize = "nro_rdap_profile_0")]
@@ -85,6 +89,8 @@ pub enum ExtensionId {
     RegType,
     #[strum(serialize = "reverse_search")]
     ReverseSearch,
+    #[strum(serialize = "rirSearch1")]
+    RirSearch1,
     #[strum(serialize = "simpleRedaction")]
     SimpleRedaction,
     #[strum(serialize = "sorting")]
@@ -0,0 +1,71 @@
+-- RDAP objects are stored as their JSON responses, along with the columns
+-- needed to look them up and search for them.
+
+create table domain (
+    ldh_name text primary key,
+    unicode_name text,
+    response jsonb not null
+);
+
+create index domain_unicode_name_idx on domain (unicode_name);
+create index domain_ldh_name_pattern_idx on domain (ldh_name text_pattern_ops);
+
+create table domain_ns_ip (
+    domain_ldh_name text not null references domain (ldh_name) on delete cascade,
+    ip inet not null
+);
+
+create index domain_ns_ip_idx on domain_ns_ip (ip);
+
+create table domain_ns_ldh_name (
+    domain_ldh_name text not null references domain (ldh_name) on delete cascade,
+    ns_ldh_name text not null
+);
+
+create index domain_ns_ldh_name_pattern_idx on domain_ns_ldh_name (ns_ldh_name text_pattern_ops);
+
+create table nameserver (
+    ldh_name text primary key,
+    response jsonb not null
+);
+
+create index nameserver_ldh_name_pattern_idx on nameserver (ldh_name text_pattern_ops);
+
+create table nameserver_ip (
+    nameserver_ldh_name text not null references nameserver (ldh_name) on delete cascade,
+    ip inet not null
+);
+
+create index nameserver_ip_idx on nameserver_ip (ip);
+
+create table entity (
+    handle text primary key,
+    full_name text,
+    response jsonb not null
+);
+
+create index entity_handle_pattern_idx on entity (handle text_pattern_ops);
+create index entity_full_name_pattern_idx on entity (full_name text_pattern_ops);
+
+-- autnum ranges may overlap, in which case the most recently added wins.
+-- adding a range again replaces it.
+create table autnum (
+    id bigserial primary key,
+    start_autnum bigint not null,
+    end_autnum bigint not null,
+    response jsonb not null,
+    unique (start_autnum, end_autnum)
+);
+
+-- networks are stored as the CIDR blocks covering their address ranges.
+create table network (
+    cidr cidr primary key,
+    response jsonb not null
+);
+
+create index network_cidr_idx on network using gist (cidr inet_ops);
+
+create table srv_help (
+    host text primary key,
+    response jsonb not null
+);
@@ -0,0 +1,6 @@
+-- networks and autnums are searched by the handle and name in their JSON responses.
+
+create index network_handle_pattern_idx on network ((response->>'handle') text_pattern_ops);
+create index network_name_pattern_idx on network ((response->>'name') text_pattern_ops);
+create index autnum_handle_pattern_idx on autnum ((response->>'handle') text_pattern_ops);
+create index autnum_name_pattern_idx on autnum ((response->>'name') text_pattern_ops);
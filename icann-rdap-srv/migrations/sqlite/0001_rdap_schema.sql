@@ -0,0 +1,72 @@
+-- RDAP objects are stored as their JSON responses, along with the columns
+-- needed to look them up and search for them.
+
+create table domain (
+    ldh_name text primary key,
+    unicode_name text,
+    response text not null
+);
+
+create index domain_unicode_name_idx on domain (unicode_name);
+
+create table domain_ns_ip (
+    domain_ldh_name text not null references domain (ldh_name) on delete cascade,
+    ip text not null
+);
+
+create index domain_ns_ip_idx on domain_ns_ip (ip);
+create index domain_ns_ip_domain_idx on domain_ns_ip (domain_ldh_name);
+
+create table domain_ns_ldh_name (
+    domain_ldh_name text not null references domain (ldh_name) on delete cascade,
+    ns_ldh_name text not null
+);
+
+create index domain_ns_ldh_name_domain_idx on domain_ns_ldh_name (domain_ldh_name);
+
+create table nameserver (
+    ldh_name text primary key,
+    response text not null
+);
+
+create table nameserver_ip (
+    nameserver_ldh_name text not null references nameserver (ldh_name) on delete cascade,
+    ip text not null
+);
+
+create index nameserver_ip_idx on nameserver_ip (ip);
+create index nameserver_ip_nameserver_idx on nameserver_ip (nameserver_ldh_name);
+
+create table entity (
+    handle text primary key,
+    full_name text,
+    response text not null
+);
+
+-- autnum ranges may overlap, in which case the most recently added wins.
+-- adding a range again replaces it.
+create table autnum (
+    id integer primary key autoincrement,
+    start_autnum integer not null,
+    end_autnum integer not null,
+    response text not null,
+    unique (start_autnum, end_autnum)
+);
+
+-- networks are stored as the CIDR blocks covering their address ranges.
+-- addresses are big-endian bytes so that they compare in address order.
+create table network (
+    ip_version integer not null,
+    start_address blob not null,
+    end_address blob not null,
+    prefix_len integer not null,
+    response text not null,
+    primary key (ip_version, start_address, prefix_len)
+);
+
+create index network_range_idx on network (ip_version, start_address, end_address);
+
+create table srv_help (
+    host text primary key,
+    response text not null
+);
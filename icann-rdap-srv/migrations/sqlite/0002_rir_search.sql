@@ -0,0 +1,6 @@
+-- networks and autnums are searched by the handle and name in their JSON responses.
+
+create index network_handle_idx on network (json_extract(response, '$.handle'));
+create index network_name_idx on network (json_extract(response, '$.name'));
+create index autnum_handle_idx on autnum (json_extract(response, '$.handle'));
+create index autnum_name_idx on autnum (json_extract(response, '$.name'));
@@ -0,0 +1,438 @@
+//! Administration of the server.
+//!
+//! When authentication is configured, clients of the [admin tier](crate::auth::ADMIN_TIER)
+//! may get, put and delete the objects served under `/admin/{class}/{id}`, where the class
+//! is one of `domain`, `entity`, `nameserver`, `autnum`, `ip` or `help`. Objects are checked
+//! for specification errors before they are stored, and an RDAP error response, such as a
+//! redirect, may be put in place of an object. Objects changed this way are replaced
+//! when the data directory is reloaded.
+
+use std::{collections::HashMap, net::IpAddr, str::FromStr};
+
+use {
+    axum::{
+        body::Bytes,
+        extract::{Path, State},
+        response::{IntoResponse, Response},
+        routing::get,
+        Json, Router,
+    },
+    http::StatusCode,
+    icann_rdap_common::{
+        check::CheckClass,
+        prelude::ToResponse,
+        response::{RdapResponse, Rfc9083Error},
+    },
+    ipnet::IpNet,
+    serde_json::{json, Value},
+    tracing::info,
+};
+
+use crate::{
+    error::RdapServerError,
+    rdap::response::{ResponseUtil, BAD_REQUEST, NOT_FOUND, RDAP_HEADERS},
+    server::DynServiceState,
+    storage::{
+        data::{AutnumId, DomainId, EntityId, NameserverId, NetworkId, NetworkIdType},
+        StoreOps, TxHandle,
+    },
+    util::bin::check::rdap_check_messages,
+};
+
+/// The routes of the administration API, which are nested under `/admin`.
+pub(crate) fn admin_router() -> Router<DynServiceState> {
+    Router::new()
+        .route("/data", get(data_status))
+        .route(
+            "/:class",
+            get(get_object).put(put_object).delete(delete_object),
+        )
+        .route(
+            "/:class/*id",
+            get(get_object).put(put_object).delete(delete_object),
+        )
+}
+
+/// The load status of each file of the data directory.
+pub(crate) async fn data_status(state: State<DynServiceState>) -> Response {
+    Json(json!({ "files": state.get_data_status().files() })).into_response()
+}
+
+/// Identifies an object from the path of a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ObjectId {
+    Domain(String),
+    Entity(String),
+    Nameserver(String),
+    Autnum(AutnumId),
+    Network(IpNet),
+
+    /// Help for a host, or the default help.
+    Help(Option<String>),
+}
+
+impl ObjectId {
+    /// Parses the class and identifier of a path, returning `None` if they are not valid.
+    ///
+    /// Autnums are identified by a number or a range such as `64496-64511`, and
+    /// networks by a CIDR or a single address.
+    fn parse(class: &str, id: Option<&str>) -> Option<Self> {
+        let id = id.filter(|id| !id.is_empty());
+        match (class, id) {
+            ("domain", Some(id)) => Some(Self::Domain(id.to_string())),
+            ("entity", Some(id)) => Some(Self::Entity(id.to_string())),
+            ("nameserver", Some(id)) => Some(Self::Nameserver(id.to_string())),
+            ("autnum", Some(id)) => {
+                let (start, end) = id.split_once('-').unwrap_or((id, id));
+                let start_autnum = start.parse().ok()?;
+                let end_autnum = end.parse().ok()?;
+                (start_autnum <= end_autnum).then(|| {
+                    Self::Autnum(
+                        AutnumId::builder()
+                            .start_autnum(start_autnum)
+                            .end_autnum(end_autnum)
+                            .build(),
+                    )
+                })
+            }
+            ("ip", Some(id)) => {
+                let net = IpNet::from_str(id)
+                    .ok()
+                    .or_else(|| IpAddr::from_str(id).ok().map(IpNet::from))?;
+                (net == net.trunc()).then_some(Self::Network(net))
+            }
+            ("help", host) => Some(Self::Help(host.map(str::to_string))),
+            _ => None,
+        }
+    }
+
+    /// Gets the stored object, or `None` if there is none.
+    async fn get(&self, storage: &dyn StoreOps) -> Result<Option<RdapResponse>, RdapServerError> {
+        let rdap = match self {
+            Self::Domain(ldh_name) => storage.get_domain_by_ldh(ldh_name).await?,
+            Self::Entity(handle) => storage.get_entity_by_handle(handle).await?,
+            Self::Nameserver(ldh_name) => storage.get_nameserver_by_ldh(ldh_name).await?,
+            Self::Autnum(autnum_id) => storage.get_autnum_by_num(autnum_id.start_autnum).await?,
+            Self::Network(net) => storage.get_network_by_cidr(&net.to_string()).await?,
+            Self::Help(host) => storage.get_srv_help(host.as_deref()).await?,
+        };
+        if rdap == *NOT_FOUND {
+            return Ok(None);
+        }
+        // autnums and networks are found by the ranges containing them, which may be larger
+        let exact = match (&rdap, self) {
+            (RdapResponse::Autnum(autnum), Self::Autnum(autnum_id)) => {
+                autnum.start_autnum() == Some(autnum_id.start_autnum)
+                    && autnum.end_autnum() == Some(autnum_id.end_autnum)
+            }
+            (RdapResponse::Network(network), Self::Network(net)) => {
+                is_network_of(network.start_address(), network.end_address(), net)
+            }
+            // errors do not have ranges, so the error must be stored up to the ends of
+            // the range and not beyond them
+            (_, Self::Autnum(autnum_id)) => {
+                storage.get_autnum_by_num(autnum_id.end_autnum).await? == rdap
+                    && match autnum_id.start_autnum.checked_sub(1) {
+                        Some(before) => storage.get_autnum_by_num(before).await? != rdap,
+                        None => true,
+                    }
+                    && match autnum_id.end_autnum.checked_add(1) {
+                        Some(after) => storage.get_autnum_by_num(after).await? != rdap,
+                        None => true,
+                    }
+            }
+            (_, Self::Network(net)) => match net.supernet() {
+                Some(supernet) => storage.get_network_by_cidr(&supernet.to_string()).await? != rdap,
+                None => true,
+            },
+            _ => true,
+        };
+        Ok(exact.then_some(rdap))
+    }
+
+    /// Adds the object to a transaction, returning false if the object is not
+    /// of the class and identifier of the path.
+    async fn add(
+        &self,
+        rdap: RdapResponse,
+        tx: &mut Box<dyn TxHandle>,
+    ) -> Result<bool, RdapServerError> {
+        match (self, rdap) {
+            (Self::Domain(ldh_name), RdapResponse::Domain(domain))
+                if domain
+                    .ldh_name()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(ldh_name)) =>
+            {
+                tx.add_domain(&domain).await?
+            }
+            (Self::Domain(ldh_name), RdapResponse::ErrorResponse(error)) => {
+                let domain_id = DomainId::builder().ldh_name(ldh_name).build();
+                tx.add_domain_err(&domain_id, &error).await?
+            }
+            (Self::Entity(handle), RdapResponse::Entity(entity))
+                if entity
+                    .object_common
+                    .handle
+                    .as_ref()
+                    .is_some_and(|entity_handle| **entity_handle == **handle) =>
+            {
+                tx.add_entity(&entity).await?
+            }
+            (Self::Entity(handle), RdapResponse::ErrorResponse(error)) => {
+                let entity_id = EntityId::builder().handle(handle).build();
+                tx.add_entity_err(&entity_id, &error).await?
+            }
+            (Self::Nameserver(ldh_name), RdapResponse::Nameserver(nameserver))
+                if nameserver
+                    .ldh_name()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(ldh_name)) =>
+            {
+                tx.add_nameserver(&nameserver).await?
+            }
+            (Self::Nameserver(ldh_name), RdapResponse::ErrorResponse(error)) => {
+                let nameserver_id = NameserverId::builder().ldh_name(ldh_name).build();
+                tx.add_nameserver_err(&nameserver_id, &error).await?
+            }
+            (Self::Autnum(autnum_id), RdapResponse::Autnum(autnum))
+                if autnum.start_autnum() == Some(autnum_id.start_autnum)
+                    && autnum.end_autnum() == Some(autnum_id.end_autnum) =>
+            {
+                tx.add_autnum(&autnum).await?
+            }
+            (Self::Autnum(autnum_id), RdapResponse::ErrorResponse(error)) => {
+                tx.add_autnum_err(autnum_id, &error).await?
+            }
+            (Self::Network(net), RdapResponse::Network(network))
+                if is_network_of(network.start_address(), network.end_address(), net) =>
+            {
+                tx.add_network(&network).await?
+            }
+            (Self::Network(net), RdapResponse::ErrorResponse(error)) => {
+                tx.add_network_err(&network_id(net), &error).await?
+            }
+            (Self::Help(host), RdapResponse::Help(help)) => {
+                tx.add_srv_help(&help, host.as_deref()).await?
+            }
+            _ => return Ok(false),
+        };
+        Ok(true)
+    }
+
+    /// Deletes the object in a transaction.
+    async fn delete(&self, tx: &mut Box<dyn TxHandle>) -> Result<(), RdapServerError> {
+        match self {
+            Self::Domain(ldh_name) => {
+                tx.delete_domain(&DomainId::builder().ldh_name(ldh_name).build())
+                    .await
+            }
+            Self::Entity(handle) => {
+                tx.delete_entity(&EntityId::builder().handle(handle).build())
+                    .await
+            }
+            Self::Nameserver(ldh_name) => {
+                tx.delete_nameserver(&NameserverId::builder().ldh_name(ldh_name).build())
+                    .await
+            }
+            Self::Autnum(autnum_id) => tx.delete_autnum(autnum_id).await,
+            Self::Network(net) => tx.delete_network(&network_id(net)).await,
+            Self::Help(host) => tx.delete_srv_help(host.as_deref()).await,
+        }
+    }
+}
+
+/// True if the start and end addresses of a network are those of a CIDR.
+fn is_network_of(start_address: Option<&str>, end_address: Option<&str>, net: &IpNet) -> bool {
+    let parse = |address: Option<&str>| address.and_then(|address| IpAddr::from_str(address).ok());
+    parse(start_address) == Some(net.network()) && parse(end_address) == Some(net.broadcast())
+}
+
+fn network_id(net: &IpNet) -> NetworkId {
+    NetworkId::builder()
+        .network_id(NetworkIdType::Cidr(*net))
+        .build()
+}
+
+/// Gets the object identified by the path from the parameters of a request.
+fn object_id(params: &HashMap<String, String>) -> Option<ObjectId> {
+    let class = params.get("class").map(String::as_str).unwrap_or_default();
+    ObjectId::parse(class, params.get("id").map(String::as_str))
+}
+
+fn error_response(status_code: StatusCode, title: &str, description: Vec<String>) -> Response {
+    let rdap = Rfc9083Error::response_obj()
+        .error_code(status_code.as_u16())
+        .title(title)
+        .description(description)
+        .build()
+        .to_response();
+    (status_code, RDAP_HEADERS, Json(rdap)).into_response()
+}
+
+/// Gets a stored object as it is stored, without the processing of RDAP queries.
+pub(crate) async fn get_object(
+    Path(params): Path<HashMap<String, String>>,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    let Some(object_id) = object_id(&params) else {
+        return Ok(BAD_REQUEST.response());
+    };
+    let storage = state.get_storage().await?;
+    match object_id.get(storage).await? {
+        Some(rdap) => Ok((StatusCode::OK, RDAP_HEADERS, Json(rdap)).into_response()),
+        None => Ok(NOT_FOUND.response()),
+    }
+}
+
+/// Adds or replaces an object after checking it for specification errors.
+pub(crate) async fn put_object(
+    Path(params): Path<HashMap<String, String>>,
+    state: State<DynServiceState>,
+    body: Bytes,
+) -> Result<Response, RdapServerError> {
+    let Some(object_id) = object_id(&params) else {
+        return Ok(BAD_REQUEST.response());
+    };
+    let rdap = serde_json::from_slice::<Value>(&body)
+        .ok()
+        .and_then(|value| RdapResponse::try_from(value).ok());
+    let Some(rdap) = rdap else {
+        return Ok(error_response(
+            StatusCode::BAD_REQUEST,
+            "Bad Request",
+            vec!["The body is not an RDAP response.".to_string()],
+        ));
+    };
+    let messages = rdap_check_messages(&rdap, &[CheckClass::Std95Error]);
+    if !messages.is_empty() {
+        return Ok(error_response(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Checks Failed",
+            messages,
+        ));
+    }
+    let mut tx = state.get_storage().await?.new_tx().await?;
+    if !object_id.add(rdap, &mut tx).await? {
+        tx.rollback().await?;
+        return Ok(error_response(
+            StatusCode::BAD_REQUEST,
+            "Bad Request",
+            vec!["The body is not an RDAP response for the object of the path.".to_string()],
+        ));
+    }
+    tx.commit().await?;
+    info!("admin put {object_id:?}");
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+/// Deletes an object.
+pub(crate) async fn delete_object(
+    Path(params): Path<HashMap<String, String>>,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    let Some(object_id) = object_id(&params) else {
+        return Ok(BAD_REQUEST.response());
+    };
+    let storage = state.get_storage().await?;
+    if object_id.get(storage).await?.is_none() {
+        return Ok(NOT_FOUND.response());
+    }
+    let mut tx = storage.new_tx().await?;
+    object_id.delete(&mut tx).await?;
+    tx.commit().await?;
+    info!("admin delete {object_id:?}");
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use {ipnet::IpNet, std::str::FromStr};
+
+    use crate::storage::data::AutnumId;
+
+    use super::ObjectId;
+
+    #[test]
+    fn GIVEN_valid_paths_WHEN_parse_THEN_object_identified() {
+        // GIVEN
+        let paths = [
+            (
+                "domain",
+                Some("foo.example"),
+                ObjectId::Domain("foo.example".to_string()),
+            ),
+            (
+                "entity",
+                Some("REG-1"),
+                ObjectId::Entity("REG-1".to_string()),
+            ),
+            (
+                "autnum",
+                Some("64496"),
+                ObjectId::Autnum(
+                    AutnumId::builder()
+                        .start_autnum(64496)
+                        .end_autnum(64496)
+                        .build(),
+                ),
+            ),
+            (
+                "autnum",
+                Some("64496-64511"),
+                ObjectId::Autnum(
+                    AutnumId::builder()
+                        .start_autnum(64496)
+                        .end_autnum(64511)
+                        .build(),
+                ),
+            ),
+            (
+                "ip",
+                Some("192.0.2.0/24"),
+                ObjectId::Network(IpNet::from_str("192.0.2.0/24").unwrap()),
+            ),
+            (
+                "ip",
+                Some("2001:db8::1"),
+                ObjectId::Network(IpNet::from_str("2001:db8::1/128").unwrap()),
+            ),
+            ("help", None, ObjectId::Help(None)),
+            (
+                "help",
+                Some("example.net"),
+                ObjectId::Help(Some("example.net".to_string())),
+            ),
+        ];
+
+        for (class, id, expected) in paths {
+            // WHEN
+            let actual = ObjectId::parse(class, id);
+
+            // THEN
+            assert_eq!(actual, Some(expected), "{class} {id:?}");
+        }
+    }
+
+    #[test]
+    fn GIVEN_invalid_paths_WHEN_parse_THEN_none() {
+        // GIVEN
+        let paths = [
+            ("domain", None),
+            ("domain", Some("")),
+            ("autnum", Some("AS64496")),
+            ("autnum", Some("64511-64496")),
+            ("ip", Some("192.0.2.1/24")),
+            ("ip", Some("not-an-ip")),
+            ("data", Some("foo")),
+            ("foo", Some("bar")),
+        ];
+
+        for (class, id) in paths {
+            // WHEN
+            let actual = ObjectId::parse(class, id);
+
+            // THEN
+            assert_eq!(actual, None, "{class} {id:?}");
+        }
+    }
+}
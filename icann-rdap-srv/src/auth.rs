@@ -0,0 +1,544 @@
+//! Authentication of clients with HTTP Basic and bearer tokens.
+//!
+//! Authentication is optional. When it is configured, clients without credentials
+//! are anonymous and get responses with contacts truncated, whereas clients with
+//! valid credentials get full responses. Clients with invalid credentials are refused.
+
+use std::{
+    fs,
+    net::{IpAddr, SocketAddr},
+    path::Path,
+    sync::Arc,
+};
+
+use {
+    axum::{
+        async_trait,
+        extract::{ConnectInfo, FromRequestParts, Request, State},
+        middleware::Next,
+        response::{IntoResponse, Response},
+        Json,
+    },
+    base64::{engine::general_purpose::STANDARD, Engine},
+    http::{
+        header::{AUTHORIZATION, WWW_AUTHENTICATE},
+        request::Parts,
+        StatusCode,
+    },
+    icann_rdap_common::{
+        media_types::RDAP_MEDIA_TYPE,
+        prelude::ToResponse,
+        response::{NrType, RdapResponse, Rfc9083Error},
+    },
+    serde::Deserialize,
+    serde_json::{json, Value},
+    sha2::{Digest, Sha256},
+    subtle::{Choice, ConditionallySelectable, ConstantTimeEq},
+    tracing::debug,
+};
+
+use crate::{client_ip::ClientIp, error::RdapServerError};
+
+/// The tier of clients allowed to use the administration API.
+pub const ADMIN_TIER: &str = "admin";
+
+/// The credentials of the clients, which are read from a JSON file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuthConfig {
+    /// Clients authenticating with HTTP Basic.
+    #[serde(default)]
+    pub users: Vec<AuthUser>,
+
+    /// Clients authenticating with bearer tokens.
+    #[serde(default)]
+    pub tokens: Vec<AuthToken>,
+}
+
+/// A client authenticating with a name and password.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuthUser {
+    pub name: String,
+    pub password: String,
+
+    /// The tier of access of the client, such as `registrar` or `registry`.
+    pub tier: Option<String>,
+}
+
+/// A client authenticating with a bearer token.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuthToken {
+    pub token: String,
+
+    /// The name identifying the client.
+    pub name: String,
+
+    /// The tier of access of the client, such as `registrar` or `registry`.
+    pub tier: Option<String>,
+}
+
+/// An authenticated client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    pub name: String,
+    pub tier: Option<String>,
+}
+
+/// The access of a client when authentication is configured.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Access {
+    /// The client did not give credentials.
+    Anonymous,
+
+    /// The client gave valid credentials.
+    Authenticated(Identity),
+}
+
+impl Identity {
+    /// True if the client is of the [ADMIN_TIER].
+    pub fn is_admin(&self) -> bool {
+        self.tier.as_deref() == Some(ADMIN_TIER)
+    }
+}
+
+impl AuthConfig {
+    /// Reads a credentials file.
+    pub fn from_file(path: &Path) -> Result<Self, RdapServerError> {
+        let json = fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(|e| {
+            RdapServerError::Config(format!(
+                "authentication file {} is invalid: {e}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Gets the identity of the credentials of an `Authorization` header value,
+    /// or `None` if they are not valid.
+    pub fn identify(&self, authorization: &str) -> Option<Identity> {
+        let (scheme, credentials) = authorization.trim().split_once(' ')?;
+        let credentials = credentials.trim();
+        if scheme.eq_ignore_ascii_case("basic") {
+            let decoded = String::from_utf8(STANDARD.decode(credentials).ok()?).ok()?;
+            let (name, password) = decoded.split_once(':')?;
+            find_secret(&self.users, |user| {
+                secret_eq(&user.name, name) & secret_eq(&user.password, password)
+            })
+            .map(|user| Identity {
+                name: user.name.clone(),
+                tier: user.tier.clone(),
+            })
+        } else if scheme.eq_ignore_ascii_case("bearer") {
+            find_secret(&self.tokens, |token| secret_eq(&token.token, credentials)).map(|token| {
+                Identity {
+                    name: token.name.clone(),
+                    tier: token.tier.clone(),
+                }
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Finds the credentials matching the given ones, comparing every configured
+/// credential rather than stopping at a match, so the time taken does not reveal
+/// which of them matched.
+fn find_secret<T>(credentials: &[T], matches: impl Fn(&T) -> Choice) -> Option<&T> {
+    let (found, index) = credentials.iter().enumerate().fold(
+        (Choice::from(0), 0u64),
+        |(found, index), (i, credential)| {
+            let matched = matches(credential) & !found;
+            (
+                found | matched,
+                u64::conditional_select(&index, &(i as u64), matched),
+            )
+        },
+    );
+    bool::from(found).then(|| &credentials[index as usize])
+}
+
+/// Compares a secret in constant time, so the time taken does not reveal how
+/// much of it was guessed. The SHA-256 digests of both sides are compared, so
+/// the time taken does not reveal the length of the secret either. User names
+/// are compared this way too, and both the name and password of a user are
+/// always compared, so the time taken does not reveal whether a name is known.
+fn secret_eq(secret: &str, given: &str) -> Choice {
+    Sha256::digest(secret.as_bytes()).ct_eq(&Sha256::digest(given.as_bytes()))
+}
+
+/// Middleware authenticating the client of a request, making its [Access]
+/// available to handlers with the [Requester] extractor.
+pub(crate) async fn authenticate(
+    State(config): State<Arc<AuthConfig>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let access = match request.headers().get(AUTHORIZATION) {
+        None => Access::Anonymous,
+        Some(authorization) => {
+            let identity = authorization
+                .to_str()
+                .ok()
+                .and_then(|authorization| config.identify(authorization));
+            let Some(identity) = identity else {
+                debug!("refusing request with invalid credentials");
+                return unauthorized();
+            };
+            debug!("request authenticated as {}", identity.name);
+            Access::Authenticated(identity)
+        }
+    };
+    request.extensions_mut().insert(access);
+    next.run(request).await
+}
+
+/// Middleware refusing requests of clients that are not of the [ADMIN_TIER].
+pub(crate) async fn authorize_admin(
+    State(config): State<Arc<AuthConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let identity = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|authorization| authorization.to_str().ok())
+        .and_then(|authorization| config.identify(authorization));
+    let Some(identity) = identity else {
+        debug!("refusing admin request without valid credentials");
+        return unauthorized();
+    };
+    if !identity.is_admin() {
+        debug!("refusing admin request of {}", identity.name);
+        let rdap = Rfc9083Error::response_obj()
+            .error_code(403)
+            .title("Forbidden")
+            .description_entry("The client is not allowed to administer the server.")
+            .build()
+            .to_response();
+        return (
+            StatusCode::FORBIDDEN,
+            [("content-type", RDAP_MEDIA_TYPE)],
+            Json(rdap),
+        )
+            .into_response();
+    }
+    next.run(request).await
+}
+
+fn unauthorized() -> Response {
+    let rdap = Rfc9083Error::response_obj()
+        .error_code(401)
+        .title("Unauthorized")
+        .description_entry("The credentials given are not valid.")
+        .build()
+        .to_response();
+    (
+        StatusCode::UNAUTHORIZED,
+        [
+            ("content-type", RDAP_MEDIA_TYPE),
+            (WWW_AUTHENTICATE.as_str(), r#"Basic realm="rdap""#),
+            (WWW_AUTHENTICATE.as_str(), r#"Bearer realm="rdap""#),
+        ],
+        Json(rdap),
+    )
+        .into_response()
+}
+
+/// The client of a request.
+#[derive(Debug, Clone)]
+pub struct Requester {
+    /// The IP address of the client, as found by the client IP source of the server.
+    pub ip: IpAddr,
+
+    /// The access of the client, or `None` if authentication is not configured.
+    pub access: Option<Access>,
+}
+
+impl Requester {
+    /// Gets the identity of the client if it is authenticated.
+    pub fn identity(&self) -> Option<&Identity> {
+        match &self.access {
+            Some(Access::Authenticated(identity)) => Some(identity),
+            _ => None,
+        }
+    }
+
+    /// True if authentication is configured and the client did not give credentials.
+    pub fn is_anonymous(&self) -> bool {
+        matches!(self.access, Some(Access::Anonymous))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Requester
+where
+    S: Send + Sync,
+{
+    type Rejection = <ConnectInfo<SocketAddr> as FromRequestParts<S>>::Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let ip = match parts.extensions.get::<ClientIp>() {
+            Some(ClientIp(ip)) => *ip,
+            None => {
+                let ConnectInfo(addr) =
+                    ConnectInfo::<SocketAddr>::from_request_parts(parts, state).await?;
+                addr.ip()
+            }
+        };
+        Ok(Self {
+            ip,
+            access: parts.extensions.get::<Access>().cloned(),
+        })
+    }
+}
+
+/// Removes the contacts of the entities of a response to an anonymous client,
+/// adding a notice that the response is truncated. Responses to other clients
+/// are returned as is.
+pub(crate) fn truncate_contacts(
+    rdap: RdapResponse,
+    requester: &Requester,
+) -> Result<RdapResponse, RdapServerError> {
+    if !requester.is_anonymous() || matches!(rdap, RdapResponse::ErrorResponse(_)) {
+        return Ok(rdap);
+    }
+    let mut json = serde_json::to_value(&rdap)?;
+    if !remove_contacts(&mut json) {
+        return Ok(rdap);
+    }
+    if let Value::Object(members) = &mut json {
+        let notices = members
+            .entry("notices")
+            .or_insert_with(|| Value::Array(vec![]));
+        if let Value::Array(notices) = notices {
+            notices.push(json!({
+                "title": "Contacts Truncated",
+                "description": ["Contact information is only given to authenticated clients."],
+                "type": NrType::ObjectTruncatedDueToAuthorization.to_string(),
+            }));
+        }
+    }
+    Ok(serde_json::from_value(json)?)
+}
+
+/// Removes the vCard and JSContact of every entity, returning true if any were removed.
+fn remove_contacts(value: &mut Value) -> bool {
+    match value {
+        Value::Object(members) => {
+            let mut removed = false;
+            if members.get("objectClassName").and_then(Value::as_str) == Some("entity") {
+                removed |= members.remove("vcardArray").is_some();
+                removed |= members.remove("jscontact_card").is_some();
+            }
+            for member in members.values_mut() {
+                removed |= remove_contacts(member);
+            }
+            removed
+        }
+        Value::Array(items) => {
+            let mut removed = false;
+            for item in items {
+                removed |= remove_contacts(item);
+            }
+            removed
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::net::IpAddr;
+
+    use {
+        base64::{engine::general_purpose::STANDARD, Engine},
+        icann_rdap_common::{
+            prelude::{Contact, Entity, EntityRole, ObjectCommonFields, ToResponse},
+            response::{Domain, NrType, RdapResponse},
+        },
+        serde_json::json,
+    };
+
+    use super::{truncate_contacts, Access, AuthConfig, Identity, Requester};
+
+    fn config() -> AuthConfig {
+        serde_json::from_value(json!({
+            "users": [ { "name": "registrar1", "password": "secret", "tier": "registrar" } ],
+            "tokens": [ { "token": "abc123", "name": "registry-lab", "tier": "registry" } ]
+        }))
+        .expect("parsing config")
+    }
+
+    fn requester(access: Option<Access>) -> Requester {
+        Requester {
+            ip: IpAddr::from([192, 0, 2, 1]),
+            access,
+        }
+    }
+
+    fn domain() -> RdapResponse {
+        let registrant = Entity::builder()
+            .handle("REG-1")
+            .role(EntityRole::Registrant.to_string())
+            .contact(Contact::builder().full_name("Alice").build())
+            .build();
+        Domain::builder()
+            .ldh_name("example.com")
+            .entity(registrant)
+            .build()
+            .to_response()
+    }
+
+    #[test]
+    fn GIVEN_valid_basic_credentials_WHEN_identify_THEN_user_identified() {
+        // GIVEN
+        let authorization = format!("Basic {}", STANDARD.encode("registrar1:secret"));
+
+        // WHEN
+        let actual = config().identify(&authorization);
+
+        // THEN
+        assert_eq!(
+            actual,
+            Some(Identity {
+                name: "registrar1".to_string(),
+                tier: Some("registrar".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn GIVEN_wrong_password_WHEN_identify_THEN_not_identified() {
+        // GIVEN
+        let authorization = format!("Basic {}", STANDARD.encode("registrar1:guess"));
+
+        // WHEN
+        let actual = config().identify(&authorization);
+
+        // THEN
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn GIVEN_unknown_name_WHEN_identify_THEN_not_identified() {
+        // GIVEN
+        let authorization = format!("Basic {}", STANDARD.encode("registrar:secret"));
+
+        // WHEN
+        let actual = config().identify(&authorization);
+
+        // THEN
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn GIVEN_many_users_WHEN_identify_THEN_only_matching_user_identified() {
+        // GIVEN
+        let config: AuthConfig = serde_json::from_value(json!({
+            "users": [
+                { "name": "registrar1", "password": "secret", "tier": "registrar" },
+                { "name": "registrar2", "password": "secret2", "tier": "registry" },
+                { "name": "registrar3", "password": "secret", "tier": "admin" }
+            ]
+        }))
+        .expect("parsing config");
+        let authorization = format!("Basic {}", STANDARD.encode("registrar2:secret2"));
+        let prefix = format!("Basic {}", STANDARD.encode("registrar2:secret"));
+
+        // WHEN
+        let actual = config.identify(&authorization);
+
+        // THEN
+        assert_eq!(
+            actual,
+            Some(Identity {
+                name: "registrar2".to_string(),
+                tier: Some("registry".to_string())
+            })
+        );
+        assert!(config.identify(&prefix).is_none());
+    }
+
+    #[test]
+    fn GIVEN_valid_bearer_token_WHEN_identify_THEN_token_identified() {
+        // GIVEN
+        let authorization = "bearer abc123";
+
+        // WHEN
+        let actual = config().identify(authorization).expect("identified");
+
+        // THEN
+        assert_eq!(actual.name, "registry-lab");
+        assert_eq!(actual.tier.as_deref(), Some("registry"));
+    }
+
+    #[test]
+    fn GIVEN_tiers_WHEN_is_admin_THEN_only_admin_tier_is_admin() {
+        // GIVEN
+        let tiers = [
+            (Some("admin"), true),
+            (Some("registrar"), false),
+            (None, false),
+        ];
+
+        for (tier, expected) in tiers {
+            // WHEN
+            let actual = Identity {
+                name: "client".to_string(),
+                tier: tier.map(str::to_string),
+            }
+            .is_admin();
+
+            // THEN
+            assert_eq!(actual, expected, "{tier:?}");
+        }
+    }
+
+    #[test]
+    fn GIVEN_anonymous_client_WHEN_truncate_contacts_THEN_contacts_removed_with_notice() {
+        // GIVEN
+        let requester = requester(Some(Access::Anonymous));
+
+        // WHEN
+        let actual = truncate_contacts(domain(), &requester).expect("truncating");
+
+        // THEN
+        let RdapResponse::Domain(domain) = actual else {
+            panic!("not a domain")
+        };
+        assert!(domain.entities()[0].contact().is_none());
+        let notices = domain.common.notices.as_ref().expect("notices");
+        assert_eq!(
+            notices[0].nr_type(),
+            Some(
+                NrType::ObjectTruncatedDueToAuthorization
+                    .to_string()
+                    .as_str()
+            )
+        );
+    }
+
+    #[test]
+    fn GIVEN_authenticated_or_unconfigured_WHEN_truncate_contacts_THEN_not_truncated() {
+        // GIVEN
+        let identity = Identity {
+            name: "registrar1".to_string(),
+            tier: None,
+        };
+
+        for requester in [
+            requester(Some(Access::Authenticated(identity))),
+            requester(None),
+        ] {
+            // WHEN
+            let actual = truncate_contacts(domain(), &requester).expect("truncating");
+
+            // THEN
+            assert_eq!(actual, domain());
+        }
+    }
+}
@@ -1,12 +1,15 @@
 use {
     envmnt::{get_or, get_parse_or, get_u16},
     icann_rdap_srv::{
+        auth::AuthConfig,
         config::{
-            data_dir, debug_config_vars, JsContactConversion, ListenConfig, ServiceConfig,
-            StorageType, AUTO_RELOAD, BOOTSTRAP, JSCONTACT_CONVERSION, LISTEN_ADDR, LISTEN_PORT,
-            LOG, UPDATE_ON_BOOTSTRAP,
+            client_ip_source_from_env, data_dir, debug_config_vars, JsContactConversion,
+            ListenConfig, RateLimitConfig, SearchConfig, ServiceConfig, StorageType, TlsConfig,
+            AUTH_FILE, AUTO_RELOAD, BOOTSTRAP, JSCONTACT_CONVERSION, LISTEN_ADDR, LISTEN_PORT, LOG,
+            METRICS_LISTEN_ADDR, METRICS_LISTEN_PORT, REDACTION_POLICY, UPDATE_ON_BOOTSTRAP,
         },
         error::RdapServerError,
+        rdap::redaction::RedactionPolicy,
         server::Listener,
     },
     std::{path::Path, str::FromStr},
@@ -42,11 +45,40 @@ async fn main() -> Result<(), RdapServerError> {
     let update_on_bootstrap: bool = get_parse_or(UPDATE_ON_BOOTSTRAP, false)?;
     let jscontact_conversion =
         JsContactConversion::from_str(&get_or(JSCONTACT_CONVERSION, "none"))?;
+    let search_config = SearchConfig::new_from_env()?;
+    let rate_limit = RateLimitConfig::new_from_env()?;
+    let client_ip_source = client_ip_source_from_env()?;
+    let metrics_listen = if envmnt::exists(METRICS_LISTEN_PORT) {
+        Some(
+            ListenConfig::builder()
+                .ip_addr(get_or(METRICS_LISTEN_ADDR, "127.0.0.1"))
+                .port(get_u16(METRICS_LISTEN_PORT, 9090))
+                .build(),
+        )
+    } else {
+        None
+    };
+    let auth = if envmnt::exists(AUTH_FILE) {
+        Some(AuthConfig::from_file(Path::new(&envmnt::get_or_panic(
+            AUTH_FILE,
+        )))?)
+    } else {
+        None
+    };
+    let redaction_policy = if envmnt::exists(REDACTION_POLICY) {
+        Some(
+            RedactionPolicy::from_file(Path::new(&envmnt::get_or_panic(REDACTION_POLICY)))?
+                .checked_with_auth(auth.as_ref())?,
+        )
+    } else {
+        None
+    };
 
     let listener = Listener::listen(
         &ListenConfig::builder()
             .ip_addr(listen_addr)
             .port(listen_port)
+            .and_tls(TlsConfig::new_from_env()?)
             .build(),
     )
     .await?;
@@ -59,6 +91,12 @@ async fn main() -> Result<(), RdapServerError> {
                 .bootstrap(bootstrap)
                 .update_on_bootstrap(update_on_bootstrap)
                 .jscontact_conversion(jscontact_conversion)
+                .search_config(search_config)
+                .and_redaction_policy(redaction_policy)
+                .and_auth(auth)
+                .and_rate_limit(rate_limit)
+                .client_ip_source(client_ip_source)
+                .and_metrics_listen(metrics_listen)
                 .build(),
         )
         .await?;
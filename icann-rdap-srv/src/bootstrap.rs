@@ -15,12 +15,13 @@ use {
         io::{AsyncBufReadExt, BufReader},
         time::sleep,
     },
-    tracing::{debug, info},
+    tracing::{debug, error, info},
 };
 
 use crate::{
     config::ServiceConfig,
     error::RdapServerError,
+    metrics::METRICS,
     storage::data::{
         trigger_reload, trigger_update, AutnumId, AutnumOrError, DomainId, DomainOrError, EntityId,
         EntityOrError, NetworkId, NetworkIdType, NetworkOrError, Template,
@@ -38,7 +39,7 @@ pub async fn init_bootstrap(config: &ServiceConfig) -> Result<(), RdapServerErro
         let client = create_client(&client_config)?;
 
         // do one run of the bootstrapping before starting the thread.
-        process_bootstrap(config, &client).await?;
+        metered_process_bootstrap(config, &client).await?;
 
         // spawn bootstrap thread
         tokio::spawn(loop_bootstrap(config.clone(), client));
@@ -46,14 +47,33 @@ pub async fn init_bootstrap(config: &ServiceConfig) -> Result<(), RdapServerErro
     Ok(())
 }
 
-async fn loop_bootstrap(config: ServiceConfig, client: Client) -> Result<(), RdapServerError> {
+/// Refreshes the bootstrap every minute. A failed refresh is logged and tried
+/// again the next minute.
+async fn loop_bootstrap(config: ServiceConfig, client: Client) {
     loop {
         sleep(Duration::from_millis(60000)).await;
-        process_bootstrap(&config, &client).await?;
+        if let Err(e) = metered_process_bootstrap(&config, &client).await {
+            error!("Unable to refresh IANA bootstrap: {e}");
+        }
     }
 }
 
-async fn process_bootstrap(config: &ServiceConfig, client: &Client) -> Result<(), RdapServerError> {
+/// Processes the bootstrap as [process_bootstrap] does, recording the outcome in the metrics.
+async fn metered_process_bootstrap(
+    config: &ServiceConfig,
+    client: &Client,
+) -> Result<bool, RdapServerError> {
+    let refreshed = process_bootstrap(config, client).await;
+    METRICS.record_bootstrap_refresh(&refreshed);
+    refreshed
+}
+
+/// Fetches the IANA registries that have changed and makes bootstrap data from them,
+/// returning true if there was new data.
+async fn process_bootstrap(
+    config: &ServiceConfig,
+    client: &Client,
+) -> Result<bool, RdapServerError> {
     let mut new_data = false;
     if let Some(iana_reg) =
         fetch_iana_registry(IanaRegistryType::RdapBootstrapDns, client, &config.data_dir).await?
@@ -105,7 +125,7 @@ async fn process_bootstrap(config: &ServiceConfig, client: &Client) -> Result<()
             trigger_reload(&config.data_dir).await?;
         }
     }
-    Ok(())
+    Ok(new_data)
 }
 
 async fn remove_previous_bootstrap(
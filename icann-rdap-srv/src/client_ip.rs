@@ -0,0 +1,32 @@
+//! Resolution of the IP address of the client of a request.
+
+use std::net::IpAddr;
+
+use {
+    axum::{
+        extract::{Request, State},
+        middleware::Next,
+        response::{IntoResponse, Response},
+    },
+    axum_client_ip::{SecureClientIp, SecureClientIpSource},
+};
+
+/// The IP address of the client of a request, as found by the client IP source
+/// of the server.
+#[derive(Debug, Clone, Copy)]
+pub struct ClientIp(pub IpAddr);
+
+/// Middleware finding the IP address of the client of a request once, so that
+/// rate limiting and redaction agree on the client.
+pub(crate) async fn resolve_client_ip(
+    State(source): State<SecureClientIpSource>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let client_ip = match SecureClientIp::from(&source, request.headers(), request.extensions()) {
+        Ok(SecureClientIp(client_ip)) => client_ip,
+        Err(e) => return e.into_response(),
+    };
+    request.extensions_mut().insert(ClientIp(client_ip));
+    next.run(request).await
+}
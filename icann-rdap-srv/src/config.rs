@@ -1,8 +1,12 @@
-use std::path::{self};
+use std::{
+    path::{self},
+    str::FromStr,
+};
 
 use strum_macros::EnumString;
 
 use {
+    axum_client_ip::SecureClientIpSource,
     buildstructor::Builder,
     envmnt::{get_or, get_parse_or},
     strum_macros::Display,
@@ -10,8 +14,12 @@ use {
 };
 
 use crate::{
+    auth::AuthConfig,
     error::RdapServerError,
-    storage::{mem::config::MemConfig, pg::config::PgConfig, CommonConfig},
+    rdap::redaction::RedactionPolicy,
+    storage::{
+        mem::config::MemConfig, pg::config::PgConfig, sqlite::config::SqliteConfig, CommonConfig,
+    },
 };
 
 pub const LOG: &str = "RDAP_SRV_LOG";
@@ -30,7 +38,27 @@ pub const NAMESERVER_SEARCH_BY_NAME_ENABLE: &str = "RDAP_SRV_NAMESERVER_SEARCH_B
 pub const NAMESERVER_SEARCH_BY_IP_ENABLE: &str = "RDAP_SRV_NAMESERVER_SEARCH_BY_IP";
 pub const ENTITY_SEARCH_BY_HANDLE_ENABLE: &str = "RDAP_SRV_ENTITY_SEARCH_BY_HANDLE";
 pub const ENTITY_SEARCH_BY_FULL_NAME_ENABLE: &str = "RDAP_SRV_ENTITY_SEARCH_BY_FULL_NAME";
+pub const NETWORK_SEARCH_BY_HANDLE_ENABLE: &str = "RDAP_SRV_NETWORK_SEARCH_BY_HANDLE";
+pub const NETWORK_SEARCH_BY_NAME_ENABLE: &str = "RDAP_SRV_NETWORK_SEARCH_BY_NAME";
+pub const AUTNUM_SEARCH_BY_HANDLE_ENABLE: &str = "RDAP_SRV_AUTNUM_SEARCH_BY_HANDLE";
+pub const AUTNUM_SEARCH_BY_NAME_ENABLE: &str = "RDAP_SRV_AUTNUM_SEARCH_BY_NAME";
+pub const REVERSE_SEARCH_ENABLE: &str = "RDAP_SRV_REVERSE_SEARCH";
+pub const NETWORK_HIERARCHY_ENABLE: &str = "RDAP_SRV_NETWORK_HIERARCHY";
 pub const JSCONTACT_CONVERSION: &str = "RDAP_SRV_JSCONTACT_CONVERSION";
+pub const MAX_SEARCH_RESULTS: &str = "RDAP_SRV_MAX_SEARCH_RESULTS";
+pub const SEARCH_PAGE_SIZE: &str = "RDAP_SRV_SEARCH_PAGE_SIZE";
+pub const REDACTION_POLICY: &str = "RDAP_SRV_REDACTION_POLICY";
+pub const AUTH_FILE: &str = "RDAP_SRV_AUTH_FILE";
+pub const RATE_LIMIT: &str = "RDAP_SRV_RATE_LIMIT";
+pub const RATE_LIMIT_BURST: &str = "RDAP_SRV_RATE_LIMIT_BURST";
+pub const CLIENT_IP_SOURCE: &str = "RDAP_SRV_CLIENT_IP_SOURCE";
+pub const METRICS_LISTEN_ADDR: &str = "RDAP_SRV_METRICS_LISTEN_ADDR";
+pub const METRICS_LISTEN_PORT: &str = "RDAP_SRV_METRICS_LISTEN_PORT";
+pub const TLS_CERT_FILE: &str = "RDAP_SRV_TLS_CERT_FILE";
+pub const TLS_KEY_FILE: &str = "RDAP_SRV_TLS_KEY_FILE";
+pub const HTTP_REDIRECT_PORT: &str = "RDAP_SRV_HTTP_REDIRECT_PORT";
+pub const HTTP_REDIRECT_HOST: &str = "RDAP_SRV_HTTP_REDIRECT_HOST";
+pub const HSTS_MAX_AGE: &str = "RDAP_SRV_HSTS_MAX_AGE";
 
 pub fn debug_config_vars() {
     let var_list = [
@@ -50,7 +78,27 @@ pub fn debug_config_vars() {
         NAMESERVER_SEARCH_BY_IP_ENABLE,
         ENTITY_SEARCH_BY_HANDLE_ENABLE,
         ENTITY_SEARCH_BY_FULL_NAME_ENABLE,
+        NETWORK_SEARCH_BY_HANDLE_ENABLE,
+        NETWORK_SEARCH_BY_NAME_ENABLE,
+        AUTNUM_SEARCH_BY_HANDLE_ENABLE,
+        AUTNUM_SEARCH_BY_NAME_ENABLE,
+        REVERSE_SEARCH_ENABLE,
+        NETWORK_HIERARCHY_ENABLE,
         JSCONTACT_CONVERSION,
+        MAX_SEARCH_RESULTS,
+        SEARCH_PAGE_SIZE,
+        REDACTION_POLICY,
+        AUTH_FILE,
+        RATE_LIMIT,
+        RATE_LIMIT_BURST,
+        CLIENT_IP_SOURCE,
+        METRICS_LISTEN_ADDR,
+        METRICS_LISTEN_PORT,
+        TLS_CERT_FILE,
+        TLS_KEY_FILE,
+        HTTP_REDIRECT_PORT,
+        HTTP_REDIRECT_HOST,
+        HSTS_MAX_AGE,
     ];
     envmnt::vars()
         .iter()
@@ -67,7 +115,7 @@ pub fn data_dir() -> Result<String, std::io::Error> {
 pub const DEFAULT_DATA_RDAP_BASE_URL: &str = "http://localhost:3000/rdap";
 
 /// RDAP server listening configuration.
-#[derive(Debug, Builder, Default)]
+#[derive(Debug, Builder, Default, Clone)]
 pub struct ListenConfig {
     /// If specified, determines the IP address of the interface to bind to.
     /// If unspecified, the server will bind all interfaces.
@@ -76,6 +124,66 @@ pub struct ListenConfig {
     /// If specified, determines the port number the server will bind to.
     /// If unspecified, the server let's the OS determine the port.
     pub port: Option<u16>,
+
+    /// If specified, the server serves HTTPS instead of HTTP.
+    pub tls: Option<TlsConfig>,
+}
+
+/// HTTPS configuration.
+#[derive(Debug, Builder, Clone)]
+pub struct TlsConfig {
+    /// The PEM file of the certificate chain, which is reloaded when it changes.
+    pub cert_file: String,
+
+    /// The PEM file of the private key, which is reloaded when it changes.
+    pub key_file: String,
+
+    /// If specified, the port on which plain HTTP requests are redirected to HTTPS.
+    pub redirect_port: Option<u16>,
+
+    /// The host name HTTP requests are redirected to, which must be given with
+    /// the redirect port.
+    pub redirect_host: Option<String>,
+
+    /// If specified, the `max-age` of the `Strict-Transport-Security` header
+    /// given with every response.
+    pub hsts_max_age: Option<u64>,
+}
+
+impl TlsConfig {
+    /// Gets the HTTPS configuration, which is `None` if there is no certificate.
+    pub fn new_from_env() -> Result<Option<Self>, RdapServerError> {
+        let cert_file = envmnt::exists(TLS_CERT_FILE).then(|| envmnt::get_or_panic(TLS_CERT_FILE));
+        let key_file = envmnt::exists(TLS_KEY_FILE).then(|| envmnt::get_or_panic(TLS_KEY_FILE));
+        let (cert_file, key_file) = match (cert_file, key_file) {
+            (None, None) => return Ok(None),
+            (Some(cert_file), Some(key_file)) => (cert_file, key_file),
+            _ => {
+                return Err(RdapServerError::Config(format!(
+                    "both {TLS_CERT_FILE} and {TLS_KEY_FILE} must be given for HTTPS"
+                )))
+            }
+        };
+        let redirect_port = if envmnt::exists(HTTP_REDIRECT_PORT) {
+            Some(envmnt::get_parse(HTTP_REDIRECT_PORT)?)
+        } else {
+            None
+        };
+        let redirect_host =
+            envmnt::exists(HTTP_REDIRECT_HOST).then(|| envmnt::get_or_panic(HTTP_REDIRECT_HOST));
+        let hsts_max_age = if envmnt::exists(HSTS_MAX_AGE) {
+            Some(envmnt::get_parse(HSTS_MAX_AGE)?)
+        } else {
+            None
+        };
+        Ok(Some(Self {
+            cert_file,
+            key_file,
+            redirect_port,
+            redirect_host,
+            hsts_max_age,
+        }))
+    }
 }
 
 /// Determines the storage type.
@@ -87,6 +195,9 @@ pub enum StorageType {
 
     /// Uses a PostgreSQL database.
     Postgres(PgConfig),
+
+    /// Uses a SQLite database file.
+    Sqlite(SqliteConfig),
 }
 
 impl StorageType {
@@ -99,6 +210,12 @@ impl StorageType {
         let nameserver_search_by_ip = get_parse_or(NAMESERVER_SEARCH_BY_IP_ENABLE, false)?;
         let entity_search_by_handle = get_parse_or(ENTITY_SEARCH_BY_HANDLE_ENABLE, false)?;
         let entity_search_by_full_name = get_parse_or(ENTITY_SEARCH_BY_FULL_NAME_ENABLE, false)?;
+        let network_search_by_handle = get_parse_or(NETWORK_SEARCH_BY_HANDLE_ENABLE, false)?;
+        let network_search_by_name = get_parse_or(NETWORK_SEARCH_BY_NAME_ENABLE, false)?;
+        let autnum_search_by_handle = get_parse_or(AUTNUM_SEARCH_BY_HANDLE_ENABLE, false)?;
+        let autnum_search_by_name = get_parse_or(AUTNUM_SEARCH_BY_NAME_ENABLE, false)?;
+        let reverse_search = get_parse_or(REVERSE_SEARCH_ENABLE, false)?;
+        let network_hierarchy = get_parse_or(NETWORK_HIERARCHY_ENABLE, false)?;
         let common_config = CommonConfig::builder()
             .domain_search_by_name_enable(domain_search_by_name)
             .domain_search_by_ns_ip_enable(domain_search_by_ns_ip)
@@ -107,6 +224,12 @@ impl StorageType {
             .nameserver_search_by_ip_enable(nameserver_search_by_ip)
             .entity_search_by_handle_enable(entity_search_by_handle)
             .entity_search_by_full_name_enable(entity_search_by_full_name)
+            .network_search_by_handle_enable(network_search_by_handle)
+            .network_search_by_name_enable(network_search_by_name)
+            .autnum_search_by_handle_enable(autnum_search_by_handle)
+            .autnum_search_by_name_enable(autnum_search_by_name)
+            .reverse_search_enable(reverse_search)
+            .network_hierarchy_enable(network_hierarchy)
             .build();
         let storage = get_or(STORAGE, "memory");
         if storage == "memory" {
@@ -121,6 +244,14 @@ impl StorageType {
                     .common_config(common_config)
                     .build(),
             ))
+        } else if storage == "sqlite" {
+            let db_url = get_or(DB_URL, "sqlite://srv/rdap.db");
+            Ok(Self::Sqlite(
+                SqliteConfig::builder()
+                    .db_url(db_url)
+                    .common_config(common_config)
+                    .build(),
+            ))
         } else {
             Err(RdapServerError::Config(format!(
                 "storage type of '{storage}' is invalid"
@@ -143,6 +274,75 @@ pub enum JsContactConversion {
     Only,
 }
 
+/// Search result limits and paging (RFC 8977) configuration.
+#[derive(Debug, Builder, Clone, Copy, Default)]
+pub struct SearchConfig {
+    /// If specified, the maximum number of results of a search.
+    /// Larger result sets are truncated and carry a notice saying so.
+    pub max_results: Option<usize>,
+
+    /// If specified, the number of results in a page. If unspecified,
+    /// results are not paged.
+    pub page_size: Option<usize>,
+}
+
+impl SearchConfig {
+    pub fn new_from_env() -> Result<Self, RdapServerError> {
+        let max_results = get_opt_parse(MAX_SEARCH_RESULTS)?;
+        let page_size = get_opt_parse(SEARCH_PAGE_SIZE)?;
+        Ok(Self {
+            max_results,
+            page_size,
+        })
+    }
+}
+
+/// Per client rate limiting configuration.
+///
+/// Each client has a bucket of tokens which is refilled at the rate, up to the
+/// burst. A request takes a token, and requests finding the bucket empty are
+/// refused with a 429. IPv6 clients in the same /64 share a bucket.
+#[derive(Debug, Builder, Clone)]
+pub struct RateLimitConfig {
+    /// The number of requests per second allowed over time.
+    pub rate: f64,
+
+    /// The number of requests allowed at once.
+    pub burst: u32,
+}
+
+impl RateLimitConfig {
+    /// Gets the rate limiting configuration, which is `None` if there is no rate.
+    pub fn new_from_env() -> Result<Option<Self>, RdapServerError> {
+        if !envmnt::exists(RATE_LIMIT) {
+            return Ok(None);
+        }
+        let rate: f64 = envmnt::get_parse(RATE_LIMIT)?;
+        if !rate.is_finite() || rate <= 0.0 {
+            return Err(RdapServerError::Config(format!(
+                "rate limit of '{rate}' is not a positive number"
+            )));
+        }
+        let burst = get_parse_or(RATE_LIMIT_BURST, rate.ceil() as u32)?.max(1);
+        Ok(Some(Self { rate, burst }))
+    }
+}
+
+/// Gets where the IP address of a client is found, which is the peer address of
+/// the connection unless the server is behind a proxy.
+pub fn client_ip_source_from_env() -> Result<SecureClientIpSource, RdapServerError> {
+    SecureClientIpSource::from_str(&get_or(CLIENT_IP_SOURCE, "ConnectInfo"))
+        .map_err(|e| RdapServerError::Config(e.to_string()))
+}
+
+fn get_opt_parse(key: &str) -> Result<Option<usize>, RdapServerError> {
+    if envmnt::exists(key) {
+        Ok(Some(envmnt::get_parse(key)?))
+    } else {
+        Ok(None)
+    }
+}
+
 /// RDAP service configuration.
 #[derive(Debug, Builder, Clone)]
 pub struct ServiceConfig {
@@ -152,6 +352,23 @@ pub struct ServiceConfig {
     pub bootstrap: bool,
     pub update_on_bootstrap: bool,
     pub jscontact_conversion: JsContactConversion,
+    pub search_config: SearchConfig,
+
+    /// If specified, the policy by which responses are redacted.
+    pub redaction_policy: Option<RedactionPolicy>,
+
+    /// If specified, the credentials of the clients, which are then authenticated.
+    pub auth: Option<AuthConfig>,
+
+    /// If specified, requests are rate limited per client.
+    pub rate_limit: Option<RateLimitConfig>,
+
+    /// Where the IP address of a client is found for rate limiting and redaction.
+    /// If not specified, this is the peer address of the connection.
+    pub client_ip_source: Option<SecureClientIpSource>,
+
+    /// If specified, where the Prometheus metrics are served.
+    pub metrics_listen: Option<ListenConfig>,
 }
 
 #[buildstructor::buildstructor]
@@ -173,6 +390,12 @@ impl ServiceConfig {
             bootstrap: false,
             update_on_bootstrap: false,
             jscontact_conversion: JsContactConversion::None,
+            search_config: SearchConfig::default(),
+            redaction_policy: None,
+            auth: None,
+            rate_limit: None,
+            client_ip_source: None,
+            metrics_listen: None,
         })
     }
 }
@@ -31,6 +31,8 @@ pub enum RdapServerError {
     Config(String),
     #[error(transparent)]
     SqlDb(#[from] sqlx::Error),
+    #[error(transparent)]
+    SqlMigrate(#[from] sqlx::migrate::MigrateError),
     #[error("index data for {0} is missing or empty")]
     EmptyIndexData(String),
     #[error("file at {0} is not JSON")]
@@ -54,6 +56,8 @@ pub enum RdapServerError {
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
     #[error(transparent)]
+    Notify(#[from] notify::Error),
+    #[error(transparent)]
     Response(#[from] RdapResponseError),
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
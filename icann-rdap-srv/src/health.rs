@@ -0,0 +1,107 @@
+//! Health and readiness of the server, for orchestrators.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use {
+    axum::{
+        extract::State,
+        response::{IntoResponse, Response},
+        Json,
+    },
+    http::StatusCode,
+    serde_json::json,
+    tracing::warn,
+};
+
+use crate::server::DynServiceState;
+
+/// Whether the server has finished initializing. Clones share the readiness.
+#[derive(Debug, Clone, Default)]
+pub struct Readiness {
+    bootstrapped: Arc<AtomicBool>,
+    data_loaded: Arc<AtomicBool>,
+}
+
+impl Readiness {
+    /// The readiness of a server with nothing left to initialize.
+    pub fn ready() -> Self {
+        let readiness = Self::default();
+        readiness.set_bootstrapped();
+        readiness.set_data_loaded();
+        readiness
+    }
+
+    /// Notes the first bootstrap has been done, or that there is no bootstrapping.
+    pub fn set_bootstrapped(&self) {
+        self.bootstrapped.store(true, Ordering::Release);
+    }
+
+    /// Notes the data has been loaded.
+    pub fn set_data_loaded(&self) {
+        self.data_loaded.store(true, Ordering::Release);
+    }
+
+    pub fn is_bootstrapped(&self) -> bool {
+        self.bootstrapped.load(Ordering::Acquire)
+    }
+
+    pub fn is_data_loaded(&self) -> bool {
+        self.data_loaded.load(Ordering::Acquire)
+    }
+}
+
+/// Liveness, which is always OK if the server is responding.
+pub(crate) async fn healthz() -> Response {
+    (StatusCode::OK, Json(json!({ "status": "ok" }))).into_response()
+}
+
+/// Readiness, which is only OK once the server has initialized and while the
+/// storage is reachable.
+pub(crate) async fn readyz(state: State<DynServiceState>) -> Response {
+    let readiness = state.get_readiness();
+    let storage = match state.get_storage().await {
+        Ok(storage) => storage.ping().await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = &storage {
+        warn!("storage is not ready: {e}");
+    }
+    let ready = readiness.is_bootstrapped() && readiness.is_data_loaded() && storage.is_ok();
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let body = json!({
+        "status": if ready { "ok" } else { "unavailable" },
+        "bootstrapped": readiness.is_bootstrapped(),
+        "dataLoaded": readiness.is_data_loaded(),
+        "storage": if storage.is_ok() { "ok" } else { "unavailable" },
+    });
+    (status, Json(body)).into_response()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::Readiness;
+
+    #[test]
+    fn GIVEN_new_readiness_WHEN_initialized_THEN_shared_by_clones() {
+        // GIVEN
+        let readiness = Readiness::default();
+        let clone = readiness.clone();
+        assert!(!clone.is_data_loaded());
+
+        // WHEN
+        readiness.set_bootstrapped();
+        readiness.set_data_loaded();
+
+        // THEN
+        assert!(clone.is_bootstrapped());
+        assert!(clone.is_data_loaded());
+    }
+}
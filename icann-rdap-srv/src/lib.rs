@@ -1,7 +1,14 @@
+pub mod admin;
+pub mod auth;
 pub mod bootstrap;
+pub mod client_ip;
 pub mod config;
 pub mod error;
+pub mod health;
+pub mod metrics;
+pub mod rate_limit;
 pub mod rdap;
 pub mod server;
 pub mod storage;
+pub mod tls;
 pub mod util;
@@ -0,0 +1,267 @@
+//! Prometheus metrics of the server.
+
+use std::{
+    sync::{Arc, LazyLock},
+    time::{Duration, Instant},
+};
+
+use {
+    axum::{
+        extract::{Request, State},
+        middleware::Next,
+        response::{IntoResponse, Response},
+    },
+    http::StatusCode,
+    prometheus_client::{
+        encoding::{text::encode, EncodeLabelSet},
+        metrics::{
+            counter::Counter,
+            family::Family,
+            gauge::Gauge,
+            histogram::{exponential_buckets, Histogram},
+        },
+        registry::Registry,
+    },
+    tracing::warn,
+};
+
+use crate::storage::StoreOps;
+
+/// The metrics of the server, which are recorded from anywhere in the server.
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+const OPENMETRICS_MEDIA_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+type HistogramFamily<L> = Family<L, Histogram, fn() -> Histogram>;
+
+pub struct Metrics {
+    registry: Registry,
+    requests: Family<RequestLabels, Counter>,
+    request_duration: HistogramFamily<RouteLabels>,
+    stored_objects: Family<MapLabels, Gauge>,
+    data_loads: Family<DataLoadLabels, Counter>,
+    data_load_duration: HistogramFamily<DataLoadLabels>,
+    bootstrap_refreshes: Family<OutcomeLabels, Counter>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct RequestLabels {
+    route: &'static str,
+    status: u16,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct RouteLabels {
+    route: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct MapLabels {
+    map: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct DataLoadLabels {
+    kind: &'static str,
+    outcome: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct OutcomeLabels {
+    outcome: &'static str,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let mut registry = Registry::with_prefix("rdap_srv");
+        let requests = Family::<RequestLabels, Counter>::default();
+        registry.register(
+            "requests",
+            "Requests by route and status code",
+            requests.clone(),
+        );
+        let request_duration: HistogramFamily<RouteLabels> =
+            Family::new_with_constructor(|| Histogram::new(exponential_buckets(0.0005, 2.0, 16)));
+        registry.register(
+            "request_duration_seconds",
+            "Time taken to respond to requests by route",
+            request_duration.clone(),
+        );
+        let stored_objects = Family::<MapLabels, Gauge>::default();
+        registry.register(
+            "stored_objects",
+            "Objects in each map of the in-memory storage",
+            stored_objects.clone(),
+        );
+        let data_loads = Family::<DataLoadLabels, Counter>::default();
+        registry.register(
+            "data_loads",
+            "Data updates and reloads by outcome",
+            data_loads.clone(),
+        );
+        let data_load_duration: HistogramFamily<DataLoadLabels> =
+            Family::new_with_constructor(|| Histogram::new(exponential_buckets(0.01, 2.0, 16)));
+        registry.register(
+            "data_load_duration_seconds",
+            "Time taken by data updates and reloads",
+            data_load_duration.clone(),
+        );
+        let bootstrap_refreshes = Family::<OutcomeLabels, Counter>::default();
+        registry.register(
+            "bootstrap_refreshes",
+            "IANA bootstrap refreshes by outcome",
+            bootstrap_refreshes.clone(),
+        );
+        Self {
+            registry,
+            requests,
+            request_duration,
+            stored_objects,
+            data_loads,
+            data_load_duration,
+            bootstrap_refreshes,
+        }
+    }
+
+    /// Records a request that has been responded to.
+    pub fn record_request(&self, route: &'static str, status: StatusCode, duration: Duration) {
+        self.requests
+            .get_or_create(&RequestLabels {
+                route,
+                status: status.as_u16(),
+            })
+            .inc();
+        self.request_duration
+            .get_or_create(&RouteLabels { route })
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Records a data update or reload, which is of the kind `update`,
+    /// `reload` or `incremental`.
+    pub fn record_data_load(&self, kind: &'static str, succeeded: bool, duration: Duration) {
+        let labels = DataLoadLabels {
+            kind,
+            outcome: outcome(succeeded),
+        };
+        self.data_loads.get_or_create(&labels).inc();
+        self.data_load_duration
+            .get_or_create(&labels)
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Records a refresh of the IANA bootstrap files, which may have found new data.
+    pub fn record_bootstrap_refresh<E>(&self, refreshed: &Result<bool, E>) {
+        let outcome = match refreshed {
+            Ok(true) => "updated",
+            Ok(false) => "unchanged",
+            Err(_) => "failure",
+        };
+        self.bootstrap_refreshes
+            .get_or_create(&OutcomeLabels { outcome })
+            .inc();
+    }
+
+    /// Encodes the metrics in the OpenMetrics text format, with the counts of
+    /// the stored objects taken from the storage.
+    pub async fn encode(&self, store: &dyn StoreOps) -> Result<String, std::fmt::Error> {
+        for (map, count) in store.object_counts().await {
+            self.stored_objects
+                .get_or_create(&MapLabels { map })
+                .set(count as i64);
+        }
+        let mut text = String::new();
+        encode(&mut text, &self.registry)?;
+        Ok(text)
+    }
+}
+
+fn outcome(succeeded: bool) -> &'static str {
+    if succeeded {
+        "success"
+    } else {
+        "failure"
+    }
+}
+
+/// Middleware recording the route, status code and duration of requests.
+pub(crate) async fn track_requests(request: Request, next: Next) -> Response {
+    let route = route_of(request.uri().path());
+    let start = Instant::now();
+    let response = next.run(request).await;
+    METRICS.record_request(route, response.status(), start.elapsed());
+    response
+}
+
+/// Gets the route of a request path, which is a label of few values.
+pub(crate) fn route_of(path: &str) -> &'static str {
+    let mut segments = path
+        .strip_prefix("/rdap/")
+        .unwrap_or_default()
+        .split('/')
+        .filter(|s| !s.is_empty());
+    let first = segments.next();
+    let second = segments.next();
+    match (first, second) {
+        (
+            Some("domains" | "nameservers" | "entities" | "ips" | "autnums"),
+            Some("reverse_search"),
+        ) => "reverse_search",
+        (Some("domains" | "ips" | "autnums"), Some("rirSearch1")) => "rir_search",
+        (Some("domain"), _) => "domain",
+        (Some("ip"), _) => "ip",
+        (Some("autnum"), _) => "autnum",
+        (Some("entity"), _) => "entity",
+        (Some("nameserver"), _) => "nameserver",
+        (Some("domains"), _) => "domain_search",
+        (Some("nameservers"), _) => "nameserver_search",
+        (Some("entities"), _) => "entity_search",
+        (Some("ips"), _) => "ip_search",
+        (Some("autnums"), _) => "autnum_search",
+        (Some("help"), _) => "help",
+        _ => "other",
+    }
+}
+
+/// Serves the metrics.
+pub(crate) async fn metrics(State(store): State<Arc<dyn StoreOps>>) -> Response {
+    match METRICS.encode(&*store).await {
+        Ok(text) => ([("content-type", OPENMETRICS_MEDIA_TYPE)], text).into_response(),
+        Err(e) => {
+            warn!("unable to encode metrics: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::route_of;
+
+    #[test]
+    fn GIVEN_request_paths_WHEN_route_of_THEN_routes_labeled() {
+        // GIVEN
+        let paths = [
+            ("/rdap/domain/foo.example", "domain"),
+            ("/rdap/ip/192.0.2.0/24", "ip"),
+            ("/rdap/autnum/65536", "autnum"),
+            ("/rdap/entity/FOO", "entity"),
+            ("/rdap/nameserver/ns.foo.example", "nameserver"),
+            ("/rdap/domains", "domain_search"),
+            ("/rdap/ips/rirSearch1/up/192.0.2.0", "rir_search"),
+            ("/rdap/autnums/rirSearch1/rdap-up/64496", "rir_search"),
+            ("/rdap/autnums", "autnum_search"),
+            ("/rdap/entities/reverse_search/entity", "reverse_search"),
+            ("/rdap/help", "help"),
+            ("/favicon.ico", "other"),
+        ];
+
+        for (path, expected) in paths {
+            // WHEN
+            let actual = route_of(path);
+
+            // THEN
+            assert_eq!(actual, expected, "{path}");
+        }
+    }
+}
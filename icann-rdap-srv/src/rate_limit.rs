@@ -0,0 +1,275 @@
+//! Per client rate limiting with token buckets.
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv6Addr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use {
+    axum::{
+        extract::{Request, State},
+        middleware::Next,
+        response::{IntoResponse, Response},
+        Json,
+    },
+    http::{header::RETRY_AFTER, StatusCode},
+    icann_rdap_common::{
+        media_types::RDAP_MEDIA_TYPE, prelude::ToResponse, response::Rfc9083Error,
+    },
+    tracing::debug,
+};
+
+use crate::{client_ip::ClientIp, config::RateLimitConfig};
+
+/// The most buckets kept. When there are this many, full buckets are forgotten,
+/// and clients without a bucket are limited until some are.
+const MAX_BUCKETS: usize = 10_000;
+
+/// The least time between two sweeps of the full buckets.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Limits the rate of requests of each client.
+///
+/// IPv6 clients are limited by their /64 network, as a host is usually given
+/// a whole /64.
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Mutex<Buckets>,
+}
+
+#[derive(Debug, Default)]
+struct Buckets {
+    clients: HashMap<IpAddr, Bucket>,
+    swept: Option<Instant>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            buckets: Mutex::new(Buckets::default()),
+        }
+    }
+
+    /// Takes a token from the bucket of a client. If the bucket is empty, the
+    /// time until a token is available is returned.
+    pub fn take(&self, client_ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        let rate = self.config.rate;
+        let burst = f64::from(self.config.burst);
+        let client = bucket_key(client_ip);
+        let mut buckets = self
+            .buckets
+            .lock()
+            .expect("rate limit buckets lock poisoned");
+        if buckets.clients.len() >= MAX_BUCKETS && !buckets.clients.contains_key(&client) {
+            let sweep_due = buckets
+                .swept
+                .is_none_or(|swept| now.saturating_duration_since(swept) >= SWEEP_INTERVAL);
+            if sweep_due {
+                buckets
+                    .clients
+                    .retain(|_, bucket| bucket.refilled(rate, burst, now) < burst);
+                buckets.swept = Some(now);
+            }
+            if buckets.clients.len() >= MAX_BUCKETS {
+                debug!("rate limit buckets are full, limiting new client {client}");
+                return Err(SWEEP_INTERVAL.max(Duration::from_secs_f64(1.0 / rate)));
+            }
+        }
+        let bucket = buckets.clients.entry(client).or_insert(Bucket {
+            tokens: burst,
+            updated: now,
+        });
+        bucket.tokens = bucket.refilled(rate, burst, now);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+}
+
+/// Gets the address a client is limited by, which is the /64 network of an
+/// IPv6 address.
+fn bucket_key(client_ip: IpAddr) -> IpAddr {
+    match client_ip.to_canonical() {
+        IpAddr::V6(v6) => IpAddr::V6(Ipv6Addr::from_bits(
+            v6.to_bits() & 0xffff_ffff_ffff_ffff_0000_0000_0000_0000,
+        )),
+        v4 => v4,
+    }
+}
+
+impl Bucket {
+    fn refilled(&self, rate: f64, burst: f64, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        (self.tokens + elapsed * rate).min(burst)
+    }
+}
+
+/// Middleware refusing requests of clients over their rate with a 429. The
+/// client is the [ClientIp] of the request.
+pub(crate) async fn rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(ClientIp(client_ip)) = request.extensions().get::<ClientIp>().copied() else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    match limiter.take(client_ip, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => {
+            debug!("rate limiting client {client_ip}");
+            too_many_requests(wait)
+        }
+    }
+}
+
+fn too_many_requests(wait: Duration) -> Response {
+    let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+    let rdap = Rfc9083Error::response_obj()
+        .error_code(429)
+        .title("Too Many Requests")
+        .description_entry(format!(
+            "The rate of requests is too high. Retry after {retry_after} seconds."
+        ))
+        .build()
+        .to_response();
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        [
+            ("content-type", RDAP_MEDIA_TYPE.to_string()),
+            (RETRY_AFTER.as_str(), retry_after.to_string()),
+        ],
+        Json(rdap),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::{
+        net::IpAddr,
+        time::{Duration, Instant},
+    };
+
+    use crate::config::RateLimitConfig;
+
+    use super::{RateLimiter, MAX_BUCKETS};
+
+    fn limiter(rate: f64, burst: u32) -> RateLimiter {
+        RateLimiter::new(RateLimitConfig::builder().rate(rate).burst(burst).build())
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().expect("ip")
+    }
+
+    #[test]
+    fn GIVEN_burst_used_WHEN_take_THEN_wait_until_refill() {
+        // GIVEN
+        let limiter = limiter(0.5, 2);
+        let now = Instant::now();
+        limiter.take(ip("192.0.2.1"), now).expect("first");
+        limiter.take(ip("192.0.2.1"), now).expect("second");
+
+        // WHEN
+        let actual = limiter.take(ip("192.0.2.1"), now);
+
+        // THEN
+        assert_eq!(actual, Err(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn GIVEN_burst_used_WHEN_take_after_refill_THEN_ok() {
+        // GIVEN
+        let limiter = limiter(1.0, 1);
+        let now = Instant::now();
+        limiter.take(ip("192.0.2.1"), now).expect("first");
+
+        // WHEN
+        let actual = limiter.take(ip("192.0.2.1"), now + Duration::from_secs(1));
+
+        // THEN
+        assert!(actual.is_ok());
+    }
+
+    #[test]
+    fn GIVEN_one_client_limited_WHEN_take_for_other_client_THEN_ok() {
+        // GIVEN
+        let limiter = limiter(1.0, 1);
+        let now = Instant::now();
+        limiter.take(ip("192.0.2.1"), now).expect("first");
+
+        // WHEN
+        let actual = limiter.take(ip("2001:db8::1"), now);
+
+        // THEN
+        assert!(actual.is_ok());
+    }
+
+    #[test]
+    fn GIVEN_ipv6_client_limited_WHEN_take_for_same_64_THEN_limited() {
+        // GIVEN
+        let limiter = limiter(1.0, 1);
+        let now = Instant::now();
+        limiter.take(ip("2001:db8:0:1::1"), now).expect("first");
+
+        // WHEN
+        let same_64 = limiter.take(ip("2001:db8:0:1::2"), now);
+        let other_64 = limiter.take(ip("2001:db8:0:2::1"), now);
+
+        // THEN
+        assert!(same_64.is_err());
+        assert!(other_64.is_ok());
+    }
+
+    #[test]
+    fn GIVEN_buckets_full_of_limited_clients_WHEN_take_for_new_client_THEN_limited() {
+        // GIVEN
+        let limiter = limiter(1.0, 2);
+        let now = Instant::now();
+        for i in 0..MAX_BUCKETS as u32 {
+            limiter
+                .take(IpAddr::from((0x0a00_0000 + i).to_be_bytes()), now)
+                .expect("filling");
+        }
+
+        // WHEN
+        let actual = limiter.take(ip("192.0.2.1"), now);
+
+        // THEN
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn GIVEN_buckets_full_of_refilled_clients_WHEN_take_for_new_client_THEN_ok() {
+        // GIVEN
+        let limiter = limiter(1.0, 2);
+        let now = Instant::now();
+        for i in 0..MAX_BUCKETS as u32 {
+            limiter
+                .take(IpAddr::from((0x0a00_0000 + i).to_be_bytes()), now)
+                .expect("filling");
+        }
+
+        // WHEN
+        let actual = limiter.take(ip("192.0.2.1"), now + Duration::from_secs(1));
+
+        // THEN
+        assert!(actual.is_ok());
+    }
+}
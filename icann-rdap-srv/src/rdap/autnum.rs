@@ -3,12 +3,12 @@ use axum::{
     response::Response,
 };
 use http::HeaderMap;
-use icann_rdap_common::prelude::normalize_extensions;
 use tracing::debug;
 
 use crate::{
+    auth::Requester,
     error::RdapServerError,
-    rdap::{jscontact_conversion, parse_extensions, response::ResponseUtil},
+    rdap::{parse_extensions, prepare_response, response::ResponseUtil},
     server::DynServiceState,
 };
 
@@ -20,6 +20,7 @@ use super::ToBootStrap;
 pub(crate) async fn autnum_by_num(
     Path(as_num): Path<u32>,
     headers: HeaderMap,
+    requester: Requester,
     state: State<DynServiceState>,
 ) -> Result<Response, RdapServerError> {
     let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
@@ -30,8 +31,7 @@ pub(crate) async fn autnum_by_num(
     Ok(if state.get_bootstrap() {
         autnum.to_autnum_bootstrap(as_num).response()
     } else {
-        let autnum = jscontact_conversion(autnum, state.get_jscontact_conversion(), &exts_list);
-        let autnum = normalize_extensions(autnum);
+        let autnum = prepare_response(autnum, &state, &requester, &headers, &exts_list)?;
         autnum.response()
     })
 }
@@ -0,0 +1,66 @@
+use http::HeaderMap;
+use serde::Deserialize;
+use tracing::debug;
+
+use axum::{
+    extract::{OriginalUri, Query, State},
+    response::Response,
+};
+
+use crate::{
+    auth::Requester,
+    error::RdapServerError,
+    rdap::{
+        paging::{sort_and_page, SortPageParams},
+        parse_extensions, prepare_response,
+        response::ResponseUtil,
+        subsetting::{subset, FieldSetParams},
+    },
+    server::DynServiceState,
+};
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct AutnumsParams {
+    handle: Option<String>,
+    name: Option<String>,
+
+    #[serde(flatten)]
+    sort_page: SortPageParams,
+
+    #[serde(flatten)]
+    field_set: FieldSetParams,
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(level = "debug")]
+pub(crate) async fn autnums(
+    Query(params): Query<AutnumsParams>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    requester: Requester,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    Ok(if let Some(handle) = params.handle {
+        let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
+        debug!("exts_list = \'{}\'", exts_list.join(" "));
+
+        let storage = state.get_storage().await?;
+        let results = storage.search_autnums_by_handle(&handle).await?;
+        let results = sort_and_page(results, &params.sort_page, &state.get_search_config(), &uri);
+        let results = subset(results, &params.field_set, &uri);
+        let results = prepare_response(results, &state, &requester, &headers, &exts_list)?;
+        results.response()
+    } else if let Some(name) = params.name {
+        let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
+        debug!("exts_list = \'{}\'", exts_list.join(" "));
+
+        let storage = state.get_storage().await?;
+        let results = storage.search_autnums_by_name(&name).await?;
+        let results = sort_and_page(results, &params.sort_page, &state.get_search_config(), &uri);
+        let results = subset(results, &params.field_set, &uri);
+        let results = prepare_response(results, &state, &requester, &headers, &exts_list)?;
+        results.response()
+    } else {
+        super::response::NOT_IMPLEMENTED.response()
+    })
+}
@@ -1,5 +1,5 @@
 use http::HeaderMap;
-use icann_rdap_common::{prelude::normalize_extensions, rdns::reverse_dns_to_ip};
+use icann_rdap_common::rdns::reverse_dns_to_ip;
 use tracing::debug;
 
 use {
@@ -11,8 +11,9 @@ use {
 };
 
 use crate::{
+    auth::Requester,
     error::RdapServerError,
-    rdap::{jscontact_conversion, parse_extensions, response::ResponseUtil},
+    rdap::{parse_extensions, prepare_response, response::ResponseUtil},
     server::DynServiceState,
 };
 
@@ -25,6 +26,7 @@ use super::ToBootStrap;
 pub(crate) async fn domain_by_name(
     Path(domain_name): Path<String>,
     headers: HeaderMap,
+    requester: Requester,
     state: State<DynServiceState>,
 ) -> Result<Response, RdapServerError> {
     let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
@@ -66,7 +68,6 @@ pub(crate) async fn domain_by_name(
         }
     }
 
-    let domain = jscontact_conversion(domain, state.get_jscontact_conversion(), &exts_list);
-    let domain = normalize_extensions(domain);
+    let domain = prepare_response(domain, &state, &requester, &headers, &exts_list)?;
     Ok(domain.response())
 }
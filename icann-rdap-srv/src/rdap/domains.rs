@@ -1,17 +1,22 @@
 use axum::{
-    extract::{Query, State},
+    extract::{OriginalUri, Query, State},
     response::Response,
 };
 
 use http::HeaderMap;
-use icann_rdap_common::prelude::normalize_extensions;
 use serde::Deserialize;
 use std::net::IpAddr;
 use tracing::debug;
 
 use crate::{
+    auth::Requester,
     error::RdapServerError,
-    rdap::{jscontact_conversion, parse_extensions, response::ResponseUtil},
+    rdap::{
+        paging::{sort_and_page, SortPageParams},
+        parse_extensions, prepare_response,
+        response::ResponseUtil,
+        subsetting::{subset, FieldSetParams},
+    },
     server::DynServiceState,
 };
 
@@ -26,13 +31,21 @@ pub(crate) struct DomainsParams {
 
     #[serde(rename = "nsIp")]
     ns_ip: Option<String>,
+
+    #[serde(flatten)]
+    sort_page: SortPageParams,
+
+    #[serde(flatten)]
+    field_set: FieldSetParams,
 }
 
 #[axum_macros::debug_handler]
 #[tracing::instrument(level = "debug")]
 pub(crate) async fn domains(
     Query(params): Query<DomainsParams>,
+    OriginalUri(uri): OriginalUri,
     headers: HeaderMap,
+    requester: Requester,
     state: State<DynServiceState>,
 ) -> Result<Response, RdapServerError> {
     Ok(if let Some(name) = params.name {
@@ -41,8 +54,9 @@ pub(crate) async fn domains(
 
         let storage = state.get_storage().await?;
         let results = storage.search_domains_by_name(&name).await?;
-        let results = jscontact_conversion(results, state.get_jscontact_conversion(), &exts_list);
-        let results = normalize_extensions(results);
+        let results = sort_and_page(results, &params.sort_page, &state.get_search_config(), &uri);
+        let results = subset(results, &params.field_set, &uri);
+        let results = prepare_response(results, &state, &requester, &headers, &exts_list)?;
         results.response()
     } else if let Some(ns_ldh_name) = params.ns_ldh_name {
         let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
@@ -50,8 +64,9 @@ pub(crate) async fn domains(
 
         let storage = state.get_storage().await?;
         let results = storage.search_domains_by_ns_ldh_name(&ns_ldh_name).await?;
-        let results = jscontact_conversion(results, state.get_jscontact_conversion(), &exts_list);
-        let results = normalize_extensions(results);
+        let results = sort_and_page(results, &params.sort_page, &state.get_search_config(), &uri);
+        let results = subset(results, &params.field_set, &uri);
+        let results = prepare_response(results, &state, &requester, &headers, &exts_list)?;
         results.response()
     } else if let Some(ip_str) = params.ns_ip {
         let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
@@ -64,8 +79,9 @@ pub(crate) async fn domains(
 
         let storage = state.get_storage().await?;
         let results = storage.search_domains_by_ns_ip(ip).await?;
-        let results = jscontact_conversion(results, state.get_jscontact_conversion(), &exts_list);
-        let results = normalize_extensions(results);
+        let results = sort_and_page(results, &params.sort_page, &state.get_search_config(), &uri);
+        let results = subset(results, &params.field_set, &uri);
+        let results = prepare_response(results, &state, &requester, &headers, &exts_list)?;
         results.response()
     } else {
         NOT_IMPLEMENTED.response()
@@ -1,16 +1,21 @@
 use http::HeaderMap;
-use icann_rdap_common::prelude::normalize_extensions;
 use serde::Deserialize;
 use tracing::debug;
 
 use axum::{
-    extract::{Query, State},
+    extract::{OriginalUri, Query, State},
     response::Response,
 };
 
 use crate::{
+    auth::Requester,
     error::RdapServerError,
-    rdap::{jscontact_conversion, parse_extensions, response::ResponseUtil},
+    rdap::{
+        paging::{sort_and_page, SortPageParams},
+        parse_extensions, prepare_response,
+        response::ResponseUtil,
+        subsetting::{subset, FieldSetParams},
+    },
     server::DynServiceState,
 };
 
@@ -20,13 +25,21 @@ pub(crate) struct EntitiesParams {
     #[serde(rename = "fn")]
     #[allow(non_snake_case)]
     fn_: Option<String>,
+
+    #[serde(flatten)]
+    sort_page: SortPageParams,
+
+    #[serde(flatten)]
+    field_set: FieldSetParams,
 }
 
 #[axum_macros::debug_handler]
 #[tracing::instrument(level = "debug")]
 pub(crate) async fn entities(
     Query(params): Query<EntitiesParams>,
+    OriginalUri(uri): OriginalUri,
     headers: HeaderMap,
+    requester: Requester,
     state: State<DynServiceState>,
 ) -> Result<Response, RdapServerError> {
     Ok(if let Some(handle) = params.handle {
@@ -35,8 +48,9 @@ pub(crate) async fn entities(
 
         let storage = state.get_storage().await?;
         let results = storage.search_entities_by_handle(&handle).await?;
-        let results = jscontact_conversion(results, state.get_jscontact_conversion(), &exts_list);
-        let results = normalize_extensions(results);
+        let results = sort_and_page(results, &params.sort_page, &state.get_search_config(), &uri);
+        let results = subset(results, &params.field_set, &uri);
+        let results = prepare_response(results, &state, &requester, &headers, &exts_list)?;
         results.response()
     } else if let Some(full_name) = params.fn_ {
         let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
@@ -44,8 +58,9 @@ pub(crate) async fn entities(
 
         let storage = state.get_storage().await?;
         let results = storage.search_entities_by_full_name(&full_name).await?;
-        let results = jscontact_conversion(results, state.get_jscontact_conversion(), &exts_list);
-        let results = normalize_extensions(results);
+        let results = sort_and_page(results, &params.sort_page, &state.get_search_config(), &uri);
+        let results = subset(results, &params.field_set, &uri);
+        let results = prepare_response(results, &state, &requester, &headers, &exts_list)?;
         results.response()
     } else {
         super::response::NOT_IMPLEMENTED.response()
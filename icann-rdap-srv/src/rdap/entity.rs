@@ -1,5 +1,4 @@
 use http::HeaderMap;
-use icann_rdap_common::prelude::normalize_extensions;
 use tracing::debug;
 
 use {
@@ -11,8 +10,9 @@ use {
 };
 
 use crate::{
+    auth::Requester,
     error::RdapServerError,
-    rdap::{jscontact_conversion, parse_extensions, response::ResponseUtil},
+    rdap::{parse_extensions, prepare_response, response::ResponseUtil},
     server::DynServiceState,
 };
 
@@ -24,6 +24,7 @@ use super::ToBootStrap;
 pub(crate) async fn entity_by_handle(
     Path(handle): Path<String>,
     headers: HeaderMap,
+    requester: Requester,
     state: State<DynServiceState>,
 ) -> Result<Response, RdapServerError> {
     let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
@@ -44,7 +45,6 @@ pub(crate) async fn entity_by_handle(
         }
     }
 
-    let entity = jscontact_conversion(entity, state.get_jscontact_conversion(), &exts_list);
-    let entity = normalize_extensions(entity);
+    let entity = prepare_response(entity, &state, &requester, &headers, &exts_list)?;
     Ok(entity.response())
 }
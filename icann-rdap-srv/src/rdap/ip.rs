@@ -1,25 +1,32 @@
 use std::{net::IpAddr, str::FromStr};
 
-use http::HeaderMap;
-use icann_rdap_common::prelude::normalize_extensions;
+use http::{HeaderMap, Uri};
+use icann_rdap_common::{
+    media_types::RDAP_MEDIA_TYPE,
+    prelude::{ExtensionId, Link, RdapResponse, ToResponse},
+    response::Network,
+};
 
 use {
     axum::{
-        extract::{Path, State},
+        extract::{OriginalUri, Path, State},
         response::Response,
     },
     cidr::IpInet,
+    ipnet::{Ipv4Subnets, Ipv6Subnets},
     tracing::debug,
 };
 
 use crate::{
+    auth::Requester,
     error::RdapServerError,
     rdap::{
-        jscontact_conversion, parse_extensions,
+        parse_extensions, prepare_response,
         response::{ResponseUtil, BAD_REQUEST},
         ToBootStrap,
     },
     server::DynServiceState,
+    storage::StoreOps,
 };
 
 /// Gets a network object by the address path.
@@ -27,7 +34,9 @@ use crate::{
 #[tracing::instrument(level = "debug")]
 pub(crate) async fn network_by_netid(
     Path(netid): Path<String>,
+    OriginalUri(uri): OriginalUri,
     headers: HeaderMap,
+    requester: Requester,
     state: State<DynServiceState>,
 ) -> Result<Response, RdapServerError> {
     let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
@@ -41,9 +50,8 @@ pub(crate) async fn network_by_netid(
             if state.get_bootstrap() {
                 Ok(network.to_ip_bootstrap(&netid).response())
             } else {
-                let network =
-                    jscontact_conversion(network, state.get_jscontact_conversion(), &exts_list);
-                let network = normalize_extensions(network);
+                let network = add_hierarchy_links(network, &uri, storage).await?;
+                let network = prepare_response(network, &state, &requester, &headers, &exts_list)?;
                 Ok(network.response())
             }
         } else {
@@ -60,11 +68,67 @@ pub(crate) async fn network_by_netid(
             if state.get_bootstrap() {
                 Ok(network.to_ip_bootstrap(&netid).response())
             } else {
-                let network =
-                    jscontact_conversion(network, state.get_jscontact_conversion(), &exts_list);
-                let network = normalize_extensions(network);
+                let network = add_hierarchy_links(network, &uri, storage).await?;
+                let network = prepare_response(network, &state, &requester, &headers, &exts_list)?;
                 Ok(network.response())
             }
         }
     }
 }
+
+/// Adds the `rdap-up`, `rdap-down`, `rdap-top` and `rdap-bottom` links of the
+/// RIR search extension to a network, for the relations the storage has networks for.
+pub(crate) async fn add_hierarchy_links(
+    rdap: RdapResponse,
+    uri: &Uri,
+    storage: &dyn StoreOps,
+) -> Result<RdapResponse, RdapServerError> {
+    let RdapResponse::Network(mut network) = rdap else {
+        return Ok(rdap);
+    };
+    let Some(cidr) = first_cidr(&network) else {
+        return Ok(network.to_response());
+    };
+    let relations = storage.get_network_relations(&cidr).await?;
+    if relations.is_empty() {
+        return Ok(network.to_response());
+    }
+
+    let path = uri.path();
+    let base = path
+        .find("/ip/")
+        .or_else(|| path.find("/ips/"))
+        .map_or("", |i| &path[..i]);
+    let links = network.object_common.links.get_or_insert_with(Vec::new);
+    for relation in relations {
+        links.push(
+            Link::builder()
+                .value(uri.to_string())
+                .rel(format!("{relation} rdap-active"))
+                .href(format!("{base}/ips/rirSearch1/{relation}/{cidr}"))
+                .media_type(RDAP_MEDIA_TYPE)
+                .build(),
+        );
+    }
+    network
+        .common
+        .rdap_conformance
+        .get_or_insert_with(Vec::new)
+        .push(ExtensionId::RirSearch1.to_extension());
+    Ok(network.to_response())
+}
+
+/// Gets the first CIDR of the range of a network.
+fn first_cidr(network: &Network) -> Option<String> {
+    let start = network.start_address.as_deref()?;
+    let end = network.end_address.as_deref()?;
+    match (start.parse().ok()?, end.parse().ok()?) {
+        (IpAddr::V4(start), IpAddr::V4(end)) => Ipv4Subnets::new(start, end, 0)
+            .next()
+            .map(|net| net.to_string()),
+        (IpAddr::V6(start), IpAddr::V6(end)) => Ipv6Subnets::new(start, end, 0)
+            .next()
+            .map(|net| net.to_string()),
+        _ => None,
+    }
+}
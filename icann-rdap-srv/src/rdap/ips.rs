@@ -0,0 +1,106 @@
+use std::str::FromStr;
+
+use http::HeaderMap;
+use serde::Deserialize;
+use tracing::debug;
+
+use axum::{
+    extract::{OriginalUri, Path, Query, State},
+    response::Response,
+};
+
+use crate::{
+    auth::Requester,
+    error::RdapServerError,
+    rdap::{
+        ip::add_hierarchy_links,
+        paging::{sort_and_page, SortPageParams},
+        parse_extensions, prepare_response,
+        response::{ResponseUtil, BAD_REQUEST},
+        subsetting::{subset, FieldSetParams},
+    },
+    server::DynServiceState,
+    storage::NetworkRelation,
+};
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct IpsParams {
+    handle: Option<String>,
+    name: Option<String>,
+
+    #[serde(flatten)]
+    sort_page: SortPageParams,
+
+    #[serde(flatten)]
+    field_set: FieldSetParams,
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(level = "debug")]
+pub(crate) async fn ips(
+    Query(params): Query<IpsParams>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    requester: Requester,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    Ok(if let Some(handle) = params.handle {
+        let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
+        debug!("exts_list = \'{}\'", exts_list.join(" "));
+
+        let storage = state.get_storage().await?;
+        let results = storage.search_networks_by_handle(&handle).await?;
+        let results = sort_and_page(results, &params.sort_page, &state.get_search_config(), &uri);
+        let results = subset(results, &params.field_set, &uri);
+        let results = prepare_response(results, &state, &requester, &headers, &exts_list)?;
+        results.response()
+    } else if let Some(name) = params.name {
+        let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
+        debug!("exts_list = \'{}\'", exts_list.join(" "));
+
+        let storage = state.get_storage().await?;
+        let results = storage.search_networks_by_name(&name).await?;
+        let results = sort_and_page(results, &params.sort_page, &state.get_search_config(), &uri);
+        let results = subset(results, &params.field_set, &uri);
+        let results = prepare_response(results, &state, &requester, &headers, &exts_list)?;
+        results.response()
+    } else {
+        super::response::NOT_IMPLEMENTED.response()
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RelatedNetworksParams {
+    #[serde(flatten)]
+    sort_page: SortPageParams,
+
+    #[serde(flatten)]
+    field_set: FieldSetParams,
+}
+
+/// Gets the networks related to a network by an `rdap-up`, `rdap-down`,
+/// `rdap-top` or `rdap-bottom` relation of the RIR search extension.
+#[axum_macros::debug_handler]
+#[tracing::instrument(level = "debug")]
+pub(crate) async fn related_networks(
+    Path((relation, netid)): Path<(String, String)>,
+    Query(params): Query<RelatedNetworksParams>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    requester: Requester,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    let Ok(relation) = NetworkRelation::from_str(&relation) else {
+        return Ok(BAD_REQUEST.response());
+    };
+    let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
+    debug!("exts_list = \'{}\'", exts_list.join(" "));
+
+    let storage = state.get_storage().await?;
+    let results = storage.get_related_networks(&netid, relation).await?;
+    let results = add_hierarchy_links(results, &uri, storage).await?;
+    let results = sort_and_page(results, &params.sort_page, &state.get_search_config(), &uri);
+    let results = subset(results, &params.field_set, &uri);
+    let results = prepare_response(results, &state, &requester, &headers, &exts_list)?;
+    Ok(results.response())
+}
@@ -1,21 +1,35 @@
-use icann_rdap_common::{
-    prelude::{ExtensionId, ToResponse},
-    response::{jscontact::JsContactConvert, RdapResponse, Rfc9083Error},
+use {
+    http::HeaderMap,
+    icann_rdap_common::{
+        prelude::{normalize_extensions, ExtensionId, ToResponse},
+        response::{jscontact::JsContactConvert, RdapResponse, Rfc9083Error},
+    },
 };
 
-use crate::config::JsContactConversion;
+use crate::{
+    auth::{truncate_contacts, Requester},
+    config::JsContactConversion,
+    error::RdapServerError,
+    server::DynServiceState,
+};
 
 pub mod autnum;
+pub mod autnums;
 pub mod domain;
 pub mod domains;
 pub mod entities;
 pub mod entity;
 pub mod ip;
+pub mod ips;
 pub mod nameserver;
 pub mod nameservers;
+pub mod paging;
+pub mod redaction;
 pub mod response;
+pub mod reverse_search;
 pub mod router;
 pub mod srvhelp;
+pub mod subsetting;
 
 trait ToBootStrap {
     fn to_ip_bootstrap(self, ip_id: &str) -> RdapResponse;
@@ -101,6 +115,22 @@ fn parse_extensions(accept_header: &str) -> Vec<String> {
         .collect::<Vec<String>>()
 }
 
+/// Prepares a response for the client: redacts it according to the redaction policy,
+/// truncates its contacts, converts its contacts to JSContact if asked to, and
+/// normalizes its extensions.
+fn prepare_response(
+    rdap: RdapResponse,
+    state: &DynServiceState,
+    requester: &Requester,
+    headers: &HeaderMap,
+    exts_list: &[String],
+) -> Result<RdapResponse, RdapServerError> {
+    let rdap = redaction::redact(rdap, state.get_redaction_policy(), requester, headers)?;
+    let rdap = truncate_contacts(rdap, requester)?;
+    let rdap = jscontact_conversion(rdap, state.get_jscontact_conversion(), exts_list);
+    Ok(normalize_extensions(rdap))
+}
+
 fn jscontact_conversion(
     rdap: RdapResponse,
     conversion: JsContactConversion,
@@ -1,5 +1,4 @@
 use http::HeaderMap;
-use icann_rdap_common::prelude::normalize_extensions;
 use tracing::debug;
 
 use {
@@ -11,8 +10,9 @@ use {
 };
 
 use crate::{
+    auth::Requester,
     error::RdapServerError,
-    rdap::{jscontact_conversion, parse_extensions, response::ResponseUtil},
+    rdap::{parse_extensions, prepare_response, response::ResponseUtil},
     server::DynServiceState,
 };
 
@@ -24,6 +24,7 @@ use super::{response::BAD_REQUEST, ToBootStrap};
 pub(crate) async fn nameserver_by_name(
     Path(ns_name): Path<String>,
     headers: HeaderMap,
+    requester: Requester,
     state: State<DynServiceState>,
 ) -> Result<Response, RdapServerError> {
     let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
@@ -54,7 +55,6 @@ pub(crate) async fn nameserver_by_name(
         }
     }
 
-    let nameserver = jscontact_conversion(nameserver, state.get_jscontact_conversion(), &exts_list);
-    let nameserver = normalize_extensions(nameserver);
+    let nameserver = prepare_response(nameserver, &state, &requester, &headers, &exts_list)?;
     Ok(nameserver.response())
 }
@@ -1,19 +1,21 @@
 use http::HeaderMap;
-use icann_rdap_common::prelude::normalize_extensions;
 use serde::Deserialize;
 use std::net::IpAddr;
 use tracing::debug;
 
 use axum::{
-    extract::{Query, State},
+    extract::{OriginalUri, Query, State},
     response::Response,
 };
 
 use crate::{
+    auth::Requester,
     error::RdapServerError,
     rdap::{
-        jscontact_conversion, parse_extensions,
+        paging::{sort_and_page, SortPageParams},
+        parse_extensions, prepare_response,
         response::{ResponseUtil, BAD_REQUEST},
+        subsetting::{subset, FieldSetParams},
     },
     server::DynServiceState,
 };
@@ -22,13 +24,21 @@ use crate::{
 pub(crate) struct NameserversParams {
     name: Option<String>,
     ip: Option<String>,
+
+    #[serde(flatten)]
+    sort_page: SortPageParams,
+
+    #[serde(flatten)]
+    field_set: FieldSetParams,
 }
 
 #[axum_macros::debug_handler]
 #[tracing::instrument(level = "debug")]
 pub(crate) async fn nameservers(
     Query(params): Query<NameserversParams>,
+    OriginalUri(uri): OriginalUri,
     headers: HeaderMap,
+    requester: Requester,
     state: State<DynServiceState>,
 ) -> Result<Response, RdapServerError> {
     Ok(if let Some(name) = params.name {
@@ -37,8 +47,9 @@ pub(crate) async fn nameservers(
 
         let storage = state.get_storage().await?;
         let results = storage.search_nameservers_by_name(&name).await?;
-        let results = jscontact_conversion(results, state.get_jscontact_conversion(), &exts_list);
-        let results = normalize_extensions(results);
+        let results = sort_and_page(results, &params.sort_page, &state.get_search_config(), &uri);
+        let results = subset(results, &params.field_set, &uri);
+        let results = prepare_response(results, &state, &requester, &headers, &exts_list)?;
         results.response()
     } else if let Some(ip_str) = params.ip {
         let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
@@ -51,8 +62,9 @@ pub(crate) async fn nameservers(
 
         let storage = state.get_storage().await?;
         let results = storage.search_nameservers_by_ip(ip).await?;
-        let results = jscontact_conversion(results, state.get_jscontact_conversion(), &exts_list);
-        let results = normalize_extensions(results);
+        let results = sort_and_page(results, &params.sort_page, &state.get_search_config(), &uri);
+        let results = subset(results, &params.field_set, &uri);
+        let results = prepare_response(results, &state, &requester, &headers, &exts_list)?;
         results.response()
     } else {
         super::response::NOT_IMPLEMENTED.response()
@@ -0,0 +1,727 @@
+//! Sorting and paging of search results, as described by RFC 8977.
+
+use std::cmp::Ordering;
+
+use {
+    base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine},
+    http::Uri,
+    icann_rdap_common::{
+        media_types::RDAP_MEDIA_TYPE,
+        prelude::{
+            Autnum, AutnumSearchResults, AvailableSort, Common, Domain, DomainSearchResults,
+            Entity, EntitySearchResults, Link, Nameserver, NameserverSearchResults, Network,
+            NetworkSearchResults, Notice, NrType, ObjectCommonFields, PagingMetadata, RdapResponse,
+            SortingMetadata, ToResponse,
+        },
+    },
+    serde::Deserialize,
+};
+
+use crate::config::SearchConfig;
+
+use super::response::BAD_REQUEST;
+
+/// The RFC 8977 query parameters of a search.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct SortPageParams {
+    sort: Option<String>,
+
+    /// `true` if the client asks for the total number of results.
+    count: Option<String>,
+    cursor: Option<String>,
+}
+
+/// Parses the `count` parameter, which is a boolean.
+fn parse_count(count: &str) -> Option<bool> {
+    if count.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if count.eq_ignore_ascii_case("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Sort properties derived from events, which are common to all object classes.
+const EVENT_SORTS: [(&str, &str); 9] = [
+    ("registrationDate", "registration"),
+    ("reregistrationDate", "reregistration"),
+    ("lastChangedDate", "last changed"),
+    ("expirationDate", "expiration"),
+    ("deletionDate", "deletion"),
+    ("reinstantiationDate", "reinstantiation"),
+    ("transferDate", "transfer"),
+    ("lockedDate", "locked"),
+    ("unlockedDate", "unlocked"),
+];
+
+/// An object found in search results that can be sorted.
+trait SortableResult: ObjectCommonFields + Clone {
+    /// The name of the search results array.
+    const RESULTS_MEMBER: &'static str;
+
+    /// Sort properties particular to the object class and their JSONPath
+    /// relative to the object. The first is the default sort.
+    const PROPERTIES: &'static [(&'static str, &'static str)];
+
+    /// Gets the value of a property in [Self::PROPERTIES].
+    fn property_value(&self, property: &str) -> Option<String>;
+
+    /// Gets the value of a sort property, if the object has it.
+    fn sort_value(&self, property: &str) -> Option<String> {
+        if let Some((_, action)) = EVENT_SORTS.iter().find(|(name, _)| *name == property) {
+            self.events()
+                .iter()
+                .find(|event| event.event_action() == Some(action))
+                .and_then(|event| event.event_date())
+                .map(str::to_string)
+        } else {
+            self.property_value(property)
+        }
+    }
+}
+
+impl SortableResult for Domain {
+    const RESULTS_MEMBER: &'static str = "domainSearchResults";
+    const PROPERTIES: &'static [(&'static str, &'static str)] = &[("name", "ldhName")];
+
+    fn property_value(&self, property: &str) -> Option<String> {
+        match property {
+            "name" => self.ldh_name().map(str::to_ascii_lowercase),
+            _ => None,
+        }
+    }
+}
+
+impl SortableResult for Nameserver {
+    const RESULTS_MEMBER: &'static str = "nameserverSearchResults";
+    const PROPERTIES: &'static [(&'static str, &'static str)] = &[("name", "ldhName")];
+
+    fn property_value(&self, property: &str) -> Option<String> {
+        match property {
+            "name" => self.ldh_name().map(str::to_ascii_lowercase),
+            _ => None,
+        }
+    }
+}
+
+impl SortableResult for Entity {
+    const RESULTS_MEMBER: &'static str = "entitySearchResults";
+    const PROPERTIES: &'static [(&'static str, &'static str)] = &[
+        ("handle", "handle"),
+        ("fn", "vcardArray[1][?(@[0]=='fn')][3]"),
+    ];
+
+    fn property_value(&self, property: &str) -> Option<String> {
+        match property {
+            "handle" => self.handle().map(str::to_string),
+            "fn" => self
+                .contact()
+                .and_then(|contact| contact.full_name().map(str::to_string)),
+            _ => None,
+        }
+    }
+}
+
+impl SortableResult for Network {
+    const RESULTS_MEMBER: &'static str = "ipSearchResults";
+    const PROPERTIES: &'static [(&'static str, &'static str)] =
+        &[("handle", "handle"), ("name", "name")];
+
+    fn property_value(&self, property: &str) -> Option<String> {
+        match property {
+            "handle" => self.handle().map(str::to_string),
+            "name" => self.name().map(str::to_string),
+            _ => None,
+        }
+    }
+}
+
+impl SortableResult for Autnum {
+    const RESULTS_MEMBER: &'static str = "autnumSearchResults";
+    const PROPERTIES: &'static [(&'static str, &'static str)] =
+        &[("handle", "handle"), ("name", "name")];
+
+    fn property_value(&self, property: &str) -> Option<String> {
+        match property {
+            "handle" => self.handle().map(str::to_string),
+            "name" => self.name().map(str::to_string),
+            _ => None,
+        }
+    }
+}
+
+/// The properties to sort by, each one ascending (`true`) or descending (`false`).
+type SortKeys = Vec<(String, bool)>;
+
+/// Parses the `sort` parameter, such as `name:d,registrationDate`.
+fn parse_sort<T: SortableResult>(sort: &str) -> Option<SortKeys> {
+    sort.split(',')
+        .map(|key| {
+            let (property, ascending) = match key.split_once(':') {
+                Some((property, "a")) => (property, true),
+                Some((property, "d")) => (property, false),
+                Some(_) => return None,
+                None => (key, true),
+            };
+            is_sort_property::<T>(property).then(|| (property.to_string(), ascending))
+        })
+        .collect()
+}
+
+fn is_sort_property<T: SortableResult>(property: &str) -> bool {
+    T::PROPERTIES.iter().any(|(name, _)| *name == property)
+        || EVENT_SORTS.iter().any(|(name, _)| *name == property)
+}
+
+/// Sorts the results. Objects lacking a sort value are placed last.
+fn sort_results<T: SortableResult>(results: &mut [T], keys: &SortKeys) {
+    results.sort_by(|a, b| {
+        for (property, ascending) in keys {
+            let ordering = match (a.sort_value(property), b.sort_value(property)) {
+                (Some(a), Some(b)) if *ascending => a.cmp(&b),
+                (Some(a), Some(b)) => b.cmp(&a),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            };
+            if ordering.is_ne() {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    });
+}
+
+fn encode_cursor(offset: usize) -> String {
+    URL_SAFE_NO_PAD.encode(offset.to_string())
+}
+
+fn decode_cursor(cursor: &str) -> Option<usize> {
+    let decoded = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+    String::from_utf8(decoded).ok()?.parse().ok()
+}
+
+/// Creates a link to the request with the given query parameters replaced.
+pub(super) fn request_link(uri: &Uri, rel: &str, replace: &[&str], add: &[(&str, &str)]) -> Link {
+    let mut query = uri
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter(|pair| {
+            let key = pair.split_once('=').map_or(*pair, |(key, _)| key);
+            !replace.contains(&key)
+        })
+        .map(str::to_string)
+        .collect::<Vec<String>>();
+    query.extend(add.iter().map(|(key, value)| format!("{key}={value}")));
+    Link::builder()
+        .value(uri.to_string())
+        .rel(rel)
+        .href(format!("{}?{}", uri.path(), query.join("&")))
+        .media_type(RDAP_MEDIA_TYPE)
+        .build()
+}
+
+fn truncated_notice() -> Notice {
+    Notice::builder()
+        .title("Result Set Truncated")
+        .description_entry("The search results have been truncated due to excessive load.")
+        .nr_type(NrType::ResultSetTruncatedDueToExcessiveLoad.to_string())
+        .build()
+}
+
+/// The results of sorting and paging.
+struct SortedPage<T> {
+    results: Vec<T>,
+    notices: Vec<Notice>,
+    paging_metadata: Option<PagingMetadata>,
+    sorting_metadata: Option<SortingMetadata>,
+}
+
+fn sort_and_page_results<T: SortableResult>(
+    mut results: Vec<T>,
+    params: &SortPageParams,
+    search_config: &SearchConfig,
+    uri: &Uri,
+) -> Option<SortedPage<T>> {
+    // sort
+    let sorting_metadata = if let Some(sort) = &params.sort {
+        let keys = parse_sort::<T>(sort)?;
+        sort_results(&mut results, &keys);
+        let available_sorts = T::PROPERTIES
+            .iter()
+            .map(|(name, path)| (*name, format!("$.{}[*].{path}", T::RESULTS_MEMBER)))
+            .chain(EVENT_SORTS.iter().map(|(name, action)| {
+                let path = format!(
+                    "$.{}[*].events[?(@.eventAction=='{action}')].eventDate",
+                    T::RESULTS_MEMBER
+                );
+                (*name, path)
+            }))
+            .enumerate()
+            .map(|(i, (name, path))| {
+                AvailableSort::builder()
+                    .property(name)
+                    .json_path(path)
+                    .default(i == 0)
+                    .link(request_link(
+                        uri,
+                        "alternate",
+                        &["sort", "cursor"],
+                        &[("sort", name)],
+                    ))
+                    .build()
+            })
+            .collect::<Vec<AvailableSort>>();
+        Some(
+            SortingMetadata::builder()
+                .current_sort(sort)
+                .available_sorts(available_sorts)
+                .build(),
+        )
+    } else {
+        None
+    };
+
+    // truncate
+    let mut notices = vec![];
+    if let Some(max_results) = search_config.max_results {
+        if results.len() > max_results {
+            results.truncate(max_results);
+            notices.push(truncated_notice());
+        }
+    }
+
+    // page
+    let count = match &params.count {
+        Some(count) => parse_count(count)?,
+        None => false,
+    };
+    let total_count = results.len();
+    let paging_metadata = if let Some(page_size) = search_config.page_size.filter(|size| *size > 0)
+    {
+        let offset = match &params.cursor {
+            Some(cursor) => decode_cursor(cursor).filter(|offset| *offset < total_count)?,
+            None => 0,
+        };
+        results = results.into_iter().skip(offset).take(page_size).collect();
+        let mut links = vec![];
+        if let Some(next) = offset
+            .checked_add(page_size)
+            .filter(|next| *next < total_count)
+        {
+            let cursor = encode_cursor(next);
+            links.push(request_link(
+                uri,
+                "next",
+                &["cursor"],
+                &[("cursor", &cursor)],
+            ));
+        }
+        if offset > 0 {
+            let prev = offset.saturating_sub(page_size);
+            let cursor = encode_cursor(prev);
+            let add = if prev > 0 {
+                vec![("cursor", cursor.as_str())]
+            } else {
+                vec![]
+            };
+            links.push(request_link(uri, "prev", &["cursor"], &add));
+        }
+        Some(
+            PagingMetadata::builder()
+                .and_total_count(count.then_some(total_count as u64))
+                .page_size(page_size as u64)
+                .page_number((offset / page_size).saturating_add(1) as u64)
+                .links(links)
+                .build(),
+        )
+    } else if count {
+        Some(
+            PagingMetadata::builder()
+                .total_count(total_count as u64)
+                .build(),
+        )
+    } else {
+        None
+    };
+
+    Some(SortedPage {
+        results,
+        notices,
+        paging_metadata,
+        sorting_metadata,
+    })
+}
+
+fn add_notices(common: Common, mut notices: Vec<Notice>) -> Common {
+    if notices.is_empty() {
+        return common;
+    }
+    let mut all_notices = common.notices.unwrap_or_default();
+    all_notices.append(&mut notices);
+    Common {
+        notices: Some(all_notices),
+        ..common
+    }
+}
+
+/// Sorts, truncates and pages search results according to the request
+/// parameters and the server configuration. Responses that are not search
+/// results are returned as is. If the parameters are invalid, a bad request
+/// error is returned.
+pub(crate) fn sort_and_page(
+    rdap: RdapResponse,
+    params: &SortPageParams,
+    search_config: &SearchConfig,
+    uri: &Uri,
+) -> RdapResponse {
+    match rdap {
+        RdapResponse::DomainSearchResults(r) => {
+            let Some(page) = sort_and_page_results(r.results, params, search_config, uri) else {
+                return BAD_REQUEST.clone();
+            };
+            DomainSearchResults {
+                common: add_notices(r.common, page.notices),
+                results: page.results,
+                paging_metadata: page.paging_metadata,
+                sorting_metadata: page.sorting_metadata,
+                subsetting_metadata: r.subsetting_metadata,
+            }
+            .to_response()
+        }
+        RdapResponse::NameserverSearchResults(r) => {
+            let Some(page) = sort_and_page_results(r.results, params, search_config, uri) else {
+                return BAD_REQUEST.clone();
+            };
+            NameserverSearchResults {
+                common: add_notices(r.common, page.notices),
+                results: page.results,
+                paging_metadata: page.paging_metadata,
+                sorting_metadata: page.sorting_metadata,
+                subsetting_metadata: r.subsetting_metadata,
+            }
+            .to_response()
+        }
+        RdapResponse::EntitySearchResults(r) => {
+            let Some(page) = sort_and_page_results(r.results, params, search_config, uri) else {
+                return BAD_REQUEST.clone();
+            };
+            EntitySearchResults {
+                common: add_notices(r.common, page.notices),
+                results: page.results,
+                paging_metadata: page.paging_metadata,
+                sorting_metadata: page.sorting_metadata,
+                subsetting_metadata: r.subsetting_metadata,
+            }
+            .to_response()
+        }
+        RdapResponse::NetworkSearchResults(r) => {
+            let Some(page) = sort_and_page_results(r.results, params, search_config, uri) else {
+                return BAD_REQUEST.clone();
+            };
+            NetworkSearchResults {
+                common: add_notices(r.common, page.notices),
+                results: page.results,
+                paging_metadata: page.paging_metadata,
+                sorting_metadata: page.sorting_metadata,
+                subsetting_metadata: r.subsetting_metadata,
+            }
+            .to_response()
+        }
+        RdapResponse::AutnumSearchResults(r) => {
+            let Some(page) = sort_and_page_results(r.results, params, search_config, uri) else {
+                return BAD_REQUEST.clone();
+            };
+            AutnumSearchResults {
+                common: add_notices(r.common, page.notices),
+                results: page.results,
+                paging_metadata: page.paging_metadata,
+                sorting_metadata: page.sorting_metadata,
+                subsetting_metadata: r.subsetting_metadata,
+            }
+            .to_response()
+        }
+        _ => rdap,
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use {
+        http::Uri,
+        icann_rdap_common::prelude::{Domain, Event},
+    };
+
+    use crate::config::SearchConfig;
+
+    use super::{decode_cursor, encode_cursor, sort_and_page_results, SortPageParams};
+
+    fn domains() -> Vec<Domain> {
+        ["b.example", "c.example", "a.example"]
+            .into_iter()
+            .zip(["2020", "2010", "2030"])
+            .map(|(name, year)| {
+                Domain::builder()
+                    .ldh_name(name)
+                    .event(
+                        Event::builder()
+                            .event_action("registration")
+                            .event_date(format!("{year}-01-01T00:00:00Z"))
+                            .build(),
+                    )
+                    .build()
+            })
+            .collect()
+    }
+
+    fn names(domains: &[Domain]) -> Vec<&str> {
+        domains.iter().filter_map(|d| d.ldh_name()).collect()
+    }
+
+    fn uri() -> Uri {
+        "/rdap/domains?name=*.example&sort=name&count=true"
+            .parse()
+            .expect("parsing uri")
+    }
+
+    #[test]
+    fn GIVEN_cursor_WHEN_decode_THEN_offset_returned() {
+        // GIVEN
+        let cursor = encode_cursor(20);
+
+        // WHEN
+        let actual = decode_cursor(&cursor);
+
+        // THEN
+        assert_eq!(actual, Some(20));
+        assert_eq!(decode_cursor("not a cursor"), None);
+    }
+
+    #[test]
+    fn GIVEN_sort_by_name_descending_WHEN_sort_and_page_THEN_results_sorted() {
+        // GIVEN
+        let params = SortPageParams {
+            sort: Some("name:d".to_string()),
+            ..Default::default()
+        };
+
+        // WHEN
+        let actual = sort_and_page_results(domains(), &params, &SearchConfig::default(), &uri())
+            .expect("valid params");
+
+        // THEN
+        assert_eq!(
+            names(&actual.results),
+            ["c.example", "b.example", "a.example"]
+        );
+        let sorting_metadata = actual.sorting_metadata.expect("sorting metadata");
+        assert_eq!(sorting_metadata.current_sort(), Some("name:d"));
+        assert!(sorting_metadata.available_sorts()[0].is_default());
+        assert!(actual.paging_metadata.is_none());
+    }
+
+    #[test]
+    fn GIVEN_sort_by_registration_date_WHEN_sort_and_page_THEN_results_sorted() {
+        // GIVEN
+        let params = SortPageParams {
+            sort: Some("registrationDate".to_string()),
+            ..Default::default()
+        };
+
+        // WHEN
+        let actual = sort_and_page_results(domains(), &params, &SearchConfig::default(), &uri())
+            .expect("valid params");
+
+        // THEN
+        assert_eq!(
+            names(&actual.results),
+            ["c.example", "b.example", "a.example"]
+        );
+    }
+
+    #[test]
+    fn GIVEN_unknown_sort_property_WHEN_sort_and_page_THEN_none() {
+        // GIVEN
+        let params = SortPageParams {
+            sort: Some("color".to_string()),
+            ..Default::default()
+        };
+
+        // WHEN
+        let actual = sort_and_page_results(domains(), &params, &SearchConfig::default(), &uri());
+
+        // THEN
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn GIVEN_page_size_and_count_WHEN_sort_and_page_THEN_first_page_with_next_link() {
+        // GIVEN
+        let params = SortPageParams {
+            sort: Some("name".to_string()),
+            count: Some("true".to_string()),
+            ..Default::default()
+        };
+        let search_config = SearchConfig::builder().page_size(2).build();
+
+        // WHEN
+        let actual = sort_and_page_results(domains(), &params, &search_config, &uri())
+            .expect("valid params");
+
+        // THEN
+        assert_eq!(names(&actual.results), ["a.example", "b.example"]);
+        let paging_metadata = actual.paging_metadata.expect("paging metadata");
+        assert_eq!(paging_metadata.total_count(), Some(3));
+        assert_eq!(paging_metadata.page_size(), Some(2));
+        assert_eq!(paging_metadata.page_number(), Some(1));
+        assert!(paging_metadata.prev_link().is_none());
+        let next = paging_metadata.next_link().expect("next link");
+        assert_eq!(
+            next.href(),
+            Some(
+                format!(
+                    "/rdap/domains?name=*.example&sort=name&count=true&cursor={}",
+                    encode_cursor(2)
+                )
+                .as_str()
+            )
+        );
+    }
+
+    #[test]
+    fn GIVEN_page_size_without_count_WHEN_sort_and_page_THEN_no_total_count() {
+        // GIVEN
+        let search_config = SearchConfig::builder().page_size(2).build();
+
+        // WHEN
+        let actual = sort_and_page_results(
+            domains(),
+            &SortPageParams::default(),
+            &search_config,
+            &uri(),
+        )
+        .expect("valid params");
+
+        // THEN
+        assert_eq!(actual.results.len(), 2);
+        let paging_metadata = actual.paging_metadata.expect("paging metadata");
+        assert_eq!(paging_metadata.total_count(), None);
+        assert_eq!(paging_metadata.page_size(), Some(2));
+    }
+
+    #[test]
+    fn GIVEN_count_without_page_size_WHEN_sort_and_page_THEN_all_results_with_total_count() {
+        // GIVEN
+        let params = SortPageParams {
+            count: Some("true".to_string()),
+            ..Default::default()
+        };
+
+        // WHEN
+        let actual = sort_and_page_results(domains(), &params, &SearchConfig::default(), &uri())
+            .expect("valid params");
+
+        // THEN
+        assert_eq!(actual.results.len(), 3);
+        let paging_metadata = actual.paging_metadata.expect("paging metadata");
+        assert_eq!(paging_metadata.total_count(), Some(3));
+        assert_eq!(paging_metadata.page_size(), None);
+        assert!(paging_metadata.links().is_empty());
+    }
+
+    #[test]
+    fn GIVEN_cursor_WHEN_sort_and_page_THEN_last_page_with_prev_link() {
+        // GIVEN
+        let params = SortPageParams {
+            sort: Some("name".to_string()),
+            count: None,
+            cursor: Some(encode_cursor(2)),
+        };
+        let search_config = SearchConfig::builder().page_size(2).build();
+
+        // WHEN
+        let actual = sort_and_page_results(domains(), &params, &search_config, &uri())
+            .expect("valid params");
+
+        // THEN
+        assert_eq!(names(&actual.results), ["c.example"]);
+        let paging_metadata = actual.paging_metadata.expect("paging metadata");
+        assert_eq!(paging_metadata.page_number(), Some(2));
+        assert!(paging_metadata.next_link().is_none());
+        assert!(paging_metadata.prev_link().is_some());
+    }
+
+    #[test]
+    fn GIVEN_huge_cursor_WHEN_sort_and_page_THEN_none() {
+        // GIVEN
+        let params = SortPageParams {
+            cursor: Some(encode_cursor(usize::MAX)),
+            ..Default::default()
+        };
+        let search_config = SearchConfig::builder().page_size(2).build();
+
+        // WHEN
+        let actual = sort_and_page_results(domains(), &params, &search_config, &uri());
+
+        // THEN
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn GIVEN_cursor_past_results_WHEN_sort_and_page_THEN_none() {
+        // GIVEN
+        let params = SortPageParams {
+            cursor: Some(encode_cursor(3)),
+            ..Default::default()
+        };
+        let search_config = SearchConfig::builder().page_size(2).build();
+
+        // WHEN
+        let actual = sort_and_page_results(domains(), &params, &search_config, &uri());
+
+        // THEN
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn GIVEN_max_results_WHEN_sort_and_page_THEN_results_truncated_with_notice() {
+        // GIVEN
+        let search_config = SearchConfig::builder().max_results(2).build();
+
+        // WHEN
+        let actual = sort_and_page_results(
+            domains(),
+            &SortPageParams::default(),
+            &search_config,
+            &uri(),
+        )
+        .expect("valid params");
+
+        // THEN
+        assert_eq!(actual.results.len(), 2);
+        assert_eq!(actual.notices.len(), 1);
+        assert_eq!(
+            actual.notices[0].nr_type.as_deref(),
+            Some("result set truncated due to excessive load")
+        );
+    }
+
+    #[test]
+    fn GIVEN_count_not_boolean_WHEN_sort_and_page_THEN_none() {
+        // GIVEN
+        let params = SortPageParams {
+            count: Some("2".to_string()),
+            ..Default::default()
+        };
+
+        // WHEN
+        let actual = sort_and_page_results(domains(), &params, &SearchConfig::default(), &uri());
+
+        // THEN
+        assert!(actual.is_none());
+    }
+}
@@ -0,0 +1,694 @@
+//! Redaction of responses according to a policy, as described by RFC 9537.
+
+use std::{fs, path::Path, str::FromStr};
+
+use {
+    http::{header::AUTHORIZATION, HeaderMap},
+    icann_rdap_client::rdap::redacted::{
+        paths::{matched_pointers, registered_path},
+        RedactedName,
+    },
+    icann_rdap_common::response::{
+        redacted::{Method, Name, Reason, Redacted},
+        RdapResponse,
+    },
+    ipnet::IpNet,
+    jsonpath_rust::parser::{model::JpQuery, parse_json_path},
+    serde::Deserialize,
+    serde_json::Value,
+    tracing::debug,
+};
+
+use crate::{
+    auth::{AuthConfig, Requester},
+    error::RdapServerError,
+};
+
+/// The members of search results holding the array of results.
+const SEARCH_RESULTS: [&str; 5] = [
+    "domainSearchResults",
+    "entitySearchResults",
+    "nameserverSearchResults",
+    "ipSearchResults",
+    "autnumSearchResults",
+];
+
+/// Put in place of removed values until they are swept out.
+const REMOVED: &str = "\u{0}removed\u{0}";
+
+/// A redaction policy, which is read from a JSON file.
+///
+/// The first profile applying to a client determines the redactions of the
+/// responses to that client. Clients to which no profile applies get responses
+/// without redactions.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RedactionPolicy {
+    pub profiles: Vec<RedactionProfile>,
+}
+
+/// The redactions for a set of clients.
+///
+/// A profile applies to clients with addresses in any of the client networks,
+/// giving any of the `Authorization` header values, or authenticated with any of
+/// the tiers. A profile with none of these applies to all clients. Authorizations
+/// cannot be used when clients are authenticated, as the server refuses
+/// `Authorization` headers not of a user; tiers are used instead.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RedactionProfile {
+    #[serde(default)]
+    pub client_networks: Vec<IpNet>,
+
+    #[serde(default)]
+    pub authorizations: Vec<String>,
+
+    #[serde(default)]
+    pub tiers: Vec<String>,
+
+    #[serde(default)]
+    pub redactions: Vec<RedactionRule>,
+}
+
+/// A redaction to be done.
+///
+/// The name is either registered in the IANA RDAP JSON Values registry or describes
+/// the redaction. Registered names do not need a path, in which case the path of the
+/// registration is used.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RedactionRule {
+    pub name: String,
+
+    /// A JSONPath to the redacted values.
+    pub path: Option<String>,
+
+    pub method: Method,
+
+    /// The value given to redacted values when the method is `replacementValue`.
+    pub replacement: Option<String>,
+
+    /// The reason given for the redaction.
+    pub reason: Option<String>,
+
+    #[serde(skip)]
+    query: Option<JpQuery>,
+}
+
+impl RedactionPolicy {
+    /// Reads and validates a policy file.
+    pub fn from_file(path: &Path) -> Result<Self, RdapServerError> {
+        let json = fs::read_to_string(path)?;
+        let policy: Self = serde_json::from_str(&json).map_err(|e| {
+            RdapServerError::Config(format!(
+                "redaction policy {} is invalid: {e}",
+                path.display()
+            ))
+        })?;
+        policy.validated()
+    }
+
+    /// Checks the redactions of the policy can be done, and parses their paths.
+    pub fn validated(mut self) -> Result<Self, RdapServerError> {
+        for rule in self
+            .profiles
+            .iter_mut()
+            .flat_map(|p| p.redactions.iter_mut())
+        {
+            let path = match (&rule.path, RedactedName::from_str(&rule.name)) {
+                (Some(path), _) => path.as_str(),
+                (None, Ok(registered)) => registered_path(registered),
+                (None, Err(_)) => {
+                    return Err(RdapServerError::Config(format!(
+                        "redaction '{}' is not registered and must have a path",
+                        rule.name
+                    )))
+                }
+            };
+            let query = parse_json_path(path).map_err(|e| {
+                RdapServerError::Config(format!("redaction '{}' path is invalid: {e}", rule.name))
+            })?;
+            match rule.method {
+                Method::PartialValue => {
+                    return Err(RdapServerError::Config(format!(
+                        "redaction '{}' cannot use partialValue",
+                        rule.name
+                    )))
+                }
+                Method::ReplacementValue if rule.replacement.is_none() => {
+                    return Err(RdapServerError::Config(format!(
+                        "redaction '{}' must have a replacement",
+                        rule.name
+                    )))
+                }
+                _ => {}
+            }
+            rule.path = Some(path.to_string());
+            rule.query = Some(query);
+        }
+        Ok(self)
+    }
+
+    /// Checks the policy can be used with the authentication of the server.
+    pub fn checked_with_auth(self, auth: Option<&AuthConfig>) -> Result<Self, RdapServerError> {
+        if auth.is_some() && self.profiles.iter().any(|p| !p.authorizations.is_empty()) {
+            return Err(RdapServerError::Config(
+                "redaction profiles cannot have authorizations when clients are authenticated, use tiers instead".to_string(),
+            ));
+        }
+        Ok(self)
+    }
+
+    /// Gets the profile applying to a client.
+    fn profile(&self, requester: &Requester, headers: &HeaderMap) -> Option<&RedactionProfile> {
+        let authorization = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok());
+        let tier = requester
+            .identity()
+            .and_then(|identity| identity.tier.as_deref());
+        self.profiles.iter().find(|profile| {
+            (profile.client_networks.is_empty()
+                && profile.authorizations.is_empty()
+                && profile.tiers.is_empty())
+                || profile
+                    .client_networks
+                    .iter()
+                    .any(|net| net.contains(&requester.ip))
+                || authorization.is_some_and(|authorization| {
+                    profile.authorizations.iter().any(|a| a == authorization)
+                })
+                || tier.is_some_and(|tier| profile.tiers.iter().any(|t| t == tier))
+        })
+    }
+}
+
+/// Redacts a response according to the profile of the policy applying to the
+/// client, adding the `redacted` arrays for the redactions done. The `rdapConformance`
+/// is left to extension normalization. Errors and responses without redactions are
+/// returned as is.
+pub(crate) fn redact(
+    rdap: RdapResponse,
+    policy: Option<&RedactionPolicy>,
+    requester: &Requester,
+    headers: &HeaderMap,
+) -> Result<RdapResponse, RdapServerError> {
+    let Some(profile) = policy.and_then(|policy| policy.profile(requester, headers)) else {
+        return Ok(rdap);
+    };
+    if profile.redactions.is_empty() || matches!(rdap, RdapResponse::ErrorResponse(_)) {
+        return Ok(rdap);
+    }
+    let mut json = serde_json::to_value(&rdap)?;
+
+    // the redactions of search results are done to each result
+    let mut redacted = false;
+    let mut searched = false;
+    for member in SEARCH_RESULTS {
+        if let Some(Value::Array(results)) = json.get_mut(member) {
+            searched = true;
+            for result in results {
+                redacted |= redact_object(result, &profile.redactions)?;
+            }
+        }
+    }
+    if !searched {
+        redacted = redact_object(&mut json, &profile.redactions)?;
+    }
+    if !redacted {
+        return Ok(rdap);
+    }
+    debug!("redacted response for client {}", requester.ip);
+    Ok(serde_json::from_value(json)?)
+}
+
+/// Does the redactions found in an object, returning true if any were done.
+fn redact_object(object: &mut Value, rules: &[RedactionRule]) -> Result<bool, RdapServerError> {
+    let mut done = vec![];
+    for rule in rules {
+        let Some(query) = &rule.query else {
+            continue;
+        };
+        let pointers = matched_pointers(object, query);
+        if pointers.is_empty() {
+            continue;
+        }
+        for pointer in &pointers {
+            let Some(value) = object.pointer_mut(pointer) else {
+                continue;
+            };
+            match rule.method {
+                Method::Removal => *value = Value::String(REMOVED.to_string()),
+                Method::EmptyValue => replace_strings(value, ""),
+                _ => replace_strings(value, rule.replacement.as_deref().unwrap_or_default()),
+            }
+        }
+        if matches!(rule.method, Method::Removal) {
+            sweep(object);
+        }
+        done.push(rule.to_redacted());
+    }
+    if done.is_empty() {
+        return Ok(false);
+    }
+    let Value::Object(members) = object else {
+        return Ok(false);
+    };
+    let redacted = members
+        .entry("redacted")
+        .or_insert_with(|| Value::Array(vec![]));
+    if let Value::Array(redacted) = redacted {
+        for entry in done {
+            redacted.push(serde_json::to_value(entry)?);
+        }
+    }
+    Ok(true)
+}
+
+impl RedactionRule {
+    fn to_redacted(&self) -> Redacted {
+        let name = if RedactedName::from_str(&self.name).is_ok() {
+            Name::builder().type_field(self.name.clone()).build()
+        } else {
+            Name::builder().description(self.name.clone()).build()
+        };
+        let (pre_path, post_path) = if matches!(self.method, Method::Removal) {
+            (self.path.clone(), None)
+        } else {
+            (None, self.path.clone())
+        };
+        Redacted {
+            name,
+            reason: self
+                .reason
+                .as_ref()
+                .map(|reason| Reason::builder().description(reason.clone()).build()),
+            pre_path,
+            post_path,
+            path_lang: None,
+            replacement_path: None,
+            method: Some(self.method.clone()),
+        }
+    }
+}
+
+/// Replaces the strings of a value. Arrays of jCard properties only have
+/// the property values replaced.
+fn replace_strings(value: &mut Value, replacement: &str) {
+    match value {
+        Value::String(s) => *s = replacement.to_string(),
+        Value::Array(items) => {
+            let skip = if is_jcard_property(items) { 3 } else { 0 };
+            for item in items.iter_mut().skip(skip) {
+                replace_strings(item, replacement);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Removes the members and array items that were marked as removed. As a jCard
+/// property without its value is not valid, the property is removed. The components
+/// of structured jCard values, such as the city of an `adr`, are positional, so they
+/// are emptied instead.
+fn sweep(value: &mut Value) {
+    match value {
+        Value::Object(members) => {
+            members.retain(|_, member| !is_removed(member));
+            members.values_mut().for_each(sweep);
+        }
+        Value::Array(items) => {
+            items.retain(|item| {
+                !is_removed(item)
+                    && !(item
+                        .as_array()
+                        .is_some_and(|p| is_jcard_property(p) && p.iter().skip(3).any(is_removed)))
+            });
+            for item in items.iter_mut() {
+                match item.as_array_mut() {
+                    Some(property) if is_jcard_property(property) => {
+                        sweep(&mut property[1]);
+                        property.iter_mut().skip(3).for_each(empty_removed);
+                    }
+                    _ => sweep(item),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Empties the components of a structured jCard value that were marked as removed.
+fn empty_removed(value: &mut Value) {
+    match value {
+        Value::String(s) if s == REMOVED => s.clear(),
+        Value::Array(components) => components.iter_mut().for_each(empty_removed),
+        _ => {}
+    }
+}
+
+fn is_removed(value: &Value) -> bool {
+    value.as_str() == Some(REMOVED)
+}
+
+fn is_jcard_property(items: &[Value]) -> bool {
+    items.len() > 3 && items[0].is_string() && items[1].is_object() && items[2].is_string()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use {
+        http::{header::AUTHORIZATION, HeaderMap},
+        icann_rdap_common::{
+            prelude::{
+                normalize_extensions, Contact, Email, Entity, EntityRole, ObjectCommonFields,
+                PostalAddress, ToResponse,
+            },
+            response::{redacted::Method, Domain, DomainSearchResults, RdapResponse, Rfc9083Error},
+        },
+        serde_json::json,
+    };
+
+    use crate::auth::{Access, AuthConfig, Identity, Requester};
+
+    use super::{redact, RedactionPolicy};
+
+    fn policy(json: serde_json::Value) -> RedactionPolicy {
+        serde_json::from_value::<RedactionPolicy>(json)
+            .expect("parsing policy")
+            .validated()
+            .expect("valid policy")
+    }
+
+    fn public_policy() -> RedactionPolicy {
+        policy(json!({
+            "profiles": [
+                { "clientNetworks": ["10.0.0.0/8"] },
+                { "authorizations": ["Bearer privileged"] },
+                { "tiers": ["registry"] },
+                { "redactions": [
+                    { "name": "Registrant Email", "method": "removal", "reason": "Server policy" },
+                    { "name": "Registry Domain ID", "method": "replacementValue", "replacement": "REDACTED" },
+                    { "name": "Port 43", "path": "$.port43", "method": "emptyValue" }
+                ]}
+            ]
+        }))
+    }
+
+    fn domain() -> Domain {
+        let registrant = Entity::builder()
+            .handle("REG-1")
+            .role(EntityRole::Registrant.to_string())
+            .contact(
+                Contact::builder()
+                    .full_name("Alice")
+                    .email(Email::builder().email("alice@example.com").build())
+                    .build(),
+            )
+            .build();
+        Domain::builder()
+            .ldh_name("example.com")
+            .handle("EXAMPLE-1")
+            .port_43("whois.example.com")
+            .entity(registrant)
+            .build()
+    }
+
+    fn client(ip: &str, access: Option<Access>) -> Requester {
+        Requester {
+            ip: ip.parse().expect("ip"),
+            access,
+        }
+    }
+
+    fn public_client() -> Requester {
+        client("192.0.2.1", None)
+    }
+
+    #[test]
+    fn GIVEN_public_client_WHEN_redact_THEN_values_redacted() {
+        // GIVEN
+        let rdap = domain().to_response();
+
+        // WHEN
+        let actual = redact(
+            rdap,
+            Some(&public_policy()),
+            &public_client(),
+            &HeaderMap::new(),
+        )
+        .expect("redacting");
+
+        // THEN
+        let RdapResponse::Domain(domain) = normalize_extensions(actual) else {
+            panic!("not a domain")
+        };
+        assert_eq!(domain.handle(), Some("REDACTED"));
+        assert_eq!(domain.port_43().map(|p| p.as_str()), Some(""));
+        let contact = domain.entities()[0].contact().expect("contact");
+        assert!(contact.emails().is_empty());
+        assert_eq!(contact.full_name(), Some("Alice"));
+        let redacted = domain.object_common.redacted.as_ref().expect("redacted");
+        assert_eq!(redacted.len(), 3);
+        assert_eq!(redacted[0].name().type_field(), Some("Registrant Email"));
+        assert_eq!(redacted[0].method(), Some(&Method::Removal));
+        assert!(redacted[0].pre_path().is_some());
+        assert_eq!(
+            redacted[0].reason().and_then(|r| r.description()),
+            Some("Server policy")
+        );
+        assert_eq!(redacted[2].name().description(), Some("Port 43"));
+        assert_eq!(redacted[2].post_path(), Some("$.port43"));
+        assert!(domain
+            .common
+            .rdap_conformance
+            .as_ref()
+            .expect("conformance")
+            .iter()
+            .any(|e| e.0 == "redacted"));
+    }
+
+    #[test]
+    fn GIVEN_client_in_privileged_network_WHEN_redact_THEN_not_redacted() {
+        // GIVEN
+        let rdap = domain().to_response();
+
+        // WHEN
+        let actual = redact(
+            rdap.clone(),
+            Some(&public_policy()),
+            &client("10.1.2.3", None),
+            &HeaderMap::new(),
+        )
+        .expect("redacting");
+
+        // THEN
+        assert_eq!(actual, rdap);
+    }
+
+    #[test]
+    fn GIVEN_privileged_authorization_WHEN_redact_THEN_not_redacted() {
+        // GIVEN
+        let rdap = domain().to_response();
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, "Bearer privileged".parse().expect("header"));
+
+        // WHEN
+        let actual = redact(
+            rdap.clone(),
+            Some(&public_policy()),
+            &public_client(),
+            &headers,
+        )
+        .expect("redacting");
+
+        // THEN
+        assert_eq!(actual, rdap);
+    }
+
+    #[test]
+    fn GIVEN_privileged_tier_WHEN_redact_THEN_not_redacted() {
+        // GIVEN
+        let rdap = domain().to_response();
+        let identity = Identity {
+            name: "registry-lab".to_string(),
+            tier: Some("registry".to_string()),
+        };
+        let requester = client("192.0.2.1", Some(Access::Authenticated(identity)));
+
+        // WHEN
+        let actual = redact(
+            rdap.clone(),
+            Some(&public_policy()),
+            &requester,
+            &HeaderMap::new(),
+        )
+        .expect("redacting");
+
+        // THEN
+        assert_eq!(actual, rdap);
+    }
+
+    #[test]
+    fn GIVEN_search_results_WHEN_redact_THEN_each_result_redacted() {
+        // GIVEN
+        let rdap = DomainSearchResults::response_obj()
+            .results(vec![
+                domain(),
+                Domain::builder().ldh_name("bar.example").build(),
+            ])
+            .build()
+            .to_response();
+
+        // WHEN
+        let actual = redact(
+            rdap,
+            Some(&public_policy()),
+            &public_client(),
+            &HeaderMap::new(),
+        )
+        .expect("redacting");
+
+        // THEN only redactions done are noted in each result
+        let RdapResponse::DomainSearchResults(results) = actual else {
+            panic!("not search results")
+        };
+        assert_eq!(
+            results.results[0]
+                .object_common
+                .redacted
+                .as_ref()
+                .map(|r| r.len()),
+            Some(3)
+        );
+        assert!(results.results[1].object_common.redacted.is_none());
+    }
+
+    #[test]
+    fn GIVEN_error_WHEN_redact_THEN_not_redacted() {
+        // GIVEN
+        let rdap = Rfc9083Error::response_obj()
+            .error_code(404)
+            .build()
+            .to_response();
+
+        // WHEN
+        let actual = redact(
+            rdap.clone(),
+            Some(&public_policy()),
+            &public_client(),
+            &HeaderMap::new(),
+        )
+        .expect("redacting");
+
+        // THEN
+        assert_eq!(actual, rdap);
+    }
+
+    #[test]
+    fn GIVEN_unregistered_name_without_path_WHEN_validated_THEN_error() {
+        // GIVEN
+        let policy = serde_json::from_value::<RedactionPolicy>(json!({
+            "profiles": [{ "redactions": [{ "name": "Abuse Email", "method": "removal" }] }]
+        }))
+        .expect("parsing policy");
+
+        // WHEN
+        let actual = policy.validated();
+
+        // THEN
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn GIVEN_replacement_value_without_replacement_WHEN_validated_THEN_error() {
+        // GIVEN
+        let policy = serde_json::from_value::<RedactionPolicy>(json!({
+            "profiles": [{ "redactions": [
+                { "name": "Registrant Name", "method": "replacementValue" }
+            ]}]
+        }))
+        .expect("parsing policy");
+
+        // WHEN
+        let actual = policy.validated();
+
+        // THEN
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn GIVEN_authorizations_and_auth_WHEN_checked_with_auth_THEN_error() {
+        // GIVEN
+        let policy = public_policy();
+
+        // WHEN
+        let actual = policy.checked_with_auth(Some(&AuthConfig::default()));
+
+        // THEN
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn GIVEN_authorizations_without_auth_WHEN_checked_with_auth_THEN_ok() {
+        // GIVEN
+        let policy = public_policy();
+
+        // WHEN
+        let actual = policy.checked_with_auth(None);
+
+        // THEN
+        assert!(actual.is_ok());
+    }
+
+    #[test]
+    fn GIVEN_registrant_city_removed_WHEN_redact_THEN_adr_components_kept_in_place() {
+        // GIVEN
+        let registrant = Entity::builder()
+            .handle("REG-1")
+            .role(EntityRole::Registrant.to_string())
+            .contact(
+                Contact::builder()
+                    .full_name("Alice")
+                    .postal_address(
+                        PostalAddress::builder()
+                            .street_part("123 Maple Ave")
+                            .locality("Quebec")
+                            .region_code("QC")
+                            .postal_code("G1V 2M2")
+                            .country_name("Canada")
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build();
+        let rdap = Domain::builder()
+            .ldh_name("example.com")
+            .entity(registrant)
+            .build()
+            .to_response();
+        let policy = policy(json!({
+            "profiles": [{ "redactions": [
+                { "name": "Registrant City", "method": "removal" }
+            ]}]
+        }));
+
+        // WHEN
+        let actual =
+            redact(rdap, Some(&policy), &public_client(), &HeaderMap::new()).expect("redacting");
+
+        // THEN
+        let json = serde_json::to_value(&actual).expect("serializing");
+        let adr = json["entities"][0]["vcardArray"][1]
+            .as_array()
+            .expect("properties")
+            .iter()
+            .find(|p| p[0] == "adr")
+            .expect("adr property");
+        assert_eq!(adr[3].as_array().map(|c| c.len()), Some(7));
+        assert_eq!(adr[3][3], "");
+        assert_eq!(adr[3][4], "QC");
+        assert_eq!(adr[3][5], "G1V 2M2");
+    }
+}
@@ -0,0 +1,183 @@
+//! Reverse searches by related entities, as described by RFC 9536.
+
+use http::HeaderMap;
+use serde::Deserialize;
+use tracing::debug;
+
+use axum::{
+    extract::{OriginalUri, Query, State},
+    response::Response,
+};
+
+use crate::{
+    auth::Requester,
+    error::RdapServerError,
+    rdap::{
+        paging::{sort_and_page, SortPageParams},
+        parse_extensions, prepare_response,
+        response::{ResponseUtil, BAD_REQUEST},
+        subsetting::{subset, FieldSetParams},
+    },
+    server::DynServiceState,
+    storage::ReverseSearch,
+};
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ReverseSearchParams {
+    handle: Option<String>,
+    #[serde(rename = "fn")]
+    #[allow(non_snake_case)]
+    fn_: Option<String>,
+    email: Option<String>,
+    role: Option<String>,
+
+    #[serde(flatten)]
+    sort_page: SortPageParams,
+
+    #[serde(flatten)]
+    field_set: FieldSetParams,
+}
+
+/// The object classes that can be searched by their related entities.
+#[derive(Debug, Clone, Copy)]
+enum SearchableResource {
+    Domains,
+    Nameservers,
+    Entities,
+    Networks,
+    Autnums,
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(level = "debug")]
+pub(crate) async fn domains_by_entity(
+    Query(params): Query<ReverseSearchParams>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    requester: Requester,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    reverse_search(
+        SearchableResource::Domains,
+        params,
+        uri,
+        headers,
+        requester,
+        state,
+    )
+    .await
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(level = "debug")]
+pub(crate) async fn nameservers_by_entity(
+    Query(params): Query<ReverseSearchParams>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    requester: Requester,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    reverse_search(
+        SearchableResource::Nameservers,
+        params,
+        uri,
+        headers,
+        requester,
+        state,
+    )
+    .await
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(level = "debug")]
+pub(crate) async fn entities_by_entity(
+    Query(params): Query<ReverseSearchParams>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    requester: Requester,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    reverse_search(
+        SearchableResource::Entities,
+        params,
+        uri,
+        headers,
+        requester,
+        state,
+    )
+    .await
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(level = "debug")]
+pub(crate) async fn networks_by_entity(
+    Query(params): Query<ReverseSearchParams>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    requester: Requester,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    reverse_search(
+        SearchableResource::Networks,
+        params,
+        uri,
+        headers,
+        requester,
+        state,
+    )
+    .await
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(level = "debug")]
+pub(crate) async fn autnums_by_entity(
+    Query(params): Query<ReverseSearchParams>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    requester: Requester,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    reverse_search(
+        SearchableResource::Autnums,
+        params,
+        uri,
+        headers,
+        requester,
+        state,
+    )
+    .await
+}
+
+async fn reverse_search(
+    resource: SearchableResource,
+    params: ReverseSearchParams,
+    uri: http::Uri,
+    headers: HeaderMap,
+    requester: Requester,
+    state: State<DynServiceState>,
+) -> Result<Response, RdapServerError> {
+    let search = ReverseSearch::builder()
+        .and_handle(params.handle)
+        .and_full_name(params.fn_)
+        .and_email(params.email)
+        .and_role(params.role)
+        .build();
+    if search.is_empty() {
+        return Ok(BAD_REQUEST.response());
+    }
+    let exts_list = parse_extensions(headers.get("accept").unwrap().to_str().unwrap());
+    debug!("exts_list = \'{}\'", exts_list.join(" "));
+
+    let storage = state.get_storage().await?;
+    let results = match resource {
+        SearchableResource::Domains => storage.reverse_search_domains(&search).await?,
+        SearchableResource::Nameservers => storage.reverse_search_nameservers(&search).await?,
+        SearchableResource::Entities => storage.reverse_search_entities(&search).await?,
+        SearchableResource::Networks => storage.reverse_search_networks(&search).await?,
+        SearchableResource::Autnums => storage.reverse_search_autnums(&search).await?,
+    };
+    let results = sort_and_page(results, &params.sort_page, &state.get_search_config(), &uri);
+    let results = subset(results, &params.field_set, &uri);
+    let results = prepare_response(results, &state, &requester, &headers, &exts_list)?;
+    Ok(results.response())
+}
@@ -1,9 +1,21 @@
 use axum::{routing::get, Router};
 
 use super::{
-    autnum::autnum_by_num, domain::domain_by_name, domains::domains, entities::entities,
-    entity::entity_by_handle, ip::network_by_netid, nameserver::nameserver_by_name,
-    nameservers::nameservers, srvhelp::srvhelp,
+    autnum::autnum_by_num,
+    autnums::autnums,
+    domain::domain_by_name,
+    domains::domains,
+    entities::entities,
+    entity::entity_by_handle,
+    ip::network_by_netid,
+    ips::{ips, related_networks},
+    nameserver::nameserver_by_name,
+    nameservers::nameservers,
+    reverse_search::{
+        autnums_by_entity, domains_by_entity, entities_by_entity, nameservers_by_entity,
+        networks_by_entity,
+    },
+    srvhelp::srvhelp,
 };
 
 pub(crate) fn rdap_router() -> Router<crate::server::DynServiceState> {
@@ -16,5 +28,16 @@ pub(crate) fn rdap_router() -> Router<crate::server::DynServiceState> {
         .route("/domains", get(domains))
         .route("/nameservers", get(nameservers))
         .route("/entities", get(entities))
+        .route("/ips", get(ips))
+        .route("/ips/rirSearch1/:relation/*netid", get(related_networks))
+        .route("/autnums", get(autnums))
+        .route("/domains/reverse_search/entity", get(domains_by_entity))
+        .route(
+            "/nameservers/reverse_search/entity",
+            get(nameservers_by_entity),
+        )
+        .route("/entities/reverse_search/entity", get(entities_by_entity))
+        .route("/ips/reverse_search/entity", get(networks_by_entity))
+        .route("/autnums/reverse_search/entity", get(autnums_by_entity))
         .route("/help", get(srvhelp))
 }
@@ -0,0 +1,257 @@
+//! Subsetting of search results, as described by RFC 8982.
+
+use std::str::FromStr;
+
+use {
+    http::Uri,
+    icann_rdap_common::prelude::{
+        Autnum, AutnumSearchResults, AvailableFieldSet, Domain, DomainSearchResults, Entity,
+        EntitySearchResults, FieldSet, Nameserver, NameserverSearchResults, Network,
+        NetworkSearchResults, RdapResponse, SubsettingMetadata, ToResponse,
+    },
+    serde::{de::DeserializeOwned, Deserialize, Serialize},
+    serde_json::Value,
+};
+
+use super::{paging::request_link, response::BAD_REQUEST};
+
+/// The RFC 8982 query parameter of a search.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct FieldSetParams {
+    #[serde(rename = "fieldSet")]
+    field_set: Option<String>,
+}
+
+/// The field sets of this server and their descriptions.
+const FIELD_SETS: [(FieldSet, &str); 3] = [
+    (FieldSet::Id, "Only the key fields of the results."),
+    (
+        FieldSet::Brief,
+        "The fields of the id field set plus a summary of the results.",
+    ),
+    (FieldSet::Full, "All the fields of the results."),
+];
+
+/// The field set of searches not asking for one.
+const DEFAULT_FIELD_SET: FieldSet = FieldSet::Full;
+
+/// An object found in search results that can be subsetted.
+trait SubsettableResult: Serialize + DeserializeOwned {
+    /// The members of the `id` field set.
+    const ID_MEMBERS: &'static [&'static str];
+
+    /// The members of the `brief` field set in addition to [Self::ID_MEMBERS].
+    const BRIEF_MEMBERS: &'static [&'static str];
+
+    /// Removes all members not in the field set.
+    fn subset(self, field_set: FieldSet) -> Self {
+        let members = match field_set {
+            FieldSet::Id => Self::ID_MEMBERS.to_vec(),
+            FieldSet::Brief => [Self::ID_MEMBERS, Self::BRIEF_MEMBERS].concat(),
+            FieldSet::Full => return self,
+        };
+        let Ok(Value::Object(mut object)) = serde_json::to_value(&self) else {
+            return self;
+        };
+        object.retain(|member, _| members.contains(&member.as_str()));
+        serde_json::from_value(Value::Object(object)).unwrap_or(self)
+    }
+}
+
+impl SubsettableResult for Domain {
+    const ID_MEMBERS: &'static [&'static str] = &["objectClassName", "ldhName", "unicodeName"];
+    const BRIEF_MEMBERS: &'static [&'static str] = &["handle", "status"];
+}
+
+impl SubsettableResult for Nameserver {
+    const ID_MEMBERS: &'static [&'static str] = &["objectClassName", "ldhName", "unicodeName"];
+    const BRIEF_MEMBERS: &'static [&'static str] = &["handle", "status"];
+}
+
+impl SubsettableResult for Entity {
+    const ID_MEMBERS: &'static [&'static str] = &["objectClassName", "handle"];
+    const BRIEF_MEMBERS: &'static [&'static str] = &["roles", "status"];
+}
+
+impl SubsettableResult for Network {
+    const ID_MEMBERS: &'static [&'static str] = &[
+        "objectClassName",
+        "handle",
+        "startAddress",
+        "endAddress",
+        "ipVersion",
+    ];
+    const BRIEF_MEMBERS: &'static [&'static str] = &["name", "type", "status"];
+}
+
+impl SubsettableResult for Autnum {
+    const ID_MEMBERS: &'static [&'static str] =
+        &["objectClassName", "handle", "startAutnum", "endAutnum"];
+    const BRIEF_MEMBERS: &'static [&'static str] = &["name", "type", "status"];
+}
+
+fn subsetting_metadata(field_set: FieldSet, uri: &Uri) -> SubsettingMetadata {
+    let available_field_sets = FIELD_SETS
+        .iter()
+        .map(|(available, description)| {
+            let name = available.to_string();
+            AvailableFieldSet::builder()
+                .name(name.clone())
+                .default(*available == DEFAULT_FIELD_SET)
+                .description(*description)
+                .link(request_link(
+                    uri,
+                    "alternate",
+                    &["fieldSet", "cursor"],
+                    &[("fieldSet", &name)],
+                ))
+                .build()
+        })
+        .collect::<Vec<AvailableFieldSet>>();
+    SubsettingMetadata::builder()
+        .current_field_set(field_set.to_string())
+        .available_field_sets(available_field_sets)
+        .build()
+}
+
+fn subset_results<T: SubsettableResult>(results: Vec<T>, field_set: FieldSet) -> Vec<T> {
+    results
+        .into_iter()
+        .map(|result| result.subset(field_set))
+        .collect()
+}
+
+/// Reduces the objects in search results to the field set requested and
+/// adds the subsetting metadata. Responses that are not search results, and
+/// searches not asking for a field set, are returned as is. If the field set
+/// is unknown, a bad request error is returned.
+pub(crate) fn subset(rdap: RdapResponse, params: &FieldSetParams, uri: &Uri) -> RdapResponse {
+    let Some(field_set) = &params.field_set else {
+        return rdap;
+    };
+    let Ok(field_set) = FieldSet::from_str(field_set) else {
+        return BAD_REQUEST.clone();
+    };
+    match rdap {
+        RdapResponse::DomainSearchResults(r) => DomainSearchResults {
+            results: subset_results(r.results, field_set),
+            subsetting_metadata: Some(subsetting_metadata(field_set, uri)),
+            ..*r
+        }
+        .to_response(),
+        RdapResponse::NameserverSearchResults(r) => NameserverSearchResults {
+            results: subset_results(r.results, field_set),
+            subsetting_metadata: Some(subsetting_metadata(field_set, uri)),
+            ..*r
+        }
+        .to_response(),
+        RdapResponse::EntitySearchResults(r) => EntitySearchResults {
+            results: subset_results(r.results, field_set),
+            subsetting_metadata: Some(subsetting_metadata(field_set, uri)),
+            ..*r
+        }
+        .to_response(),
+        RdapResponse::NetworkSearchResults(r) => NetworkSearchResults {
+            results: subset_results(r.results, field_set),
+            subsetting_metadata: Some(subsetting_metadata(field_set, uri)),
+            ..*r
+        }
+        .to_response(),
+        RdapResponse::AutnumSearchResults(r) => AutnumSearchResults {
+            results: subset_results(r.results, field_set),
+            subsetting_metadata: Some(subsetting_metadata(field_set, uri)),
+            ..*r
+        }
+        .to_response(),
+        _ => rdap,
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use {
+        http::Uri,
+        icann_rdap_common::prelude::{Domain, Entity, FieldSet, ObjectCommonFields},
+    };
+
+    use super::{subsetting_metadata, SubsettableResult};
+
+    fn domain() -> Domain {
+        Domain::builder()
+            .ldh_name("foo.example")
+            .handle("FOO-1")
+            .status("active")
+            .entity(Entity::builder().handle("bob").build())
+            .build()
+    }
+
+    #[test]
+    fn GIVEN_domain_WHEN_subset_to_id_THEN_only_names_remain() {
+        // GIVEN
+        let domain = domain();
+
+        // WHEN
+        let actual = domain.subset(FieldSet::Id);
+
+        // THEN
+        assert_eq!(actual.ldh_name(), Some("foo.example"));
+        assert!(actual.handle().is_none());
+        assert!(actual.status().is_empty());
+        assert!(actual.entities().is_empty());
+    }
+
+    #[test]
+    fn GIVEN_domain_WHEN_subset_to_brief_THEN_handle_and_status_remain() {
+        // GIVEN
+        let domain = domain();
+
+        // WHEN
+        let actual = domain.subset(FieldSet::Brief);
+
+        // THEN
+        assert_eq!(actual.ldh_name(), Some("foo.example"));
+        assert_eq!(actual.handle(), Some("FOO-1"));
+        assert_eq!(actual.status(), ["active"]);
+        assert!(actual.entities().is_empty());
+    }
+
+    #[test]
+    fn GIVEN_domain_WHEN_subset_to_full_THEN_unchanged() {
+        // GIVEN
+        let domain = domain();
+
+        // WHEN
+        let actual = domain.clone().subset(FieldSet::Full);
+
+        // THEN
+        assert_eq!(actual, domain);
+    }
+
+    #[test]
+    fn GIVEN_field_set_WHEN_subsetting_metadata_THEN_links_to_other_field_sets() {
+        // GIVEN
+        let uri = "/rdap/domains?name=foo*.example&fieldSet=id"
+            .parse::<Uri>()
+            .expect("parsing uri");
+
+        // WHEN
+        let actual = subsetting_metadata(FieldSet::Id, &uri);
+
+        // THEN
+        assert_eq!(actual.current_field_set(), Some("id"));
+        let names = actual
+            .available_field_sets()
+            .iter()
+            .filter_map(|f| f.name())
+            .collect::<Vec<&str>>();
+        assert_eq!(names, ["id", "brief", "full"]);
+        let brief = &actual.available_field_sets()[1];
+        assert!(!brief.is_default());
+        assert_eq!(
+            brief.links()[0].href(),
+            Some("/rdap/domains?name=foo*.example&fieldSet=brief")
+        );
+        assert!(actual.available_field_sets()[2].is_default());
+    }
+}
@@ -2,34 +2,54 @@ use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 use {
     async_trait::async_trait,
-    axum::{error_handling::HandleErrorLayer, Router},
-    http::{Method, StatusCode},
+    axum::{
+        error_handling::HandleErrorLayer,
+        middleware::{from_fn, from_fn_with_state},
+        routing::get,
+        Router,
+    },
+    axum_client_ip::SecureClientIpSource,
+    axum_server::Handle,
+    http::{header::STRICT_TRANSPORT_SECURITY, HeaderValue, Method, StatusCode},
     icann_rdap_common::VERSION,
-    tokio::net::TcpListener,
+    tokio::{net::TcpListener, signal},
     tower::{BoxError, ServiceBuilder},
     tower_http::{
         cors::{Any, CorsLayer},
+        set_header::SetResponseHeaderLayer,
         trace::TraceLayer,
     },
 };
 
 use crate::{
+    admin::admin_router,
+    auth::{authenticate, authorize_admin, AuthConfig},
     bootstrap::init_bootstrap,
-    config::{JsContactConversion, ListenConfig, ServiceConfig, StorageType},
+    client_ip::resolve_client_ip,
+    config::{
+        JsContactConversion, ListenConfig, RateLimitConfig, SearchConfig, ServiceConfig,
+        StorageType,
+    },
     error::RdapServerError,
-    rdap::router::rdap_router,
+    health::{healthz, readyz, Readiness},
+    metrics::{metrics, track_requests},
+    rate_limit::{self, RateLimiter},
+    rdap::{redaction::RedactionPolicy, router::rdap_router},
     storage::{
-        data::{load_data, reload_data},
+        data::{load_data, reload_data, DataStatus},
         mem::{config::MemConfig, ops::Mem},
         pg::{config::PgConfig, ops::Pg},
+        sqlite::{config::SqliteConfig, ops::Sqlite},
         StoreOps,
     },
+    tls::{start_redirect, watch_certificate, Tls},
 };
 
 /// Holds information on the server listening.
 pub struct Listener {
     pub local_addr: SocketAddr,
     tcp_listener: TcpListener,
+    tls: Option<Tls>,
 }
 
 /// Starts the RDAP service.
@@ -50,22 +70,28 @@ impl Listener {
 
         let listener = TcpListener::bind(binding).await?;
         let local_addr = listener.local_addr()?;
+        let tls = match &config.tls {
+            Some(tls) => Some(Tls::load(tls, local_addr.ip()).await?),
+            None => None,
+        };
         Ok(Self {
             local_addr,
             tcp_listener: listener,
+            tls,
         })
     }
 
     pub fn rdap_base(&self) -> String {
+        let scheme = if self.tls.is_some() { "https" } else { "http" };
         if self.local_addr.is_ipv4() {
             format!(
-                "http://{}:{}/rdap",
+                "{scheme}://{}:{}/rdap",
                 self.local_addr.ip(),
                 self.local_addr.port()
             )
         } else {
             format!(
-                "http://[{}]:{}/rdap",
+                "{scheme}://[{}]:{}/rdap",
                 self.local_addr.ip(),
                 self.local_addr.port()
             )
@@ -76,17 +102,40 @@ impl Listener {
     /// This function will initiate any needed non-HTTP services and then call
     /// call [Listener::start_with_state], which initiates the HTTP service.
     pub async fn start_server(self, service_config: &ServiceConfig) -> Result<(), RdapServerError> {
-        init_bootstrap(service_config).await?;
         if let StorageType::Memory(config) = &service_config.storage_type {
-            let app_state = AppState::new_mem(config.clone(), service_config).await?;
-            self.start_with_state(app_state).await?;
+            let app_state = AppState::new_mem_uninitialized(config.clone(), service_config).await?;
+            self.start_and_initialize(app_state, service_config).await?;
         } else if let StorageType::Postgres(config) = &service_config.storage_type {
-            let app_state = AppState::new_pg(config.clone(), service_config).await?;
-            self.start_with_state(app_state).await?;
+            let app_state = AppState::new_pg_uninitialized(config.clone(), service_config).await?;
+            self.start_and_initialize(app_state, service_config).await?;
+        } else if let StorageType::Sqlite(config) = &service_config.storage_type {
+            let app_state =
+                AppState::new_sqlite_uninitialized(config.clone(), service_config).await?;
+            self.start_and_initialize(app_state, service_config).await?;
         };
         Ok(())
     }
 
+    /// Starts the HTTP service while the bootstrap and data are being loaded, which
+    /// the service reports as not ready until they are.
+    async fn start_and_initialize<T>(
+        self,
+        app_state: AppState<T>,
+        service_config: &ServiceConfig,
+    ) -> Result<(), RdapServerError>
+    where
+        T: StoreOps + Clone + Send + Sync + 'static,
+        AppState<T>: ServiceState,
+    {
+        start_metrics_server(service_config, Box::new(app_state.storage.clone())).await?;
+        let initializing = app_state.clone();
+        tokio::try_join!(
+            self.start_with_state(app_state),
+            initializing.initialize(service_config)
+        )?;
+        Ok(())
+    }
+
     /// Starts the HTTP server with a specific [AppState]. This is the entry point for a library or testing
     /// framework.
     pub async fn start_with_state<T>(self, app_state: AppState<T>) -> Result<(), RdapServerError>
@@ -97,22 +146,125 @@ impl Listener {
         let app = app_router::<T>(app_state);
 
         tracing::debug!("listening on {}", self.local_addr);
-        axum::serve(
-            self.tcp_listener,
-            app.into_make_service_with_connect_info::<SocketAddr>(),
-        )
-        .await?;
+        if let Some(tls) = self.tls {
+            serve_tls(self.tcp_listener, self.local_addr, tls, app).await?;
+        } else {
+            axum::serve(
+                self.tcp_listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .with_graceful_shutdown(shutdown_signal())
+            .await?;
+        }
+        tracing::info!("rdap-srv has shut down");
+        Ok(())
+    }
+
+    /// Starts an HTTP server of the Prometheus metrics at `/metrics`, with the
+    /// counts of stored objects taken from the storage.
+    pub async fn start_metrics(self, store: Box<dyn StoreOps>) -> Result<(), RdapServerError> {
+        let app = Router::new()
+            .route("/metrics", get(metrics))
+            .with_state(Arc::from(store));
+
+        tracing::debug!("metrics listening on {}", self.local_addr);
+        axum::serve(self.tcp_listener, app)
+            .with_graceful_shutdown(shutdown_signal())
+            .await?;
         Ok(())
     }
 }
 
+/// Serves HTTPS, along with the redirects from HTTP and the reloading of the
+/// certificate.
+async fn serve_tls(
+    tcp_listener: TcpListener,
+    local_addr: SocketAddr,
+    tls: Tls,
+    mut app: Router,
+) -> Result<(), RdapServerError> {
+    if let Some(hsts) = tls.hsts() {
+        let hsts = HeaderValue::from_str(&hsts)
+            .map_err(|e| RdapServerError::Config(format!("invalid HSTS header: {e}")))?;
+        app = app.layer(SetResponseHeaderLayer::overriding(
+            STRICT_TRANSPORT_SECURITY,
+            hsts,
+        ));
+    }
+    let redirect = match (tls.redirect_listener, tls.config.redirect_host.clone()) {
+        (Some(listener), Some(host)) => Some(tokio::spawn(start_redirect(
+            listener,
+            host,
+            local_addr.port(),
+            shutdown_signal(),
+        ))),
+        _ => None,
+    };
+    let watcher = tokio::spawn(watch_certificate(tls.config, tls.rustls.clone()));
+    let handle = Handle::new();
+    let shutdown = handle.clone();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        shutdown.graceful_shutdown(None);
+    });
+    let served = axum_server::from_tcp_rustls(tcp_listener.into_std()?, tls.rustls)
+        .handle(handle)
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+        .await;
+    watcher.abort();
+    if let Some(redirect) = redirect {
+        // the redirects are shut down gracefully along with HTTPS, unless HTTPS failed
+        if served.is_ok() {
+            let _ = redirect.await;
+        } else {
+            redirect.abort();
+        }
+    }
+    Ok(served?)
+}
+
+/// Starts the metrics server in the background if it is configured.
+async fn start_metrics_server(
+    config: &ServiceConfig,
+    store: Box<dyn StoreOps>,
+) -> Result<(), RdapServerError> {
+    if let Some(metrics_listen) = &config.metrics_listen {
+        let listener = Listener::listen(metrics_listen).await?;
+        tokio::spawn(listener.start_metrics(store));
+    }
+    Ok(())
+}
+
+/// Completes when the process is asked to stop with SIGINT or SIGTERM. The servers
+/// then stop accepting connections and finish the requests in flight.
+async fn shutdown_signal() {
+    let interrupt = async {
+        signal::ctrl_c().await.expect("unable to listen for SIGINT");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("unable to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        _ = interrupt => {},
+        _ = terminate => {},
+    }
+    tracing::info!("shutting down after requests in flight are done");
+}
+
 async fn init_data(
     store: Box<dyn StoreOps>,
     config: &ServiceConfig,
+    data_status: DataStatus,
 ) -> Result<(), RdapServerError> {
-    load_data(config, &*store, false).await?;
+    data_status.replace(load_data(config, &*store, false).await?);
     if config.auto_reload {
-        tokio::spawn(reload_data(store, config.clone()));
+        tokio::spawn(reload_data(store, config.clone(), data_status));
     }
     Ok(())
 }
@@ -122,9 +274,33 @@ where
     T: StoreOps + Clone + Send + Sync + 'static,
     AppState<T>: ServiceState,
 {
+    let auth = state.auth.clone().map(Arc::new);
+    let rate_limit = state.rate_limit.clone();
+    let client_ip_source = state
+        .client_ip_source
+        .clone()
+        .unwrap_or(SecureClientIpSource::ConnectInfo);
     let state = Arc::new(state) as DynServiceState;
-    Router::new()
-        .nest("/rdap", rdap_router())
+    let mut router = Router::new().nest("/rdap", rdap_router());
+    if let Some(auth) = &auth {
+        router = router.layer(from_fn_with_state(auth.clone(), authenticate));
+    }
+    if let Some(rate_limit) = rate_limit {
+        let limiter = Arc::new(RateLimiter::new(rate_limit));
+        router = router.layer(from_fn_with_state(limiter, rate_limit::rate_limit));
+    }
+    router = router.layer(from_fn_with_state(client_ip_source, resolve_client_ip));
+    // the administration API is only served to authenticated clients
+    if let Some(auth) = auth {
+        router = router.nest(
+            "/admin",
+            admin_router().layer(from_fn_with_state(auth, authorize_admin)),
+        );
+    }
+    router
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .layer(from_fn(track_requests))
         .layer(
             ServiceBuilder::new()
                 .layer(HandleErrorLayer::new(|error: BoxError| async move {
@@ -163,6 +339,18 @@ pub trait ServiceState: std::fmt::Debug {
 
     /// Get the JsContactConversion configuration option.
     fn get_jscontact_conversion(&self) -> JsContactConversion;
+
+    /// Get the search result limits and paging configuration.
+    fn get_search_config(&self) -> SearchConfig;
+
+    /// Get the redaction policy, if responses are redacted.
+    fn get_redaction_policy(&self) -> Option<&RedactionPolicy>;
+
+    /// Get the readiness of the server.
+    fn get_readiness(&self) -> &Readiness;
+
+    /// Get the load status of the files of the data directory.
+    fn get_data_status(&self) -> &DataStatus;
 }
 
 /// State that is passed to the HTTP service router and used by functions
@@ -172,20 +360,61 @@ pub struct AppState<T: StoreOps + Clone + Send + Sync + 'static> {
     pub storage: T,
     pub bootstrap: bool,
     pub jscontact_conversion: JsContactConversion,
+    pub search_config: SearchConfig,
+    pub redaction_policy: Option<RedactionPolicy>,
+    pub auth: Option<AuthConfig>,
+    pub rate_limit: Option<RateLimitConfig>,
+    pub client_ip_source: Option<SecureClientIpSource>,
+    pub readiness: Readiness,
+    pub data_status: DataStatus,
+}
+
+impl<T> AppState<T>
+where
+    T: StoreOps + Clone + Send + Sync + 'static,
+{
+    /// Does the first bootstrap and loads the data, noting each in the readiness.
+    async fn initialize(&self, service_config: &ServiceConfig) -> Result<(), RdapServerError> {
+        init_bootstrap(service_config).await?;
+        self.readiness.set_bootstrapped();
+        let store = Box::new(self.storage.clone());
+        init_data(store, service_config, self.data_status.clone()).await?;
+        self.readiness.set_data_loaded();
+        tracing::info!("rdap-srv is ready");
+        Ok(())
+    }
 }
 
 impl AppState<Mem> {
+    /// Creates the state of a server, bootstrapping and loading the data before
+    /// returning it.
     pub async fn new_mem(
         config: MemConfig,
         service_config: &ServiceConfig,
+    ) -> Result<Self, RdapServerError> {
+        let app_state = Self::new_mem_uninitialized(config, service_config).await?;
+        app_state.initialize(service_config).await?;
+        Ok(app_state)
+    }
+
+    /// Creates the state of a server that is not ready until it is initialized.
+    async fn new_mem_uninitialized(
+        config: MemConfig,
+        service_config: &ServiceConfig,
     ) -> Result<Self, RdapServerError> {
         let storage = Mem::new(config);
         storage.init().await?;
-        init_data(Box::new(storage.clone()), service_config).await?;
         Ok(Self {
             storage,
             bootstrap: service_config.bootstrap,
             jscontact_conversion: service_config.jscontact_conversion,
+            search_config: service_config.search_config,
+            redaction_policy: service_config.redaction_policy.clone(),
+            auth: service_config.auth.clone(),
+            rate_limit: service_config.rate_limit.clone(),
+            client_ip_source: service_config.client_ip_source.clone(),
+            readiness: Readiness::default(),
+            data_status: DataStatus::default(),
         })
     }
 }
@@ -197,17 +426,35 @@ impl std::fmt::Debug for AppState<Mem> {
 }
 
 impl AppState<Pg> {
+    /// Creates the state of a server, bootstrapping and loading the data before
+    /// returning it.
     pub async fn new_pg(
         config: PgConfig,
         service_config: &ServiceConfig,
+    ) -> Result<Self, RdapServerError> {
+        let app_state = Self::new_pg_uninitialized(config, service_config).await?;
+        app_state.initialize(service_config).await?;
+        Ok(app_state)
+    }
+
+    /// Creates the state of a server that is not ready until it is initialized.
+    async fn new_pg_uninitialized(
+        config: PgConfig,
+        service_config: &ServiceConfig,
     ) -> Result<Self, RdapServerError> {
         let storage = Pg::new(config).await?;
         storage.init().await?;
-        init_data(Box::new(storage.clone()), service_config).await?;
         Ok(Self {
             storage,
             bootstrap: service_config.bootstrap,
             jscontact_conversion: service_config.jscontact_conversion,
+            search_config: service_config.search_config,
+            redaction_policy: service_config.redaction_policy.clone(),
+            auth: service_config.auth.clone(),
+            rate_limit: service_config.rate_limit.clone(),
+            client_ip_source: service_config.client_ip_source.clone(),
+            readiness: Readiness::default(),
+            data_status: DataStatus::default(),
         })
     }
 }
@@ -231,6 +478,93 @@ impl ServiceState for AppState<Pg> {
     fn get_jscontact_conversion(&self) -> JsContactConversion {
         self.jscontact_conversion
     }
+
+    fn get_search_config(&self) -> SearchConfig {
+        self.search_config
+    }
+
+    fn get_redaction_policy(&self) -> Option<&RedactionPolicy> {
+        self.redaction_policy.as_ref()
+    }
+
+    fn get_readiness(&self) -> &Readiness {
+        &self.readiness
+    }
+
+    fn get_data_status(&self) -> &DataStatus {
+        &self.data_status
+    }
+}
+
+impl AppState<Sqlite> {
+    /// Creates the state of a server, bootstrapping and loading the data before
+    /// returning it.
+    pub async fn new_sqlite(
+        config: SqliteConfig,
+        service_config: &ServiceConfig,
+    ) -> Result<Self, RdapServerError> {
+        let app_state = Self::new_sqlite_uninitialized(config, service_config).await?;
+        app_state.initialize(service_config).await?;
+        Ok(app_state)
+    }
+
+    /// Creates the state of a server that is not ready until it is initialized.
+    async fn new_sqlite_uninitialized(
+        config: SqliteConfig,
+        service_config: &ServiceConfig,
+    ) -> Result<Self, RdapServerError> {
+        let storage = Sqlite::new(config).await?;
+        storage.init().await?;
+        Ok(Self {
+            storage,
+            bootstrap: service_config.bootstrap,
+            jscontact_conversion: service_config.jscontact_conversion,
+            search_config: service_config.search_config,
+            redaction_policy: service_config.redaction_policy.clone(),
+            auth: service_config.auth.clone(),
+            rate_limit: service_config.rate_limit.clone(),
+            client_ip_source: service_config.client_ip_source.clone(),
+            readiness: Readiness::default(),
+            data_status: DataStatus::default(),
+        })
+    }
+}
+
+impl std::fmt::Debug for AppState<Sqlite> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppState<Sqlite>").finish()
+    }
+}
+
+#[async_trait]
+impl ServiceState for AppState<Sqlite> {
+    async fn get_storage(&self) -> Result<&dyn StoreOps, RdapServerError> {
+        Ok(&self.storage)
+    }
+
+    fn get_bootstrap(&self) -> bool {
+        self.bootstrap
+    }
+
+    fn get_jscontact_conversion(&self) -> JsContactConversion {
+        self.jscontact_conversion
+    }
+
+    fn get_search_config(&self) -> SearchConfig {
+        self.search_config
+    }
+
+    fn get_redaction_policy(&self) -> Option<&RedactionPolicy> {
+        self.redaction_policy.as_ref()
+    }
+
+    fn get_readiness(&self) -> &Readiness {
+        &self.readiness
+    }
+
+    fn get_data_status(&self) -> &DataStatus {
+        &self.data_status
+    }
 }
 
 #[async_trait]
@@ -246,4 +580,20 @@ impl ServiceState for AppState<Mem> {
     fn get_jscontact_conversion(&self) -> JsContactConversion {
         self.jscontact_conversion
     }
+
+    fn get_search_config(&self) -> SearchConfig {
+        self.search_config
+    }
+
+    fn get_redaction_policy(&self) -> Option<&RedactionPolicy> {
+        self.redaction_policy.as_ref()
+    }
+
+    fn get_readiness(&self) -> &Readiness {
+        &self.readiness
+    }
+
+    fn get_data_status(&self) -> &DataStatus {
+        &self.data_status
+    }
 }
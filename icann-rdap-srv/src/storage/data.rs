@@ -1,11 +1,15 @@
 use std::{
+    collections::{BTreeMap, BTreeSet},
+    future::Future,
     net::IpAddr,
-    path::PathBuf,
-    time::{Duration, SystemTime},
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
 };
 
 use {
     buildstructor::Builder,
+    chrono::{DateTime, Utc},
     icann_rdap_common::{
         prelude::Numberish,
         response::{
@@ -14,22 +18,98 @@ use {
         },
     },
     ipnet::{IpNet, Ipv4Subnets, Ipv6Subnets},
+    notify::{
+        event::{ModifyKind, RenameMode},
+        Event, EventKind, RecursiveMode, Watcher,
+    },
     serde::{Deserialize, Serialize},
     serde_json::Value,
     strum_macros::Display,
-    tokio::time::sleep,
-    tracing::{debug, info, warn},
+    tokio::{sync::mpsc::unbounded_channel, time::sleep},
+    tracing::{debug, error, info, warn},
 };
 
 use crate::{
     config::ServiceConfig,
     error::RdapServerError,
+    metrics::METRICS,
     storage::{StoreOps, TxHandle},
 };
 
 pub const UPDATE: &str = "update";
 pub const RELOAD: &str = "reload";
 
+/// How long to wait for more changes to the data directory before loading.
+const SETTLE_TIME: Duration = Duration::from_millis(500);
+
+/// How long to wait before trying again to watch the data directory.
+const WATCH_RETRY_TIME: Duration = Duration::from_secs(5);
+
+/// The outcome of loading a file of the data directory.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FileLoadStatus {
+    pub file: String,
+    pub loaded: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub time: DateTime<Utc>,
+}
+
+impl FileLoadStatus {
+    fn loaded(file: String) -> Self {
+        Self {
+            file,
+            loaded: true,
+            error: None,
+            time: Utc::now(),
+        }
+    }
+
+    fn failed(file: String, error: String) -> Self {
+        Self {
+            file,
+            loaded: false,
+            error: Some(error),
+            time: Utc::now(),
+        }
+    }
+}
+
+/// The last outcome of loading each file of the data directory. Clones share the status.
+#[derive(Debug, Clone, Default)]
+pub struct DataStatus {
+    files: Arc<RwLock<BTreeMap<String, FileLoadStatus>>>,
+}
+
+impl DataStatus {
+    /// Records the outcomes of loading files, keeping those of the other files.
+    pub fn record(&self, statuses: Vec<FileLoadStatus>) {
+        let mut files = self.files.write().expect("data status lock poisoned");
+        for status in statuses {
+            files.insert(status.file.clone(), status);
+        }
+    }
+
+    /// Replaces the outcomes of all files, such as after a reload.
+    pub fn replace(&self, statuses: Vec<FileLoadStatus>) {
+        self.files
+            .write()
+            .expect("data status lock poisoned")
+            .clear();
+        self.record(statuses);
+    }
+
+    /// Gets the outcomes of the files, ordered by file.
+    pub fn files(&self) -> Vec<FileLoadStatus> {
+        self.files
+            .read()
+            .expect("data status lock poisoned")
+            .values()
+            .cloned()
+            .collect()
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display)]
 #[serde(untagged)]
 pub enum Template {
@@ -171,58 +251,144 @@ pub enum NetworkIdType {
 /// ```
 /// In this example, 2 domains will be created for "foo.example" and "bar.example" using
 /// the template.
+///
+/// Loading stops at the first file that cannot be loaded, and nothing is stored.
+/// The outcome of loading each file is returned.
 pub async fn load_data(
     config: &ServiceConfig,
     store: &dyn StoreOps,
     truncate: bool,
-) -> Result<(), RdapServerError> {
-    let mut json_count: usize = 0;
-    let mut template_count: usize = 0;
-    let mut srvhelp_count: usize = 0;
-    let mut tx = if truncate {
-        store.new_truncate_tx().await?
-    } else {
-        store.new_tx().await?
-    };
+) -> Result<Vec<FileLoadStatus>, RdapServerError> {
+    load_data_dir(config, store, truncate, false).await
+}
+
+/// Loads the data directory like [load_data], but logs and skips the files that
+/// cannot be loaded, as is done when the data directory is being watched.
+pub async fn load_data_skipping_failed(
+    config: &ServiceConfig,
+    store: &dyn StoreOps,
+    truncate: bool,
+) -> Result<Vec<FileLoadStatus>, RdapServerError> {
+    load_data_dir(config, store, truncate, true).await
+}
+
+async fn load_data_dir(
+    config: &ServiceConfig,
+    store: &dyn StoreOps,
+    truncate: bool,
+    skip_failed: bool,
+) -> Result<Vec<FileLoadStatus>, RdapServerError> {
     let path = PathBuf::from(&config.data_dir);
     if !path.exists() || !path.is_dir() {
         warn!(
             "Directory {} does not exist or is not a directory. Server has no content to serve.",
             path.to_string_lossy()
         );
-        return Ok(());
+        return Ok(vec![]);
     }
 
+    let mut paths = vec![];
     let mut entries = tokio::fs::read_dir(path).await?;
     while let Some(entry) = entries.next_entry().await? {
         let entry_path = entry.path();
-        let contents = tokio::fs::read_to_string(&entry_path).await?;
-        if entry_path.extension().is_some_and(|ext| ext == "template") {
-            load_rdap_template(&contents, &entry_path.to_string_lossy(), &mut tx).await?;
-            template_count += 1;
-        } else if entry_path.extension().is_some_and(|ext| ext == "json") {
-            load_rdap(&contents, &entry_path.to_string_lossy(), &mut tx).await?;
-            json_count += 1;
-        } else if entry_path.extension().is_some_and(|ext| ext == "help") {
-            load_srvhelp(
-                &contents,
-                &entry_path.to_string_lossy(),
-                &entry.file_name().to_string_lossy(),
-                &mut tx,
-            )
-            .await?;
-            srvhelp_count += 1;
+        if is_data_file(&entry_path) {
+            paths.push(entry_path);
+        }
+    }
+    let tx = if truncate {
+        store.new_truncate_tx().await?
+    } else {
+        store.new_tx().await?
+    };
+    let statuses = load_files(&paths, tx, skip_failed).await?;
+    if statuses.iter().all(|status| !status.loaded) {
+        warn!("No data loaded. Server has no content to serve.");
+    }
+    Ok(statuses)
+}
+
+/// Loads only the given files into storage, keeping the data already stored.
+/// Files that cannot be loaded are skipped.
+pub async fn load_changed_files(
+    paths: &[PathBuf],
+    store: &dyn StoreOps,
+) -> Result<Vec<FileLoadStatus>, RdapServerError> {
+    let tx = store.new_tx().await?;
+    load_files(paths, tx, true).await
+}
+
+/// Loads files in a transaction. When skipping failed files, each file is loaded
+/// after a savepoint, so a file failing part way does not leave some of its objects
+/// or spoil the transaction. Otherwise the transaction is rolled back at the first
+/// file that fails.
+async fn load_files(
+    paths: &[PathBuf],
+    mut tx: Box<dyn TxHandle>,
+    skip_failed: bool,
+) -> Result<Vec<FileLoadStatus>, RdapServerError> {
+    let mut json_count: usize = 0;
+    let mut template_count: usize = 0;
+    let mut srvhelp_count: usize = 0;
+    let mut statuses = vec![];
+    for path in paths {
+        tx.savepoint().await?;
+        let loaded = load_file(path, &mut tx).await;
+        let file = path.to_string_lossy().to_string();
+        match loaded {
+            Ok(()) => {
+                tx.release_savepoint().await?;
+                match path.extension().and_then(|ext| ext.to_str()) {
+                    Some("template") => template_count += 1,
+                    Some("help") => srvhelp_count += 1,
+                    _ => json_count += 1,
+                };
+                statuses.push(FileLoadStatus::loaded(file));
+            }
+            Err(e) if !skip_failed => {
+                error!("Unable to load {file}: {e}");
+                tx.rollback().await?;
+                return Err(e);
+            }
+            Err(e) => {
+                tx.rollback_to_savepoint().await?;
+                warn!("Skipping {file}: {e}");
+                statuses.push(FileLoadStatus::failed(file, e.to_string()));
+            }
         }
     }
 
     info!("{json_count} RDAP JSON files loaded.");
     info!("{template_count} RDAP template files loaded.");
     info!("{srvhelp_count} RDAP server help files loaded.");
-    if json_count == 0 && template_count == 0 && srvhelp_count == 0 {
-        warn!("No data loaded. Server has no content to serve.");
+    let failed_count = statuses.iter().filter(|status| !status.loaded).count();
+    if failed_count > 0 {
+        warn!("{failed_count} files skipped because they could not be loaded.");
     }
     tx.commit().await?;
-    Ok(())
+    Ok(statuses)
+}
+
+/// Loads a JSON, template or server help file.
+async fn load_file(path: &Path, tx: &mut Box<dyn TxHandle>) -> Result<(), RdapServerError> {
+    let contents = tokio::fs::read_to_string(path).await?;
+    let path_name = path.to_string_lossy();
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("template") => load_rdap_template(&contents, &path_name, tx).await,
+        Some("help") => {
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default();
+            load_srvhelp(&contents, &path_name, &file_name, tx).await
+        }
+        _ => load_rdap(&contents, &path_name, tx).await,
+    }
+}
+
+/// True if the path is of a file to be loaded, which is determined by its extension.
+fn is_data_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == "json" || ext == "template" || ext == "help")
 }
 
 /// Loads the RDAP JSON files and puts them in storage.
@@ -366,38 +532,139 @@ async fn load_rdap_template(
     Ok(())
 }
 
+/// Watches the data directory, loading the files that are changed. A full
+/// reload is done when a data file is removed or when the `reload` file is
+/// touched, and all the files are loaded again when the `update` file is touched.
+///
+/// Objects taken out of a file that is changed are kept until the next reload.
 pub(crate) async fn reload_data(
     store: Box<dyn StoreOps>,
     config: ServiceConfig,
+    status: DataStatus,
 ) -> Result<(), RdapServerError> {
-    let update_path = PathBuf::from(&config.data_dir);
-    let update_path = update_path.join(UPDATE);
-    let reload_path = PathBuf::from(&config.data_dir);
-    let reload_path = reload_path.join(RELOAD);
-    let mut last_time = SystemTime::now();
-    loop {
-        sleep(Duration::from_millis(1000)).await;
-        let update_meta = tokio::fs::metadata(&update_path).await;
-        if let Ok(update_meta) = update_meta {
-            let modified = update_meta.modified()?;
-            if modified > last_time {
-                last_time = modified;
+    let (sender, mut receiver) = unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event| {
+        let _ = sender.send(event);
+    })?;
+    let mut retried = false;
+    while let Err(e) = watcher.watch(Path::new(&config.data_dir), RecursiveMode::NonRecursive) {
+        warn!(
+            "Unable to watch data directory {}, retrying: {e}",
+            config.data_dir
+        );
+        retried = true;
+        sleep(WATCH_RETRY_TIME).await;
+    }
+    if retried {
+        // changes made while the directory could not be watched were missed
+        info!("Data being reloaded.");
+        match metered_load("reload", load_data_skipping_failed(&config, &*store, true)).await {
+            Ok(statuses) => status.replace(statuses),
+            Err(e) => error!("Unable to load data: {e}"),
+        }
+    }
+    while let Some(event) = receiver.recv().await {
+        // let the writing of files settle, taking the changes as one
+        sleep(SETTLE_TIME).await;
+        let mut events = vec![event];
+        while let Ok(event) = receiver.try_recv() {
+            events.push(event);
+        }
+        let events = events.into_iter().filter_map(|event| {
+            event
+                .inspect_err(|e| warn!("Error watching data directory: {e}"))
+                .ok()
+        });
+        let loaded = match data_change(events) {
+            DataChange::None => continue,
+            DataChange::Reload => {
+                info!("Data being reloaded.");
+                metered_load("reload", load_data_skipping_failed(&config, &*store, true))
+                    .await
+                    .map(|statuses| status.replace(statuses))
+            }
+            DataChange::Update => {
                 info!("Data being updated.");
-                load_data(&config, &*store, false).await?;
+                metered_load("update", load_data_skipping_failed(&config, &*store, false))
+                    .await
+                    .map(|statuses| status.record(statuses))
+            }
+            DataChange::Files(paths) => {
+                info!("Data being loaded from {} changed files.", paths.len());
+                let paths = paths.into_iter().collect::<Vec<_>>();
+                metered_load("incremental", load_changed_files(&paths, &*store))
+                    .await
+                    .map(|statuses| status.record(statuses))
             }
         };
-        let reload_meta = tokio::fs::metadata(&reload_path).await;
-        if let Ok(reload_meta) = reload_meta {
-            let modified = reload_meta.modified()?;
-            if modified > last_time {
-                last_time = modified;
-                info!("Data being reloaded.");
-                load_data(&config, &*store, true).await?;
+        if let Err(e) = loaded {
+            error!("Unable to load data: {e}");
+        }
+    }
+    Ok(())
+}
+
+/// What is to be loaded after changes to the data directory.
+#[derive(Debug, PartialEq, Eq)]
+enum DataChange {
+    None,
+    Reload,
+    Update,
+    Files(BTreeSet<PathBuf>),
+}
+
+/// Determines what is to be loaded from the events of the data directory.
+fn data_change(events: impl IntoIterator<Item = Event>) -> DataChange {
+    let mut update = false;
+    let mut changed = BTreeSet::new();
+    for event in events {
+        let (removed, added): (&[PathBuf], &[PathBuf]) = match event.kind {
+            EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                (&event.paths, &[])
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => {
+                (&event.paths[..1], &event.paths[1..])
             }
+            EventKind::Create(_) | EventKind::Modify(_) => (&[], &event.paths),
+            _ => continue,
         };
+        if removed.iter().any(|path| is_data_file(path)) {
+            return DataChange::Reload;
+        }
+        for path in added {
+            match path.file_name().and_then(|name| name.to_str()) {
+                Some(RELOAD) => return DataChange::Reload,
+                Some(UPDATE) => update = true,
+                _ if is_data_file(path) => {
+                    changed.insert(path.clone());
+                }
+                _ => {}
+            }
+        }
+    }
+    if update {
+        DataChange::Update
+    } else if changed.is_empty() {
+        DataChange::None
+    } else {
+        DataChange::Files(changed)
     }
 }
 
+/// Loads data, recording the load of a kind in the metrics.
+async fn metered_load(
+    kind: &'static str,
+    load: impl Future<Output = Result<Vec<FileLoadStatus>, RdapServerError>>,
+) -> Result<Vec<FileLoadStatus>, RdapServerError> {
+    let start = Instant::now();
+    let loaded = load.await;
+    let succeeded = loaded
+        .as_ref()
+        .is_ok_and(|statuses| statuses.iter().all(|status| status.loaded));
+    METRICS.record_data_load(kind, succeeded, start.elapsed());
+    loaded
+}
+
 pub async fn trigger_reload(data_dir: &str) -> Result<(), RdapServerError> {
     let reload_path = PathBuf::from(&data_dir);
     let reload_path = reload_path.join(RELOAD);
@@ -989,4 +1256,62 @@ mod tests {
             "http://reg.example/ip/11.0.0.0/24"
         );
     }
+
+    #[test]
+    fn test_data_change_for_changed_files() {
+        // GIVEN
+        let events = vec![
+            Event::new(EventKind::Create(notify::event::CreateKind::File))
+                .add_path(PathBuf::from("/data/foo.json")),
+            Event::new(EventKind::Modify(ModifyKind::Any))
+                .add_path(PathBuf::from("/data/foo.json")),
+            Event::new(EventKind::Modify(ModifyKind::Any)).add_path(PathBuf::from("/data/foo.txt")),
+            Event::new(EventKind::Access(notify::event::AccessKind::Any))
+                .add_path(PathBuf::from("/data/bar.template")),
+        ];
+
+        // WHEN
+        let actual = data_change(events);
+
+        // THEN
+        assert_eq!(
+            actual,
+            DataChange::Files(BTreeSet::from([PathBuf::from("/data/foo.json")]))
+        );
+    }
+
+    #[test]
+    fn test_data_change_for_removed_file_or_markers() {
+        // GIVEN
+        let removed = Event::new(EventKind::Remove(notify::event::RemoveKind::File))
+            .add_path(PathBuf::from("/data/foo.json"));
+        let renamed = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+            .add_path(PathBuf::from("/data/foo.json"))
+            .add_path(PathBuf::from("/data/foo.json.bak"));
+        let reload = Event::new(EventKind::Create(notify::event::CreateKind::File))
+            .add_path(PathBuf::from("/data/reload"));
+        let update =
+            Event::new(EventKind::Modify(ModifyKind::Any)).add_path(PathBuf::from("/data/update"));
+        let changed = Event::new(EventKind::Modify(ModifyKind::Any))
+            .add_path(PathBuf::from("/data/foo.json"));
+
+        // WHEN
+        let actuals = [
+            data_change([removed]),
+            data_change([renamed]),
+            data_change([reload, update.clone()]),
+            data_change([update, changed]),
+        ];
+
+        // THEN
+        assert_eq!(
+            actuals,
+            [
+                DataChange::Reload,
+                DataChange::Reload,
+                DataChange::Reload,
+                DataChange::Update
+            ]
+        );
+    }
 }
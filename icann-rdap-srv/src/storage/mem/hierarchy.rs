@@ -0,0 +1,303 @@
+//! The hierarchy of the networks in a prefix map, for the `rdap-up`, `rdap-down`,
+//! `rdap-top` and `rdap-bottom` relations of the RIR search extension.
+
+use std::{collections::HashSet, sync::Arc};
+
+use {
+    icann_rdap_common::response::{Network, RdapResponse},
+    ipnet::{Ipv4Net, Ipv4Subnets, Ipv6Net, Ipv6Subnets},
+    prefix_trie::{Prefix, PrefixMap},
+};
+
+use crate::storage::NetworkRelation;
+
+/// A prefix of the networks in a prefix map.
+pub(crate) trait NetworkPrefix: Prefix + Copy {
+    /// Gets the prefix one bit shorter than this one, if any.
+    fn up(&self) -> Option<Self>;
+
+    /// Gets the prefixes a network is stored under, which are many if its
+    /// range is not a single CIDR.
+    fn of_network(network: &Network) -> Vec<Self>;
+}
+
+impl NetworkPrefix for Ipv4Net {
+    fn up(&self) -> Option<Self> {
+        self.supernet()
+    }
+
+    fn of_network(network: &Network) -> Vec<Self> {
+        let (Some(Ok(start)), Some(Ok(end))) = (
+            network.start_address.as_ref().map(|a| a.parse()),
+            network.end_address.as_ref().map(|a| a.parse()),
+        ) else {
+            return vec![];
+        };
+        Ipv4Subnets::new(start, end, 0).collect()
+    }
+}
+
+impl NetworkPrefix for Ipv6Net {
+    fn up(&self) -> Option<Self> {
+        self.supernet()
+    }
+
+    fn of_network(network: &Network) -> Vec<Self> {
+        let (Some(Ok(start)), Some(Ok(end))) = (
+            network.start_address.as_ref().map(|a| a.parse()),
+            network.end_address.as_ref().map(|a| a.parse()),
+        ) else {
+            return vec![];
+        };
+        Ipv6Subnets::new(start, end, 0).collect()
+    }
+}
+
+type Networks<P> = PrefixMap<P, Arc<RdapResponse>>;
+
+/// Finds the networks related to the most specific network containing the prefix.
+///
+/// Returns `None` if no network contains the prefix.
+pub(crate) fn related_networks<P: NetworkPrefix>(
+    networks: &Networks<P>,
+    prefix: &P,
+    relation: NetworkRelation,
+) -> Option<Vec<Arc<RdapResponse>>> {
+    let (prefix, network) = networks.get_lpm(prefix)?;
+    let related = match relation {
+        NetworkRelation::Up => parent(networks, prefix, network)
+            .map(|(_, parent)| parent.clone())
+            .into_iter()
+            .collect(),
+        NetworkRelation::Top => networks
+            .get_spm(prefix)
+            .map(|(_, top)| top.clone())
+            .into_iter()
+            .collect(),
+        NetworkRelation::Down => descendants(networks, network)
+            .into_iter()
+            .filter(|(prefix, child)| {
+                parent(networks, prefix, child).is_some_and(|(_, p)| Arc::ptr_eq(p, network))
+            })
+            .map(|(_, child)| child.clone())
+            .collect(),
+        NetworkRelation::Bottom => {
+            let descendants = descendants(networks, network);
+            let parents = descendants
+                .iter()
+                .filter_map(|(prefix, child)| parent(networks, prefix, child))
+                .map(|(_, parent)| Arc::as_ptr(parent))
+                .collect::<HashSet<*const RdapResponse>>();
+            descendants
+                .into_iter()
+                .filter(|(_, child)| !parents.contains(&Arc::as_ptr(child)))
+                .map(|(_, child)| child.clone())
+                .collect()
+        }
+    };
+    Some(related)
+}
+
+/// Finds the relations for which the most specific network containing the
+/// prefix has related networks.
+pub(crate) fn network_relations<P: NetworkPrefix>(
+    networks: &Networks<P>,
+    prefix: &P,
+) -> Vec<NetworkRelation> {
+    let Some((prefix, network)) = networks.get_lpm(prefix) else {
+        return vec![];
+    };
+    let mut relations = vec![];
+    if parent(networks, prefix, network).is_some() {
+        relations.extend([NetworkRelation::Up, NetworkRelation::Top]);
+    }
+    if !descendants(networks, network).is_empty() {
+        relations.extend([NetworkRelation::Down, NetworkRelation::Bottom]);
+    }
+    relations
+}
+
+/// Finds the closest network containing the network found under the prefix.
+fn parent<'a, P: NetworkPrefix>(
+    networks: &'a Networks<P>,
+    prefix: &P,
+    network: &Arc<RdapResponse>,
+) -> Option<(&'a P, &'a Arc<RdapResponse>)> {
+    let mut current = *prefix;
+    while let Some(up) = current.up() {
+        let (found_prefix, found) = networks.get_lpm(&up)?;
+        if !Arc::ptr_eq(found, network) {
+            return Some((found_prefix, found));
+        }
+        current = *found_prefix;
+    }
+    None
+}
+
+/// Finds all the networks within a network, each once with one of its prefixes.
+fn descendants<'a, P: NetworkPrefix>(
+    networks: &'a Networks<P>,
+    network: &Arc<RdapResponse>,
+) -> Vec<(&'a P, &'a Arc<RdapResponse>)> {
+    let RdapResponse::Network(net) = network.as_ref() else {
+        return vec![];
+    };
+    let mut seen = HashSet::from([Arc::as_ptr(network)]);
+    P::of_network(net)
+        .iter()
+        .flat_map(|prefix| networks.children(prefix))
+        .filter(|(_, child)| seen.insert(Arc::as_ptr(child)))
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::sync::Arc;
+
+    use {
+        icann_rdap_common::{
+            prelude::{ObjectCommonFields, ToResponse},
+            response::{Network, RdapResponse},
+        },
+        ipnet::Ipv4Net,
+        prefix_trie::PrefixMap,
+    };
+
+    use crate::storage::NetworkRelation;
+
+    use super::{network_relations, related_networks, NetworkPrefix};
+
+    fn networks() -> PrefixMap<Ipv4Net, Arc<RdapResponse>> {
+        let mut networks = PrefixMap::new();
+        for (start, end, handle) in [
+            ("10.0.0.0", "10.255.255.255", "NET-10"),
+            ("10.0.0.0", "10.0.2.255", "NET-10-0"),
+            ("10.0.0.0", "10.0.0.255", "NET-10-0-0"),
+            ("10.0.1.128", "10.0.1.255", "NET-10-0-1"),
+            ("10.1.0.0", "10.1.255.255", "NET-10-1"),
+        ] {
+            let mut network = Network::builder()
+                .cidr("0.0.0.0/0")
+                .handle(handle)
+                .build()
+                .expect("cidr parsing");
+            network.start_address = Some(start.to_string());
+            network.end_address = Some(end.to_string());
+            let prefixes = Ipv4Net::of_network(&network);
+            let response = Arc::new(network.to_response());
+            for prefix in prefixes {
+                networks.insert(prefix, response.clone());
+            }
+        }
+        networks
+    }
+
+    fn handles(networks: Option<Vec<Arc<RdapResponse>>>) -> Vec<String> {
+        let mut handles = networks
+            .expect("network found")
+            .iter()
+            .filter_map(|n| match n.as_ref() {
+                RdapResponse::Network(n) => n.handle().map(str::to_string),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+        handles.sort();
+        handles
+    }
+
+    fn prefix(cidr: &str) -> Ipv4Net {
+        cidr.parse().expect("parsing cidr")
+    }
+
+    #[test]
+    fn GIVEN_nested_networks_WHEN_up_THEN_closest_containing_network() {
+        // GIVEN
+        let networks = networks();
+
+        // WHEN
+        let from_child = related_networks(&networks, &prefix("10.0.0.0/24"), NetworkRelation::Up);
+        let from_multi_cidr =
+            related_networks(&networks, &prefix("10.0.2.0/24"), NetworkRelation::Up);
+        let from_top = related_networks(&networks, &prefix("10.0.0.0/8"), NetworkRelation::Up);
+
+        // THEN
+        assert_eq!(handles(from_child), ["NET-10-0"]);
+        assert_eq!(handles(from_multi_cidr), ["NET-10"]);
+        assert!(handles(from_top).is_empty());
+    }
+
+    #[test]
+    fn GIVEN_nested_networks_WHEN_top_THEN_least_specific_network() {
+        // GIVEN
+        let networks = networks();
+
+        // WHEN
+        let actual = related_networks(&networks, &prefix("10.0.1.200/32"), NetworkRelation::Top);
+
+        // THEN
+        assert_eq!(handles(actual), ["NET-10"]);
+    }
+
+    #[test]
+    fn GIVEN_nested_networks_WHEN_down_THEN_only_direct_children() {
+        // GIVEN
+        let networks = networks();
+
+        // WHEN
+        let actual = related_networks(&networks, &prefix("10.0.0.0/8"), NetworkRelation::Down);
+
+        // THEN
+        assert_eq!(handles(actual), ["NET-10-0", "NET-10-1"]);
+    }
+
+    #[test]
+    fn GIVEN_nested_networks_WHEN_bottom_THEN_only_networks_without_children() {
+        // GIVEN
+        let networks = networks();
+
+        // WHEN
+        let actual = related_networks(&networks, &prefix("10.0.0.0/8"), NetworkRelation::Bottom);
+
+        // THEN
+        assert_eq!(handles(actual), ["NET-10-0-0", "NET-10-0-1", "NET-10-1"]);
+    }
+
+    #[test]
+    fn GIVEN_no_containing_network_WHEN_related_THEN_none() {
+        // GIVEN
+        let networks = networks();
+
+        // WHEN
+        let actual = related_networks(&networks, &prefix("192.0.2.0/24"), NetworkRelation::Up);
+
+        // THEN
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn GIVEN_nested_networks_WHEN_network_relations_THEN_relations_with_networks() {
+        // GIVEN
+        let networks = networks();
+
+        // WHEN
+        let of_top = network_relations(&networks, &prefix("10.0.0.0/8"));
+        let of_middle = network_relations(&networks, &prefix("10.0.0.0/23"));
+        let of_bottom = network_relations(&networks, &prefix("10.1.0.0/16"));
+        let of_none = network_relations(&networks, &prefix("192.0.2.0/24"));
+
+        // THEN
+        assert_eq!(of_top, [NetworkRelation::Down, NetworkRelation::Bottom]);
+        assert_eq!(
+            of_middle,
+            [
+                NetworkRelation::Up,
+                NetworkRelation::Top,
+                NetworkRelation::Down,
+                NetworkRelation::Bottom
+            ]
+        );
+        assert_eq!(of_bottom, [NetworkRelation::Up, NetworkRelation::Top]);
+        assert!(of_none.is_empty());
+    }
+}
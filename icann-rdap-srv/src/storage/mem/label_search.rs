@@ -4,6 +4,15 @@ use ab_radix_trie::Trie;
 
 use crate::error::RdapServerError;
 
+/// Label separators for domain names.
+pub(crate) const DNS_SEPARATORS: &[char] = &['.'];
+
+/// Label separators for handles.
+pub(crate) const HANDLE_SEPARATORS: &[char] = &['.', '-', '_'];
+
+/// Label separators for natural names.
+pub(crate) const NAME_SEPARATORS: &[char] = &[' ', '-'];
+
 /// A structure for searching strings as labels, such as DNS labels in domain names as specified in RFC 9082.
 /// For RDAP, type T is likely RdapResponse or Arc<RdapResponse>.
 pub struct SearchLabels<T: Clone> {
@@ -18,7 +27,7 @@ impl<T: Clone> SearchLabels<T> {
     pub(crate) fn new_dns_labels() -> Self {
         Self {
             label_suffixes: HashMap::new(),
-            separaters: vec!['.'],
+            separaters: DNS_SEPARATORS.to_vec(),
         }
     }
 
@@ -27,7 +36,7 @@ impl<T: Clone> SearchLabels<T> {
     pub(crate) fn new_handle_labels() -> Self {
         Self {
             label_suffixes: HashMap::new(),
-            separaters: vec!['.', '-', '_'],
+            separaters: HANDLE_SEPARATORS.to_vec(),
         }
     }
 
@@ -36,7 +45,7 @@ impl<T: Clone> SearchLabels<T> {
     pub(crate) fn new_name_labels() -> Self {
         Self {
             label_suffixes: HashMap::new(),
-            separaters: vec![' ', '-'],
+            separaters: NAME_SEPARATORS.to_vec(),
         }
     }
 
@@ -67,41 +76,10 @@ impl<T: Clone> SearchLabels<T> {
 
     /// Search values based on a label search
     pub(crate) fn search(&self, search: &str) -> Result<Vec<T>, RdapServerError> {
-        // search string is invalid if it doesn't have only one asterisk ('*')
-        if search.chars().filter(|c| *c == '*').count() != 1 {
-            return Err(RdapServerError::InvalidArg(
-                "Search string must contain one and only one asterisk ('*')".to_string(),
-            ));
-        }
-        // asterisk must not be followed by a character other than dot ('.')
-        let star = search
-            .find('*')
-            .expect("internal error. previous check should have caught this");
-        if star != search.chars().count() - 1
-            && search
-                .chars()
-                .nth(star + 1)
-                .expect("should have been short circuited")
-                != '.'
-        {
-            return Err(RdapServerError::InvalidArg(
-                "Search string asterisk ('*') must terminate domain label".to_string(),
-            ));
-        }
-
-        let parts = search
-            .split_once('*')
-            .expect("internal error. previous check should insure there is an asterisk");
+        let (prefix, suffix) = split_search(search)?;
 
-        // this is a limitation of the trie in that it requires a prefix
-        if parts.0.is_empty() {
-            return Err(RdapServerError::InvalidArg(
-                "Search string must have a prefix".to_string(),
-            ));
-        }
-
-        if let Some(trie) = self.label_suffixes.get(parts.1.trim_start_matches('.')) {
-            if let Some(entries) = trie.get_suffixes_values(parts.0) {
+        if let Some(trie) = self.label_suffixes.get(suffix) {
+            if let Some(entries) = trie.get_suffixes_values(prefix) {
                 if !entries.is_empty() {
                     let values = entries
                         .iter()
@@ -116,12 +94,84 @@ impl<T: Clone> SearchLabels<T> {
     }
 }
 
+/// Splits a partial string search into the text before the asterisk and the
+/// label suffix after it (without the leading separator).
+///
+/// Returns an error if the search string is not a valid partial string search.
+pub(crate) fn split_search(search: &str) -> Result<(&str, &str), RdapServerError> {
+    // search string is invalid if it doesn't have only one asterisk ('*')
+    if search.chars().filter(|c| *c == '*').count() != 1 {
+        return Err(RdapServerError::InvalidArg(
+            "Search string must contain one and only one asterisk ('*')".to_string(),
+        ));
+    }
+    // asterisk must not be followed by a character other than dot ('.')
+    let star = search
+        .find('*')
+        .expect("internal error. previous check should have caught this");
+    if star != search.chars().count() - 1
+        && search
+            .chars()
+            .nth(star + 1)
+            .expect("should have been short circuited")
+            != '.'
+    {
+        return Err(RdapServerError::InvalidArg(
+            "Search string asterisk ('*') must terminate domain label".to_string(),
+        ));
+    }
+
+    let parts = search
+        .split_once('*')
+        .expect("internal error. previous check should insure there is an asterisk");
+
+    // this is a limitation of the trie in that it requires a prefix
+    if parts.0.is_empty() {
+        return Err(RdapServerError::InvalidArg(
+            "Search string must have a prefix".to_string(),
+        ));
+    }
+
+    Ok((parts.0, parts.1.trim_start_matches('.')))
+}
+
+/// Converts a partial string search into SQL `LIKE` patterns, one per separator,
+/// that match the same strings as [SearchLabels::search].
+///
+/// The patterns use backslash (`\\`) as the escape character.
+pub(crate) fn like_patterns(
+    search: &str,
+    separators: &[char],
+) -> Result<Vec<String>, RdapServerError> {
+    let (prefix, suffix) = split_search(search)?;
+    let prefix = escape_like(prefix);
+    if suffix.is_empty() {
+        return Ok(vec![format!("{prefix}%")]);
+    }
+    let suffix = escape_like(suffix);
+    Ok(separators
+        .iter()
+        .map(|sep| format!("{prefix}%{}{suffix}", escape_like(&sep.to_string())))
+        .collect())
+}
+
+fn escape_like(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
 #[cfg(test)]
 mod tests {
 
     use ab_radix_trie::{Entry, Trie};
 
-    use super::SearchLabels;
+    use super::{like_patterns, SearchLabels, DNS_SEPARATORS, HANDLE_SEPARATORS};
 
     #[test]
     fn test_inserting_domain_names() {
@@ -422,4 +472,55 @@ mod tests {
         assert_eq!(actual.len(), 1);
         assert!(actual.contains(&"Bob Person".to_string()));
     }
+
+    #[test]
+    fn test_like_patterns_for_domain_names() {
+        // GIVEN a domain name search with a suffix
+        let search = "foo*.example.com";
+
+        // WHEN converted to like patterns
+        let actual = like_patterns(search, DNS_SEPARATORS).expect("valid search");
+
+        // THEN there is a pattern for the dot separator
+        assert_eq!(actual, vec!["foo%.example.com"]);
+    }
+
+    #[test]
+    fn test_like_patterns_without_suffix() {
+        // GIVEN a search without a suffix
+        let search = "foo*";
+
+        // WHEN converted to like patterns
+        let actual = like_patterns(search, HANDLE_SEPARATORS).expect("valid search");
+
+        // THEN there is a single prefix pattern
+        assert_eq!(actual, vec!["foo%"]);
+    }
+
+    #[test]
+    fn test_like_patterns_escape_wildcards() {
+        // GIVEN a handle search containing like wildcards
+        let search = "foo_1%*.bar";
+
+        // WHEN converted to like patterns
+        let actual = like_patterns(search, HANDLE_SEPARATORS).expect("valid search");
+
+        // THEN the wildcards are escaped, and there is a pattern for each separator
+        assert_eq!(
+            actual,
+            vec![r"foo\_1\%%.bar", r"foo\_1\%%-bar", r"foo\_1\%%\_bar"]
+        );
+    }
+
+    #[test]
+    fn test_like_patterns_with_invalid_search() {
+        // GIVEN a search without an asterisk
+        let search = "foo.example.com";
+
+        // WHEN converted to like patterns
+        let actual = like_patterns(search, DNS_SEPARATORS);
+
+        // THEN it is an error
+        assert!(actual.is_err());
+    }
 }
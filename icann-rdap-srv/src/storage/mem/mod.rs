@@ -1,6 +1,8 @@
 #![allow(dead_code)] // TODO remove
 
 pub mod config;
-mod label_search;
+pub(crate) mod hierarchy;
+pub(crate) mod label_search;
 pub mod ops;
+pub(crate) mod reverse_search;
 pub mod tx;
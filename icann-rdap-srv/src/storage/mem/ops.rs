@@ -4,10 +4,10 @@ use {
     async_trait::async_trait,
     btree_range_map::RangeMap,
     icann_rdap_common::{
-        prelude::ToResponse,
+        prelude::{ExtensionId, ToResponse},
         response::{
-            Domain, DomainSearchResults, Entity, EntitySearchResults, Nameserver,
-            NameserverSearchResults, RdapResponse,
+            Autnum, AutnumSearchResults, Domain, DomainSearchResults, Entity, EntitySearchResults,
+            Nameserver, NameserverSearchResults, Network, NetworkSearchResults, RdapResponse,
         },
     },
     ipnet::{IpNet, Ipv4Net, Ipv6Net},
@@ -17,11 +17,17 @@ use {
 
 use crate::{
     error::RdapServerError,
-    rdap::response::{NOT_FOUND, NOT_IMPLEMENTED},
-    storage::{CommonConfig, StoreOps, TxHandle},
+    rdap::response::{BAD_REQUEST, NOT_FOUND, NOT_IMPLEMENTED},
+    storage::{CommonConfig, NetworkRelation, ReverseSearch, StoreOps, TxHandle},
 };
 
-use super::{config::MemConfig, label_search::SearchLabels, tx::MemTx};
+use super::{
+    config::MemConfig,
+    hierarchy::{network_relations, related_networks},
+    label_search::SearchLabels,
+    reverse_search::ReverseIndex,
+    tx::MemTx,
+};
 
 #[derive(Clone)]
 pub struct Mem {
@@ -39,7 +45,16 @@ pub struct Mem {
     pub(crate) entities: Arc<RwLock<HashMap<String, Arc<RdapResponse>>>>,
     pub(crate) entities_by_handle: Arc<RwLock<SearchLabels<Arc<RdapResponse>>>>,
     pub(crate) entities_by_full_name: Arc<RwLock<SearchLabels<Arc<RdapResponse>>>>,
+    pub(crate) networks_by_handle: Arc<RwLock<SearchLabels<Vec<Arc<RdapResponse>>>>>,
+    pub(crate) networks_by_name: Arc<RwLock<SearchLabels<Vec<Arc<RdapResponse>>>>>,
+    pub(crate) autnums_by_handle: Arc<RwLock<SearchLabels<Vec<Arc<RdapResponse>>>>>,
+    pub(crate) autnums_by_name: Arc<RwLock<SearchLabels<Vec<Arc<RdapResponse>>>>>,
     pub(crate) srvhelps: Arc<RwLock<HashMap<String, Arc<RdapResponse>>>>,
+    pub(crate) domains_by_entity: Arc<RwLock<ReverseIndex>>,
+    pub(crate) nameservers_by_entity: Arc<RwLock<ReverseIndex>>,
+    pub(crate) entities_by_entity: Arc<RwLock<ReverseIndex>>,
+    pub(crate) networks_by_entity: Arc<RwLock<ReverseIndex>>,
+    pub(crate) autnums_by_entity: Arc<RwLock<ReverseIndex>>,
     pub(crate) config: MemConfig,
 }
 
@@ -60,7 +75,16 @@ impl Mem {
             entities: <_>::default(),
             entities_by_handle: Arc::new(RwLock::new(SearchLabels::handle_labels().build())),
             entities_by_full_name: Arc::new(RwLock::new(SearchLabels::name_labels().build())),
+            networks_by_handle: Arc::new(RwLock::new(SearchLabels::handle_labels().build())),
+            networks_by_name: Arc::new(RwLock::new(SearchLabels::name_labels().build())),
+            autnums_by_handle: Arc::new(RwLock::new(SearchLabels::handle_labels().build())),
+            autnums_by_name: Arc::new(RwLock::new(SearchLabels::name_labels().build())),
             srvhelps: <_>::default(),
+            domains_by_entity: <_>::default(),
+            nameservers_by_entity: <_>::default(),
+            entities_by_entity: <_>::default(),
+            networks_by_entity: <_>::default(),
+            autnums_by_entity: <_>::default(),
             config,
         }
     }
@@ -76,12 +100,24 @@ impl Default for Mem {
     }
 }
 
+/// Parses a network given by an IP address or CIDR.
+fn parse_netid(netid: &str) -> Option<IpNet> {
+    match netid.parse::<IpAddr>() {
+        Ok(addr) => Some(IpNet::from(addr)),
+        Err(_) => IpNet::from_str(netid).ok(),
+    }
+}
+
 #[async_trait]
 impl StoreOps for Mem {
     async fn init(&self) -> Result<(), RdapServerError> {
         Ok(())
     }
 
+    async fn ping(&self) -> Result<(), RdapServerError> {
+        Ok(())
+    }
+
     async fn new_tx(&self) -> Result<Box<dyn TxHandle>, RdapServerError> {
         Ok(Box::new(MemTx::new(self).await))
     }
@@ -181,6 +217,62 @@ impl StoreOps for Mem {
         }
     }
 
+    async fn get_related_networks(
+        &self,
+        netid: &str,
+        relation: NetworkRelation,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.network_hierarchy_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let Some(net) = parse_netid(netid) else {
+            return Ok(BAD_REQUEST.clone());
+        };
+        let related = match net {
+            IpNet::V4(ipv4net) => related_networks(&*self.ip4.read().await, &ipv4net, relation),
+            IpNet::V6(ipv6net) => related_networks(&*self.ip6.read().await, &ipv6net, relation),
+        };
+        let Some(related) = related else {
+            return Ok(NOT_FOUND.clone());
+        };
+        let mut networks = related
+            .into_iter()
+            .map(Arc::<RdapResponse>::unwrap_or_clone)
+            .filter_map(|n| match n {
+                RdapResponse::Network(net) => Some(*net),
+                _ => None,
+            })
+            .collect::<Vec<Network>>();
+        let response = match relation {
+            NetworkRelation::Up | NetworkRelation::Top => match networks.pop() {
+                Some(network) => network.to_response(),
+                None => NOT_FOUND.clone(),
+            },
+            NetworkRelation::Down | NetworkRelation::Bottom => NetworkSearchResults::response_obj()
+                .results(networks)
+                .extension(ExtensionId::RirSearch1.to_extension())
+                .extension(ExtensionId::Ips.to_extension())
+                .build()
+                .to_response(),
+        };
+        Ok(response)
+    }
+
+    async fn get_network_relations(
+        &self,
+        netid: &str,
+    ) -> Result<Vec<NetworkRelation>, RdapServerError> {
+        if !self.config.common_config.network_hierarchy_enable {
+            return Ok(vec![]);
+        }
+        let relations = match parse_netid(netid) {
+            Some(IpNet::V4(ipv4net)) => network_relations(&*self.ip4.read().await, &ipv4net),
+            Some(IpNet::V6(ipv6net)) => network_relations(&*self.ip6.read().await, &ipv6net),
+            None => vec![],
+        };
+        Ok(relations)
+    }
+
     async fn get_srv_help(&self, host: Option<&str>) -> Result<RdapResponse, RdapServerError> {
         let host = host.unwrap_or("..default");
         let srvhelps = self.srvhelps.read().await;
@@ -367,4 +459,248 @@ impl StoreOps for Mem {
             .to_response();
         Ok(response)
     }
+
+    async fn search_networks_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.network_search_by_handle_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let networks_by_handle = self.networks_by_handle.read().await;
+        let results = networks_by_handle
+            .search(handle)
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .map(Arc::<RdapResponse>::unwrap_or_clone)
+            .filter_map(|r| match r {
+                RdapResponse::Network(net) => Some(*net),
+                _ => None,
+            })
+            .collect::<Vec<Network>>();
+        let response = NetworkSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::RirSearch1.to_extension())
+            .extension(ExtensionId::Ips.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn search_networks_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.network_search_by_name_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let networks_by_name = self.networks_by_name.read().await;
+        let results = networks_by_name
+            .search(name)
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .map(Arc::<RdapResponse>::unwrap_or_clone)
+            .filter_map(|r| match r {
+                RdapResponse::Network(net) => Some(*net),
+                _ => None,
+            })
+            .collect::<Vec<Network>>();
+        let response = NetworkSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::RirSearch1.to_extension())
+            .extension(ExtensionId::Ips.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn search_autnums_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.autnum_search_by_handle_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let autnums_by_handle = self.autnums_by_handle.read().await;
+        let results = autnums_by_handle
+            .search(handle)
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .map(Arc::<RdapResponse>::unwrap_or_clone)
+            .filter_map(|r| match r {
+                RdapResponse::Autnum(a) => Some(*a),
+                _ => None,
+            })
+            .collect::<Vec<Autnum>>();
+        let response = AutnumSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::RirSearch1.to_extension())
+            .extension(ExtensionId::Autnums.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn search_autnums_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.autnum_search_by_name_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let autnums_by_name = self.autnums_by_name.read().await;
+        let results = autnums_by_name
+            .search(name)
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .map(Arc::<RdapResponse>::unwrap_or_clone)
+            .filter_map(|r| match r {
+                RdapResponse::Autnum(a) => Some(*a),
+                _ => None,
+            })
+            .collect::<Vec<Autnum>>();
+        let response = AutnumSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::RirSearch1.to_extension())
+            .extension(ExtensionId::Autnums.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn reverse_search_domains(
+        &self,
+        search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.reverse_search_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let domains_by_entity = self.domains_by_entity.read().await;
+        let results = domains_by_entity
+            .search(search)
+            .into_iter()
+            .map(Arc::<RdapResponse>::unwrap_or_clone)
+            .filter_map(|r| match r {
+                RdapResponse::Domain(d) => Some(*d),
+                _ => None,
+            })
+            .collect::<Vec<Domain>>();
+        let response = DomainSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::ReverseSearch.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn reverse_search_nameservers(
+        &self,
+        search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.reverse_search_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let nameservers_by_entity = self.nameservers_by_entity.read().await;
+        let results = nameservers_by_entity
+            .search(search)
+            .into_iter()
+            .map(Arc::<RdapResponse>::unwrap_or_clone)
+            .filter_map(|r| match r {
+                RdapResponse::Nameserver(ns) => Some(*ns),
+                _ => None,
+            })
+            .collect::<Vec<Nameserver>>();
+        let response = NameserverSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::ReverseSearch.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn reverse_search_entities(
+        &self,
+        search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.reverse_search_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let entities_by_entity = self.entities_by_entity.read().await;
+        let results = entities_by_entity
+            .search(search)
+            .into_iter()
+            .map(Arc::<RdapResponse>::unwrap_or_clone)
+            .filter_map(|r| match r {
+                RdapResponse::Entity(ent) => Some(*ent),
+                _ => None,
+            })
+            .collect::<Vec<Entity>>();
+        let response = EntitySearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::ReverseSearch.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn reverse_search_networks(
+        &self,
+        search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.reverse_search_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let networks_by_entity = self.networks_by_entity.read().await;
+        let results = networks_by_entity
+            .search(search)
+            .into_iter()
+            .map(Arc::<RdapResponse>::unwrap_or_clone)
+            .filter_map(|r| match r {
+                RdapResponse::Network(net) => Some(*net),
+                _ => None,
+            })
+            .collect::<Vec<Network>>();
+        let response = NetworkSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::ReverseSearch.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn reverse_search_autnums(
+        &self,
+        search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.reverse_search_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let autnums_by_entity = self.autnums_by_entity.read().await;
+        let results = autnums_by_entity
+            .search(search)
+            .into_iter()
+            .map(Arc::<RdapResponse>::unwrap_or_clone)
+            .filter_map(|r| match r {
+                RdapResponse::Autnum(a) => Some(*a),
+                _ => None,
+            })
+            .collect::<Vec<Autnum>>();
+        let response = AutnumSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::ReverseSearch.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn object_counts(&self) -> Vec<(&'static str, usize)> {
+        vec![
+            ("autnums", self.autnums.read().await.range_count()),
+            ("ip4", self.ip4.read().await.iter().count()),
+            ("ip6", self.ip6.read().await.iter().count()),
+            ("domains", self.domains.read().await.len()),
+            ("idns", self.idns.read().await.len()),
+            ("nameservers", self.nameservers.read().await.len()),
+            ("entities", self.entities.read().await.len()),
+            ("srvhelps", self.srvhelps.read().await.len()),
+        ]
+    }
 }
@@ -0,0 +1,350 @@
+//! Indexes of objects by their related entities, for reverse searches as specified in RFC 9536.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
+};
+
+use icann_rdap_common::response::{Entity, RdapResponse};
+
+use crate::storage::ReverseSearch;
+
+/// The searchable properties of an entity related to an object, all in lowercase.
+#[derive(Debug, Clone)]
+struct RelatedEntity {
+    handle: Option<String>,
+    full_name: Option<String>,
+    emails: Vec<String>,
+    roles: Vec<String>,
+}
+
+impl RelatedEntity {
+    fn new(entity: &Entity) -> Self {
+        let contact = entity.contact();
+        Self {
+            handle: entity
+                .object_common
+                .handle
+                .as_deref()
+                .map(str::to_lowercase),
+            full_name: contact
+                .as_ref()
+                .and_then(|contact| contact.full_name())
+                .map(str::to_lowercase),
+            emails: contact
+                .as_ref()
+                .map(|contact| {
+                    contact
+                        .emails()
+                        .iter()
+                        .map(|email| email.email().to_lowercase())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            roles: entity.roles().iter().map(|r| r.to_lowercase()).collect(),
+        }
+    }
+
+    /// Returns true if this entity matches all the conditions of the search.
+    fn matches(&self, search: &ReverseSearch) -> bool {
+        let property_matches = |value: Option<&str>, search: Option<&str>| match search {
+            Some(search) => value.is_some_and(|value| value_matches(value, search)),
+            None => true,
+        };
+        property_matches(self.handle.as_deref(), search.handle.as_deref())
+            && property_matches(self.full_name.as_deref(), search.full_name.as_deref())
+            && search
+                .email
+                .as_deref()
+                .is_none_or(|search| self.emails.iter().any(|email| value_matches(email, search)))
+            && search
+                .role
+                .as_deref()
+                .is_none_or(|role| self.roles.contains(&role.to_lowercase()))
+    }
+}
+
+/// Matches a lowercase value against a search value, which may end with `*`.
+fn value_matches(value: &str, search: &str) -> bool {
+    let search = search.to_lowercase();
+    match search.strip_suffix('*') {
+        Some(prefix) => value.starts_with(prefix),
+        None => value == search,
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Related {
+    object: Arc<RdapResponse>,
+    entities: Vec<RelatedEntity>,
+}
+
+/// Maps a lowercase property of a related entity to the keys of the objects.
+type PropertyIndex = BTreeMap<String, BTreeSet<String>>;
+
+/// An index of objects by the handles, full names and email addresses of
+/// the entities directly related to them.
+///
+/// Objects are identified by a key unique to their object class, such as the
+/// ldhName of a domain, so that indexing an object again replaces it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ReverseIndex {
+    objects: HashMap<String, Related>,
+    by_handle: PropertyIndex,
+    by_full_name: PropertyIndex,
+    by_email: PropertyIndex,
+}
+
+impl ReverseIndex {
+    /// Indexes an object by its related entities.
+    pub(crate) fn insert(&mut self, key: &str, entities: &[Entity], object: Arc<RdapResponse>) {
+        self.remove(key);
+        let entities = entities
+            .iter()
+            .map(RelatedEntity::new)
+            .collect::<Vec<RelatedEntity>>();
+        for entity in &entities {
+            if let Some(handle) = &entity.handle {
+                add_key(&mut self.by_handle, handle, key);
+            }
+            if let Some(full_name) = &entity.full_name {
+                add_key(&mut self.by_full_name, full_name, key);
+            }
+            for email in &entity.emails {
+                add_key(&mut self.by_email, email, key);
+            }
+        }
+        self.objects
+            .insert(key.to_string(), Related { object, entities });
+    }
+
+    /// Removes an object from the index.
+    pub(crate) fn remove(&mut self, key: &str) {
+        let Some(related) = self.objects.remove(key) else {
+            return;
+        };
+        for entity in related.entities {
+            if let Some(handle) = entity.handle {
+                remove_key(&mut self.by_handle, &handle, key);
+            }
+            if let Some(full_name) = entity.full_name {
+                remove_key(&mut self.by_full_name, &full_name, key);
+            }
+            for email in entity.emails {
+                remove_key(&mut self.by_email, &email, key);
+            }
+        }
+    }
+
+    /// Finds the objects with a related entity matching all the conditions
+    /// of the search, ordered by their keys.
+    ///
+    /// A search with no condition other than the role finds nothing.
+    pub(crate) fn search(&self, search: &ReverseSearch) -> Vec<Arc<RdapResponse>> {
+        let candidates = if let Some(handle) = &search.handle {
+            candidate_keys(&self.by_handle, handle)
+        } else if let Some(full_name) = &search.full_name {
+            candidate_keys(&self.by_full_name, full_name)
+        } else if let Some(email) = &search.email {
+            candidate_keys(&self.by_email, email)
+        } else {
+            return vec![];
+        };
+        candidates
+            .into_iter()
+            .filter_map(|key| self.objects.get(key))
+            .filter(|related| related.entities.iter().any(|e| e.matches(search)))
+            .map(|related| related.object.clone())
+            .collect()
+    }
+}
+
+fn add_key(index: &mut PropertyIndex, value: &str, key: &str) {
+    index
+        .entry(value.to_string())
+        .or_default()
+        .insert(key.to_string());
+}
+
+fn remove_key(index: &mut PropertyIndex, value: &str, key: &str) {
+    if let Some(keys) = index.get_mut(value) {
+        keys.remove(key);
+        if keys.is_empty() {
+            index.remove(value);
+        }
+    }
+}
+
+/// Gets the keys of the objects with a property matching the search value.
+fn candidate_keys<'a>(index: &'a PropertyIndex, search: &str) -> BTreeSet<&'a String> {
+    let search = search.to_lowercase();
+    match search.strip_suffix('*') {
+        Some(prefix) => index
+            .range(prefix.to_string()..)
+            .take_while(|(value, _)| value.starts_with(prefix))
+            .flat_map(|(_, keys)| keys)
+            .collect(),
+        None => index.get(&search).into_iter().flatten().collect(),
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::sync::Arc;
+
+    use icann_rdap_common::{
+        contact::{Contact, Email},
+        prelude::{ObjectCommonFields, ToResponse},
+        response::{Domain, Entity, RdapResponse},
+    };
+
+    use crate::storage::ReverseSearch;
+
+    use super::ReverseIndex;
+
+    fn entity(handle: &str, full_name: &str, email: &str, role: &str) -> Entity {
+        Entity::builder()
+            .handle(handle)
+            .contact(
+                Contact::builder()
+                    .full_name(full_name)
+                    .email(Email::builder().email(email).build())
+                    .build(),
+            )
+            .role(role.to_string())
+            .build()
+    }
+
+    fn index() -> ReverseIndex {
+        let mut index = ReverseIndex::default();
+        let domains = [
+            (
+                "foo.example",
+                entity("BOB-1", "Bob Smurd", "bob@example.com", "registrant"),
+            ),
+            (
+                "bar.example",
+                entity("BOB-1", "Bob Smurd", "bob@example.com", "technical"),
+            ),
+            (
+                "baz.example",
+                entity("ALICE-1", "Alice Smurd", "alice@example.com", "registrant"),
+            ),
+        ];
+        for (ldh, entity) in domains {
+            let domain = Domain::builder().ldh_name(ldh).entity(entity).build();
+            index.insert(
+                ldh,
+                domain.entities(),
+                Arc::new(domain.clone().to_response()),
+            );
+        }
+        index
+    }
+
+    fn ldh_names(results: Vec<Arc<RdapResponse>>) -> Vec<String> {
+        results
+            .iter()
+            .filter_map(|r| match r.as_ref() {
+                RdapResponse::Domain(d) => d.ldh_name.clone(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn GIVEN_index_WHEN_search_by_handle_THEN_objects_of_entity_found() {
+        // GIVEN
+        let index = index();
+
+        // WHEN
+        let actual = index.search(&ReverseSearch::builder().handle("bob-1").build());
+
+        // THEN
+        assert_eq!(ldh_names(actual), ["bar.example", "foo.example"]);
+    }
+
+    #[test]
+    fn GIVEN_index_WHEN_search_by_full_name_and_role_THEN_only_objects_with_role_found() {
+        // GIVEN
+        let index = index();
+
+        // WHEN
+        let actual = index.search(
+            &ReverseSearch::builder()
+                .full_name("* Smurd")
+                .role("registrant")
+                .build(),
+        );
+
+        // THEN nothing found because the asterisk is only allowed at the end
+        assert!(actual.is_empty());
+
+        // WHEN
+        let actual = index.search(
+            &ReverseSearch::builder()
+                .full_name("Bob*")
+                .role("registrant")
+                .build(),
+        );
+
+        // THEN
+        assert_eq!(ldh_names(actual), ["foo.example"]);
+    }
+
+    #[test]
+    fn GIVEN_index_WHEN_search_by_email_prefix_THEN_all_matches_found() {
+        // GIVEN
+        let index = index();
+
+        // WHEN
+        let actual = index.search(&ReverseSearch::builder().email("*").build());
+
+        // THEN
+        assert_eq!(
+            ldh_names(actual),
+            ["bar.example", "baz.example", "foo.example"]
+        );
+    }
+
+    #[test]
+    fn GIVEN_object_indexed_again_WHEN_search_by_old_entity_THEN_not_found() {
+        // GIVEN
+        let mut index = index();
+        let domain = Domain::builder()
+            .ldh_name("foo.example")
+            .entity(entity(
+                "ALICE-1",
+                "Alice Smurd",
+                "alice@example.com",
+                "registrant",
+            ))
+            .build();
+        index.insert(
+            "foo.example",
+            domain.entities(),
+            Arc::new(domain.clone().to_response()),
+        );
+
+        // WHEN
+        let bob = index.search(&ReverseSearch::builder().handle("BOB-1").build());
+        let alice = index.search(&ReverseSearch::builder().handle("ALICE-1").build());
+
+        // THEN
+        assert_eq!(ldh_names(bob), ["bar.example"]);
+        assert_eq!(ldh_names(alice), ["baz.example", "foo.example"]);
+    }
+
+    #[test]
+    fn GIVEN_index_WHEN_search_by_role_only_THEN_nothing_found() {
+        // GIVEN
+        let index = index();
+
+        // WHEN
+        let actual = index.search(&ReverseSearch::builder().role("registrant").build());
+
+        // THEN
+        assert!(actual.is_empty());
+    }
+}
@@ -1,10 +1,15 @@
-use std::{collections::HashMap, net::IpAddr, str::FromStr, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+    str::FromStr,
+    sync::Arc,
+};
 
 use {
     async_trait::async_trait,
     btree_range_map::RangeMap,
     icann_rdap_common::{
-        prelude::ToResponse,
+        prelude::{ObjectCommonFields, ToResponse},
         response::{Autnum, Domain, Entity, Help, Nameserver, Network, RdapResponse, Rfc9083Error},
     },
     ipnet::{IpSubnets, Ipv4Net, Ipv4Subnets, Ipv6Net, Ipv6Subnets},
@@ -19,7 +24,7 @@ use crate::{
     },
 };
 
-use super::{label_search::SearchLabels, ops::Mem};
+use super::{label_search::SearchLabels, ops::Mem, reverse_search::ReverseIndex};
 
 pub struct MemTx {
     mem: Mem,
@@ -37,46 +42,176 @@ pub struct MemTx {
     entities: HashMap<String, Arc<RdapResponse>>,
     entities_by_handle: SearchLabels<Arc<RdapResponse>>,
     entities_by_full_name: SearchLabels<Arc<RdapResponse>>,
+    networks_by_handle: SearchLabels<Vec<Arc<RdapResponse>>>,
+    networks_by_name: SearchLabels<Vec<Arc<RdapResponse>>>,
+    autnums_by_handle: SearchLabels<Vec<Arc<RdapResponse>>>,
+    autnums_by_name: SearchLabels<Vec<Arc<RdapResponse>>>,
     srvhelps: HashMap<String, Arc<RdapResponse>>,
+    domains_by_entity: ReverseIndex,
+    nameservers_by_entity: ReverseIndex,
+    entities_by_entity: ReverseIndex,
+    networks_by_entity: ReverseIndex,
+    autnums_by_entity: ReverseIndex,
+
+    /// True if objects have been deleted, so the search labels must be indexed again.
+    reindex: bool,
+
+    /// The changes made since the savepoint, if there is one.
+    savepoint: Option<Savepoint>,
+}
+
+/// The changes made since a savepoint, which are only made to the transaction when
+/// the savepoint is released. Each change is first made to an empty transaction,
+/// so changes that cannot be made fail as they would without the savepoint.
+struct Savepoint {
+    scratch: Box<MemTx>,
+    staged: Vec<Staged>,
+}
+
+/// A change made since a savepoint.
+enum Staged {
+    Domain(Box<Domain>),
+    DomainErr(DomainId, Rfc9083Error),
+    Entity(Box<Entity>),
+    EntityErr(EntityId, Rfc9083Error),
+    Nameserver(Box<Nameserver>),
+    NameserverErr(NameserverId, Rfc9083Error),
+    Autnum(Box<Autnum>),
+    AutnumErr(AutnumId, Rfc9083Error),
+    Network(Box<Network>),
+    NetworkErr(NetworkId, Rfc9083Error),
+    SrvHelp(Box<Help>, Option<String>),
+    DeleteDomain(DomainId),
+    DeleteEntity(EntityId),
+    DeleteNameserver(NameserverId),
+    DeleteAutnum(AutnumId),
+    DeleteNetwork(NetworkId),
+    DeleteSrvHelp(Option<String>),
 }
 
 impl MemTx {
     pub async fn new(mem: &Mem) -> Self {
-        let domains = Arc::clone(&mem.domains).read_owned().await.clone();
-        let mut domains_by_name = SearchLabels::dns_labels().build();
-        let domains_by_ns_ip = Arc::clone(&mem.domains_by_ns_ip).read_owned().await.clone();
-        let mut domains_by_ns_ldh_name = SearchLabels::dns_labels().build();
-        let nameservers = Arc::clone(&mem.nameservers).read_owned().await.clone();
-        let mut nameservers_by_name = SearchLabels::dns_labels().build();
-        let nameservers_by_ip = Arc::clone(&mem.nameservers_by_ip)
+        let mut tx = Self::new_truncate(mem);
+        tx.autnums = Arc::clone(&mem.autnums).read_owned().await.clone();
+        tx.ip4 = Arc::clone(&mem.ip4).read_owned().await.clone();
+        tx.ip6 = Arc::clone(&mem.ip6).read_owned().await.clone();
+        tx.domains = Arc::clone(&mem.domains).read_owned().await.clone();
+        tx.domains_by_ns_ip = Arc::clone(&mem.domains_by_ns_ip).read_owned().await.clone();
+        tx.idns = Arc::clone(&mem.idns).read_owned().await.clone();
+        tx.nameservers = Arc::clone(&mem.nameservers).read_owned().await.clone();
+        tx.nameservers_by_ip = Arc::clone(&mem.nameservers_by_ip)
+            .read_owned()
+            .await
+            .clone();
+        tx.entities = Arc::clone(&mem.entities).read_owned().await.clone();
+        tx.srvhelps = Arc::clone(&mem.srvhelps).read_owned().await.clone();
+        tx.domains_by_entity = Arc::clone(&mem.domains_by_entity)
+            .read_owned()
+            .await
+            .clone();
+        tx.nameservers_by_entity = Arc::clone(&mem.nameservers_by_entity)
             .read_owned()
             .await
             .clone();
-        let entities = Arc::clone(&mem.entities).read_owned().await.clone();
-        let mut entities_by_handle = SearchLabels::handle_labels().build();
+        tx.entities_by_entity = Arc::clone(&mem.entities_by_entity)
+            .read_owned()
+            .await
+            .clone();
+        tx.networks_by_entity = Arc::clone(&mem.networks_by_entity)
+            .read_owned()
+            .await
+            .clone();
+        tx.autnums_by_entity = Arc::clone(&mem.autnums_by_entity)
+            .read_owned()
+            .await
+            .clone();
+        tx.index_search_labels();
+        tx
+    }
+
+    pub fn new_truncate(mem: &Mem) -> Self {
+        Self {
+            mem: mem.clone(),
+            autnums: RangeMap::new(),
+            ip4: PrefixMap::new(),
+            ip6: PrefixMap::new(),
+            domains: HashMap::new(),
+            domains_by_name: SearchLabels::dns_labels().build(),
+            domains_by_ns_ip: HashMap::new(),
+            domains_by_ns_ldh_name: SearchLabels::dns_labels().build(),
+            idns: HashMap::new(),
+            nameservers: HashMap::new(),
+            nameservers_by_name: SearchLabels::dns_labels().build(),
+            nameservers_by_ip: HashMap::new(),
+            entities: HashMap::new(),
+            entities_by_handle: SearchLabels::handle_labels().build(),
+            entities_by_full_name: SearchLabels::name_labels().build(),
+            networks_by_handle: SearchLabels::handle_labels().build(),
+            networks_by_name: SearchLabels::name_labels().build(),
+            autnums_by_handle: SearchLabels::handle_labels().build(),
+            autnums_by_name: SearchLabels::name_labels().build(),
+            srvhelps: HashMap::new(),
+            domains_by_entity: ReverseIndex::default(),
+            nameservers_by_entity: ReverseIndex::default(),
+            entities_by_entity: ReverseIndex::default(),
+            networks_by_entity: ReverseIndex::default(),
+            autnums_by_entity: ReverseIndex::default(),
+            reindex: false,
+            savepoint: None,
+        }
+    }
+
+    /// Removes networks that are no longer stored from the reverse search index.
+    fn remove_networks_by_entity(&mut self, removed: &[Arc<RdapResponse>]) {
+        for response in distinct(removed.iter()) {
+            if let RdapResponse::Network(network) = response.as_ref() {
+                if let (Some(start_addr), Some(end_addr)) =
+                    (network.start_address.as_ref(), network.end_address.as_ref())
+                {
+                    self.networks_by_entity
+                        .remove(&format!("{start_addr}-{end_addr}"));
+                }
+            }
+        }
+    }
+
+    /// Indexes the search labels of the objects, for only the searches that are enabled.
+    fn index_search_labels(&mut self) {
+        let common_config = self.mem.config.common_config;
+        self.domains_by_name = SearchLabels::dns_labels().build();
+        self.domains_by_ns_ldh_name = SearchLabels::dns_labels().build();
+        self.nameservers_by_name = SearchLabels::dns_labels().build();
+        self.entities_by_handle = SearchLabels::handle_labels().build();
+        self.entities_by_full_name = SearchLabels::name_labels().build();
+        self.networks_by_handle = SearchLabels::handle_labels().build();
+        self.networks_by_name = SearchLabels::name_labels().build();
+        self.autnums_by_handle = SearchLabels::handle_labels().build();
+        self.autnums_by_name = SearchLabels::name_labels().build();
+        self.reindex = false;
 
         // only do load up domain search labels if search by domain names is supported
-        if mem.config.common_config.domain_search_by_name_enable {
-            for (name, value) in domains.iter() {
-                domains_by_name.insert(name, value.clone());
+        if common_config.domain_search_by_name_enable {
+            for (name, value) in self.domains.iter() {
+                self.domains_by_name.insert(name, value.clone());
             }
         }
 
         // only do load up nameserver search labels if search by nameserver names is supported
-        if mem.config.common_config.nameserver_search_by_name_enable {
-            for (name, value) in nameservers.iter() {
-                nameservers_by_name.insert(name, value.clone());
+        if common_config.nameserver_search_by_name_enable {
+            for (name, value) in self.nameservers.iter() {
+                self.nameservers_by_name.insert(name, value.clone());
             }
         }
 
         // only load up domain search by ns ldh name if supported
-        if mem.config.common_config.domain_search_by_ns_ldh_name_enable {
-            for (_name, value) in domains.iter() {
+        if common_config.domain_search_by_ns_ldh_name_enable {
+            for (_name, value) in self.domains.iter() {
                 if let RdapResponse::Domain(domain) = value.as_ref() {
                     if let Some(nameservers) = domain.nameservers.as_ref() {
                         for ns in nameservers {
                             if let Some(ns_ldh_name) = ns.ldh_name.as_ref() {
-                                domains_by_ns_ldh_name.insert(ns_ldh_name, value.clone());
+                                self.domains_by_ns_ldh_name
+                                    .insert(ns_ldh_name, value.clone());
                             }
                         }
                     }
@@ -85,70 +220,157 @@ impl MemTx {
         }
 
         // only load up entity search by handle if supported
-        if mem.config.common_config.entity_search_by_handle_enable {
-            for (handle, value) in entities.iter() {
-                entities_by_handle.insert(handle, value.clone());
+        if common_config.entity_search_by_handle_enable {
+            for (handle, value) in self.entities.iter() {
+                self.entities_by_handle.insert(handle, value.clone());
             }
         }
 
-        let mut entities_by_full_name = SearchLabels::name_labels().build();
-        if mem.config.common_config.entity_search_by_full_name_enable {
-            for (_handle, value) in entities.iter() {
+        if common_config.entity_search_by_full_name_enable {
+            for (_handle, value) in self.entities.iter() {
                 if let RdapResponse::Entity(entity) = value.as_ref() {
                     if let Some(contact) = entity.contact() {
                         if let Some(full_name) = contact.full_name() {
-                            entities_by_full_name.insert(full_name, value.clone());
+                            self.entities_by_full_name.insert(full_name, value.clone());
                         }
                     }
                 }
             }
         }
 
-        Self {
-            mem: mem.clone(),
-            autnums: Arc::clone(&mem.autnums).read_owned().await.clone(),
-            ip4: Arc::clone(&mem.ip4).read_owned().await.clone(),
-            ip6: Arc::clone(&mem.ip6).read_owned().await.clone(),
-            domains,
-            domains_by_name,
-            domains_by_ns_ip,
-            domains_by_ns_ldh_name,
-            idns: Arc::clone(&mem.idns).read_owned().await.clone(),
-            nameservers,
-            nameservers_by_name,
-            nameservers_by_ip,
-            entities,
-            entities_by_handle,
-            entities_by_full_name,
-            srvhelps: Arc::clone(&mem.srvhelps).read_owned().await.clone(),
+        // networks and autnums may be stored under many prefixes or ranges, so only index each once
+        let networks = distinct(
+            self.ip4
+                .iter()
+                .map(|(_, v)| v)
+                .chain(self.ip6.iter().map(|(_, v)| v)),
+        );
+        let mut network_handles = HashMap::new();
+        let mut network_names = HashMap::new();
+        for value in networks {
+            if let RdapResponse::Network(network) = value.as_ref() {
+                if common_config.network_search_by_handle_enable {
+                    if let Some(handle) = network.handle() {
+                        group(&mut network_handles, handle, value);
+                    }
+                }
+                if common_config.network_search_by_name_enable {
+                    if let Some(name) = network.name() {
+                        group(&mut network_names, name, value);
+                    }
+                }
+            }
+        }
+        insert_groups(&mut self.networks_by_handle, network_handles);
+        insert_groups(&mut self.networks_by_name, network_names);
+
+        let mut autnum_handles = HashMap::new();
+        let mut autnum_names = HashMap::new();
+        for value in distinct(self.autnums.iter().map(|(_, v)| v)) {
+            if let RdapResponse::Autnum(autnum) = value.as_ref() {
+                if common_config.autnum_search_by_handle_enable {
+                    if let Some(handle) = autnum.handle() {
+                        group(&mut autnum_handles, handle, value);
+                    }
+                }
+                if common_config.autnum_search_by_name_enable {
+                    if let Some(name) = autnum.name() {
+                        group(&mut autnum_names, name, value);
+                    }
+                }
+            }
         }
+        insert_groups(&mut self.autnums_by_handle, autnum_handles);
+        insert_groups(&mut self.autnums_by_name, autnum_names);
     }
+}
 
-    pub fn new_truncate(mem: &Mem) -> Self {
-        Self {
-            mem: mem.clone(),
-            autnums: RangeMap::new(),
-            ip4: PrefixMap::new(),
-            ip6: PrefixMap::new(),
-            domains: HashMap::new(),
-            domains_by_name: SearchLabels::dns_labels().build(),
-            domains_by_ns_ip: HashMap::new(),
-            domains_by_ns_ldh_name: SearchLabels::dns_labels().build(),
-            idns: HashMap::new(),
-            nameservers: HashMap::new(),
-            nameservers_by_name: SearchLabels::dns_labels().build(),
-            nameservers_by_ip: HashMap::new(),
-            entities: HashMap::new(),
-            entities_by_handle: SearchLabels::handle_labels().build(),
-            entities_by_full_name: SearchLabels::name_labels().build(),
-            srvhelps: HashMap::new(),
+/// Groups a response with the other responses sharing a string, such as a name.
+fn group<'a>(
+    groups: &mut HashMap<&'a str, Vec<Arc<RdapResponse>>>,
+    text: &'a str,
+    value: &Arc<RdapResponse>,
+) {
+    groups.entry(text).or_default().push(value.clone());
+}
+
+/// Inserts grouped responses, each string once, as inserting a string again
+/// does not add to the responses already inserted.
+fn insert_groups(
+    labels: &mut SearchLabels<Vec<Arc<RdapResponse>>>,
+    groups: HashMap<&str, Vec<Arc<RdapResponse>>>,
+) {
+    for (text, values) in groups {
+        labels.insert(text, values);
+    }
+}
+
+/// Gets the subnets of the id of a network.
+fn network_subnets(network_id: &NetworkId) -> Result<IpSubnets, RdapServerError> {
+    match &network_id.network_id {
+        crate::storage::data::NetworkIdType::Cidr(cidr) => Ok(cidr.subnets(cidr.prefix_len())?),
+        crate::storage::data::NetworkIdType::Range {
+            start_address,
+            end_address,
+        } => {
+            let start_addr = IpAddr::from_str(start_address)?;
+            let end_addr = IpAddr::from_str(end_address)?;
+            if start_addr.is_ipv4() && end_addr.is_ipv4() {
+                let IpAddr::V4(start_addr) = start_addr else {
+                    panic!("check failed")
+                };
+                let IpAddr::V4(end_addr) = end_addr else {
+                    panic!("check failed")
+                };
+                Ok(IpSubnets::from(Ipv4Subnets::new(start_addr, end_addr, 0)))
+            } else if start_addr.is_ipv6() && end_addr.is_ipv6() {
+                let IpAddr::V6(start_addr) = start_addr else {
+                    panic!("check failed")
+                };
+                let IpAddr::V6(end_addr) = end_addr else {
+                    panic!("check failed")
+                };
+                Ok(IpSubnets::from(Ipv6Subnets::new(start_addr, end_addr, 0)))
+            } else {
+                Err(RdapServerError::EmptyIndexData(
+                    "mismatch ip version".to_string(),
+                ))
+            }
         }
     }
 }
 
+/// Removes a response from an index of responses by IP address.
+fn remove_from_ip_index(
+    index: &mut HashMap<IpAddr, Vec<Arc<RdapResponse>>>,
+    removed: &Arc<RdapResponse>,
+) {
+    for responses in index.values_mut() {
+        responses.retain(|response| !Arc::ptr_eq(response, removed));
+    }
+    index.retain(|_, responses| !responses.is_empty());
+}
+
+/// Gets the distinct responses, as the same response may be shared by many entries of an index.
+fn distinct<'a>(
+    responses: impl Iterator<Item = &'a Arc<RdapResponse>>,
+) -> Vec<&'a Arc<RdapResponse>> {
+    let mut seen = HashSet::new();
+    responses
+        .filter(|response| seen.insert(Arc::as_ptr(response)))
+        .collect()
+}
+
 #[async_trait]
 impl TxHandle for MemTx {
     async fn add_entity(&mut self, entity: &Entity) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint.scratch.add_entity(entity).await?;
+            savepoint
+                .staged
+                .push(Staged::Entity(Box::new(entity.clone())));
+            return Ok(());
+        }
         let handle = entity
             .object_common
             .handle
@@ -174,6 +396,10 @@ impl TxHandle for MemTx {
                 }
             }
         }
+        if self.mem.config.common_config.reverse_search_enable {
+            self.entities_by_entity
+                .insert(handle, entity.entities(), entity_response.clone());
+        }
         Ok(())
     }
 
@@ -182,14 +408,29 @@ impl TxHandle for MemTx {
         entity_id: &EntityId,
         error: &Rfc9083Error,
     ) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint.scratch.add_entity_err(entity_id, error).await?;
+            savepoint
+                .staged
+                .push(Staged::EntityErr(entity_id.clone(), error.clone()));
+            return Ok(());
+        }
         self.entities.insert(
             entity_id.handle.to_owned(),
             Arc::new(error.clone().to_response()),
         );
+        self.entities_by_entity.remove(&entity_id.handle);
         Ok(())
     }
 
     async fn add_domain(&mut self, domain: &Domain) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint.scratch.add_domain(domain).await?;
+            savepoint
+                .staged
+                .push(Staged::Domain(Box::new(domain.clone())));
+            return Ok(());
+        }
         let domain_response = Arc::new(domain.clone().to_response());
 
         // add the domain as LDH, which is required.
@@ -252,6 +493,11 @@ impl TxHandle for MemTx {
             }
         }
 
+        if self.mem.config.common_config.reverse_search_enable {
+            self.domains_by_entity
+                .insert(ldh_name, domain.entities(), domain_response.clone());
+        }
+
         Ok(())
     }
 
@@ -260,14 +506,29 @@ impl TxHandle for MemTx {
         domain_id: &DomainId,
         error: &Rfc9083Error,
     ) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint.scratch.add_domain_err(domain_id, error).await?;
+            savepoint
+                .staged
+                .push(Staged::DomainErr(domain_id.clone(), error.clone()));
+            return Ok(());
+        }
         self.domains.insert(
             domain_id.ldh_name.to_owned(),
             Arc::new(error.clone().to_response()),
         );
+        self.domains_by_entity.remove(&domain_id.ldh_name);
         Ok(())
     }
 
     async fn add_nameserver(&mut self, nameserver: &Nameserver) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint.scratch.add_nameserver(nameserver).await?;
+            savepoint
+                .staged
+                .push(Staged::Nameserver(Box::new(nameserver.clone())));
+            return Ok(());
+        }
         let ldh_name = nameserver
             .ldh_name
             .as_ref()
@@ -307,6 +568,14 @@ impl TxHandle for MemTx {
             }
         }
 
+        if self.mem.config.common_config.reverse_search_enable {
+            self.nameservers_by_entity.insert(
+                ldh_name,
+                nameserver.entities(),
+                nameserver_response.clone(),
+            );
+        }
+
         Ok(())
     }
 
@@ -315,14 +584,32 @@ impl TxHandle for MemTx {
         nameserver_id: &NameserverId,
         error: &Rfc9083Error,
     ) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint
+                .scratch
+                .add_nameserver_err(nameserver_id, error)
+                .await?;
+            savepoint
+                .staged
+                .push(Staged::NameserverErr(nameserver_id.clone(), error.clone()));
+            return Ok(());
+        }
         self.nameservers.insert(
             nameserver_id.ldh_name.to_owned(),
             Arc::new(error.clone().to_response()),
         );
+        self.nameservers_by_entity.remove(&nameserver_id.ldh_name);
         Ok(())
     }
 
     async fn add_autnum(&mut self, autnum: &Autnum) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint.scratch.add_autnum(autnum).await?;
+            savepoint
+                .staged
+                .push(Staged::Autnum(Box::new(autnum.clone())));
+            return Ok(());
+        }
         let start_num = autnum
             .start_autnum
             .as_ref()
@@ -333,10 +620,22 @@ impl TxHandle for MemTx {
             .as_ref()
             .and_then(|n| n.as_u32())
             .ok_or_else(|| RdapServerError::EmptyIndexData("endNum".to_string()))?;
-        self.autnums.insert(
-            (start_num)..=(end_num),
-            Arc::new(autnum.clone().to_response()),
-        );
+        let autnum_response = Arc::new(autnum.clone().to_response());
+        self.autnums
+            .insert((start_num)..=(end_num), autnum_response.clone());
+        // autnums may share handles and names, so their search labels are indexed together
+        if self.mem.config.common_config.autnum_search_by_handle_enable
+            || self.mem.config.common_config.autnum_search_by_name_enable
+        {
+            self.reindex = true;
+        }
+        if self.mem.config.common_config.reverse_search_enable {
+            self.autnums_by_entity.insert(
+                &format!("{start_num}-{end_num}"),
+                autnum.entities(),
+                autnum_response,
+            );
+        }
         Ok(())
     }
 
@@ -345,14 +644,33 @@ impl TxHandle for MemTx {
         autnum_id: &AutnumId,
         error: &Rfc9083Error,
     ) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint.scratch.add_autnum_err(autnum_id, error).await?;
+            savepoint
+                .staged
+                .push(Staged::AutnumErr(autnum_id.clone(), error.clone()));
+            return Ok(());
+        }
         self.autnums.insert(
             (autnum_id.start_autnum)..=(autnum_id.end_autnum),
             Arc::new(error.clone().to_response()),
         );
+        self.autnums_by_entity.remove(&format!(
+            "{}-{}",
+            autnum_id.start_autnum, autnum_id.end_autnum
+        ));
+        self.reindex = true;
         Ok(())
     }
 
     async fn add_network(&mut self, network: &Network) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint.scratch.add_network(network).await?;
+            savepoint
+                .staged
+                .push(Staged::Network(Box::new(network.clone())));
+            return Ok(());
+        }
         let start_addr = network
             .start_address
             .as_ref()
@@ -366,19 +684,35 @@ impl TxHandle for MemTx {
             .as_ref()
             .ok_or_else(|| RdapServerError::EmptyIndexData("ipVersion".to_string()))?;
         let is_v4 = ip_type.eq_ignore_ascii_case("v4");
+        let network_response = Arc::new(network.clone().to_response());
         if is_v4 {
             let subnets = Ipv4Subnets::new(start_addr.parse()?, end_addr.parse()?, 0);
             for net in subnets {
-                self.ip4
-                    .insert(net, Arc::new(network.clone().to_response()));
+                self.ip4.insert(net, network_response.clone());
             }
         } else {
             let subnets = Ipv6Subnets::new(start_addr.parse()?, end_addr.parse()?, 0);
             for net in subnets {
-                self.ip6
-                    .insert(net, Arc::new(network.clone().to_response()));
+                self.ip6.insert(net, network_response.clone());
             }
         };
+        // networks may share handles and names, so their search labels are indexed together
+        if self
+            .mem
+            .config
+            .common_config
+            .network_search_by_handle_enable
+            || self.mem.config.common_config.network_search_by_name_enable
+        {
+            self.reindex = true;
+        }
+        if self.mem.config.common_config.reverse_search_enable {
+            self.networks_by_entity.insert(
+                &format!("{start_addr}-{end_addr}"),
+                network.entities(),
+                network_response,
+            );
+        }
         Ok(())
     }
 
@@ -387,49 +721,29 @@ impl TxHandle for MemTx {
         network_id: &NetworkId,
         error: &Rfc9083Error,
     ) -> Result<(), RdapServerError> {
-        let subnets = match &network_id.network_id {
-            crate::storage::data::NetworkIdType::Cidr(cidr) => cidr.subnets(cidr.prefix_len())?,
-            crate::storage::data::NetworkIdType::Range {
-                start_address,
-                end_address,
-            } => {
-                let start_addr = IpAddr::from_str(start_address)?;
-                let end_addr = IpAddr::from_str(end_address)?;
-                if start_addr.is_ipv4() && end_addr.is_ipv4() {
-                    let IpAddr::V4(start_addr) = start_addr else {
-                        panic!("check failed")
-                    };
-                    let IpAddr::V4(end_addr) = end_addr else {
-                        panic!("check failed")
-                    };
-                    IpSubnets::from(Ipv4Subnets::new(start_addr, end_addr, 0))
-                } else if start_addr.is_ipv6() && end_addr.is_ipv6() {
-                    let IpAddr::V6(start_addr) = start_addr else {
-                        panic!("check failed")
-                    };
-                    let IpAddr::V6(end_addr) = end_addr else {
-                        panic!("check failed")
-                    };
-                    IpSubnets::from(Ipv6Subnets::new(start_addr, end_addr, 0))
-                } else {
-                    return Err(RdapServerError::EmptyIndexData(
-                        "mismatch ip version".to_string(),
-                    ));
-                }
-            }
-        };
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint.scratch.add_network_err(network_id, error).await?;
+            savepoint
+                .staged
+                .push(Staged::NetworkErr(network_id.clone(), error.clone()));
+            return Ok(());
+        }
+        let subnets = network_subnets(network_id)?;
+        let mut replaced = vec![];
         match subnets {
             IpSubnets::V4(subnets) => {
                 for net in subnets {
-                    self.ip4.insert(net, Arc::new(error.clone().to_response()));
+                    replaced.extend(self.ip4.insert(net, Arc::new(error.clone().to_response())));
                 }
             }
             IpSubnets::V6(subnets) => {
                 for net in subnets {
-                    self.ip6.insert(net, Arc::new(error.clone().to_response()));
+                    replaced.extend(self.ip6.insert(net, Arc::new(error.clone().to_response())));
                 }
             }
         }
+        self.remove_networks_by_entity(&replaced);
+        self.reindex = true;
         Ok(())
     }
 
@@ -438,13 +752,212 @@ impl TxHandle for MemTx {
         help: &Help,
         host: Option<&str>,
     ) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint.scratch.add_srv_help(help, host).await?;
+            savepoint.staged.push(Staged::SrvHelp(
+                Box::new(help.clone()),
+                host.map(str::to_string),
+            ));
+            return Ok(());
+        }
         let host = host.unwrap_or("..default");
         self.srvhelps
             .insert(host.to_string(), Arc::new(help.clone().to_response()));
         Ok(())
     }
 
+    async fn delete_domain(&mut self, domain_id: &DomainId) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint
+                .staged
+                .push(Staged::DeleteDomain(domain_id.clone()));
+            return Ok(());
+        }
+        let Some(removed) = self.domains.remove(&domain_id.ldh_name) else {
+            return Ok(());
+        };
+        if let RdapResponse::Domain(domain) = removed.as_ref() {
+            if let Some(unicode_name) = domain.unicode_name.as_ref() {
+                self.idns.remove(unicode_name);
+            }
+        }
+        remove_from_ip_index(&mut self.domains_by_ns_ip, &removed);
+        self.domains_by_entity.remove(&domain_id.ldh_name);
+        self.reindex = true;
+        Ok(())
+    }
+
+    async fn delete_entity(&mut self, entity_id: &EntityId) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint
+                .staged
+                .push(Staged::DeleteEntity(entity_id.clone()));
+            return Ok(());
+        }
+        if self.entities.remove(&entity_id.handle).is_some() {
+            self.entities_by_entity.remove(&entity_id.handle);
+            self.reindex = true;
+        }
+        Ok(())
+    }
+
+    async fn delete_nameserver(
+        &mut self,
+        nameserver_id: &NameserverId,
+    ) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint
+                .staged
+                .push(Staged::DeleteNameserver(nameserver_id.clone()));
+            return Ok(());
+        }
+        let Some(removed) = self.nameservers.remove(&nameserver_id.ldh_name) else {
+            return Ok(());
+        };
+        remove_from_ip_index(&mut self.nameservers_by_ip, &removed);
+        self.nameservers_by_entity.remove(&nameserver_id.ldh_name);
+        self.reindex = true;
+        Ok(())
+    }
+
+    async fn delete_autnum(&mut self, autnum_id: &AutnumId) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint
+                .staged
+                .push(Staged::DeleteAutnum(autnum_id.clone()));
+            return Ok(());
+        }
+        // only an autnum with exactly the range is deleted, not the autnums within it
+        let stored = self
+            .autnums
+            .get(autnum_id.start_autnum)
+            .is_some_and(|r| match r.as_ref() {
+                RdapResponse::Autnum(autnum) => {
+                    autnum.start_autnum.as_ref().and_then(|n| n.as_u32())
+                        == Some(autnum_id.start_autnum)
+                        && autnum.end_autnum.as_ref().and_then(|n| n.as_u32())
+                            == Some(autnum_id.end_autnum)
+                }
+                // errors do not have ranges, so an error must be stored over exactly the range
+                _ => {
+                    let stored_at = |num: Option<u32>| {
+                        num.and_then(|num| self.autnums.get(num))
+                            .is_some_and(|other| Arc::ptr_eq(other, r))
+                    };
+                    stored_at(Some(autnum_id.end_autnum))
+                        && !stored_at(autnum_id.start_autnum.checked_sub(1))
+                        && !stored_at(autnum_id.end_autnum.checked_add(1))
+                }
+            });
+        if !stored {
+            return Ok(());
+        }
+        self.autnums
+            .remove((autnum_id.start_autnum)..=(autnum_id.end_autnum));
+        self.autnums_by_entity.remove(&format!(
+            "{}-{}",
+            autnum_id.start_autnum, autnum_id.end_autnum
+        ));
+        self.reindex = true;
+        Ok(())
+    }
+
+    async fn delete_network(&mut self, network_id: &NetworkId) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint
+                .staged
+                .push(Staged::DeleteNetwork(network_id.clone()));
+            return Ok(());
+        }
+        let mut removed = vec![];
+        match network_subnets(network_id)? {
+            IpSubnets::V4(subnets) => {
+                for net in subnets {
+                    removed.extend(self.ip4.remove(&net));
+                }
+            }
+            IpSubnets::V6(subnets) => {
+                for net in subnets {
+                    removed.extend(self.ip6.remove(&net));
+                }
+            }
+        }
+        self.remove_networks_by_entity(&removed);
+        self.reindex = true;
+        Ok(())
+    }
+
+    async fn delete_srv_help(&mut self, host: Option<&str>) -> Result<(), RdapServerError> {
+        if let Some(savepoint) = &mut self.savepoint {
+            savepoint
+                .staged
+                .push(Staged::DeleteSrvHelp(host.map(str::to_string)));
+            return Ok(());
+        }
+        self.srvhelps.remove(host.unwrap_or("..default"));
+        Ok(())
+    }
+
+    async fn savepoint(&mut self) -> Result<(), RdapServerError> {
+        self.release_savepoint().await?;
+        self.savepoint = Some(Savepoint {
+            scratch: Box::new(Self::new_truncate(&self.mem)),
+            staged: vec![],
+        });
+        Ok(())
+    }
+
+    async fn release_savepoint(&mut self) -> Result<(), RdapServerError> {
+        let Some(savepoint) = self.savepoint.take() else {
+            return Ok(());
+        };
+        for staged in savepoint.staged {
+            match staged {
+                Staged::Domain(domain) => self.add_domain(&domain).await?,
+                Staged::DomainErr(domain_id, error) => {
+                    self.add_domain_err(&domain_id, &error).await?
+                }
+                Staged::Entity(entity) => self.add_entity(&entity).await?,
+                Staged::EntityErr(entity_id, error) => {
+                    self.add_entity_err(&entity_id, &error).await?
+                }
+                Staged::Nameserver(nameserver) => self.add_nameserver(&nameserver).await?,
+                Staged::NameserverErr(nameserver_id, error) => {
+                    self.add_nameserver_err(&nameserver_id, &error).await?
+                }
+                Staged::Autnum(autnum) => self.add_autnum(&autnum).await?,
+                Staged::AutnumErr(autnum_id, error) => {
+                    self.add_autnum_err(&autnum_id, &error).await?
+                }
+                Staged::Network(network) => self.add_network(&network).await?,
+                Staged::NetworkErr(network_id, error) => {
+                    self.add_network_err(&network_id, &error).await?
+                }
+                Staged::SrvHelp(help, host) => self.add_srv_help(&help, host.as_deref()).await?,
+                Staged::DeleteDomain(domain_id) => self.delete_domain(&domain_id).await?,
+                Staged::DeleteEntity(entity_id) => self.delete_entity(&entity_id).await?,
+                Staged::DeleteNameserver(nameserver_id) => {
+                    self.delete_nameserver(&nameserver_id).await?
+                }
+                Staged::DeleteAutnum(autnum_id) => self.delete_autnum(&autnum_id).await?,
+                Staged::DeleteNetwork(network_id) => self.delete_network(&network_id).await?,
+                Staged::DeleteSrvHelp(host) => self.delete_srv_help(host.as_deref()).await?,
+            }
+        }
+        Ok(())
+    }
+
+    async fn rollback_to_savepoint(&mut self) -> Result<(), RdapServerError> {
+        self.savepoint = None;
+        Ok(())
+    }
+
     async fn commit(mut self: Box<Self>) -> Result<(), RdapServerError> {
+        self.release_savepoint().await?;
+        if self.reindex {
+            self.index_search_labels();
+        }
+
         // autnums
         let mut autnum_g = self.mem.autnums.write().await;
         std::mem::swap(&mut self.autnums, &mut autnum_g);
@@ -507,10 +1020,49 @@ impl TxHandle for MemTx {
             &mut entities_by_full_name_g,
         );
 
+        // networks by handle
+        let mut networks_by_handle_g = self.mem.networks_by_handle.write().await;
+        std::mem::swap(&mut self.networks_by_handle, &mut networks_by_handle_g);
+
+        // networks by name
+        let mut networks_by_name_g = self.mem.networks_by_name.write().await;
+        std::mem::swap(&mut self.networks_by_name, &mut networks_by_name_g);
+
+        // autnums by handle
+        let mut autnums_by_handle_g = self.mem.autnums_by_handle.write().await;
+        std::mem::swap(&mut self.autnums_by_handle, &mut autnums_by_handle_g);
+
+        // autnums by name
+        let mut autnums_by_name_g = self.mem.autnums_by_name.write().await;
+        std::mem::swap(&mut self.autnums_by_name, &mut autnums_by_name_g);
+
         //srvhelps
         let mut srvhelps_g = self.mem.srvhelps.write().await;
         std::mem::swap(&mut self.srvhelps, &mut srvhelps_g);
 
+        // domains by entity
+        let mut domains_by_entity_g = self.mem.domains_by_entity.write().await;
+        std::mem::swap(&mut self.domains_by_entity, &mut domains_by_entity_g);
+
+        // nameservers by entity
+        let mut nameservers_by_entity_g = self.mem.nameservers_by_entity.write().await;
+        std::mem::swap(
+            &mut self.nameservers_by_entity,
+            &mut nameservers_by_entity_g,
+        );
+
+        // entities by entity
+        let mut entities_by_entity_g = self.mem.entities_by_entity.write().await;
+        std::mem::swap(&mut self.entities_by_entity, &mut entities_by_entity_g);
+
+        // networks by entity
+        let mut networks_by_entity_g = self.mem.networks_by_entity.write().await;
+        std::mem::swap(&mut self.networks_by_entity, &mut networks_by_entity_g);
+
+        // autnums by entity
+        let mut autnums_by_entity_g = self.mem.autnums_by_entity.write().await;
+        std::mem::swap(&mut self.autnums_by_entity, &mut autnums_by_entity_g);
+
         Ok(())
     }
 
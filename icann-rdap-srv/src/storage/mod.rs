@@ -3,6 +3,7 @@ use {
     icann_rdap_common::response::{
         Autnum, Domain, Entity, Help, Nameserver, Network, RdapResponse, Rfc9083Error,
     },
+    strum_macros::{Display, EnumString},
 };
 
 use crate::error::RdapServerError;
@@ -12,6 +13,8 @@ use self::data::{AutnumId, DomainId, EntityId, NameserverId, NetworkId};
 pub mod data;
 pub mod mem;
 pub mod pg;
+mod sql;
+pub mod sqlite;
 
 pub type DynStoreOps = dyn StoreOps + Send + Sync;
 
@@ -21,6 +24,9 @@ pub trait StoreOps: Send + Sync {
     /// Initializes the backend storage
     async fn init(&self) -> Result<(), RdapServerError>;
 
+    /// Checks the backend storage can be reached, without changing it.
+    async fn ping(&self) -> Result<(), RdapServerError>;
+
     /// Gets a new transaction.
     async fn new_tx(&self) -> Result<Box<dyn TxHandle>, RdapServerError>;
 
@@ -51,6 +57,22 @@ pub trait StoreOps: Send + Sync {
     /// returned should be the most specific (longest prefix) network containing the IP address.
     async fn get_network_by_cidr(&self, cidr: &str) -> Result<RdapResponse, RdapServerError>;
 
+    /// Get the networks related to the most specific network containing an IP address or
+    /// CIDR. A network is returned for [NetworkRelation::Up] and [NetworkRelation::Top],
+    /// and network search results for [NetworkRelation::Down] and [NetworkRelation::Bottom].
+    async fn get_related_networks(
+        &self,
+        netid: &str,
+        relation: NetworkRelation,
+    ) -> Result<RdapResponse, RdapServerError>;
+
+    /// Get the relations for which the most specific network containing an IP address
+    /// or CIDR has related networks.
+    async fn get_network_relations(
+        &self,
+        netid: &str,
+    ) -> Result<Vec<NetworkRelation>, RdapServerError>;
+
     /// Get server help.
     async fn get_srv_help(&self, host: Option<&str>) -> Result<RdapResponse, RdapServerError>;
 
@@ -90,6 +112,121 @@ pub trait StoreOps: Send + Sync {
         &self,
         full_name: &str,
     ) -> Result<RdapResponse, RdapServerError>;
+
+    /// Search for networks by handle.
+    async fn search_networks_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError>;
+
+    /// Search for networks by name.
+    async fn search_networks_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError>;
+
+    /// Search for autnums by handle.
+    async fn search_autnums_by_handle(&self, handle: &str)
+        -> Result<RdapResponse, RdapServerError>;
+
+    /// Search for autnums by name.
+    async fn search_autnums_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError>;
+
+    /// Search for domains by their related entities.
+    async fn reverse_search_domains(
+        &self,
+        search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError>;
+
+    /// Search for nameservers by their related entities.
+    async fn reverse_search_nameservers(
+        &self,
+        search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError>;
+
+    /// Search for entities by their related entities.
+    async fn reverse_search_entities(
+        &self,
+        search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError>;
+
+    /// Search for networks by their related entities.
+    async fn reverse_search_networks(
+        &self,
+        search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError>;
+
+    /// Search for autnums by their related entities.
+    async fn reverse_search_autnums(
+        &self,
+        search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError>;
+
+    /// Get the number of objects in each part of the storage, for metrics. Storage
+    /// engines that are not able to count cheaply give no counts.
+    async fn object_counts(&self) -> Vec<(&'static str, usize)> {
+        vec![]
+    }
+}
+
+/// The relations between networks used by the RIR search extension, named
+/// by their link relation types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum NetworkRelation {
+    /// The closest network containing the network.
+    #[strum(serialize = "rdap-up")]
+    Up,
+
+    /// The networks directly within the network.
+    #[strum(serialize = "rdap-down")]
+    Down,
+
+    /// The least specific network containing the network.
+    #[strum(serialize = "rdap-top")]
+    Top,
+
+    /// The most specific networks within the network.
+    #[strum(serialize = "rdap-bottom")]
+    Bottom,
+}
+
+/// The conditions of an RFC 9536 reverse search.
+///
+/// An object matches if one of its entities matches all of the given
+/// conditions. Values other than the role may end with `*` for a partial match.
+#[derive(Debug, Clone, Default)]
+pub struct ReverseSearch {
+    /// The handle of the related entity.
+    pub handle: Option<String>,
+
+    /// The full name (vCard `fn`) of the related entity.
+    pub full_name: Option<String>,
+
+    /// An email address of the related entity.
+    pub email: Option<String>,
+
+    /// A role the related entity has with the object.
+    pub role: Option<String>,
+}
+
+#[buildstructor::buildstructor]
+impl ReverseSearch {
+    #[builder]
+    pub fn new(
+        handle: Option<String>,
+        full_name: Option<String>,
+        email: Option<String>,
+        role: Option<String>,
+    ) -> Self {
+        Self {
+            handle,
+            full_name,
+            email,
+            role,
+        }
+    }
+
+    /// Returns true if there is no condition on the related entity other than its role.
+    pub fn is_empty(&self) -> bool {
+        self.handle.is_none() && self.full_name.is_none() && self.email.is_none()
+    }
 }
 
 /// Represents a handle to a transaction.
@@ -153,6 +290,37 @@ pub trait TxHandle: Send {
         host: Option<&str>,
     ) -> Result<(), RdapServerError>;
 
+    /// Delete a domain, or the error stored as the domain, from storage.
+    async fn delete_domain(&mut self, domain_id: &DomainId) -> Result<(), RdapServerError>;
+
+    /// Delete an entity, or the error stored as the entity, from storage.
+    async fn delete_entity(&mut self, entity_id: &EntityId) -> Result<(), RdapServerError>;
+
+    /// Delete a nameserver, or the error stored as the nameserver, from storage.
+    async fn delete_nameserver(
+        &mut self,
+        nameserver_id: &NameserverId,
+    ) -> Result<(), RdapServerError>;
+
+    /// Delete an autnum, or the error stored as the autnum, from storage.
+    async fn delete_autnum(&mut self, autnum_id: &AutnumId) -> Result<(), RdapServerError>;
+
+    /// Delete a network, or the error stored as the network, from storage.
+    async fn delete_network(&mut self, network_id: &NetworkId) -> Result<(), RdapServerError>;
+
+    /// Delete the help of a host, or the default help, from storage.
+    async fn delete_srv_help(&mut self, host: Option<&str>) -> Result<(), RdapServerError>;
+
+    /// Marks the point to which [TxHandle::rollback_to_savepoint] returns, such as
+    /// before the objects of one file are added.
+    async fn savepoint(&mut self) -> Result<(), RdapServerError>;
+
+    /// Keeps the changes made since the savepoint.
+    async fn release_savepoint(&mut self) -> Result<(), RdapServerError>;
+
+    /// Undoes the changes made since the savepoint, keeping the transaction usable.
+    async fn rollback_to_savepoint(&mut self) -> Result<(), RdapServerError>;
+
     /// Commit the transaction.
     async fn commit(self: Box<Self>) -> Result<(), RdapServerError>;
 
@@ -170,6 +338,12 @@ pub struct CommonConfig {
     pub domain_search_by_ns_ldh_name_enable: bool,
     pub entity_search_by_handle_enable: bool,
     pub entity_search_by_full_name_enable: bool,
+    pub network_search_by_handle_enable: bool,
+    pub network_search_by_name_enable: bool,
+    pub autnum_search_by_handle_enable: bool,
+    pub autnum_search_by_name_enable: bool,
+    pub reverse_search_enable: bool,
+    pub network_hierarchy_enable: bool,
 }
 
 #[buildstructor::buildstructor]
@@ -183,6 +357,12 @@ impl CommonConfig {
         nameserver_search_by_ip_enable: Option<bool>,
         entity_search_by_handle_enable: Option<bool>,
         entity_search_by_full_name_enable: Option<bool>,
+        network_search_by_handle_enable: Option<bool>,
+        network_search_by_name_enable: Option<bool>,
+        autnum_search_by_handle_enable: Option<bool>,
+        autnum_search_by_name_enable: Option<bool>,
+        reverse_search_enable: Option<bool>,
+        network_hierarchy_enable: Option<bool>,
     ) -> Self {
         Self {
             domain_search_by_name_enable: domain_search_by_name_enable.unwrap_or_default(),
@@ -194,6 +374,12 @@ impl CommonConfig {
             entity_search_by_handle_enable: entity_search_by_handle_enable.unwrap_or_default(),
             entity_search_by_full_name_enable: entity_search_by_full_name_enable
                 .unwrap_or_default(),
+            network_search_by_handle_enable: network_search_by_handle_enable.unwrap_or_default(),
+            network_search_by_name_enable: network_search_by_name_enable.unwrap_or_default(),
+            autnum_search_by_handle_enable: autnum_search_by_handle_enable.unwrap_or_default(),
+            autnum_search_by_name_enable: autnum_search_by_name_enable.unwrap_or_default(),
+            reverse_search_enable: reverse_search_enable.unwrap_or_default(),
+            network_hierarchy_enable: network_hierarchy_enable.unwrap_or_default(),
         }
     }
 }
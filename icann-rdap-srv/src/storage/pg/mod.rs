@@ -1,5 +1,3 @@
-#![allow(dead_code)] // TODO remove
-
 pub mod config;
 pub mod ops;
 pub mod tx;
@@ -1,29 +1,167 @@
-#![allow(clippy::diverging_sub_expression)]
-use std::net::IpAddr;
+use std::{net::IpAddr, str::FromStr};
 
 use {
     async_trait::async_trait,
-    icann_rdap_common::response::RdapResponse,
-    sqlx::{query, PgPool},
+    icann_rdap_common::{
+        prelude::{ExtensionId, ToResponse},
+        response::{
+            Autnum, AutnumSearchResults, Domain, DomainSearchResults, Entity, EntitySearchResults,
+            Nameserver, NameserverSearchResults, Network, NetworkSearchResults, RdapResponse,
+        },
+    },
+    ipnet::IpNet,
+    sqlx::{
+        postgres::PgArguments, query, query::QueryScalar, query_scalar, types::Json, PgPool,
+        Postgres,
+    },
     tracing::{debug, info},
 };
 
 use crate::{
     error::RdapServerError,
-    storage::{StoreOps, TxHandle},
+    rdap::response::{NOT_FOUND, NOT_IMPLEMENTED},
+    storage::{
+        mem::label_search::{like_patterns, DNS_SEPARATORS, HANDLE_SEPARATORS, NAME_SEPARATORS},
+        NetworkRelation, ReverseSearch, StoreOps, TxHandle,
+    },
 };
 
 use super::{config::PgConfig, tx::PgTx};
 
+type ResponseQuery<'q> = QueryScalar<'q, Postgres, Json<RdapResponse>, PgArguments>;
+
 #[derive(Clone)]
 pub struct Pg {
     pg_pool: PgPool,
+    config: PgConfig,
 }
 
 impl Pg {
     pub async fn new(config: PgConfig) -> Result<Self, RdapServerError> {
         let pg_pool = PgPool::connect(&config.db_url).await?;
-        Ok(Self { pg_pool })
+        Ok(Self { pg_pool, config })
+    }
+
+    /// Gets the first response of the query, or [NOT_FOUND] if there is none.
+    async fn get_response(
+        &self,
+        query: ResponseQuery<'_>,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let response = query.fetch_optional(&self.pg_pool).await?;
+        Ok(response.map_or_else(|| NOT_FOUND.clone(), |json| json.0))
+    }
+
+    /// Gets all the responses of the query.
+    async fn get_responses(
+        &self,
+        query: ResponseQuery<'_>,
+    ) -> Result<Vec<RdapResponse>, RdapServerError> {
+        let responses = query.fetch_all(&self.pg_pool).await?;
+        Ok(responses.into_iter().map(|json| json.0).collect())
+    }
+
+    async fn domain_search_results(
+        &self,
+        query: ResponseQuery<'_>,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let results = self
+            .get_responses(query)
+            .await?
+            .into_iter()
+            .filter_map(|d| match d {
+                RdapResponse::Domain(d) => Some(*d),
+                _ => None,
+            })
+            .collect::<Vec<Domain>>();
+        let response = DomainSearchResults::response_obj()
+            .results(results)
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn nameserver_search_results(
+        &self,
+        query: ResponseQuery<'_>,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let results = self
+            .get_responses(query)
+            .await?
+            .into_iter()
+            .filter_map(|n| match n {
+                RdapResponse::Nameserver(ns) => Some(*ns),
+                _ => None,
+            })
+            .collect::<Vec<Nameserver>>();
+        let response = NameserverSearchResults::response_obj()
+            .results(results)
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn entity_search_results(
+        &self,
+        query: ResponseQuery<'_>,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let results = self
+            .get_responses(query)
+            .await?
+            .into_iter()
+            .filter_map(|e| match e {
+                RdapResponse::Entity(ent) => Some(*ent),
+                _ => None,
+            })
+            .collect::<Vec<Entity>>();
+        let response = EntitySearchResults::response_obj()
+            .results(results)
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn network_search_results(
+        &self,
+        query: ResponseQuery<'_>,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let results = self
+            .get_responses(query)
+            .await?
+            .into_iter()
+            .filter_map(|r| match r {
+                RdapResponse::Network(net) => Some(*net),
+                _ => None,
+            })
+            .collect::<Vec<Network>>();
+        let response = NetworkSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::RirSearch1.to_extension())
+            .extension(ExtensionId::Ips.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn autnum_search_results(
+        &self,
+        query: ResponseQuery<'_>,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let results = self
+            .get_responses(query)
+            .await?
+            .into_iter()
+            .filter_map(|r| match r {
+                RdapResponse::Autnum(a) => Some(*a),
+                _ => None,
+            })
+            .collect::<Vec<Autnum>>();
+        let response = AutnumSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::RirSearch1.to_extension())
+            .extension(ExtensionId::Autnums.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
     }
 }
 
@@ -34,6 +172,13 @@ impl StoreOps for Pg {
         let mut conn = self.pg_pool.acquire().await?;
         query("select 1").fetch_one(&mut *conn).await?;
         info!("Database connection test is successful.");
+        debug!("Running database migrations.");
+        sqlx::migrate!("./migrations/pg").run(&self.pg_pool).await?;
+        Ok(())
+    }
+
+    async fn ping(&self) -> Result<(), RdapServerError> {
+        query("select 1").fetch_one(&self.pg_pool).await?;
         Ok(())
     }
 
@@ -45,66 +190,322 @@ impl StoreOps for Pg {
         Ok(Box::new(PgTx::new_truncate(&self.pg_pool).await?))
     }
 
-    async fn get_domain_by_ldh(&self, _ldh: &str) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+    async fn get_domain_by_ldh(&self, ldh: &str) -> Result<RdapResponse, RdapServerError> {
+        self.get_response(
+            query_scalar("select response from domain where ldh_name = $1").bind(ldh.to_owned()),
+        )
+        .await
+    }
+
+    async fn get_domain_by_unicode(&self, unicode: &str) -> Result<RdapResponse, RdapServerError> {
+        self.get_response(
+            query_scalar("select response from domain where unicode_name = $1 limit 1")
+                .bind(unicode.to_owned()),
+        )
+        .await
     }
 
-    async fn get_domain_by_unicode(&self, _unicode: &str) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+    async fn get_entity_by_handle(&self, handle: &str) -> Result<RdapResponse, RdapServerError> {
+        self.get_response(
+            query_scalar("select response from entity where handle = $1").bind(handle.to_owned()),
+        )
+        .await
     }
 
-    async fn get_entity_by_handle(&self, _handle: &str) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+    async fn get_nameserver_by_ldh(&self, ldh: &str) -> Result<RdapResponse, RdapServerError> {
+        self.get_response(
+            query_scalar("select response from nameserver where ldh_name = $1")
+                .bind(ldh.to_owned()),
+        )
+        .await
     }
 
-    async fn get_nameserver_by_ldh(&self, _ldh: &str) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+    async fn get_autnum_by_num(&self, num: u32) -> Result<RdapResponse, RdapServerError> {
+        // overlapping ranges are resolved in favor of the last one added.
+        self.get_response(
+            query_scalar(
+                "select response from autnum \
+                 where start_autnum <= $1 and end_autnum >= $1 \
+                 order by id desc limit 1",
+            )
+            .bind(i64::from(num)),
+        )
+        .await
     }
 
-    async fn get_autnum_by_num(&self, _num: u32) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+    async fn get_network_by_ipaddr(&self, ipaddr: &str) -> Result<RdapResponse, RdapServerError> {
+        let addr = ipaddr.parse::<IpAddr>()?;
+        self.get_response(
+            query_scalar(
+                "select response from network where cidr >>= $1::inet \
+                 order by masklen(cidr) desc limit 1",
+            )
+            .bind(addr.to_string()),
+        )
+        .await
     }
 
-    async fn get_network_by_ipaddr(&self, _ipaddr: &str) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+    async fn get_network_by_cidr(&self, cidr: &str) -> Result<RdapResponse, RdapServerError> {
+        let net = IpNet::from_str(cidr)?;
+        self.get_response(
+            query_scalar(
+                "select response from network where cidr >>= $1::inet \
+                 order by masklen(cidr) desc limit 1",
+            )
+            .bind(net.to_string()),
+        )
+        .await
     }
-    async fn get_network_by_cidr(&self, _cidr: &str) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+
+    async fn get_related_networks(
+        &self,
+        _netid: &str,
+        _relation: NetworkRelation,
+    ) -> Result<RdapResponse, RdapServerError> {
+        // the network hierarchy is only kept by the in-memory storage
+        Ok(NOT_IMPLEMENTED.clone())
     }
-    async fn get_srv_help(&self, _host: Option<&str>) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+
+    async fn get_network_relations(
+        &self,
+        _netid: &str,
+    ) -> Result<Vec<NetworkRelation>, RdapServerError> {
+        // the network hierarchy is only kept by the in-memory storage
+        Ok(vec![])
     }
-    async fn search_domains_by_name(&self, _name: &str) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+
+    async fn get_srv_help(&self, host: Option<&str>) -> Result<RdapResponse, RdapServerError> {
+        let host = host.unwrap_or("..default");
+        self.get_response(
+            query_scalar("select response from srv_help where host = $1").bind(host.to_owned()),
+        )
+        .await
     }
+
+    async fn search_domains_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.domain_search_by_name_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        //else
+        // an invalid search matches nothing, as with the in-memory storage
+        let patterns = like_patterns(name, DNS_SEPARATORS).unwrap_or_default();
+        self.domain_search_results(
+            query_scalar(
+                "select response from domain where ldh_name like any($1) order by ldh_name",
+            )
+            .bind(patterns),
+        )
+        .await
+    }
+
     async fn search_nameservers_by_name(
         &self,
-        _name: &str,
+        name: &str,
     ) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+        if !self.config.common_config.nameserver_search_by_name_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        //else
+        let patterns = like_patterns(name, DNS_SEPARATORS).unwrap_or_default();
+        self.nameserver_search_results(
+            query_scalar(
+                "select response from nameserver where ldh_name like any($1) order by ldh_name",
+            )
+            .bind(patterns),
+        )
+        .await
     }
-    async fn search_nameservers_by_ip(&self, _ip: IpAddr) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+
+    async fn search_nameservers_by_ip(&self, ip: IpAddr) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.nameserver_search_by_ip_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        //else
+        self.nameserver_search_results(
+            query_scalar(
+                "select response from nameserver where ldh_name in \
+                 (select nameserver_ldh_name from nameserver_ip where ip = $1::inet) \
+                 order by ldh_name",
+            )
+            .bind(ip.to_string()),
+        )
+        .await
     }
-    async fn search_domains_by_ns_ip(&self, _ip: IpAddr) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+
+    async fn search_domains_by_ns_ip(&self, ip: IpAddr) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.domain_search_by_ns_ip_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        self.domain_search_results(
+            query_scalar(
+                "select response from domain where ldh_name in \
+                 (select domain_ldh_name from domain_ns_ip where ip = $1::inet) \
+                 order by ldh_name",
+            )
+            .bind(ip.to_string()),
+        )
+        .await
     }
+
     async fn search_domains_by_ns_ldh_name(
         &self,
-        _name: &str,
+        name: &str,
     ) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+        if !self
+            .config
+            .common_config
+            .domain_search_by_ns_ldh_name_enable
+        {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let patterns = like_patterns(name, DNS_SEPARATORS).unwrap_or_default();
+        self.domain_search_results(
+            query_scalar(
+                "select response from domain where ldh_name in \
+                 (select domain_ldh_name from domain_ns_ldh_name where ns_ldh_name like any($1)) \
+                 order by ldh_name",
+            )
+            .bind(patterns),
+        )
+        .await
     }
+
     async fn search_entities_by_handle(
         &self,
-        _handle: &str,
+        handle: &str,
     ) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+        if !self.config.common_config.entity_search_by_handle_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let patterns = like_patterns(handle, HANDLE_SEPARATORS).unwrap_or_default();
+        self.entity_search_results(
+            query_scalar("select response from entity where handle like any($1) order by handle")
+                .bind(patterns),
+        )
+        .await
     }
+
     async fn search_entities_by_full_name(
         &self,
-        _full_name: &str,
+        full_name: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.entity_search_by_full_name_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let patterns = like_patterns(full_name, NAME_SEPARATORS).unwrap_or_default();
+        self.entity_search_results(
+            query_scalar(
+                "select response from entity where full_name like any($1) order by handle",
+            )
+            .bind(patterns),
+        )
+        .await
+    }
+
+    async fn search_networks_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.network_search_by_handle_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        // a network has a row for each of its CIDRs
+        let patterns = like_patterns(handle, HANDLE_SEPARATORS).unwrap_or_default();
+        self.network_search_results(
+            query_scalar(
+                "select response from network where response->>'handle' like any($1) \
+                 group by response order by min(response->>'handle')",
+            )
+            .bind(patterns),
+        )
+        .await
+    }
+
+    async fn search_networks_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.network_search_by_name_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let patterns = like_patterns(name, NAME_SEPARATORS).unwrap_or_default();
+        self.network_search_results(
+            query_scalar(
+                "select response from network where response->>'name' like any($1) \
+                 group by response order by min(response->>'name')",
+            )
+            .bind(patterns),
+        )
+        .await
+    }
+
+    async fn search_autnums_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.autnum_search_by_handle_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let patterns = like_patterns(handle, HANDLE_SEPARATORS).unwrap_or_default();
+        self.autnum_search_results(
+            query_scalar(
+                "select response from autnum where response->>'handle' like any($1) \
+                 order by response->>'handle'",
+            )
+            .bind(patterns),
+        )
+        .await
+    }
+
+    async fn search_autnums_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.autnum_search_by_name_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let patterns = like_patterns(name, NAME_SEPARATORS).unwrap_or_default();
+        self.autnum_search_results(
+            query_scalar(
+                "select response from autnum where response->>'name' like any($1) \
+                 order by response->>'name'",
+            )
+            .bind(patterns),
+        )
+        .await
+    }
+
+    async fn reverse_search_domains(
+        &self,
+        _search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError> {
+        // reverse searches are only indexed by the in-memory storage
+        Ok(NOT_IMPLEMENTED.clone())
+    }
+
+    async fn reverse_search_nameservers(
+        &self,
+        _search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError> {
+        // reverse searches are only indexed by the in-memory storage
+        Ok(NOT_IMPLEMENTED.clone())
+    }
+
+    async fn reverse_search_entities(
+        &self,
+        _search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError> {
+        // reverse searches are only indexed by the in-memory storage
+        Ok(NOT_IMPLEMENTED.clone())
+    }
+
+    async fn reverse_search_networks(
+        &self,
+        _search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError> {
+        // reverse searches are only indexed by the in-memory storage
+        Ok(NOT_IMPLEMENTED.clone())
+    }
+
+    async fn reverse_search_autnums(
+        &self,
+        _search: &ReverseSearch,
     ) -> Result<RdapResponse, RdapServerError> {
-        todo!()
+        // reverse searches are only indexed by the in-memory storage
+        Ok(NOT_IMPLEMENTED.clone())
     }
 }
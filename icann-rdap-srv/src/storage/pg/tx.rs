@@ -1,14 +1,18 @@
-#![allow(clippy::diverging_sub_expression)]
 use {
     async_trait::async_trait,
-    icann_rdap_common::response::{Autnum, Domain, Entity, Nameserver, Network, Rfc9083Error},
-    sqlx::{PgPool, Postgres},
+    icann_rdap_common::{
+        prelude::ToResponse,
+        response::{Autnum, Domain, Entity, Help, Nameserver, Network, RdapResponse, Rfc9083Error},
+    },
+    ipnet::IpNet,
+    sqlx::{query, types::Json, PgPool, Postgres},
 };
 
 use crate::{
     error::RdapServerError,
     storage::{
         data::{AutnumId, DomainId, EntityId, NameserverId, NetworkId},
+        sql::{nameserver_ips, network_id_subnets, range_subnets},
         TxHandle,
     },
 };
@@ -25,86 +29,378 @@ impl PgTx<'_> {
 
     pub async fn new_truncate(pg_pool: &PgPool) -> Result<Self, RdapServerError> {
         let mut db_tx = pg_pool.begin().await?;
-        // TODO actually complete this
-        // this is just here to make sure something will compile
-        sqlx::query("truncate domain").execute(&mut *db_tx).await?;
+        query(
+            "truncate table domain, domain_ns_ip, domain_ns_ldh_name, nameserver, nameserver_ip, \
+             entity, autnum, network, srv_help",
+        )
+        .execute(&mut *db_tx)
+        .await?;
         Ok(Self { db_tx })
     }
+
+    async fn upsert_domain(
+        &mut self,
+        ldh_name: &str,
+        unicode_name: Option<&str>,
+        response: RdapResponse,
+    ) -> Result<(), RdapServerError> {
+        query(
+            "insert into domain (ldh_name, unicode_name, response) values ($1, $2, $3) \
+             on conflict (ldh_name) do update \
+             set unicode_name = excluded.unicode_name, response = excluded.response",
+        )
+        .bind(ldh_name)
+        .bind(unicode_name)
+        .bind(Json(response))
+        .execute(&mut *self.db_tx)
+        .await?;
+
+        // the search indexes are rebuilt by the caller
+        query("delete from domain_ns_ip where domain_ldh_name = $1")
+            .bind(ldh_name)
+            .execute(&mut *self.db_tx)
+            .await?;
+        query("delete from domain_ns_ldh_name where domain_ldh_name = $1")
+            .bind(ldh_name)
+            .execute(&mut *self.db_tx)
+            .await?;
+        Ok(())
+    }
+
+    async fn upsert_nameserver(
+        &mut self,
+        ldh_name: &str,
+        response: RdapResponse,
+    ) -> Result<(), RdapServerError> {
+        query(
+            "insert into nameserver (ldh_name, response) values ($1, $2) \
+             on conflict (ldh_name) do update set response = excluded.response",
+        )
+        .bind(ldh_name)
+        .bind(Json(response))
+        .execute(&mut *self.db_tx)
+        .await?;
+
+        // the search index is rebuilt by the caller
+        query("delete from nameserver_ip where nameserver_ldh_name = $1")
+            .bind(ldh_name)
+            .execute(&mut *self.db_tx)
+            .await?;
+        Ok(())
+    }
+
+    async fn upsert_entity(
+        &mut self,
+        handle: &str,
+        full_name: Option<&str>,
+        response: RdapResponse,
+    ) -> Result<(), RdapServerError> {
+        query(
+            "insert into entity (handle, full_name, response) values ($1, $2, $3) \
+             on conflict (handle) do update \
+             set full_name = excluded.full_name, response = excluded.response",
+        )
+        .bind(handle)
+        .bind(full_name)
+        .bind(Json(response))
+        .execute(&mut *self.db_tx)
+        .await?;
+        Ok(())
+    }
+
+    async fn upsert_autnum(
+        &mut self,
+        start_autnum: u32,
+        end_autnum: u32,
+        response: RdapResponse,
+    ) -> Result<(), RdapServerError> {
+        // the new id keeps the range the most recently added
+        query(
+            "insert into autnum (start_autnum, end_autnum, response) values ($1, $2, $3) \
+             on conflict (start_autnum, end_autnum) do update \
+             set id = excluded.id, response = excluded.response",
+        )
+        .bind(i64::from(start_autnum))
+        .bind(i64::from(end_autnum))
+        .bind(Json(response))
+        .execute(&mut *self.db_tx)
+        .await?;
+        Ok(())
+    }
+
+    async fn upsert_networks(
+        &mut self,
+        subnets: Vec<IpNet>,
+        response: RdapResponse,
+    ) -> Result<(), RdapServerError> {
+        let response = Json(response);
+        for net in subnets {
+            query(
+                "insert into network (cidr, response) values ($1::cidr, $2) \
+                 on conflict (cidr) do update set response = excluded.response",
+            )
+            .bind(net.trunc().to_string())
+            .bind(&response)
+            .execute(&mut *self.db_tx)
+            .await?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl TxHandle for PgTx<'_> {
-    async fn add_entity(&mut self, _entity: &Entity) -> Result<(), RdapServerError> {
-        todo!()
+    async fn add_entity(&mut self, entity: &Entity) -> Result<(), RdapServerError> {
+        let handle = entity
+            .object_common
+            .handle
+            .as_ref()
+            .ok_or_else(|| RdapServerError::EmptyIndexData("handle".to_string()))?;
+        let full_name = entity
+            .contact()
+            .and_then(|contact| contact.full_name().map(str::to_string));
+        self.upsert_entity(handle, full_name.as_deref(), entity.clone().to_response())
+            .await
     }
 
     async fn add_entity_err(
         &mut self,
-        _entity_id: &EntityId,
-        _error: &Rfc9083Error,
+        entity_id: &EntityId,
+        error: &Rfc9083Error,
     ) -> Result<(), RdapServerError> {
-        todo!()
+        self.upsert_entity(&entity_id.handle, None, error.clone().to_response())
+            .await
     }
 
-    async fn add_domain(&mut self, _domain: &Domain) -> Result<(), RdapServerError> {
-        // TODO actually complete this
-        // this is just here to make sure something will compile
-        sqlx::query("insert domain")
-            .execute(&mut *self.db_tx)
-            .await?;
+    async fn add_domain(&mut self, domain: &Domain) -> Result<(), RdapServerError> {
+        // add the domain as LDH, which is required.
+        let ldh_name = domain
+            .ldh_name
+            .as_ref()
+            .ok_or_else(|| RdapServerError::EmptyIndexData("ldhName".to_string()))?;
+        self.upsert_domain(
+            ldh_name,
+            domain.unicode_name.as_deref(),
+            domain.clone().to_response(),
+        )
+        .await?;
+
+        if let Some(nameservers) = domain.nameservers.as_ref() {
+            for nameserver in nameservers {
+                for ip in nameserver_ips(nameserver) {
+                    query("insert into domain_ns_ip (domain_ldh_name, ip) values ($1, $2::inet)")
+                        .bind(ldh_name)
+                        .bind(ip.to_string())
+                        .execute(&mut *self.db_tx)
+                        .await?;
+                }
+                if let Some(ns_ldh_name) = nameserver.ldh_name.as_ref() {
+                    query(
+                        "insert into domain_ns_ldh_name (domain_ldh_name, ns_ldh_name) \
+                         values ($1, $2)",
+                    )
+                    .bind(ldh_name)
+                    .bind(ns_ldh_name)
+                    .execute(&mut *self.db_tx)
+                    .await?;
+                }
+            }
+        }
         Ok(())
     }
 
     async fn add_domain_err(
         &mut self,
-        _domain_id: &DomainId,
-        _error: &Rfc9083Error,
+        domain_id: &DomainId,
+        error: &Rfc9083Error,
     ) -> Result<(), RdapServerError> {
-        todo!()
+        self.upsert_domain(&domain_id.ldh_name, None, error.clone().to_response())
+            .await
     }
 
-    async fn add_nameserver(&mut self, _nameserver: &Nameserver) -> Result<(), RdapServerError> {
-        todo!()
+    async fn add_nameserver(&mut self, nameserver: &Nameserver) -> Result<(), RdapServerError> {
+        let ldh_name = nameserver
+            .ldh_name
+            .as_ref()
+            .ok_or_else(|| RdapServerError::EmptyIndexData("ldhName".to_string()))?;
+        self.upsert_nameserver(ldh_name, nameserver.clone().to_response())
+            .await?;
+        for ip in nameserver_ips(nameserver) {
+            query("insert into nameserver_ip (nameserver_ldh_name, ip) values ($1, $2::inet)")
+                .bind(ldh_name)
+                .bind(ip.to_string())
+                .execute(&mut *self.db_tx)
+                .await?;
+        }
+        Ok(())
     }
 
     async fn add_nameserver_err(
         &mut self,
-        _nameserver_id: &NameserverId,
-        _error: &Rfc9083Error,
+        nameserver_id: &NameserverId,
+        error: &Rfc9083Error,
     ) -> Result<(), RdapServerError> {
-        todo!()
+        self.upsert_nameserver(&nameserver_id.ldh_name, error.clone().to_response())
+            .await
     }
 
-    async fn add_autnum(&mut self, _autnum: &Autnum) -> Result<(), RdapServerError> {
-        todo!()
+    async fn add_autnum(&mut self, autnum: &Autnum) -> Result<(), RdapServerError> {
+        let start_num = autnum
+            .start_autnum
+            .as_ref()
+            .and_then(|n| n.as_u32())
+            .ok_or_else(|| RdapServerError::EmptyIndexData("startNum".to_string()))?;
+        let end_num = autnum
+            .end_autnum
+            .as_ref()
+            .and_then(|n| n.as_u32())
+            .ok_or_else(|| RdapServerError::EmptyIndexData("endNum".to_string()))?;
+        self.upsert_autnum(start_num, end_num, autnum.clone().to_response())
+            .await
     }
 
     async fn add_autnum_err(
         &mut self,
-        _autnum_id: &AutnumId,
-        _error: &Rfc9083Error,
+        autnum_id: &AutnumId,
+        error: &Rfc9083Error,
     ) -> Result<(), RdapServerError> {
-        todo!()
+        self.upsert_autnum(
+            autnum_id.start_autnum,
+            autnum_id.end_autnum,
+            error.clone().to_response(),
+        )
+        .await
     }
 
-    async fn add_network(&mut self, _network: &Network) -> Result<(), RdapServerError> {
-        todo!()
+    async fn add_network(&mut self, network: &Network) -> Result<(), RdapServerError> {
+        let start_addr = network
+            .start_address
+            .as_ref()
+            .ok_or_else(|| RdapServerError::EmptyIndexData("startAddress".to_string()))?;
+        let end_addr = network
+            .end_address
+            .as_ref()
+            .ok_or_else(|| RdapServerError::EmptyIndexData("endAddress".to_string()))?;
+        network
+            .ip_version
+            .as_ref()
+            .ok_or_else(|| RdapServerError::EmptyIndexData("ipVersion".to_string()))?;
+        let subnets = range_subnets(start_addr, end_addr)?;
+        self.upsert_networks(subnets, network.clone().to_response())
+            .await
     }
 
     async fn add_network_err(
         &mut self,
-        _network_id: &NetworkId,
-        _error: &Rfc9083Error,
+        network_id: &NetworkId,
+        error: &Rfc9083Error,
     ) -> Result<(), RdapServerError> {
-        todo!()
+        let subnets = network_id_subnets(network_id)?;
+        self.upsert_networks(subnets, error.clone().to_response())
+            .await
     }
 
     async fn add_srv_help(
         &mut self,
-        _help: &icann_rdap_common::response::Help,
-        _host: Option<&str>,
+        help: &Help,
+        host: Option<&str>,
     ) -> Result<(), RdapServerError> {
-        todo!()
+        let host = host.unwrap_or("..default");
+        query(
+            "insert into srv_help (host, response) values ($1, $2) \
+             on conflict (host) do update set response = excluded.response",
+        )
+        .bind(host)
+        .bind(Json(help.clone().to_response()))
+        .execute(&mut *self.db_tx)
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_domain(&mut self, domain_id: &DomainId) -> Result<(), RdapServerError> {
+        for statement in [
+            "delete from domain_ns_ip where domain_ldh_name = $1",
+            "delete from domain_ns_ldh_name where domain_ldh_name = $1",
+            "delete from domain where ldh_name = $1",
+        ] {
+            query(statement)
+                .bind(&domain_id.ldh_name)
+                .execute(&mut *self.db_tx)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn delete_entity(&mut self, entity_id: &EntityId) -> Result<(), RdapServerError> {
+        query("delete from entity where handle = $1")
+            .bind(&entity_id.handle)
+            .execute(&mut *self.db_tx)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_nameserver(
+        &mut self,
+        nameserver_id: &NameserverId,
+    ) -> Result<(), RdapServerError> {
+        for statement in [
+            "delete from nameserver_ip where nameserver_ldh_name = $1",
+            "delete from nameserver where ldh_name = $1",
+        ] {
+            query(statement)
+                .bind(&nameserver_id.ldh_name)
+                .execute(&mut *self.db_tx)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn delete_autnum(&mut self, autnum_id: &AutnumId) -> Result<(), RdapServerError> {
+        query("delete from autnum where start_autnum = $1 and end_autnum = $2")
+            .bind(i64::from(autnum_id.start_autnum))
+            .bind(i64::from(autnum_id.end_autnum))
+            .execute(&mut *self.db_tx)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_network(&mut self, network_id: &NetworkId) -> Result<(), RdapServerError> {
+        for net in network_id_subnets(network_id)? {
+            query("delete from network where cidr = $1::cidr")
+                .bind(net.trunc().to_string())
+                .execute(&mut *self.db_tx)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn delete_srv_help(&mut self, host: Option<&str>) -> Result<(), RdapServerError> {
+        query("delete from srv_help where host = $1")
+            .bind(host.unwrap_or("..default"))
+            .execute(&mut *self.db_tx)
+            .await?;
+        Ok(())
+    }
+
+    async fn savepoint(&mut self) -> Result<(), RdapServerError> {
+        query("savepoint tx_savepoint")
+            .execute(&mut *self.db_tx)
+            .await?;
+        Ok(())
+    }
+
+    async fn release_savepoint(&mut self) -> Result<(), RdapServerError> {
+        query("release savepoint tx_savepoint")
+            .execute(&mut *self.db_tx)
+            .await?;
+        Ok(())
+    }
+
+    async fn rollback_to_savepoint(&mut self) -> Result<(), RdapServerError> {
+        query("rollback to savepoint tx_savepoint")
+            .execute(&mut *self.db_tx)
+            .await?;
+        self.release_savepoint().await
     }
 
     async fn commit(self: Box<Self>) -> Result<(), RdapServerError> {
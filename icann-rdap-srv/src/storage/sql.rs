@@ -0,0 +1,80 @@
+//! Utilities shared by the SQL storage backends.
+
+use std::{net::IpAddr, str::FromStr};
+
+use {
+    icann_rdap_common::response::Nameserver,
+    ipnet::{IpNet, Ipv4Subnets, Ipv6Subnets},
+};
+
+use crate::{
+    error::RdapServerError,
+    storage::data::{NetworkId, NetworkIdType},
+};
+
+/// Gets the CIDR blocks covering an address range.
+pub(crate) fn range_subnets(
+    start_address: &str,
+    end_address: &str,
+) -> Result<Vec<IpNet>, RdapServerError> {
+    let start_addr = IpAddr::from_str(start_address)?;
+    let end_addr = IpAddr::from_str(end_address)?;
+    match (start_addr, end_addr) {
+        (IpAddr::V4(start_addr), IpAddr::V4(end_addr)) => {
+            Ok(Ipv4Subnets::new(start_addr, end_addr, 0)
+                .map(IpNet::V4)
+                .collect())
+        }
+        (IpAddr::V6(start_addr), IpAddr::V6(end_addr)) => {
+            Ok(Ipv6Subnets::new(start_addr, end_addr, 0)
+                .map(IpNet::V6)
+                .collect())
+        }
+        _ => Err(RdapServerError::EmptyIndexData(
+            "mismatch ip version".to_string(),
+        )),
+    }
+}
+
+/// Gets the CIDR blocks of the id of a network.
+pub(crate) fn network_id_subnets(network_id: &NetworkId) -> Result<Vec<IpNet>, RdapServerError> {
+    match &network_id.network_id {
+        NetworkIdType::Cidr(cidr) => Ok(cidr.subnets(cidr.prefix_len())?.collect()),
+        NetworkIdType::Range {
+            start_address,
+            end_address,
+        } => range_subnets(start_address, end_address),
+    }
+}
+
+/// Gets the parseable IP addresses of a nameserver.
+pub(crate) fn nameserver_ips(nameserver: &Nameserver) -> Vec<IpAddr> {
+    nameserver
+        .ip_addresses()
+        .map(|ip_addresses| {
+            ip_addresses
+                .v4s()
+                .iter()
+                .chain(ip_addresses.v6s().iter())
+                .filter_map(|ip_str| ip_str.parse::<IpAddr>().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Gets the octets of an IP address. Octets of the same IP version compare
+/// in the same order as the addresses.
+pub(crate) fn ip_octets(addr: IpAddr) -> Vec<u8> {
+    match addr {
+        IpAddr::V4(v4) => v4.octets().to_vec(),
+        IpAddr::V6(v6) => v6.octets().to_vec(),
+    }
+}
+
+/// Gets the IP version number (4 or 6) of an IP address.
+pub(crate) fn ip_version(addr: IpAddr) -> i64 {
+    match addr {
+        IpAddr::V4(_) => 4,
+        IpAddr::V6(_) => 6,
+    }
+}
@@ -0,0 +1,543 @@
+use std::{net::IpAddr, str::FromStr};
+
+use {
+    async_trait::async_trait,
+    icann_rdap_common::{
+        prelude::{ExtensionId, ToResponse},
+        response::{
+            Autnum, AutnumSearchResults, Domain, DomainSearchResults, Entity, EntitySearchResults,
+            Nameserver, NameserverSearchResults, Network, NetworkSearchResults, RdapResponse,
+        },
+    },
+    ipnet::IpNet,
+    sqlx::{
+        query,
+        query::QueryScalar,
+        query_scalar,
+        sqlite::{SqliteArguments, SqliteConnectOptions, SqliteJournalMode},
+        types::Json,
+        SqlitePool,
+    },
+    tracing::{debug, info},
+};
+
+use crate::{
+    error::RdapServerError,
+    rdap::response::{NOT_FOUND, NOT_IMPLEMENTED},
+    storage::{
+        mem::label_search::{like_patterns, DNS_SEPARATORS, HANDLE_SEPARATORS, NAME_SEPARATORS},
+        sql::{ip_octets, ip_version},
+        NetworkRelation, ReverseSearch, StoreOps, TxHandle,
+    },
+};
+
+use super::{config::SqliteConfig, tx::SqliteTx};
+
+type ResponseQuery<'q> = QueryScalar<'q, sqlx::Sqlite, Json<RdapResponse>, SqliteArguments<'q>>;
+
+#[derive(Clone)]
+pub struct Sqlite {
+    sqlite_pool: SqlitePool,
+    config: SqliteConfig,
+}
+
+impl Sqlite {
+    pub async fn new(config: SqliteConfig) -> Result<Self, RdapServerError> {
+        // LIKE is case sensitive to match the other storage engines.
+        let options = SqliteConnectOptions::from_str(&config.db_url)?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("case_sensitive_like", "ON");
+        let sqlite_pool = SqlitePool::connect_with(options).await?;
+        Ok(Self {
+            sqlite_pool,
+            config,
+        })
+    }
+
+    /// Gets the first response of the query, or [NOT_FOUND] if there is none.
+    async fn get_response(
+        &self,
+        query: ResponseQuery<'_>,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let response = query.fetch_optional(&self.sqlite_pool).await?;
+        Ok(response.map_or_else(|| NOT_FOUND.clone(), |json| json.0))
+    }
+
+    /// Gets all the responses of the query.
+    async fn get_responses(
+        &self,
+        query: ResponseQuery<'_>,
+    ) -> Result<Vec<RdapResponse>, RdapServerError> {
+        let responses = query.fetch_all(&self.sqlite_pool).await?;
+        Ok(responses.into_iter().map(|json| json.0).collect())
+    }
+
+    async fn domain_search_results(
+        &self,
+        query: ResponseQuery<'_>,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let results = self
+            .get_responses(query)
+            .await?
+            .into_iter()
+            .filter_map(|d| match d {
+                RdapResponse::Domain(d) => Some(*d),
+                _ => None,
+            })
+            .collect::<Vec<Domain>>();
+        let response = DomainSearchResults::response_obj()
+            .results(results)
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn nameserver_search_results(
+        &self,
+        query: ResponseQuery<'_>,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let results = self
+            .get_responses(query)
+            .await?
+            .into_iter()
+            .filter_map(|n| match n {
+                RdapResponse::Nameserver(ns) => Some(*ns),
+                _ => None,
+            })
+            .collect::<Vec<Nameserver>>();
+        let response = NameserverSearchResults::response_obj()
+            .results(results)
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn entity_search_results(
+        &self,
+        query: ResponseQuery<'_>,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let results = self
+            .get_responses(query)
+            .await?
+            .into_iter()
+            .filter_map(|e| match e {
+                RdapResponse::Entity(ent) => Some(*ent),
+                _ => None,
+            })
+            .collect::<Vec<Entity>>();
+        let response = EntitySearchResults::response_obj()
+            .results(results)
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn network_search_results(
+        &self,
+        query: ResponseQuery<'_>,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let results = self
+            .get_responses(query)
+            .await?
+            .into_iter()
+            .filter_map(|r| match r {
+                RdapResponse::Network(net) => Some(*net),
+                _ => None,
+            })
+            .collect::<Vec<Network>>();
+        let response = NetworkSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::RirSearch1.to_extension())
+            .extension(ExtensionId::Ips.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
+    }
+
+    async fn autnum_search_results(
+        &self,
+        query: ResponseQuery<'_>,
+    ) -> Result<RdapResponse, RdapServerError> {
+        let results = self
+            .get_responses(query)
+            .await?
+            .into_iter()
+            .filter_map(|r| match r {
+                RdapResponse::Autnum(a) => Some(*a),
+                _ => None,
+            })
+            .collect::<Vec<Autnum>>();
+        let response = AutnumSearchResults::response_obj()
+            .results(results)
+            .extension(ExtensionId::RirSearch1.to_extension())
+            .extension(ExtensionId::Autnums.to_extension())
+            .build()
+            .to_response();
+        Ok(response)
+    }
+}
+
+/// Builds a SQL condition that matches a column against any of the `LIKE` patterns,
+/// or nothing if there are no patterns.
+fn like_any(column: &str, patterns: &[String]) -> String {
+    if patterns.is_empty() {
+        return "false".to_string();
+    }
+    let conditions = patterns
+        .iter()
+        .map(|_| format!(r"{column} like ? escape '\'"))
+        .collect::<Vec<String>>();
+    format!("({})", conditions.join(" or "))
+}
+
+/// Binds the `LIKE` patterns to a query, in order.
+fn bind_patterns(query: ResponseQuery<'_>, patterns: Vec<String>) -> ResponseQuery<'_> {
+    patterns
+        .into_iter()
+        .fold(query, |query, pattern| query.bind(pattern))
+}
+
+#[async_trait]
+impl StoreOps for Sqlite {
+    async fn init(&self) -> Result<(), RdapServerError> {
+        debug!("Testing database connection.");
+        let mut conn = self.sqlite_pool.acquire().await?;
+        query("select 1").fetch_one(&mut *conn).await?;
+        info!("Database connection test is successful.");
+        debug!("Running database migrations.");
+        sqlx::migrate!("./migrations/sqlite")
+            .run(&self.sqlite_pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn ping(&self) -> Result<(), RdapServerError> {
+        query("select 1").fetch_one(&self.sqlite_pool).await?;
+        Ok(())
+    }
+
+    async fn new_tx(&self) -> Result<Box<dyn TxHandle>, RdapServerError> {
+        Ok(Box::new(SqliteTx::new(&self.sqlite_pool).await?))
+    }
+
+    async fn new_truncate_tx(&self) -> Result<Box<dyn TxHandle>, RdapServerError> {
+        Ok(Box::new(SqliteTx::new_truncate(&self.sqlite_pool).await?))
+    }
+
+    async fn get_domain_by_ldh(&self, ldh: &str) -> Result<RdapResponse, RdapServerError> {
+        self.get_response(
+            query_scalar("select response from domain where ldh_name = ?").bind(ldh.to_owned()),
+        )
+        .await
+    }
+
+    async fn get_domain_by_unicode(&self, unicode: &str) -> Result<RdapResponse, RdapServerError> {
+        self.get_response(
+            query_scalar("select response from domain where unicode_name = ? limit 1")
+                .bind(unicode.to_owned()),
+        )
+        .await
+    }
+
+    async fn get_entity_by_handle(&self, handle: &str) -> Result<RdapResponse, RdapServerError> {
+        self.get_response(
+            query_scalar("select response from entity where handle = ?").bind(handle.to_owned()),
+        )
+        .await
+    }
+
+    async fn get_nameserver_by_ldh(&self, ldh: &str) -> Result<RdapResponse, RdapServerError> {
+        self.get_response(
+            query_scalar("select response from nameserver where ldh_name = ?").bind(ldh.to_owned()),
+        )
+        .await
+    }
+
+    async fn get_autnum_by_num(&self, num: u32) -> Result<RdapResponse, RdapServerError> {
+        // overlapping ranges are resolved in favor of the last one added.
+        self.get_response(
+            query_scalar(
+                "select response from autnum \
+                 where start_autnum <= ?1 and end_autnum >= ?1 \
+                 order by id desc limit 1",
+            )
+            .bind(i64::from(num)),
+        )
+        .await
+    }
+
+    async fn get_network_by_ipaddr(&self, ipaddr: &str) -> Result<RdapResponse, RdapServerError> {
+        let addr = ipaddr.parse::<IpAddr>()?;
+        self.get_response(
+            query_scalar(
+                "select response from network \
+                 where ip_version = ?1 and start_address <= ?2 and end_address >= ?2 \
+                 order by prefix_len desc limit 1",
+            )
+            .bind(ip_version(addr))
+            .bind(ip_octets(addr)),
+        )
+        .await
+    }
+
+    async fn get_network_by_cidr(&self, cidr: &str) -> Result<RdapResponse, RdapServerError> {
+        let net = IpNet::from_str(cidr)?;
+        self.get_response(
+            query_scalar(
+                "select response from network \
+                 where ip_version = ?1 and start_address <= ?2 and end_address >= ?3 \
+                 order by prefix_len desc limit 1",
+            )
+            .bind(ip_version(net.network()))
+            .bind(ip_octets(net.network()))
+            .bind(ip_octets(net.broadcast())),
+        )
+        .await
+    }
+
+    async fn get_related_networks(
+        &self,
+        _netid: &str,
+        _relation: NetworkRelation,
+    ) -> Result<RdapResponse, RdapServerError> {
+        // the network hierarchy is only kept by the in-memory storage
+        Ok(NOT_IMPLEMENTED.clone())
+    }
+
+    async fn get_network_relations(
+        &self,
+        _netid: &str,
+    ) -> Result<Vec<NetworkRelation>, RdapServerError> {
+        // the network hierarchy is only kept by the in-memory storage
+        Ok(vec![])
+    }
+
+    async fn get_srv_help(&self, host: Option<&str>) -> Result<RdapResponse, RdapServerError> {
+        let host = host.unwrap_or("..default");
+        self.get_response(
+            query_scalar("select response from srv_help where host = ?").bind(host.to_owned()),
+        )
+        .await
+    }
+
+    async fn search_domains_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.domain_search_by_name_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        //else
+        // an invalid search matches nothing, as with the in-memory storage
+        let patterns = like_patterns(name, DNS_SEPARATORS).unwrap_or_default();
+        let sql = format!(
+            "select response from domain where {} order by ldh_name",
+            like_any("ldh_name", &patterns)
+        );
+        self.domain_search_results(bind_patterns(query_scalar(&sql), patterns))
+            .await
+    }
+
+    async fn search_nameservers_by_name(
+        &self,
+        name: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.nameserver_search_by_name_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        //else
+        let patterns = like_patterns(name, DNS_SEPARATORS).unwrap_or_default();
+        let sql = format!(
+            "select response from nameserver where {} order by ldh_name",
+            like_any("ldh_name", &patterns)
+        );
+        self.nameserver_search_results(bind_patterns(query_scalar(&sql), patterns))
+            .await
+    }
+
+    async fn search_nameservers_by_ip(&self, ip: IpAddr) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.nameserver_search_by_ip_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        //else
+        self.nameserver_search_results(
+            query_scalar(
+                "select response from nameserver where ldh_name in \
+                 (select nameserver_ldh_name from nameserver_ip where ip = ?) \
+                 order by ldh_name",
+            )
+            .bind(ip.to_string()),
+        )
+        .await
+    }
+
+    async fn search_domains_by_ns_ip(&self, ip: IpAddr) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.domain_search_by_ns_ip_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        self.domain_search_results(
+            query_scalar(
+                "select response from domain where ldh_name in \
+                 (select domain_ldh_name from domain_ns_ip where ip = ?) \
+                 order by ldh_name",
+            )
+            .bind(ip.to_string()),
+        )
+        .await
+    }
+
+    async fn search_domains_by_ns_ldh_name(
+        &self,
+        name: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self
+            .config
+            .common_config
+            .domain_search_by_ns_ldh_name_enable
+        {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let patterns = like_patterns(name, DNS_SEPARATORS).unwrap_or_default();
+        let sql = format!(
+            "select response from domain where ldh_name in \
+             (select domain_ldh_name from domain_ns_ldh_name where {}) \
+             order by ldh_name",
+            like_any("ns_ldh_name", &patterns)
+        );
+        self.domain_search_results(bind_patterns(query_scalar(&sql), patterns))
+            .await
+    }
+
+    async fn search_entities_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.entity_search_by_handle_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let patterns = like_patterns(handle, HANDLE_SEPARATORS).unwrap_or_default();
+        let sql = format!(
+            "select response from entity where {} order by handle",
+            like_any("handle", &patterns)
+        );
+        self.entity_search_results(bind_patterns(query_scalar(&sql), patterns))
+            .await
+    }
+
+    async fn search_entities_by_full_name(
+        &self,
+        full_name: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.entity_search_by_full_name_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let patterns = like_patterns(full_name, NAME_SEPARATORS).unwrap_or_default();
+        let sql = format!(
+            "select response from entity where {} order by handle",
+            like_any("full_name", &patterns)
+        );
+        self.entity_search_results(bind_patterns(query_scalar(&sql), patterns))
+            .await
+    }
+
+    async fn search_networks_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.network_search_by_handle_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        // a network has a row for each of its CIDRs
+        let patterns = like_patterns(handle, HANDLE_SEPARATORS).unwrap_or_default();
+        let sql = format!(
+            "select response from network where {} \
+             group by response order by min(json_extract(response, '$.handle'))",
+            like_any("json_extract(response, '$.handle')", &patterns)
+        );
+        self.network_search_results(bind_patterns(query_scalar(&sql), patterns))
+            .await
+    }
+
+    async fn search_networks_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.network_search_by_name_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let patterns = like_patterns(name, NAME_SEPARATORS).unwrap_or_default();
+        let sql = format!(
+            "select response from network where {} \
+             group by response order by min(json_extract(response, '$.name'))",
+            like_any("json_extract(response, '$.name')", &patterns)
+        );
+        self.network_search_results(bind_patterns(query_scalar(&sql), patterns))
+            .await
+    }
+
+    async fn search_autnums_by_handle(
+        &self,
+        handle: &str,
+    ) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.autnum_search_by_handle_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let patterns = like_patterns(handle, HANDLE_SEPARATORS).unwrap_or_default();
+        let sql = format!(
+            "select response from autnum where {} \
+             order by json_extract(response, '$.handle')",
+            like_any("json_extract(response, '$.handle')", &patterns)
+        );
+        self.autnum_search_results(bind_patterns(query_scalar(&sql), patterns))
+            .await
+    }
+
+    async fn search_autnums_by_name(&self, name: &str) -> Result<RdapResponse, RdapServerError> {
+        if !self.config.common_config.autnum_search_by_name_enable {
+            return Ok(NOT_IMPLEMENTED.clone());
+        }
+        let patterns = like_patterns(name, NAME_SEPARATORS).unwrap_or_default();
+        let sql = format!(
+            "select response from autnum where {} \
+             order by json_extract(response, '$.name')",
+            like_any("json_extract(response, '$.name')", &patterns)
+        );
+        self.autnum_search_results(bind_patterns(query_scalar(&sql), patterns))
+            .await
+    }
+
+    async fn reverse_search_domains(
+        &self,
+        _search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError> {
+        // reverse searches are only indexed by the in-memory storage
+        Ok(NOT_IMPLEMENTED.clone())
+    }
+
+    async fn reverse_search_nameservers(
+        &self,
+        _search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError> {
+        // reverse searches are only indexed by the in-memory storage
+        Ok(NOT_IMPLEMENTED.clone())
+    }
+
+    async fn reverse_search_entities(
+        &self,
+        _search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError> {
+        // reverse searches are only indexed by the in-memory storage
+        Ok(NOT_IMPLEMENTED.clone())
+    }
+
+    async fn reverse_search_networks(
+        &self,
+        _search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError> {
+        // reverse searches are only indexed by the in-memory storage
+        Ok(NOT_IMPLEMENTED.clone())
+    }
+
+    async fn reverse_search_autnums(
+        &self,
+        _search: &ReverseSearch,
+    ) -> Result<RdapResponse, RdapServerError> {
+        // reverse searches are only indexed by the in-memory storage
+        Ok(NOT_IMPLEMENTED.clone())
+    }
+}
@@ -0,0 +1,195 @@
+//! HTTPS serving, with the certificate reloaded when its files change and plain
+//! HTTP requests redirected to HTTPS.
+
+use std::{
+    fs,
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use {
+    axum::{
+        extract::State,
+        response::{IntoResponse, Response},
+        Router,
+    },
+    axum_server::tls_rustls::RustlsConfig,
+    http::{header::LOCATION, StatusCode, Uri},
+    rustls::{
+        crypto::aws_lc_rs,
+        pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+        ServerConfig,
+    },
+    tokio::net::TcpListener,
+    tracing::{debug, info, warn},
+};
+
+use crate::{config::TlsConfig, error::RdapServerError};
+
+/// How often the certificate files are checked for changes.
+const RELOAD_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The HTTPS state of a listener.
+pub(crate) struct Tls {
+    pub(crate) config: TlsConfig,
+    pub(crate) rustls: RustlsConfig,
+    pub(crate) redirect_listener: Option<TcpListener>,
+}
+
+impl Tls {
+    /// Loads the certificate and binds the redirect port, if there is one, to
+    /// the same address as the HTTPS port.
+    pub(crate) async fn load(config: &TlsConfig, ip_addr: IpAddr) -> Result<Self, RdapServerError> {
+        let rustls = RustlsConfig::from_config(server_config(config).await?);
+        let redirect_listener = match config.redirect_port {
+            Some(_) if config.redirect_host.is_none() => {
+                return Err(RdapServerError::Config(
+                    "a redirect host must be given with the redirect port".to_string(),
+                ))
+            }
+            Some(port) => {
+                let listener = TcpListener::bind(SocketAddr::new(ip_addr, port)).await?;
+                debug!("redirecting http from {}", listener.local_addr()?);
+                Some(listener)
+            }
+            None => None,
+        };
+        Ok(Self {
+            config: config.clone(),
+            rustls,
+            redirect_listener,
+        })
+    }
+
+    /// The value of the `Strict-Transport-Security` header, if it is configured.
+    pub(crate) fn hsts(&self) -> Option<String> {
+        self.config
+            .hsts_max_age
+            .map(|max_age| format!("max-age={max_age}"))
+    }
+}
+
+/// Reads the certificate chain and key into a rustls configuration.
+///
+/// The crypto provider is given to the configuration instead of being installed
+/// as the process default, which is left to the application.
+async fn server_config(config: &TlsConfig) -> Result<Arc<ServerConfig>, RdapServerError> {
+    let invalid = |e: String| {
+        RdapServerError::Config(format!(
+            "unable to load certificate {} and key {}: {e}",
+            config.cert_file, config.key_file
+        ))
+    };
+    let cert = tokio::fs::read(&config.cert_file).await?;
+    let key = tokio::fs::read(&config.key_file).await?;
+    let certs = CertificateDer::pem_slice_iter(&cert)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| invalid(e.to_string()))?;
+    if certs.is_empty() {
+        return Err(invalid("no certificate found".to_string()));
+    }
+    let key = PrivateKeyDer::from_pem_slice(&key).map_err(|e| invalid(e.to_string()))?;
+    let mut server_config =
+        ServerConfig::builder_with_provider(Arc::new(aws_lc_rs::default_provider()))
+            .with_safe_default_protocol_versions()
+            .and_then(|builder| builder.with_no_client_auth().with_single_cert(certs, key))
+            .map_err(|e| invalid(e.to_string()))?;
+    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(Arc::new(server_config))
+}
+
+/// Serves redirects of plain HTTP requests to the HTTPS port of the host,
+/// until the shutdown completes.
+pub(crate) async fn start_redirect(
+    listener: TcpListener,
+    host: String,
+    https_port: u16,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) {
+    let app = Router::new()
+        .fallback(redirect_to_https)
+        .with_state(Arc::new((host, https_port)));
+    if let Err(e) = axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown)
+        .await
+    {
+        warn!("http redirect server failed: {e}");
+    }
+}
+
+async fn redirect_to_https(State(https): State<Arc<(String, u16)>>, uri: Uri) -> Response {
+    let (host, https_port) = &*https;
+    let path = uri.path_and_query().map_or("/", |path| path.as_str());
+    (
+        StatusCode::PERMANENT_REDIRECT,
+        [(LOCATION, redirect_location(host, *https_port, path))],
+    )
+        .into_response()
+}
+
+/// Gets the HTTPS URL of a path on the configured host. The `Host` header of the
+/// request is not used, as clients could redirect themselves anywhere with it.
+fn redirect_location(host: &str, https_port: u16, path: &str) -> String {
+    if https_port == 443 {
+        format!("https://{host}{path}")
+    } else {
+        format!("https://{host}:{https_port}{path}")
+    }
+}
+
+/// Reloads the certificate whenever its files are modified.
+pub(crate) async fn watch_certificate(config: TlsConfig, rustls: RustlsConfig) {
+    let mut modified = modified_times(&config);
+    let mut interval = tokio::time::interval(RELOAD_INTERVAL);
+    loop {
+        interval.tick().await;
+        let now_modified = modified_times(&config);
+        if now_modified == modified {
+            continue;
+        }
+        modified = now_modified;
+        match server_config(&config).await {
+            Ok(server_config) => {
+                rustls.reload_from_config(server_config);
+                info!("reloaded certificate {}", config.cert_file)
+            }
+            Err(e) => warn!("unable to reload certificate {}: {e}", config.cert_file),
+        }
+    }
+}
+
+fn modified_times(config: &TlsConfig) -> [Option<SystemTime>; 2] {
+    [&config.cert_file, &config.key_file]
+        .map(|file| fs::metadata(file).and_then(|m| m.modified()).ok())
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::redirect_location;
+
+    #[test]
+    fn GIVEN_hosts_WHEN_redirect_location_THEN_https_port_used() {
+        // GIVEN
+        let hosts = [
+            ("example.net", 443, "https://example.net/rdap/help"),
+            ("localhost", 8443, "https://localhost:8443/rdap/help"),
+            ("[2001:db8::1]", 443, "https://[2001:db8::1]/rdap/help"),
+            (
+                "[2001:db8::1]",
+                8443,
+                "https://[2001:db8::1]:8443/rdap/help",
+            ),
+        ];
+
+        for (host, port, expected) in hosts {
+            // WHEN
+            let actual = redirect_location(host, port, "/rdap/help");
+
+            // THEN
+            assert_eq!(actual, expected, "{host}");
+        }
+    }
+}
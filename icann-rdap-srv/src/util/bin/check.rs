@@ -46,18 +46,27 @@ pub fn to_check_classes(args: &CheckArgs) -> Vec<CheckClass> {
 
 /// Print errors and returns true if a check is found.
 pub fn check_rdap(rdap: RdapResponse, check_types: &[CheckClass]) -> bool {
+    let messages = rdap_check_messages(&rdap, check_types);
+    messages.iter().for_each(|message| error!("{message}"));
+    !messages.is_empty()
+}
+
+/// Gets a description of each check of the given types found in the RDAP.
+pub fn rdap_check_messages(rdap: &RdapResponse, check_types: &[CheckClass]) -> Vec<String> {
     let checks = rdap.get_checks(
         None,
         CheckParams {
-            root: &rdap,
+            root: rdap,
             parent_type: rdap.get_type(),
             allow_unreg_ext: true,
         },
     );
+    let mut messages = vec![];
     traverse_checks(
         &checks,
         check_types,
         None,
-        &mut |struct_tree, check_item| error!("{struct_tree} -> {check_item}"),
-    )
+        &mut |struct_tree, check_item| messages.push(format!("{struct_tree} -> {check_item}")),
+    );
+    messages
 }
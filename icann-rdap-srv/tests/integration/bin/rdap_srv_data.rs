@@ -76,6 +76,30 @@ fn GIVEN_no_data_dir_WHEN_invoked_THEN_data_stored_in_data_dir() {
         .is_some());
 }
 
+#[test]
+fn GIVEN_data_dir_with_bad_file_WHEN_invoked_THEN_failure() {
+    // GIVEN
+    let mut test_jig = RdapSrvDataTestJig::new();
+    std::fs::write(test_jig.source_dir.path("bad.json"), "{ not json").expect("writing file");
+
+    // WHEN
+    test_jig
+        .cmd
+        .arg("--data-dir")
+        .arg(test_jig.source_dir.root())
+        .arg("entity")
+        .arg("--handle")
+        .arg("foo1234")
+        .arg("--email")
+        .arg("joe@example.com")
+        .arg("--full-name")
+        .arg("Joe User");
+
+    // THEN
+    let assert = test_jig.cmd.assert();
+    assert.failure();
+}
+
 #[test]
 fn GIVEN_entity_options_WHEN_create_data_THEN_success() {
     // GIVEN
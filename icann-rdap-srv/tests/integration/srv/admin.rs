@@ -0,0 +1,244 @@
+use {
+    icann_rdap_common::prelude::*,
+    icann_rdap_srv::{auth::AuthConfig, storage::StoreOps},
+    rstest::rstest,
+    serde_json::json,
+};
+
+use crate::test_jig::SrvTestJig;
+
+async fn new_admin_srv() -> SrvTestJig {
+    let auth = serde_json::from_value::<AuthConfig>(json!({
+        "users": [
+            { "name": "operator", "password": "secret", "tier": "admin" },
+            { "name": "registrar1", "password": "secret", "tier": "registrar" }
+        ]
+    }))
+    .expect("parsing auth config");
+    SrvTestJig::new_auth(auth).await
+}
+
+fn admin_url(test_srv: &SrvTestJig, path: &str) -> String {
+    format!("{}/{path}", test_srv.rdap_base.replace("/rdap", "/admin"))
+}
+
+async fn put(test_srv: &SrvTestJig, path: &str, rdap: &RdapResponse) -> reqwest::Response {
+    reqwest::Client::new()
+        .put(admin_url(test_srv, path))
+        .basic_auth("operator", Some("secret"))
+        .json(rdap)
+        .send()
+        .await
+        .expect("putting object")
+}
+
+async fn rdap_status(test_srv: &SrvTestJig, path: &str) -> u16 {
+    reqwest::Client::new()
+        .get(format!("{}/{path}", test_srv.rdap_base))
+        .send()
+        .await
+        .expect("querying server")
+        .status()
+        .as_u16()
+}
+
+#[tokio::test]
+async fn test_put_domain_is_served() {
+    // GIVEN
+    let test_srv = new_admin_srv().await;
+    let domain = Domain::response_obj()
+        .ldh_name("foo.example")
+        .build()
+        .to_response();
+
+    // WHEN
+    let response = put(&test_srv, "domain/foo.example", &domain).await;
+
+    // THEN
+    assert_eq!(response.status(), 204);
+    assert_eq!(rdap_status(&test_srv, "domain/foo.example").await, 200);
+    let stored = reqwest::Client::new()
+        .get(admin_url(&test_srv, "domain/foo.example"))
+        .basic_auth("operator", Some("secret"))
+        .send()
+        .await
+        .expect("getting object");
+    assert_eq!(stored.status(), 200);
+}
+
+#[tokio::test]
+async fn test_delete_network() {
+    // GIVEN
+    let test_srv = new_admin_srv().await;
+    let network = Network::response_obj()
+        .cidr("192.0.2.0/24")
+        .build()
+        .expect("cidr parsing")
+        .to_response();
+    let response = put(&test_srv, "ip/192.0.2.0/24", &network).await;
+    assert_eq!(response.status(), 204);
+    assert_eq!(rdap_status(&test_srv, "ip/192.0.2.1").await, 200);
+
+    // WHEN
+    let response = reqwest::Client::new()
+        .delete(admin_url(&test_srv, "ip/192.0.2.0/24"))
+        .basic_auth("operator", Some("secret"))
+        .send()
+        .await
+        .expect("deleting object");
+
+    // THEN
+    assert_eq!(response.status(), 204);
+    assert_eq!(rdap_status(&test_srv, "ip/192.0.2.1").await, 404);
+}
+
+#[tokio::test]
+async fn test_delete_absent_object_is_not_found() {
+    // GIVEN
+    let test_srv = new_admin_srv().await;
+
+    // WHEN
+    let response = reqwest::Client::new()
+        .delete(admin_url(&test_srv, "entity/REG-1"))
+        .basic_auth("operator", Some("secret"))
+        .send()
+        .await
+        .expect("deleting object");
+
+    // THEN
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_put_redirect_is_served() {
+    // GIVEN
+    let test_srv = new_admin_srv().await;
+    let redirect = Rfc9083Error::redirect()
+        .url("https://rdap.example.net/rdap/autnum/64496")
+        .build()
+        .to_response();
+
+    // WHEN
+    let response = put(&test_srv, "autnum/64496-64511", &redirect).await;
+
+    // THEN
+    assert_eq!(response.status(), 204);
+    let response = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("building client")
+        .get(format!("{}/autnum/64500", test_srv.rdap_base))
+        .send()
+        .await
+        .expect("querying server");
+    assert_eq!(response.status(), 307);
+    assert_eq!(
+        response.headers().get("location").expect("location header"),
+        "https://rdap.example.net/rdap/autnum/64496"
+    );
+}
+
+#[rstest]
+#[case("autnum/64500", 404)]
+#[case("autnum/64496-64511", 204)]
+#[case("ip/198.51.100.0/25", 404)]
+#[case("ip/198.51.100.0/24", 204)]
+#[tokio::test]
+async fn test_delete_redirect_only_of_exact_range(#[case] path: &str, #[case] expected: u16) {
+    // GIVEN
+    let test_srv = new_admin_srv().await;
+    let redirect = Rfc9083Error::redirect()
+        .url("https://rdap.example.net/rdap/")
+        .build()
+        .to_response();
+    for range in ["autnum/64496-64511", "ip/198.51.100.0/24"] {
+        let response = put(&test_srv, range, &redirect).await;
+        assert_eq!(response.status(), 204);
+    }
+
+    // WHEN
+    let response = reqwest::Client::new()
+        .delete(admin_url(&test_srv, path))
+        .basic_auth("operator", Some("secret"))
+        .send()
+        .await
+        .expect("deleting object");
+
+    // THEN
+    assert_eq!(response.status(), expected);
+}
+
+#[tokio::test]
+async fn test_put_object_failing_checks_is_refused() {
+    // GIVEN
+    let test_srv = new_admin_srv().await;
+    let domain = RdapResponse::try_from(json!({
+        "objectClassName": "domain",
+        "rdapConformance": ["rdap_level_0"],
+        "ldhName": "foo.example",
+        "status": []
+    }))
+    .expect("parsing domain");
+
+    // WHEN
+    let response = put(&test_srv, "domain/foo.example", &domain).await;
+
+    // THEN
+    assert_eq!(response.status(), 422);
+    assert_eq!(rdap_status(&test_srv, "domain/foo.example").await, 404);
+}
+
+#[tokio::test]
+async fn test_put_object_of_other_path_is_refused() {
+    // GIVEN
+    let test_srv = new_admin_srv().await;
+    let domain = Domain::response_obj()
+        .ldh_name("bar.example")
+        .build()
+        .to_response();
+
+    // WHEN
+    let response = put(&test_srv, "domain/foo.example", &domain).await;
+
+    // THEN
+    assert_eq!(response.status(), 400);
+    let stored = test_srv
+        .mem
+        .get_domain_by_ldh("bar.example")
+        .await
+        .expect("getting domain");
+    assert!(matches!(stored, RdapResponse::ErrorResponse(_)));
+}
+
+#[tokio::test]
+async fn test_non_admin_client_is_forbidden() {
+    // GIVEN
+    let test_srv = new_admin_srv().await;
+
+    // WHEN
+    let response = reqwest::Client::new()
+        .get(admin_url(&test_srv, "data"))
+        .basic_auth("registrar1", Some("secret"))
+        .send()
+        .await
+        .expect("querying server");
+
+    // THEN
+    assert_eq!(response.status(), 403);
+}
+
+#[tokio::test]
+async fn test_anonymous_client_is_unauthorized() {
+    // GIVEN
+    let test_srv = new_admin_srv().await;
+
+    // WHEN
+    let response = reqwest::Client::new()
+        .get(admin_url(&test_srv, "data"))
+        .send()
+        .await
+        .expect("querying server");
+
+    // THEN
+    assert_eq!(response.status(), 401);
+}
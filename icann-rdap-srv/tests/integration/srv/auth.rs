@@ -0,0 +1,107 @@
+use {
+    icann_rdap_common::prelude::*,
+    icann_rdap_srv::{auth::AuthConfig, storage::StoreOps},
+    serde_json::json,
+};
+
+use crate::test_jig::SrvTestJig;
+
+async fn new_auth_srv() -> SrvTestJig {
+    let auth = serde_json::from_value::<AuthConfig>(json!({
+        "users": [ { "name": "registrar1", "password": "secret", "tier": "registrar" } ],
+        "tokens": [ { "token": "abc123", "name": "registry-lab", "tier": "registry" } ]
+    }))
+    .expect("parsing auth config");
+    let test_srv = SrvTestJig::new_auth(auth).await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    let registrant = Entity::builder()
+        .handle("REG-1")
+        .role(EntityRole::Registrant.to_string())
+        .contact(Contact::builder().full_name("Alice").build())
+        .build();
+    tx.add_domain(
+        &Domain::builder()
+            .ldh_name("foo.example")
+            .entity(registrant)
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+    test_srv
+}
+
+fn domain_request(test_srv: &SrvTestJig) -> reqwest::RequestBuilder {
+    reqwest::Client::new()
+        .get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .header("accept", "application/rdap+json")
+}
+
+async fn domain_of(response: reqwest::Response) -> Domain {
+    assert_eq!(response.status(), 200);
+    let rdap: RdapResponse = response.json().await.expect("parsing response");
+    let RdapResponse::Domain(domain) = rdap else {
+        panic!("not a domain")
+    };
+    *domain
+}
+
+#[tokio::test]
+async fn test_anonymous_client_gets_truncated_contacts() {
+    // GIVEN
+    let test_srv = new_auth_srv().await;
+
+    // WHEN
+    let response = domain_request(&test_srv)
+        .send()
+        .await
+        .expect("querying server");
+
+    // THEN
+    let domain = domain_of(response).await;
+    assert!(domain.entities()[0].contact().is_none());
+    let notices = domain.common.notices.as_ref().expect("notices");
+    assert!(notices.iter().any(|n| n.nr_type()
+        == Some(
+            NrType::ObjectTruncatedDueToAuthorization
+                .to_string()
+                .as_str()
+        )));
+}
+
+#[tokio::test]
+async fn test_basic_and_bearer_clients_get_full_contacts() {
+    // GIVEN
+    let test_srv = new_auth_srv().await;
+
+    for request in [
+        domain_request(&test_srv).basic_auth("registrar1", Some("secret")),
+        domain_request(&test_srv).bearer_auth("abc123"),
+    ] {
+        // WHEN
+        let response = request.send().await.expect("querying server");
+
+        // THEN
+        let domain = domain_of(response).await;
+        let contact = domain.entities()[0].contact().expect("contact");
+        assert_eq!(contact.full_name(), Some("Alice"));
+        assert!(domain.common.notices.is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_credentials_are_unauthorized() {
+    // GIVEN
+    let test_srv = new_auth_srv().await;
+
+    // WHEN
+    let response = domain_request(&test_srv)
+        .basic_auth("registrar1", Some("guess"))
+        .send()
+        .await
+        .expect("querying server");
+
+    // THEN
+    assert_eq!(response.status(), 401);
+    assert!(response.headers().contains_key("www-authenticate"));
+}
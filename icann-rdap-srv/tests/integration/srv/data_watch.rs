@@ -0,0 +1,160 @@
+use std::time::Duration;
+
+use {
+    icann_rdap_common::prelude::*,
+    icann_rdap_srv::{
+        auth::AuthConfig,
+        config::{JsContactConversion, ListenConfig, SearchConfig, ServiceConfig, StorageType},
+        server::Listener,
+        storage::{mem::config::MemConfig, CommonConfig},
+    },
+    serde_json::json,
+    test_dir::{DirBuilder, TestDir},
+};
+
+fn write_domain(dir: &TestDir, ldh_name: &str) {
+    let domain = Domain::builder().ldh_name(ldh_name).build();
+    std::fs::write(
+        dir.path(&format!("{}.json", ldh_name.replace('.', "_"))),
+        serde_json::to_string(&domain).expect("serializing domain"),
+    )
+    .expect("writing file");
+}
+
+/// Starts a server loading and watching a data directory, returning its base URL.
+async fn start_server(dir: &TestDir) -> String {
+    let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+    let listener = Listener::listen(&ListenConfig::default())
+        .await
+        .expect("listening on interface");
+    let base = listener.rdap_base().trim_end_matches("/rdap").to_string();
+    let service_config = ServiceConfig::builder()
+        .storage_type(StorageType::Memory(
+            MemConfig::builder()
+                .common_config(CommonConfig::default())
+                .build(),
+        ))
+        .data_dir(dir.root().to_string_lossy())
+        .auto_reload(true)
+        .bootstrap(false)
+        .update_on_bootstrap(false)
+        .jscontact_conversion(JsContactConversion::None)
+        .search_config(SearchConfig::default())
+        .auth(
+            serde_json::from_value::<AuthConfig>(json!({
+                "users": [ { "name": "operator", "password": "secret", "tier": "admin" } ]
+            }))
+            .expect("parsing auth config"),
+        )
+        .build();
+    tokio::spawn(async move {
+        listener
+            .start_server(&service_config)
+            .await
+            .expect("starting server");
+    });
+    let client = reqwest::Client::new();
+    for _ in 0..50 {
+        let ready = client.get(format!("{base}/readyz")).send().await;
+        if ready.is_ok_and(|response| response.status() == 200) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    base
+}
+
+async fn domain_status(base: &str, ldh_name: &str) -> u16 {
+    reqwest::get(format!("{base}/rdap/domain/{ldh_name}"))
+        .await
+        .expect("querying server")
+        .status()
+        .as_u16()
+}
+
+#[tokio::test]
+async fn test_changed_file_is_loaded() {
+    // GIVEN
+    let dir = TestDir::temp();
+    write_domain(&dir, "foo.example");
+    let base = start_server(&dir).await;
+    assert_eq!(domain_status(&base, "foo.example").await, 200);
+    assert_eq!(domain_status(&base, "bar.example").await, 404);
+
+    // WHEN
+    write_domain(&dir, "bar.example");
+    tokio::time::sleep(Duration::from_millis(2000)).await;
+
+    // THEN
+    assert_eq!(domain_status(&base, "bar.example").await, 200);
+    assert_eq!(domain_status(&base, "foo.example").await, 200);
+}
+
+#[tokio::test]
+async fn test_removed_file_is_unloaded() {
+    // GIVEN
+    let dir = TestDir::temp();
+    write_domain(&dir, "foo.example");
+    write_domain(&dir, "bar.example");
+    let base = start_server(&dir).await;
+    assert_eq!(domain_status(&base, "bar.example").await, 200);
+
+    // WHEN
+    std::fs::remove_file(dir.path("bar_example.json")).expect("removing file");
+    tokio::time::sleep(Duration::from_millis(2000)).await;
+
+    // THEN
+    assert_eq!(domain_status(&base, "bar.example").await, 404);
+    assert_eq!(domain_status(&base, "foo.example").await, 200);
+}
+
+#[tokio::test]
+async fn test_admin_reports_file_status() {
+    // GIVEN
+    let dir = TestDir::temp();
+    write_domain(&dir, "foo.example");
+    let base = start_server(&dir).await;
+
+    // WHEN
+    std::fs::write(dir.path("bad.json"), "{ not json").expect("writing file");
+    tokio::time::sleep(Duration::from_millis(2000)).await;
+
+    // THEN
+    let response = reqwest::Client::new()
+        .get(format!("{base}/admin/data"))
+        .basic_auth("operator", Some("secret"))
+        .send()
+        .await
+        .expect("querying server");
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.expect("parsing body");
+    let files = body["files"].as_array().expect("files");
+    assert_eq!(files.len(), 2);
+    let bad = files
+        .iter()
+        .find(|file| {
+            file["file"]
+                .as_str()
+                .is_some_and(|f| f.ends_with("bad.json"))
+        })
+        .expect("status of bad file");
+    assert_eq!(bad["loaded"], false);
+    assert!(bad["error"].is_string());
+    assert_eq!(domain_status(&base, "foo.example").await, 200);
+}
+
+#[tokio::test]
+async fn test_admin_data_requires_credentials() {
+    // GIVEN
+    let dir = TestDir::temp();
+    write_domain(&dir, "foo.example");
+    let base = start_server(&dir).await;
+
+    // WHEN
+    let response = reqwest::get(format!("{base}/admin/data"))
+        .await
+        .expect("querying server");
+
+    // THEN
+    assert_eq!(response.status(), 401);
+}
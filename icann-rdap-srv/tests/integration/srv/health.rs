@@ -0,0 +1,95 @@
+use {
+    icann_rdap_common::prelude::*,
+    icann_rdap_srv::{
+        config::{ListenConfig, ServiceConfig, StorageType},
+        health::Readiness,
+        server::{AppState, Listener},
+        storage::{mem::config::MemConfig, CommonConfig},
+    },
+    test_dir::{DirBuilder, TestDir},
+};
+
+use crate::test_jig::SrvTestJig;
+
+fn server_url(test_srv: &SrvTestJig, path: &str) -> String {
+    format!("{}{path}", test_srv.rdap_base.trim_end_matches("/rdap"))
+}
+
+#[tokio::test]
+async fn test_healthz_is_ok() {
+    // GIVEN
+    let test_srv = SrvTestJig::new().await;
+
+    // WHEN
+    let response = reqwest::get(server_url(&test_srv, "/healthz"))
+        .await
+        .expect("querying server");
+
+    // THEN
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn test_readyz_is_unavailable_until_initialized() {
+    // GIVEN
+    let readiness = Readiness::default();
+    let test_srv = SrvTestJig::new_readiness(readiness.clone()).await;
+    let url = server_url(&test_srv, "/readyz");
+    let response = reqwest::get(&url).await.expect("querying server");
+    assert_eq!(response.status(), 503);
+
+    // WHEN
+    readiness.set_bootstrapped();
+    readiness.set_data_loaded();
+
+    // THEN
+    let response = reqwest::get(&url).await.expect("querying server");
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.expect("parsing body");
+    assert_eq!(body["storage"], "ok");
+}
+
+#[tokio::test]
+async fn test_state_from_constructor_is_ready_with_data() {
+    // GIVEN
+    let dir = TestDir::temp();
+    let domain = Domain::builder().ldh_name("foo.example").build();
+    std::fs::write(
+        dir.path("foo_example.json"),
+        serde_json::to_string(&domain).expect("serializing domain"),
+    )
+    .expect("writing file");
+    let mem_config = MemConfig::builder()
+        .common_config(CommonConfig::default())
+        .build();
+    let service_config = ServiceConfig::non_server()
+        .data_dir(dir.root().to_string_lossy())
+        .storage_type(StorageType::Memory(mem_config.clone()))
+        .build()
+        .expect("building service config");
+
+    // WHEN
+    let app_state = AppState::new_mem(mem_config, &service_config)
+        .await
+        .expect("creating state");
+    let listener = Listener::listen(&ListenConfig::default())
+        .await
+        .expect("listening on interface");
+    let base = listener.rdap_base().trim_end_matches("/rdap").to_string();
+    tokio::spawn(async move {
+        listener
+            .start_with_state(app_state)
+            .await
+            .expect("starting server");
+    });
+
+    // THEN
+    let ready = reqwest::get(format!("{base}/readyz"))
+        .await
+        .expect("querying server");
+    assert_eq!(ready.status(), 200);
+    let domain = reqwest::get(format!("{base}/rdap/domain/foo.example"))
+        .await
+        .expect("querying server");
+    assert_eq!(domain.status(), 200);
+}
@@ -0,0 +1,42 @@
+use {
+    icann_rdap_common::prelude::*,
+    icann_rdap_srv::{config::ListenConfig, server::Listener, storage::StoreOps},
+};
+
+use crate::test_jig::SrvTestJig;
+
+#[tokio::test]
+async fn test_metrics_count_requests_and_stored_objects() {
+    // GIVEN
+    let test_srv = SrvTestJig::new().await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+    let metrics_listener = Listener::listen(&ListenConfig::default())
+        .await
+        .expect("listening on interface");
+    let metrics_url = format!("http://{}/metrics", metrics_listener.local_addr);
+    tokio::spawn(metrics_listener.start_metrics(Box::new(test_srv.mem.clone())));
+    let client = reqwest::Client::new();
+    client
+        .get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .send()
+        .await
+        .expect("querying server");
+
+    // WHEN
+    let response = client
+        .get(metrics_url)
+        .send()
+        .await
+        .expect("querying metrics");
+
+    // THEN
+    assert_eq!(response.status(), 200);
+    let text = response.text().await.expect("metrics text");
+    assert!(text.contains(r#"rdap_srv_requests_total{route="domain",status="200"}"#));
+    assert!(text.contains(r#"rdap_srv_request_duration_seconds_count{route="domain"}"#));
+    assert!(text.contains(r#"rdap_srv_stored_objects{map="domains"} 1"#));
+}
@@ -1,9 +1,22 @@
+mod admin;
+mod auth;
 mod bootstrap;
+mod data_watch;
 mod domain;
 mod entity;
+mod health;
 mod jscontact;
+mod metrics;
 mod nameserver;
+mod network_hierarchy;
+mod paging;
+mod rate_limit;
+mod redaction;
 mod redirect;
 mod reverse_dns;
+mod reverse_search;
+mod rir_search;
 mod srvhelp;
+mod subsetting;
+mod tls;
 mod ttl;
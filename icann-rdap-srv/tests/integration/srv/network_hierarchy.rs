@@ -0,0 +1,197 @@
+use {
+    icann_rdap_client::{
+        http::{create_client, ClientConfig},
+        rdap::{rdap_request, QueryType},
+    },
+    icann_rdap_common::{
+        prelude::{CommonFields, ObjectCommonFields, RdapResponse},
+        response::Network,
+    },
+    icann_rdap_srv::storage::{CommonConfig, StoreOps},
+};
+
+use crate::test_jig::SrvTestJig;
+
+async fn new_srv(network_hierarchy_enable: bool) -> SrvTestJig {
+    let common_config = CommonConfig::builder()
+        .network_hierarchy_enable(network_hierarchy_enable)
+        .build();
+    let test_srv = SrvTestJig::new_common_config(common_config).await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    for (cidr, handle) in [
+        ("10.0.0.0/8", "NET-10"),
+        ("10.0.0.0/16", "NET-10-0"),
+        ("10.0.0.0/24", "NET-10-0-0"),
+        ("10.0.1.0/24", "NET-10-0-1"),
+    ] {
+        tx.add_network(
+            &Network::builder()
+                .cidr(cidr)
+                .handle(handle)
+                .build()
+                .expect("cidr parsing"),
+        )
+        .await
+        .expect("add network in tx");
+    }
+    tx.commit().await.expect("tx commit");
+    test_srv
+}
+
+async fn query(test_srv: &SrvTestJig, path: &str) -> (u16, RdapResponse) {
+    let client_config = ClientConfig::builder()
+        .https_only(false)
+        .follow_redirects(false)
+        .build();
+    let client = create_client(&client_config).expect("creating client");
+    let query = QueryType::Url(format!("{}/{path}", test_srv.rdap_base));
+    let response = rdap_request(&test_srv.rdap_base, &query, &client)
+        .await
+        .expect("querying server");
+    (response.http_data.status_code(), response.rdap)
+}
+
+#[tokio::test]
+async fn test_server_network_hierarchy_links() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+
+    // WHEN
+    let (status, rdap) = query(&test_srv, "ip/10.0.0.0/16").await;
+
+    // THEN
+    assert_eq!(status, 200);
+    let RdapResponse::Network(network) = rdap else {
+        panic!("not a network")
+    };
+    let links = network
+        .links()
+        .iter()
+        .filter_map(|l| Some((l.rel()?, l.href()?)))
+        .collect::<Vec<(&str, &str)>>();
+    assert_eq!(
+        links,
+        [
+            (
+                "rdap-up rdap-active",
+                "/rdap/ips/rirSearch1/rdap-up/10.0.0.0/16"
+            ),
+            (
+                "rdap-top rdap-active",
+                "/rdap/ips/rirSearch1/rdap-top/10.0.0.0/16"
+            ),
+            (
+                "rdap-down rdap-active",
+                "/rdap/ips/rirSearch1/rdap-down/10.0.0.0/16"
+            ),
+            (
+                "rdap-bottom rdap-active",
+                "/rdap/ips/rirSearch1/rdap-bottom/10.0.0.0/16"
+            ),
+        ]
+    );
+    assert!(network.extensions().iter().any(|e| e.0 == "rirSearch1"));
+}
+
+#[tokio::test]
+async fn test_server_network_hierarchy_links_of_top_and_bottom() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+
+    // WHEN
+    let (_, top) = query(&test_srv, "ip/10.0.0.0/8").await;
+    let (_, bottom) = query(&test_srv, "ip/10.0.1.1").await;
+
+    // THEN
+    let rels = |rdap: &RdapResponse| {
+        rdap.get_links()
+            .into_iter()
+            .flatten()
+            .filter_map(|l| l.rel.clone())
+            .collect::<Vec<String>>()
+    };
+    assert_eq!(
+        rels(&top),
+        ["rdap-down rdap-active", "rdap-bottom rdap-active"]
+    );
+    assert_eq!(
+        rels(&bottom),
+        ["rdap-up rdap-active", "rdap-top rdap-active"]
+    );
+}
+
+#[tokio::test]
+async fn test_server_network_up() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+
+    // WHEN
+    let (status, rdap) = query(&test_srv, "ips/rirSearch1/rdap-up/10.0.1.0/24").await;
+
+    // THEN
+    assert_eq!(status, 200);
+    let RdapResponse::Network(network) = rdap else {
+        panic!("not a network")
+    };
+    assert_eq!(network.handle(), Some("NET-10-0"));
+}
+
+#[tokio::test]
+async fn test_server_network_bottom() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+
+    // WHEN
+    let (status, rdap) = query(&test_srv, "ips/rirSearch1/rdap-bottom/10.0.0.0/8").await;
+
+    // THEN
+    assert_eq!(status, 200);
+    let RdapResponse::NetworkSearchResults(results) = rdap else {
+        panic!("not network search results")
+    };
+    let mut handles = results
+        .results()
+        .iter()
+        .filter_map(|n| n.handle())
+        .collect::<Vec<&str>>();
+    handles.sort();
+    assert_eq!(handles, ["NET-10-0-0", "NET-10-0-1"]);
+}
+
+#[tokio::test]
+async fn test_server_network_hierarchy_unknown_relation() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+
+    // WHEN
+    let (status, _) = query(&test_srv, "ips/rirSearch1/rdap-sideways/10.0.0.0/8").await;
+
+    // THEN
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn test_server_network_hierarchy_invalid_network() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+
+    // WHEN
+    let (status, _) = query(&test_srv, "ips/rirSearch1/rdap-up/10.0.0.300").await;
+
+    // THEN
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn test_server_network_hierarchy_disabled() {
+    // GIVEN
+    let test_srv = new_srv(false).await;
+
+    // WHEN
+    let (status, _) = query(&test_srv, "ips/rirSearch1/rdap-up/10.0.1.0/24").await;
+    let (_, network) = query(&test_srv, "ip/10.0.0.0/16").await;
+
+    // THEN
+    assert_eq!(status, 501);
+    assert!(network.get_links().is_none());
+}
@@ -0,0 +1,237 @@
+use {
+    icann_rdap_client::{
+        http::{create_client, ClientConfig},
+        rdap::{rdap_follow_pages, rdap_request, PagingLimits, QueryType},
+        RdapClientError,
+    },
+    icann_rdap_common::{
+        prelude::{CommonFields, RdapResponse},
+        response::{Domain, Entity},
+    },
+    icann_rdap_srv::{
+        config::SearchConfig,
+        storage::{CommonConfig, StoreOps},
+    },
+};
+
+use crate::test_jig::SrvTestJig;
+
+async fn new_domains_srv(search_config: SearchConfig) -> SrvTestJig {
+    let common_config = CommonConfig::builder()
+        .domain_search_by_name_enable(true)
+        .build();
+    let test_srv = SrvTestJig::new_search_config(common_config, search_config).await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    for ldh in ["foob.example", "fooc.example", "fooa.example"] {
+        tx.add_domain(&Domain::builder().ldh_name(ldh).build())
+            .await
+            .expect("add domain in tx");
+    }
+    tx.commit().await.expect("tx commit");
+    test_srv
+}
+
+async fn search(test_srv: &SrvTestJig, path_and_query: &str) -> (u16, RdapResponse) {
+    let client_config = ClientConfig::builder()
+        .https_only(false)
+        .follow_redirects(false)
+        .build();
+    let client = create_client(&client_config).expect("creating client");
+    let origin = test_srv.rdap_base.trim_end_matches("/rdap");
+    let query = QueryType::Url(format!("{origin}{path_and_query}"));
+    let response = rdap_request(&test_srv.rdap_base, &query, &client)
+        .await
+        .expect("querying server");
+    (response.http_data.status_code(), response.rdap)
+}
+
+fn domain_names(rdap: &RdapResponse) -> Vec<String> {
+    let RdapResponse::DomainSearchResults(results) = rdap else {
+        panic!("not domain search results")
+    };
+    results
+        .results()
+        .iter()
+        .filter_map(|d| d.ldh_name().map(str::to_string))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_server_search_sorted_and_paged() {
+    // GIVEN
+    let test_srv = new_domains_srv(SearchConfig::builder().page_size(2).build()).await;
+
+    // WHEN
+    let (status, rdap) = search(
+        &test_srv,
+        "/rdap/domains?name=foo*.example&sort=name:d&count=true",
+    )
+    .await;
+
+    // THEN
+    assert_eq!(status, 200);
+    assert_eq!(domain_names(&rdap), ["fooc.example", "foob.example"]);
+    let RdapResponse::DomainSearchResults(results) = rdap else {
+        panic!("not domain search results")
+    };
+    assert!(results.extensions().iter().any(|e| e.0 == "paging"));
+    assert!(results.extensions().iter().any(|e| e.0 == "sorting"));
+    let sorting_metadata = results.sorting_metadata.expect("sorting metadata");
+    assert_eq!(sorting_metadata.current_sort(), Some("name:d"));
+    let paging_metadata = results.paging_metadata.expect("paging metadata");
+    assert_eq!(paging_metadata.total_count(), Some(3));
+    assert_eq!(paging_metadata.page_size(), Some(2));
+    assert_eq!(paging_metadata.page_number(), Some(1));
+
+    // WHEN the next page is requested
+    let next = paging_metadata
+        .next_link()
+        .and_then(|link| link.href())
+        .expect("next link");
+    let (status, rdap) = search(&test_srv, next).await;
+
+    // THEN
+    assert_eq!(status, 200);
+    assert_eq!(domain_names(&rdap), ["fooa.example"]);
+    let RdapResponse::DomainSearchResults(results) = rdap else {
+        panic!("not domain search results")
+    };
+    let paging_metadata = results.paging_metadata.expect("paging metadata");
+    assert_eq!(paging_metadata.page_number(), Some(2));
+    assert!(paging_metadata.next_link().is_none());
+    assert!(paging_metadata.prev_link().is_some());
+}
+
+#[tokio::test]
+async fn test_server_search_paged_by_config() {
+    // GIVEN
+    let test_srv = new_domains_srv(SearchConfig::builder().page_size(1).build()).await;
+
+    // WHEN
+    let (status, rdap) = search(&test_srv, "/rdap/domains?name=foo*.example").await;
+
+    // THEN
+    assert_eq!(status, 200);
+    let RdapResponse::DomainSearchResults(results) = rdap else {
+        panic!("not domain search results")
+    };
+    assert_eq!(results.results().len(), 1);
+    let paging_metadata = results.paging_metadata.expect("paging metadata");
+    assert_eq!(paging_metadata.total_count(), None);
+    assert!(paging_metadata.next_link().is_some());
+}
+
+#[tokio::test]
+async fn test_server_search_truncated_by_max_results() {
+    // GIVEN
+    let test_srv = new_domains_srv(SearchConfig::builder().max_results(2).build()).await;
+
+    // WHEN
+    let (status, rdap) = search(&test_srv, "/rdap/domains?name=foo*.example").await;
+
+    // THEN
+    assert_eq!(status, 200);
+    let RdapResponse::DomainSearchResults(results) = rdap else {
+        panic!("not domain search results")
+    };
+    assert_eq!(results.results().len(), 2);
+    assert!(results.paging_metadata.is_none());
+    assert!(results
+        .notices()
+        .iter()
+        .any(|n| n.nr_type.as_deref() == Some("result set truncated due to excessive load")));
+}
+
+#[tokio::test]
+async fn test_server_search_with_unknown_sort_is_bad_request() {
+    // GIVEN
+    let test_srv = new_domains_srv(SearchConfig::default()).await;
+
+    // WHEN
+    let (status, _) = search(&test_srv, "/rdap/domains?name=foo*.example&sort=color").await;
+
+    // THEN
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn test_server_search_with_huge_cursor_is_bad_request() {
+    // GIVEN
+    let test_srv = new_domains_srv(SearchConfig::builder().page_size(2).build()).await;
+
+    // WHEN the cursor is usize::MAX
+    let (status, _) = search(
+        &test_srv,
+        "/rdap/domains?name=foo*.example&cursor=MTg0NDY3NDQwNzM3MDk1NTE2MTU",
+    )
+    .await;
+
+    // THEN
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn test_server_entity_search_sorted_by_handle() {
+    // GIVEN
+    let common_config = CommonConfig::builder()
+        .entity_search_by_handle_enable(true)
+        .build();
+    let test_srv = SrvTestJig::new_search_config(common_config, SearchConfig::default()).await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    for handle in ["foo-2.example", "foo-1.example"] {
+        tx.add_entity(&Entity::builder().handle(handle).build())
+            .await
+            .expect("add entity in tx");
+    }
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let (status, rdap) = search(&test_srv, "/rdap/entities?handle=foo*.example&sort=handle").await;
+
+    // THEN
+    assert_eq!(status, 200);
+    let RdapResponse::EntitySearchResults(results) = rdap else {
+        panic!("not entity search results")
+    };
+    let handles = results
+        .results()
+        .iter()
+        .filter_map(|e| e.object_common.handle.as_deref())
+        .collect::<Vec<&str>>();
+    assert_eq!(handles, ["foo-1.example", "foo-2.example"]);
+}
+
+#[tokio::test]
+async fn test_following_pages_after_results_change_is_error() {
+    // GIVEN a first page of one result
+    let test_srv = new_domains_srv(SearchConfig::builder().page_size(1).build()).await;
+    let client_config = ClientConfig::builder()
+        .https_only(false)
+        .follow_redirects(false)
+        .build();
+    let client = create_client(&client_config).expect("creating client");
+    let query = QueryType::DomainNameSearch("foo*.example".to_string());
+    let first = rdap_request(&test_srv.rdap_base, &query, &client)
+        .await
+        .expect("querying server");
+
+    // AND the results are gone before the next page is fetched
+    let tx = test_srv
+        .mem
+        .new_truncate_tx()
+        .await
+        .expect("new transaction");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = rdap_follow_pages(first, &client, PagingLimits::default()).await;
+
+    // THEN
+    assert!(matches!(
+        actual,
+        Err(RdapClientError::PageUnavailable {
+            page: 2,
+            status_code: 400
+        })
+    ));
+}
@@ -0,0 +1,42 @@
+use {
+    icann_rdap_common::prelude::*,
+    icann_rdap_srv::{config::RateLimitConfig, storage::StoreOps},
+};
+
+use crate::test_jig::SrvTestJig;
+
+#[tokio::test]
+async fn test_client_over_rate_gets_429_with_retry_after() {
+    // GIVEN a server allowing one request every 10 seconds
+    let test_srv =
+        SrvTestJig::new_rate_limit(RateLimitConfig::builder().rate(0.1).burst(1).build()).await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+    let client = reqwest::Client::new();
+    let url = format!("{}/domain/foo.example", test_srv.rdap_base);
+    let first = client.get(&url).send().await.expect("querying server");
+    assert_eq!(first.status(), 200);
+
+    // WHEN
+    let second = client.get(&url).send().await.expect("querying server");
+
+    // THEN
+    assert_eq!(second.status(), 429);
+    let retry_after: u64 = second
+        .headers()
+        .get("retry-after")
+        .expect("retry-after header")
+        .to_str()
+        .expect("header string")
+        .parse()
+        .expect("retry-after seconds");
+    assert!((1..=10).contains(&retry_after));
+    let rdap: RdapResponse = second.json().await.expect("parsing response");
+    let RdapResponse::ErrorResponse(error) = rdap else {
+        panic!("not an error")
+    };
+    assert_eq!(error.error_code, 429);
+}
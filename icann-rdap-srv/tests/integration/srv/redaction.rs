@@ -0,0 +1,156 @@
+use {
+    axum_client_ip::SecureClientIpSource,
+    icann_rdap_common::prelude::*,
+    icann_rdap_srv::{rdap::redaction::RedactionPolicy, storage::StoreOps},
+    serde_json::json,
+};
+
+use crate::test_jig::SrvTestJig;
+
+async fn new_redacting_srv() -> SrvTestJig {
+    let policy = serde_json::from_value::<RedactionPolicy>(json!({
+        "profiles": [
+            { "authorizations": ["Bearer privileged"] },
+            { "redactions": [
+                { "name": "Registrant Email", "method": "removal" },
+                { "name": "Registrant Name", "method": "emptyValue" }
+            ]}
+        ]
+    }))
+    .expect("parsing policy")
+    .validated()
+    .expect("valid policy");
+    let test_srv = SrvTestJig::new_redaction_policy(policy).await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    let registrant = Entity::builder()
+        .handle("REG-1")
+        .role(EntityRole::Registrant.to_string())
+        .contact(
+            Contact::builder()
+                .full_name("Alice")
+                .email(Email::builder().email("alice@example.com").build())
+                .build(),
+        )
+        .build();
+    tx.add_domain(
+        &Domain::builder()
+            .ldh_name("foo.example")
+            .entity(registrant)
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+    test_srv
+}
+
+async fn get_domain(test_srv: &SrvTestJig, authorization: Option<&str>) -> Domain {
+    let mut request = reqwest::Client::new()
+        .get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .header("accept", "application/rdap+json");
+    if let Some(authorization) = authorization {
+        request = request.header("authorization", authorization);
+    }
+    let response = request.send().await.expect("querying server");
+    assert_eq!(response.status(), 200);
+    let rdap: RdapResponse = response.json().await.expect("parsing response");
+    let RdapResponse::Domain(domain) = rdap else {
+        panic!("not a domain")
+    };
+    *domain
+}
+
+#[tokio::test]
+async fn test_public_client_gets_redacted_response() {
+    // GIVEN
+    let test_srv = new_redacting_srv().await;
+
+    // WHEN
+    let domain = get_domain(&test_srv, None).await;
+
+    // THEN
+    let contact = domain.entities()[0].contact().expect("contact");
+    assert!(contact.emails().is_empty());
+    assert_eq!(contact.full_name(), Some(""));
+    let redacted = domain.object_common.redacted.as_ref().expect("redacted");
+    assert_eq!(redacted.len(), 2);
+    assert!(domain
+        .common
+        .rdap_conformance
+        .as_ref()
+        .expect("conformance")
+        .contains(&ExtensionId::Redacted.to_extension()));
+}
+
+#[tokio::test]
+async fn test_privileged_client_gets_full_response() {
+    // GIVEN
+    let test_srv = new_redacting_srv().await;
+
+    // WHEN
+    let domain = get_domain(&test_srv, Some("Bearer privileged")).await;
+
+    // THEN
+    let contact = domain.entities()[0].contact().expect("contact");
+    assert_eq!(contact.emails().len(), 1);
+    assert_eq!(contact.full_name(), Some("Alice"));
+    assert!(domain.object_common.redacted.is_none());
+}
+
+#[tokio::test]
+async fn test_client_network_matched_against_client_ip_source() {
+    // GIVEN a server behind a proxy not redacting responses to a network
+    let policy = serde_json::from_value::<RedactionPolicy>(json!({
+        "profiles": [
+            { "clientNetworks": ["203.0.113.0/24"] },
+            { "redactions": [{ "name": "Registrant Email", "method": "removal" }] }
+        ]
+    }))
+    .expect("parsing policy")
+    .validated()
+    .expect("valid policy");
+    let test_srv = SrvTestJig::new_redaction_policy_behind_proxy(
+        policy,
+        SecureClientIpSource::RightmostXForwardedFor,
+    )
+    .await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::builder()
+            .ldh_name("foo.example")
+            .entity(
+                Entity::builder()
+                    .handle("REG-1")
+                    .role(EntityRole::Registrant.to_string())
+                    .contact(
+                        Contact::builder()
+                            .email(Email::builder().email("alice@example.com").build())
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN the proxy forwards a request of a client in the network
+    let response = reqwest::Client::new()
+        .get(format!("{}/domain/foo.example", test_srv.rdap_base))
+        .header("accept", "application/rdap+json")
+        .header("x-forwarded-for", "203.0.113.5")
+        .send()
+        .await
+        .expect("querying server");
+
+    // THEN the response is not redacted
+    assert_eq!(response.status(), 200);
+    let rdap: RdapResponse = response.json().await.expect("parsing response");
+    let RdapResponse::Domain(domain) = rdap else {
+        panic!("not a domain")
+    };
+    let contact = domain.entities()[0].contact().expect("contact");
+    assert_eq!(contact.emails().len(), 1);
+    assert!(domain.object_common.redacted.is_none());
+}
@@ -0,0 +1,302 @@
+use {
+    icann_rdap_client::{
+        http::{create_client, ClientConfig},
+        rdap::{rdap_request, QueryType, RelatedEntitySearch},
+    },
+    icann_rdap_common::{
+        contact::{Contact, Email},
+        prelude::{CommonFields, ObjectCommonFields, RdapResponse},
+        response::{Autnum, Domain, Entity, Network, Rfc9083Error},
+    },
+    icann_rdap_srv::storage::{
+        data::{AutnumId, NetworkId, NetworkIdType},
+        CommonConfig, StoreOps,
+    },
+};
+
+use crate::test_jig::SrvTestJig;
+
+fn entity(handle: &str, full_name: &str, email: &str, role: &str) -> Entity {
+    Entity::builder()
+        .handle(handle)
+        .contact(
+            Contact::builder()
+                .full_name(full_name)
+                .email(Email::builder().email(email).build())
+                .build(),
+        )
+        .role(role.to_string())
+        .build()
+}
+
+async fn new_srv(reverse_search_enable: bool) -> SrvTestJig {
+    let common_config = CommonConfig::builder()
+        .reverse_search_enable(reverse_search_enable)
+        .build();
+    let test_srv = SrvTestJig::new_common_config(common_config).await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::builder()
+            .ldh_name("foo.example")
+            .entity(entity(
+                "BOB-1",
+                "Bob Smurd",
+                "bob@example.com",
+                "registrant",
+            ))
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.add_domain(
+        &Domain::builder()
+            .ldh_name("bar.example")
+            .entity(entity("BOB-1", "Bob Smurd", "bob@example.com", "technical"))
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.add_network(
+        &Network::builder()
+            .cidr("10.0.0.0/24")
+            .entity(entity(
+                "ABUSE-1",
+                "Abuse Desk",
+                "abuse@example.net",
+                "abuse",
+            ))
+            .build()
+            .expect("cidr parsing"),
+    )
+    .await
+    .expect("add network in tx");
+    tx.add_network(
+        &Network::builder()
+            .cidr("10.0.1.0/24")
+            .entity(entity(
+                "ABUSE-1",
+                "Abuse Desk",
+                "abuse@example.net",
+                "technical",
+            ))
+            .build()
+            .expect("cidr parsing"),
+    )
+    .await
+    .expect("add network in tx");
+    tx.add_autnum(
+        &Autnum::builder()
+            .autnum_range(700..710)
+            .entity(entity(
+                "ABUSE-1",
+                "Abuse Desk",
+                "abuse@example.net",
+                "abuse",
+            ))
+            .build(),
+    )
+    .await
+    .expect("add autnum in tx");
+    tx.commit().await.expect("tx commit");
+    test_srv
+}
+
+async fn search(test_srv: &SrvTestJig, query: &QueryType) -> (u16, RdapResponse) {
+    let client_config = ClientConfig::builder()
+        .https_only(false)
+        .follow_redirects(false)
+        .build();
+    let client = create_client(&client_config).expect("creating client");
+    let response = rdap_request(&test_srv.rdap_base, query, &client)
+        .await
+        .expect("querying server");
+    (response.http_data.status_code(), response.rdap)
+}
+
+#[tokio::test]
+async fn test_server_reverse_search_disabled() {
+    // GIVEN
+    let test_srv = new_srv(false).await;
+
+    // WHEN
+    let query = QueryType::DomainReverseSearch(
+        RelatedEntitySearch::builder()
+            .full_name("Bob*")
+            .role("registrant")
+            .build(),
+    );
+    let (status, _) = search(&test_srv, &query).await;
+
+    // THEN
+    assert_eq!(status, 501);
+}
+
+#[tokio::test]
+async fn test_server_domains_by_registrant_name() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+
+    // WHEN
+    let query = QueryType::DomainReverseSearch(
+        RelatedEntitySearch::builder()
+            .full_name("Bob*")
+            .role("registrant")
+            .build(),
+    );
+    let (status, rdap) = search(&test_srv, &query).await;
+
+    // THEN
+    assert_eq!(status, 200);
+    let RdapResponse::DomainSearchResults(results) = rdap else {
+        panic!("not domain search results")
+    };
+    let names = results
+        .results()
+        .iter()
+        .filter_map(|d| d.ldh_name())
+        .collect::<Vec<&str>>();
+    assert_eq!(names, ["foo.example"]);
+    assert!(results.extensions().iter().any(|e| e.0 == "reverse_search"));
+}
+
+#[tokio::test]
+async fn test_server_domains_by_entity_handle_any_role() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+
+    // WHEN
+    let query =
+        QueryType::DomainReverseSearch(RelatedEntitySearch::builder().handle("BOB-1").build());
+    let (status, rdap) = search(&test_srv, &query).await;
+
+    // THEN
+    assert_eq!(status, 200);
+    let RdapResponse::DomainSearchResults(results) = rdap else {
+        panic!("not domain search results")
+    };
+    assert_eq!(results.results().len(), 2);
+}
+
+#[tokio::test]
+async fn test_server_networks_by_abuse_contact() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+
+    // WHEN
+    let query = QueryType::NetworkReverseSearch(
+        RelatedEntitySearch::builder()
+            .email("abuse@example.net")
+            .role("abuse")
+            .build(),
+    );
+    let (status, rdap) = search(&test_srv, &query).await;
+
+    // THEN
+    assert_eq!(status, 200);
+    let RdapResponse::NetworkSearchResults(results) = rdap else {
+        panic!("not network search results")
+    };
+    let starts = results
+        .results()
+        .iter()
+        .filter_map(|n| n.start_address.as_deref())
+        .collect::<Vec<&str>>();
+    assert_eq!(starts, ["10.0.0.0"]);
+    assert_eq!(results.results()[0].entities()[0].roles(), ["abuse"]);
+}
+
+#[tokio::test]
+async fn test_server_autnums_by_abuse_contact() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+
+    // WHEN
+    let query = QueryType::AutnumReverseSearch(
+        RelatedEntitySearch::builder()
+            .handle("abuse-*")
+            .role("abuse")
+            .build(),
+    );
+    let (status, rdap) = search(&test_srv, &query).await;
+
+    // THEN
+    assert_eq!(status, 200);
+    let RdapResponse::AutnumSearchResults(results) = rdap else {
+        panic!("not autnum search results")
+    };
+    assert_eq!(results.results().len(), 1);
+}
+
+#[tokio::test]
+async fn test_server_reverse_search_after_redirects() {
+    // GIVEN the network and autnum of the abuse contact replaced by redirects
+    let test_srv = new_srv(true).await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_network_err(
+        &NetworkId::builder()
+            .network_id(NetworkIdType::Cidr(
+                "10.0.0.0/24".parse().expect("parsing cidr"),
+            ))
+            .build(),
+        &Rfc9083Error::redirect()
+            .url("https://rdap.example.net/rdap/")
+            .build(),
+    )
+    .await
+    .expect("add network error in tx");
+    tx.add_autnum_err(
+        &AutnumId::builder()
+            .start_autnum(700)
+            .end_autnum(710)
+            .build(),
+        &Rfc9083Error::redirect()
+            .url("https://rdap.example.net/rdap/")
+            .build(),
+    )
+    .await
+    .expect("add autnum error in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let network_query =
+        QueryType::NetworkReverseSearch(RelatedEntitySearch::builder().handle("abuse-*").build());
+    let (network_status, network_rdap) = search(&test_srv, &network_query).await;
+    let autnum_query =
+        QueryType::AutnumReverseSearch(RelatedEntitySearch::builder().handle("abuse-*").build());
+    let (autnum_status, autnum_rdap) = search(&test_srv, &autnum_query).await;
+
+    // THEN only the network that is not redirected is found
+    assert_eq!(network_status, 200);
+    let RdapResponse::NetworkSearchResults(results) = network_rdap else {
+        panic!("not network search results")
+    };
+    let starts = results
+        .results()
+        .iter()
+        .filter_map(|n| n.start_address.as_deref())
+        .collect::<Vec<&str>>();
+    assert_eq!(starts, ["10.0.1.0"]);
+
+    // AND the redirected autnum is not found
+    assert_eq!(autnum_status, 200);
+    let RdapResponse::AutnumSearchResults(results) = autnum_rdap else {
+        panic!("not autnum search results")
+    };
+    assert!(results.results().is_empty());
+}
+
+#[tokio::test]
+async fn test_server_reverse_search_without_entity_condition_is_bad_request() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+    let origin = test_srv.rdap_base.trim_end_matches("/rdap");
+
+    // WHEN
+    let query = QueryType::Url(format!(
+        "{origin}/rdap/domains/reverse_search/entity?role=registrant"
+    ));
+    let (status, _) = search(&test_srv, &query).await;
+
+    // THEN
+    assert_eq!(status, 400);
+}
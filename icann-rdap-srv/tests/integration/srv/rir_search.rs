@@ -0,0 +1,164 @@
+use {
+    icann_rdap_client::{
+        http::{create_client, ClientConfig},
+        rdap::{rdap_request, QueryType},
+    },
+    icann_rdap_common::{
+        prelude::{CommonFields, ObjectCommonFields, RdapResponse},
+        response::{Autnum, Network},
+    },
+    icann_rdap_srv::storage::{CommonConfig, StoreOps},
+};
+
+use crate::test_jig::SrvTestJig;
+
+async fn new_srv(enable: bool) -> SrvTestJig {
+    let common_config = CommonConfig::builder()
+        .network_search_by_handle_enable(enable)
+        .network_search_by_name_enable(enable)
+        .autnum_search_by_handle_enable(enable)
+        .autnum_search_by_name_enable(enable)
+        .build();
+    let test_srv = SrvTestJig::new_common_config(common_config).await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    for (cidr, handle, name) in [
+        ("10.0.0.0/24", "NET-10-0-0-0-1", "EXAMPLE-NET"),
+        ("10.0.1.0/24", "NET-10-0-1-0-1", "OTHER-NET"),
+        ("2001:db8::/32", "NET6-2001-DB8-1", "EXAMPLE-NET6"),
+    ] {
+        tx.add_network(
+            &Network::builder()
+                .cidr(cidr)
+                .handle(handle)
+                .name(name)
+                .build()
+                .expect("cidr parsing"),
+        )
+        .await
+        .expect("add network in tx");
+    }
+    tx.add_autnum(
+        &Autnum::builder()
+            .autnum_range(700..710)
+            .handle("AS700")
+            .name("EXAMPLE-AS")
+            .build(),
+    )
+    .await
+    .expect("add autnum in tx");
+    tx.commit().await.expect("tx commit");
+    test_srv
+}
+
+async fn search(test_srv: &SrvTestJig, query: &QueryType) -> (u16, RdapResponse) {
+    let client_config = ClientConfig::builder()
+        .https_only(false)
+        .follow_redirects(false)
+        .build();
+    let client = create_client(&client_config).expect("creating client");
+    let response = rdap_request(&test_srv.rdap_base, query, &client)
+        .await
+        .expect("querying server");
+    (response.http_data.status_code(), response.rdap)
+}
+
+#[tokio::test]
+async fn test_server_rir_search_disabled() {
+    // GIVEN
+    let test_srv = new_srv(false).await;
+
+    // WHEN
+    let query = QueryType::NetworkHandleSearch("NET-*".to_string());
+    let (status, _) = search(&test_srv, &query).await;
+
+    // THEN
+    assert_eq!(status, 501);
+}
+
+#[tokio::test]
+async fn test_server_networks_by_handle() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+
+    // WHEN
+    let query = QueryType::NetworkHandleSearch("NET-10*".to_string());
+    let (status, rdap) = search(&test_srv, &query).await;
+
+    // THEN
+    assert_eq!(status, 200);
+    let RdapResponse::NetworkSearchResults(results) = rdap else {
+        panic!("not network search results")
+    };
+    let mut handles = results
+        .results()
+        .iter()
+        .filter_map(|n| n.handle())
+        .collect::<Vec<&str>>();
+    handles.sort();
+    assert_eq!(handles, ["NET-10-0-0-0-1", "NET-10-0-1-0-1"]);
+    let extensions = results
+        .extensions()
+        .iter()
+        .map(|e| e.0.as_str())
+        .collect::<Vec<&str>>();
+    assert!(extensions.contains(&"rirSearch1"));
+    assert!(extensions.contains(&"ips"));
+}
+
+#[tokio::test]
+async fn test_server_networks_by_name() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+
+    // WHEN
+    let query = QueryType::NetworkNameSearch("EXAMPLE*".to_string());
+    let (status, rdap) = search(&test_srv, &query).await;
+
+    // THEN
+    assert_eq!(status, 200);
+    let RdapResponse::NetworkSearchResults(results) = rdap else {
+        panic!("not network search results")
+    };
+    let mut names = results
+        .results()
+        .iter()
+        .filter_map(|n| n.name.as_deref())
+        .collect::<Vec<&str>>();
+    names.sort();
+    assert_eq!(names, ["EXAMPLE-NET", "EXAMPLE-NET6"]);
+}
+
+#[tokio::test]
+async fn test_server_autnums_by_name() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+
+    // WHEN
+    let query = QueryType::AutnumNameSearch("EXAMPLE*".to_string());
+    let (status, rdap) = search(&test_srv, &query).await;
+
+    // THEN
+    assert_eq!(status, 200);
+    let RdapResponse::AutnumSearchResults(results) = rdap else {
+        panic!("not autnum search results")
+    };
+    assert_eq!(results.results().len(), 1);
+    assert!(results.extensions().iter().any(|e| e.0 == "autnums"));
+}
+
+#[tokio::test]
+async fn test_server_autnums_by_handle_not_found() {
+    // GIVEN
+    let test_srv = new_srv(true).await;
+
+    // WHEN
+    let query = QueryType::AutnumHandleSearch("AS800*".to_string());
+    let (status, rdap) = search(&test_srv, &query).await;
+
+    // THEN
+    assert_eq!(status, 200);
+    let RdapResponse::AutnumSearchResults(results) = rdap else {
+        panic!("not autnum search results")
+    };
+    assert!(results.results().is_empty());
+}
@@ -0,0 +1,162 @@
+use {
+    icann_rdap_client::{
+        http::{create_client, ClientConfig},
+        rdap::{rdap_request, QueryType},
+    },
+    icann_rdap_common::{
+        prelude::{CommonFields, ObjectCommonFields, RdapResponse},
+        response::{Domain, Entity, FieldSet},
+    },
+    icann_rdap_srv::{
+        config::SearchConfig,
+        storage::{CommonConfig, StoreOps},
+    },
+};
+
+use crate::test_jig::SrvTestJig;
+
+async fn new_domain_srv() -> SrvTestJig {
+    let common_config = CommonConfig::builder()
+        .domain_search_by_name_enable(true)
+        .build();
+    let test_srv = SrvTestJig::new_search_config(common_config, SearchConfig::default()).await;
+    let mut tx = test_srv.mem.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::builder()
+            .ldh_name("foo.example")
+            .handle("FOO-1")
+            .status("active")
+            .entity(Entity::builder().handle("bob").build())
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+    test_srv
+}
+
+async fn search(test_srv: &SrvTestJig, query: &QueryType, field_set: FieldSet) -> RdapResponse {
+    let client_config = ClientConfig::builder()
+        .https_only(false)
+        .follow_redirects(false)
+        .build();
+    let client = create_client(&client_config).expect("creating client");
+    let url = query
+        .query_url_with_field_set(&test_srv.rdap_base, field_set)
+        .expect("query url");
+    rdap_request(&test_srv.rdap_base, &QueryType::Url(url), &client)
+        .await
+        .expect("querying server")
+        .rdap
+}
+
+#[tokio::test]
+async fn test_server_domain_search_with_id_field_set() {
+    // GIVEN
+    let test_srv = new_domain_srv().await;
+    let query = QueryType::DomainNameSearch("foo*.example".to_string());
+
+    // WHEN
+    let rdap = search(&test_srv, &query, FieldSet::Id).await;
+
+    // THEN
+    let RdapResponse::DomainSearchResults(results) = rdap else {
+        panic!("not domain search results")
+    };
+    assert!(results.extensions().iter().any(|e| e.0 == "subsetting"));
+    let domain = results.results().first().expect("domain in results");
+    assert_eq!(domain.ldh_name(), Some("foo.example"));
+    assert!(domain.handle().is_none());
+    assert!(domain.entities().is_empty());
+    let subsetting_metadata = results.subsetting_metadata.expect("subsetting metadata");
+    assert_eq!(subsetting_metadata.current_field_set(), Some("id"));
+    assert_eq!(subsetting_metadata.available_field_sets().len(), 3);
+}
+
+#[tokio::test]
+async fn test_server_domain_search_with_brief_field_set() {
+    // GIVEN
+    let test_srv = new_domain_srv().await;
+    let query = QueryType::DomainNameSearch("foo*.example".to_string());
+
+    // WHEN
+    let rdap = search(&test_srv, &query, FieldSet::Brief).await;
+
+    // THEN
+    let RdapResponse::DomainSearchResults(results) = rdap else {
+        panic!("not domain search results")
+    };
+    let domain = results.results().first().expect("domain in results");
+    assert_eq!(domain.handle(), Some("FOO-1"));
+    assert_eq!(domain.status(), ["active"]);
+    assert!(domain.entities().is_empty());
+}
+
+#[tokio::test]
+async fn test_server_domain_search_with_full_field_set() {
+    // GIVEN
+    let test_srv = new_domain_srv().await;
+    let query = QueryType::DomainNameSearch("foo*.example".to_string());
+
+    // WHEN
+    let rdap = search(&test_srv, &query, FieldSet::Full).await;
+
+    // THEN
+    let RdapResponse::DomainSearchResults(results) = rdap else {
+        panic!("not domain search results")
+    };
+    let domain = results.results().first().expect("domain in results");
+    assert_eq!(domain.entities().len(), 1);
+    let subsetting_metadata = results.subsetting_metadata.expect("subsetting metadata");
+    assert_eq!(subsetting_metadata.current_field_set(), Some("full"));
+}
+
+#[tokio::test]
+async fn test_server_domain_search_without_field_set_has_no_subsetting_metadata() {
+    // GIVEN
+    let test_srv = new_domain_srv().await;
+    let client_config = ClientConfig::builder()
+        .https_only(false)
+        .follow_redirects(false)
+        .build();
+    let client = create_client(&client_config).expect("creating client");
+    let query = QueryType::DomainNameSearch("foo*.example".to_string());
+
+    // WHEN
+    let rdap = rdap_request(&test_srv.rdap_base, &query, &client)
+        .await
+        .expect("querying server")
+        .rdap;
+
+    // THEN
+    let RdapResponse::DomainSearchResults(results) = rdap else {
+        panic!("not domain search results")
+    };
+    let domain = results.results().first().expect("domain in results");
+    assert_eq!(domain.entities().len(), 1);
+    assert!(results.subsetting_metadata.is_none());
+    assert!(!results.extensions().iter().any(|e| e.0 == "subsetting"));
+}
+
+#[tokio::test]
+async fn test_server_search_with_unknown_field_set_is_bad_request() {
+    // GIVEN
+    let test_srv = new_domain_srv().await;
+    let client_config = ClientConfig::builder()
+        .https_only(false)
+        .follow_redirects(false)
+        .build();
+    let client = create_client(&client_config).expect("creating client");
+    let url = format!(
+        "{}/domains?name=foo*.example&fieldSet=partial",
+        test_srv.rdap_base
+    );
+
+    // WHEN
+    let response = rdap_request(&test_srv.rdap_base, &QueryType::Url(url), &client)
+        .await
+        .expect("querying server");
+
+    // THEN
+    assert_eq!(response.http_data.status_code(), 400);
+}
@@ -0,0 +1,132 @@
+use std::{fs, net::TcpListener, time::Duration};
+
+use {
+    icann_rdap_srv::config::TlsConfig,
+    reqwest::{redirect::Policy, tls::TlsInfo, Client},
+    test_dir::{DirBuilder, TestDir},
+};
+
+use crate::test_jig::SrvTestJig;
+
+/// Writes a new self-signed certificate and key for localhost, returning the
+/// DER of the certificate.
+fn write_certificate(dir: &TestDir) -> Vec<u8> {
+    let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()])
+        .expect("generating certificate");
+    fs::write(dir.path("cert.pem"), certified.cert.pem()).expect("writing certificate");
+    fs::write(dir.path("key.pem"), certified.key_pair.serialize_pem()).expect("writing key");
+    certified.cert.der().to_vec()
+}
+
+fn tls_config(dir: &TestDir) -> TlsConfig {
+    TlsConfig::builder()
+        .cert_file(dir.path("cert.pem").to_string_lossy())
+        .key_file(dir.path("key.pem").to_string_lossy())
+        .build()
+}
+
+fn client() -> Client {
+    Client::builder()
+        .danger_accept_invalid_certs(true)
+        .tls_info(true)
+        .build()
+        .expect("building client")
+}
+
+async fn peer_certificate(test_srv: &SrvTestJig) -> Vec<u8> {
+    let response = client()
+        .get(format!("{}/help", test_srv.rdap_base))
+        .send()
+        .await
+        .expect("querying server");
+    response
+        .extensions()
+        .get::<TlsInfo>()
+        .and_then(|info| info.peer_certificate())
+        .expect("peer certificate")
+        .to_vec()
+}
+
+#[tokio::test]
+async fn test_https_with_hsts() {
+    // GIVEN
+    let dir = TestDir::temp();
+    write_certificate(&dir);
+    let mut tls = tls_config(&dir);
+    tls.hsts_max_age = Some(31536000);
+    let test_srv = SrvTestJig::new_tls(tls).await;
+
+    // WHEN
+    let response = client()
+        .get(test_srv.rdap_base.replace("/rdap", "/healthz"))
+        .send()
+        .await
+        .expect("querying server");
+
+    // THEN
+    assert!(test_srv.rdap_base.starts_with("https://"));
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response
+            .headers()
+            .get("strict-transport-security")
+            .expect("hsts header"),
+        "max-age=31536000"
+    );
+}
+
+#[tokio::test]
+async fn test_http_redirected_to_https() {
+    // GIVEN
+    let dir = TestDir::temp();
+    write_certificate(&dir);
+    let redirect_port = TcpListener::bind("[::]:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("finding free port")
+        .port();
+    let mut tls = tls_config(&dir);
+    tls.redirect_port = Some(redirect_port);
+    tls.redirect_host = Some("rdap.example.net".to_string());
+    let test_srv = SrvTestJig::new_tls(tls).await;
+    let https_port = test_srv
+        .rdap_base
+        .rsplit_once(':')
+        .and_then(|(_, port)| port.strip_suffix("/rdap"))
+        .expect("https port")
+        .to_string();
+
+    // WHEN
+    let response = Client::builder()
+        .redirect(Policy::none())
+        .build()
+        .expect("building client")
+        .get(format!("http://localhost:{redirect_port}/rdap/help?x=1"))
+        .send()
+        .await
+        .expect("querying server");
+
+    // THEN
+    assert_eq!(response.status(), 308);
+    assert_eq!(
+        response.headers().get("location").expect("location header"),
+        format!("https://rdap.example.net:{https_port}/rdap/help?x=1").as_str()
+    );
+}
+
+#[tokio::test]
+async fn test_certificate_reloaded_when_changed() {
+    // GIVEN
+    let dir = TestDir::temp();
+    let first = write_certificate(&dir);
+    let test_srv = SrvTestJig::new_tls(tls_config(&dir)).await;
+    assert_eq!(peer_certificate(&test_srv).await, first);
+
+    // WHEN
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    let second = write_certificate(&dir);
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+
+    // THEN
+    assert_ne!(first, second);
+    assert_eq!(peer_certificate(&test_srv).await, second);
+}
@@ -11,8 +11,8 @@ use {
         config::{ServiceConfig, StorageType},
         storage::{
             data::{
-                load_data, AutnumId, AutnumOrError::AutnumObject, DomainId, DomainOrError,
-                EntityId, EntityOrError::EntityObject, NameserverId,
+                load_data, load_data_skipping_failed, AutnumId, AutnumOrError::AutnumObject,
+                DomainId, DomainOrError, EntityId, EntityOrError::EntityObject, NameserverId,
                 NameserverOrError::NameserverObject, NetworkId, NetworkIdType,
                 NetworkOrError::NetworkObject, Template,
             },
@@ -583,3 +583,149 @@ async fn GIVEN_data_dir_with_host_help_WHEN_mem_init_THEN_host_help_is_loaded()
         "bar"
     );
 }
+
+#[tokio::test]
+async fn GIVEN_data_dir_with_bad_file_WHEN_load_data_THEN_error_and_nothing_loaded() {
+    // GIVEN
+    let ldh_name = "foo.example";
+    let temp = TestDir::temp();
+    let domain = Domain::builder().ldh_name(ldh_name).build();
+    std::fs::write(
+        temp.path("foo_example.json"),
+        serde_json::to_string(&domain).expect("serializing domain"),
+    )
+    .expect("writing file");
+    std::fs::write(temp.path("bad.json"), "{ not json").expect("writing file");
+    let mem_config = MemConfig::builder()
+        .common_config(CommonConfig::default())
+        .build();
+    let mem = Mem::new(mem_config.clone());
+    mem.init().await.expect("initializing memory");
+
+    // WHEN
+    let result = load_data(
+        &ServiceConfig::non_server()
+            .data_dir(temp.root().to_string_lossy().to_string())
+            .storage_type(StorageType::Memory(mem_config))
+            .build()
+            .expect("building service config"),
+        &mem,
+        false,
+    )
+    .await;
+
+    // THEN
+    assert!(result.is_err());
+    let actual = mem
+        .get_domain_by_ldh(ldh_name)
+        .await
+        .expect("getting domain by ldh");
+    assert!(!matches!(actual, RdapResponse::Domain(_)));
+}
+
+#[tokio::test]
+async fn GIVEN_data_dir_with_bad_file_WHEN_load_data_skipping_failed_THEN_bad_file_skipped() {
+    // GIVEN
+    let ldh_name = "foo.example";
+    let temp = TestDir::temp();
+    let domain = Domain::builder().ldh_name(ldh_name).build();
+    std::fs::write(
+        temp.path("foo_example.json"),
+        serde_json::to_string(&domain).expect("serializing domain"),
+    )
+    .expect("writing file");
+    std::fs::write(temp.path("bad.json"), "{ not json").expect("writing file");
+    let mem_config = MemConfig::builder()
+        .common_config(CommonConfig::default())
+        .build();
+    let mem = Mem::new(mem_config.clone());
+    mem.init().await.expect("initializing memory");
+
+    // WHEN
+    let statuses = load_data_skipping_failed(
+        &ServiceConfig::non_server()
+            .data_dir(temp.root().to_string_lossy().to_string())
+            .storage_type(StorageType::Memory(mem_config))
+            .build()
+            .expect("building service config"),
+        &mem,
+        false,
+    )
+    .await
+    .expect("loading data");
+
+    // THEN
+    let actual = mem
+        .get_domain_by_ldh(ldh_name)
+        .await
+        .expect("getting domain by ldh");
+    assert!(matches!(actual, RdapResponse::Domain(_)));
+    assert_eq!(statuses.len(), 2);
+    let bad = statuses
+        .iter()
+        .find(|status| status.file.ends_with("bad.json"))
+        .expect("status of bad file");
+    assert!(!bad.loaded);
+    assert!(bad.error.is_some());
+}
+
+#[tokio::test]
+async fn GIVEN_template_failing_part_way_WHEN_load_data_skipping_failed_THEN_none_of_its_objects_loaded(
+) {
+    // GIVEN a network template whose second id is not a range
+    let temp = TestDir::temp();
+    let template = Template::Network {
+        network: NetworkObject(Box::new(
+            Network::builder()
+                .cidr("1.1.1.1/32")
+                .build()
+                .expect("parsing cidr"),
+        )),
+        ids: vec![
+            NetworkId::builder()
+                .network_id(NetworkIdType::Range {
+                    start_address: "10.0.0.0".to_string(),
+                    end_address: "10.0.0.255".to_string(),
+                })
+                .build(),
+            NetworkId::builder()
+                .network_id(NetworkIdType::Range {
+                    start_address: "10.0.1.0".to_string(),
+                    end_address: "not an address".to_string(),
+                })
+                .build(),
+        ],
+    };
+    std::fs::write(
+        temp.path("example.template"),
+        serde_json::to_string(&template).expect("serializing template"),
+    )
+    .expect("writing file");
+    let mem_config = MemConfig::builder()
+        .common_config(CommonConfig::default())
+        .build();
+    let mem = Mem::new(mem_config.clone());
+    mem.init().await.expect("initializing memory");
+
+    // WHEN
+    let statuses = load_data_skipping_failed(
+        &ServiceConfig::non_server()
+            .data_dir(temp.root().to_string_lossy().to_string())
+            .storage_type(StorageType::Memory(mem_config))
+            .build()
+            .expect("building service config"),
+        &mem,
+        false,
+    )
+    .await
+    .expect("loading data");
+
+    // THEN
+    assert_eq!(statuses.len(), 1);
+    assert!(!statuses[0].loaded);
+    let actual = mem
+        .get_network_by_ipaddr("10.0.0.1")
+        .await
+        .expect("getting network by ip");
+    assert!(!matches!(actual, RdapResponse::Network(_)));
+}
@@ -5,10 +5,11 @@ use {
         prelude::Numberish,
         response::{
             Autnum, Common, Domain, Entity, Help, Nameserver, Network, Notice, NoticeOrRemark,
-            ObjectCommon, RdapResponse,
+            ObjectCommon, RdapResponse, Rfc9083Error,
         },
     },
     icann_rdap_srv::storage::{
+        data::{AutnumId, DomainId, NetworkId, NetworkIdType},
         mem::{config::MemConfig, ops::Mem},
         CommonConfig, StoreOps,
     },
@@ -142,6 +143,53 @@ async fn GIVEN_domain_in_mem_WHEN_search_domain_by_name_THEN_domain_returned() {
     )
 }
 
+#[tokio::test]
+async fn GIVEN_domain_in_mem_WHEN_delete_domain_THEN_domain_not_found_or_searched() {
+    // GIVEN
+    let mem_config = MemConfig::builder()
+        .common_config(
+            CommonConfig::builder()
+                .domain_search_by_name_enable(true)
+                .build(),
+        )
+        .build();
+    let mem = Mem::new(mem_config);
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    for ldh_name in ["foo.example.com", "bar.example.com"] {
+        tx.add_domain(&Domain::builder().ldh_name(ldh_name).build())
+            .await
+            .expect("add domain in tx");
+    }
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.delete_domain(&DomainId::builder().ldh_name("foo.example.com").build())
+        .await
+        .expect("delete domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // THEN
+    let actual = mem
+        .get_domain_by_ldh("foo.example.com")
+        .await
+        .expect("getting domain by ldh");
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, 404);
+    for (search, expected) in [("foo*.example.com", 0), ("bar*.example.com", 1)] {
+        let actual = mem
+            .search_domains_by_name(search)
+            .await
+            .expect("searching domains");
+        let RdapResponse::DomainSearchResults(domains) = actual else {
+            panic!()
+        };
+        assert_eq!(domains.results.len(), expected, "{search}");
+    }
+}
+
 #[tokio::test]
 async fn GIVEN_domain_in_mem_but_search_not_enabled_WHEN_search_domain_by_name_THEN_not_implemented(
 ) {
@@ -657,6 +705,282 @@ async fn GIVEN_no_network_in_mem_WHEN_lookup_network_by_cidr_THEN_404_returned()
     assert_eq!(error.error_code, 404)
 }
 
+#[tokio::test]
+async fn GIVEN_networks_and_autnums_sharing_names_in_mem_WHEN_search_by_name_THEN_all_returned() {
+    // GIVEN two networks and two autnums sharing names, added in separate transactions
+    let mem_config = MemConfig::builder()
+        .common_config(
+            CommonConfig::builder()
+                .network_search_by_handle_enable(true)
+                .network_search_by_name_enable(true)
+                .autnum_search_by_handle_enable(true)
+                .autnum_search_by_name_enable(true)
+                .build(),
+        )
+        .build();
+    let mem = Mem::new(mem_config);
+    for (i, cidr) in ["10.0.0.0/24", "10.0.1.0/24"].into_iter().enumerate() {
+        let mut tx = mem.new_tx().await.expect("new transaction");
+        tx.add_network(
+            &Network::builder()
+                .cidr(cidr)
+                .handle(format!("NET-{i}"))
+                .name("EXAMPLE-NET")
+                .build()
+                .expect("cidr parsing"),
+        )
+        .await
+        .expect("add network in tx");
+        let start = 700 + i as u32 * 100;
+        tx.add_autnum(
+            &Autnum::builder()
+                .autnum_range(start..start + 10)
+                .handle(format!("AS{start}"))
+                .name("EXAMPLE-AS")
+                .build(),
+        )
+        .await
+        .expect("add autnum in tx");
+        tx.commit().await.expect("tx commit");
+    }
+
+    // WHEN
+    let networks_by_handle = mem
+        .search_networks_by_handle("NET*")
+        .await
+        .expect("searching networks by handle");
+    let networks_by_name = mem
+        .search_networks_by_name("EXAMPLE*")
+        .await
+        .expect("searching networks by name");
+    let autnums_by_handle = mem
+        .search_autnums_by_handle("AS*")
+        .await
+        .expect("searching autnums by handle");
+    let autnums_by_name = mem
+        .search_autnums_by_name("EXAMPLE*")
+        .await
+        .expect("searching autnums by name");
+
+    // THEN
+    for networks in [networks_by_handle, networks_by_name] {
+        let RdapResponse::NetworkSearchResults(networks) = networks else {
+            panic!()
+        };
+        assert_eq!(networks.results.len(), 2);
+    }
+    for autnums in [autnums_by_handle, autnums_by_name] {
+        let RdapResponse::AutnumSearchResults(autnums) = autnums else {
+            panic!()
+        };
+        assert_eq!(autnums.results.len(), 2);
+    }
+}
+
+#[tokio::test]
+async fn GIVEN_network_and_autnum_redirected_in_mem_WHEN_search_by_name_THEN_not_returned() {
+    // GIVEN a network and an autnum replaced by redirects
+    let mem_config = MemConfig::builder()
+        .common_config(
+            CommonConfig::builder()
+                .network_search_by_name_enable(true)
+                .autnum_search_by_name_enable(true)
+                .build(),
+        )
+        .build();
+    let mem = Mem::new(mem_config);
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_network(
+        &Network::builder()
+            .cidr("10.0.0.0/24")
+            .name("EXAMPLE-NET")
+            .build()
+            .expect("cidr parsing"),
+    )
+    .await
+    .expect("add network in tx");
+    tx.add_autnum(
+        &Autnum::builder()
+            .autnum_range(700..710)
+            .name("EXAMPLE-AS")
+            .build(),
+    )
+    .await
+    .expect("add autnum in tx");
+    tx.commit().await.expect("tx commit");
+    let redirect = Rfc9083Error::redirect()
+        .url("https://rdap.example.net/rdap/")
+        .build();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_network_err(
+        &NetworkId::builder()
+            .network_id(NetworkIdType::Cidr(
+                "10.0.0.0/24".parse().expect("parsing cidr"),
+            ))
+            .build(),
+        &redirect,
+    )
+    .await
+    .expect("add network error in tx");
+    tx.add_autnum_err(
+        &AutnumId::builder()
+            .start_autnum(700)
+            .end_autnum(710)
+            .build(),
+        &redirect,
+    )
+    .await
+    .expect("add autnum error in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let networks = mem
+        .search_networks_by_name("EXAMPLE*")
+        .await
+        .expect("searching networks by name");
+    let autnums = mem
+        .search_autnums_by_name("EXAMPLE*")
+        .await
+        .expect("searching autnums by name");
+
+    // THEN
+    let RdapResponse::NetworkSearchResults(networks) = networks else {
+        panic!()
+    };
+    assert!(networks.results.is_empty());
+    let RdapResponse::AutnumSearchResults(autnums) = autnums else {
+        panic!()
+    };
+    assert!(autnums.results.is_empty());
+}
+
+#[tokio::test]
+async fn GIVEN_contained_networks_in_mem_WHEN_delete_network_THEN_containing_network_returned() {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    for cidr in ["192.168.0.0/16", "192.168.0.0/24"] {
+        tx.add_network(&Network::builder().cidr(cidr).build().expect("cidr parsing"))
+            .await
+            .expect("add network in tx");
+    }
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.delete_network(
+        &NetworkId::builder()
+            .network_id(NetworkIdType::Cidr(
+                "192.168.0.0/24".parse().expect("parsing cidr"),
+            ))
+            .build(),
+    )
+    .await
+    .expect("delete network in tx");
+    tx.commit().await.expect("tx commit");
+
+    // THEN
+    let actual = mem
+        .get_network_by_ipaddr("192.168.0.1")
+        .await
+        .expect("getting network by address");
+    let RdapResponse::Network(network) = actual else {
+        panic!()
+    };
+    assert_eq!(network.end_address.as_deref(), Some("192.168.255.255"));
+}
+
+#[rstest]
+#[case(700, 799, 404)]
+#[case(700, 710, 200)]
+#[case(705, 705, 200)]
+#[tokio::test]
+async fn GIVEN_autnum_in_mem_WHEN_delete_autnum_THEN_only_exact_range_deleted(
+    #[case] start_autnum: u32,
+    #[case] end_autnum: u32,
+    #[case] expected: u16,
+) {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_autnum(&Autnum::builder().autnum_range(700..799).build())
+        .await
+        .expect("add autnum in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.delete_autnum(
+        &AutnumId::builder()
+            .start_autnum(start_autnum)
+            .end_autnum(end_autnum)
+            .build(),
+    )
+    .await
+    .expect("delete autnum in tx");
+    tx.commit().await.expect("tx commit");
+
+    // THEN
+    let actual = mem
+        .get_autnum_by_num(705)
+        .await
+        .expect("getting autnum by num");
+    let status = match actual {
+        RdapResponse::ErrorResponse(error) => error.error_code,
+        _ => 200,
+    };
+    assert_eq!(status, expected);
+}
+
+#[rstest]
+#[case(700, 799, 404)]
+#[case(700, 710, 307)]
+#[case(705, 705, 307)]
+#[tokio::test]
+async fn GIVEN_autnum_error_in_mem_WHEN_delete_autnum_THEN_only_exact_range_deleted(
+    #[case] start_autnum: u32,
+    #[case] end_autnum: u32,
+    #[case] expected: u16,
+) {
+    // GIVEN
+    let mem = Mem::default();
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.add_autnum_err(
+        &AutnumId::builder()
+            .start_autnum(700)
+            .end_autnum(799)
+            .build(),
+        &Rfc9083Error::redirect()
+            .url("https://rdap.example.net/rdap/")
+            .build(),
+    )
+    .await
+    .expect("add autnum error in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let mut tx = mem.new_tx().await.expect("new transaction");
+    tx.delete_autnum(
+        &AutnumId::builder()
+            .start_autnum(start_autnum)
+            .end_autnum(end_autnum)
+            .build(),
+    )
+    .await
+    .expect("delete autnum in tx");
+    tx.commit().await.expect("tx commit");
+
+    // THEN
+    let actual = mem
+        .get_autnum_by_num(705)
+        .await
+        .expect("getting autnum by num");
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, expected);
+}
+
 #[tokio::test]
 async fn GIVEN_default_help_in_mem_WHEN_lookup_help_with_no_host_THEN_get_default_help() {
     // GIVEN
@@ -0,0 +1,970 @@
+#![allow(non_snake_case)]
+
+use std::process::Command;
+
+use {
+    icann_rdap_common::{
+        prelude::{Numberish, ToResponse},
+        response::{
+            Autnum, Domain, Entity, Help, Nameserver, Network, Notice, NoticeOrRemark,
+            RdapResponse, Rfc9083Error,
+        },
+    },
+    icann_rdap_srv::storage::{
+        data::{AutnumId, DomainId, NetworkId, NetworkIdType},
+        pg::{config::PgConfig, ops::Pg},
+        CommonConfig, StoreOps,
+    },
+    rstest::rstest,
+    test_dir::{DirBuilder, TestDir},
+};
+
+/// Runs a PostgreSQL cluster in a temporary directory for the duration of a test.
+///
+/// Starting the cluster requires `initdb` and `pg_ctl` to be on the path and a
+/// non-root user, so the tests using it are ignored unless run with
+/// `cargo test -- --ignored` (see `just test_pg`).
+struct PgTestJig {
+    pg: Pg,
+    cluster_dir: TestDir,
+}
+
+impl PgTestJig {
+    async fn new() -> Self {
+        Self::new_common_config(CommonConfig::default()).await
+    }
+
+    async fn new_common_config(common_config: CommonConfig) -> Self {
+        let cluster_dir = TestDir::temp();
+        let data_dir = cluster_dir.path("data");
+        let initdb = Command::new("initdb")
+            .arg("-D")
+            .arg(&data_dir)
+            .args(["-U", "rdap", "--auth=trust", "-E", "UTF8"])
+            .output();
+        assert!(
+            initdb.is_ok_and(|output| output.status.success()),
+            "unable to initialize a PostgreSQL cluster"
+        );
+        let start = Command::new("pg_ctl")
+            .arg("-D")
+            .arg(&data_dir)
+            .arg("-l")
+            .arg(cluster_dir.path("log"))
+            .arg("-o")
+            .arg(format!(
+                "-k {} -c listen_addresses=''",
+                cluster_dir.root().display()
+            ))
+            .args(["-w", "start"])
+            .output()
+            .expect("running pg_ctl");
+        assert!(start.status.success(), "unable to start PostgreSQL");
+        let db_url = format!(
+            "postgresql://rdap@localhost/postgres?host={}",
+            cluster_dir.root().display()
+        );
+        let pg = Pg::new(
+            PgConfig::builder()
+                .db_url(db_url)
+                .common_config(common_config)
+                .build(),
+        )
+        .await
+        .expect("connecting to PostgreSQL");
+        pg.init().await.expect("initializing storage");
+        Self { pg, cluster_dir }
+    }
+}
+
+impl Drop for PgTestJig {
+    fn drop(&mut self) {
+        let _ = Command::new("pg_ctl")
+            .arg("-D")
+            .arg(self.cluster_dir.path("data"))
+            .args(["-m", "immediate", "stop"])
+            .output();
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_domain_in_pg_WHEN_new_truncate_tx_THEN_no_domain_in_pg() {
+    // GIVEN
+    let jig = PgTestJig::new().await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let tx = jig.pg.new_truncate_tx().await.expect("new truncate tx");
+    tx.commit().await.expect("tx commit");
+
+    // THEN
+    let actual = jig
+        .pg
+        .get_domain_by_ldh("foo.example")
+        .await
+        .expect("getting domain by ldh");
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, 404)
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_domain_in_pg_WHEN_rollback_THEN_no_domain_in_pg() {
+    // GIVEN
+    let jig = PgTestJig::new().await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+
+    // WHEN
+    tx.rollback().await.expect("tx rollback");
+
+    // THEN
+    let actual = jig
+        .pg
+        .get_domain_by_ldh("foo.example")
+        .await
+        .expect("getting domain by ldh");
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, 404)
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_domain_in_pg_WHEN_delete_domain_THEN_no_domain_in_pg() {
+    // GIVEN
+    let jig = PgTestJig::new().await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::builder()
+            .ldh_name("foo.example")
+            .nameserver(
+                Nameserver::builder()
+                    .ldh_name("ns.foo.example")
+                    .address("192.0.2.1")
+                    .build()
+                    .expect("building nameserver"),
+            )
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.delete_domain(&DomainId::builder().ldh_name("foo.example").build())
+        .await
+        .expect("delete domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // THEN
+    let actual = jig
+        .pg
+        .get_domain_by_ldh("foo.example")
+        .await
+        .expect("getting domain by ldh");
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, 404)
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_contained_networks_in_pg_WHEN_delete_network_THEN_containing_network_returned() {
+    // GIVEN
+    let jig = PgTestJig::new().await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    for cidr in ["192.168.0.0/16", "192.168.0.0/24"] {
+        tx.add_network(&Network::builder().cidr(cidr).build().expect("cidr parsing"))
+            .await
+            .expect("add network in tx");
+    }
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.delete_network(
+        &NetworkId::builder()
+            .network_id(NetworkIdType::Cidr(
+                "192.168.0.0/24".parse().expect("parsing cidr"),
+            ))
+            .build(),
+    )
+    .await
+    .expect("delete network in tx");
+    tx.commit().await.expect("tx commit");
+
+    // THEN
+    let actual = jig
+        .pg
+        .get_network_by_ipaddr("192.168.0.1")
+        .await
+        .expect("getting network by address");
+    let RdapResponse::Network(network) = actual else {
+        panic!()
+    };
+    assert_eq!(network.end_address.as_deref(), Some("192.168.255.255"));
+}
+
+#[rstest]
+#[case(700, 799, 404)]
+#[case(700, 710, 307)]
+#[case(705, 705, 307)]
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_autnum_error_in_pg_WHEN_delete_autnum_THEN_only_exact_range_deleted(
+    #[case] start_autnum: u32,
+    #[case] end_autnum: u32,
+    #[case] expected: u16,
+) {
+    // GIVEN
+    let jig = PgTestJig::new().await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.add_autnum_err(
+        &AutnumId::builder()
+            .start_autnum(700)
+            .end_autnum(799)
+            .build(),
+        &Rfc9083Error::redirect()
+            .url("https://rdap.example.net/rdap/")
+            .build(),
+    )
+    .await
+    .expect("add autnum error in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.delete_autnum(
+        &AutnumId::builder()
+            .start_autnum(start_autnum)
+            .end_autnum(end_autnum)
+            .build(),
+    )
+    .await
+    .expect("delete autnum in tx");
+    tx.commit().await.expect("tx commit");
+
+    // THEN
+    let actual = jig
+        .pg
+        .get_autnum_by_num(705)
+        .await
+        .expect("getting autnum by num");
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, expected);
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_idn_in_pg_WHEN_lookup_domain_by_ldh_and_unicode_THEN_domain_returned() {
+    // GIVEN
+    let jig = PgTestJig::new().await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::idn()
+            .unicode_name("café.example")
+            .ldh_name("xn--caf-dma.example")
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let by_ldh = jig
+        .pg
+        .get_domain_by_ldh("xn--caf-dma.example")
+        .await
+        .expect("getting domain by ldh");
+    let by_unicode = jig
+        .pg
+        .get_domain_by_unicode("café.example")
+        .await
+        .expect("getting domain by unicode");
+
+    // THEN
+    let RdapResponse::Domain(by_ldh) = by_ldh else {
+        panic!()
+    };
+    let RdapResponse::Domain(by_unicode) = by_unicode else {
+        panic!()
+    };
+    assert_eq!(by_ldh, by_unicode);
+    assert_eq!(
+        by_ldh.unicode_name.as_ref().expect("unicodeName is none"),
+        "café.example"
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_domain_error_in_pg_WHEN_lookup_domain_by_ldh_THEN_redirect_returned() {
+    // GIVEN
+    let jig = PgTestJig::new().await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.add_domain_err(
+        &DomainId::builder().ldh_name("foo.example").build(),
+        &Rfc9083Error::redirect()
+            .url("https://other.example.com")
+            .build(),
+    )
+    .await
+    .expect("add domain error in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = jig
+        .pg
+        .get_domain_by_ldh("foo.example")
+        .await
+        .expect("getting domain by ldh");
+
+    // THEN
+    assert!(actual.is_redirect());
+}
+
+#[rstest]
+#[case("foo.example.*", &["foo.example.com", "foo.example.net"])]
+#[case("foo*.example.com", &["foo.example.com", "foobar.example.com"])]
+#[case("bar*.example.com", &[])]
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_domains_in_pg_WHEN_search_domain_by_name_THEN_domains_returned(
+    #[case] search: &str,
+    #[case] expected: &[&str],
+) {
+    // GIVEN
+    let jig = PgTestJig::new_common_config(
+        CommonConfig::builder()
+            .domain_search_by_name_enable(true)
+            .build(),
+    )
+    .await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    for ldh in ["foo.example.com", "foo.example.net", "foobar.example.com"] {
+        tx.add_domain(&Domain::builder().ldh_name(ldh).build())
+            .await
+            .expect("add domain in tx");
+    }
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = jig
+        .pg
+        .search_domains_by_name(search)
+        .await
+        .expect("searching domains by name");
+
+    // THEN
+    let RdapResponse::DomainSearchResults(domains) = actual else {
+        panic!()
+    };
+    let actual = domains
+        .results
+        .iter()
+        .filter_map(|d| d.ldh_name.as_deref())
+        .collect::<Vec<&str>>();
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_domain_in_pg_but_search_not_enabled_WHEN_search_domain_by_name_THEN_not_implemented()
+{
+    // GIVEN
+    let jig = PgTestJig::new().await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example.com").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = jig
+        .pg
+        .search_domains_by_name("foo.example.*")
+        .await
+        .expect("searching domains by name");
+
+    // THEN
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, 501)
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_domain_with_nameservers_in_pg_WHEN_search_by_ns_THEN_domain_returned() {
+    // GIVEN
+    let jig = PgTestJig::new_common_config(
+        CommonConfig::builder()
+            .domain_search_by_ns_ip_enable(true)
+            .domain_search_by_ns_ldh_name_enable(true)
+            .build(),
+    )
+    .await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::builder()
+            .ldh_name("foo.example.com")
+            .nameservers(vec![
+                Nameserver::builder()
+                    .ldh_name("ns1.example.net")
+                    .address("10.0.0.1")
+                    .build()
+                    .expect("building nameserver"),
+                Nameserver::builder()
+                    .ldh_name("ns2.example.net")
+                    .address("10.0.0.2")
+                    .build()
+                    .expect("building nameserver"),
+            ])
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let by_ip = jig
+        .pg
+        .search_domains_by_ns_ip("10.0.0.2".parse().expect("ip address"))
+        .await
+        .expect("searching domains by ns ip");
+    let by_name = jig
+        .pg
+        .search_domains_by_ns_ldh_name("ns*.example.net")
+        .await
+        .expect("searching domains by ns name");
+
+    // THEN
+    let RdapResponse::DomainSearchResults(by_ip) = by_ip else {
+        panic!()
+    };
+    assert_eq!(by_ip.results.len(), 1);
+    let RdapResponse::DomainSearchResults(by_name) = by_name else {
+        panic!()
+    };
+    assert_eq!(by_name.results.len(), 1);
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_nameserver_in_pg_WHEN_lookup_and_search_THEN_nameserver_returned() {
+    // GIVEN
+    let jig = PgTestJig::new_common_config(
+        CommonConfig::builder()
+            .nameserver_search_by_name_enable(true)
+            .nameserver_search_by_ip_enable(true)
+            .build(),
+    )
+    .await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.add_nameserver(
+        &Nameserver::builder()
+            .ldh_name("ns.foo.example")
+            .address("2001:db8::1")
+            .build()
+            .expect("building nameserver"),
+    )
+    .await
+    .expect("add nameserver in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let by_ldh = jig
+        .pg
+        .get_nameserver_by_ldh("ns.foo.example")
+        .await
+        .expect("getting nameserver by ldh");
+    let by_name = jig
+        .pg
+        .search_nameservers_by_name("ns.foo*")
+        .await
+        .expect("searching nameservers by name");
+    let by_ip = jig
+        .pg
+        .search_nameservers_by_ip("2001:db8::1".parse().expect("ip address"))
+        .await
+        .expect("searching nameservers by ip");
+
+    // THEN
+    let RdapResponse::Nameserver(nameserver) = by_ldh else {
+        panic!()
+    };
+    assert_eq!(
+        nameserver.ldh_name.as_ref().expect("ldhName is none"),
+        "ns.foo.example"
+    );
+    let RdapResponse::NameserverSearchResults(by_name) = by_name else {
+        panic!()
+    };
+    assert_eq!(by_name.results.len(), 1);
+    let RdapResponse::NameserverSearchResults(by_ip) = by_ip else {
+        panic!()
+    };
+    assert_eq!(by_ip.results.len(), 1);
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_entity_in_pg_WHEN_lookup_and_search_THEN_entity_returned() {
+    // GIVEN
+    let jig = PgTestJig::new_common_config(
+        CommonConfig::builder()
+            .entity_search_by_handle_enable(true)
+            .entity_search_by_full_name_enable(true)
+            .build(),
+    )
+    .await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.add_entity(
+        &Entity::builder()
+            .handle("foo-1234_example")
+            .contact(
+                icann_rdap_common::contact::Contact::builder()
+                    .full_name("Bob Smurd")
+                    .build(),
+            )
+            .build(),
+    )
+    .await
+    .expect("add entity in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let by_handle = jig
+        .pg
+        .get_entity_by_handle("foo-1234_example")
+        .await
+        .expect("getting entity by handle");
+    let search_handle = jig
+        .pg
+        .search_entities_by_handle("foo*.example")
+        .await
+        .expect("searching entities by handle");
+    let search_name = jig
+        .pg
+        .search_entities_by_full_name("Bob*")
+        .await
+        .expect("searching entities by full name");
+
+    // THEN
+    let RdapResponse::Entity(entity) = by_handle else {
+        panic!()
+    };
+    assert_eq!(
+        entity
+            .object_common
+            .handle
+            .as_ref()
+            .expect("handle is none"),
+        "foo-1234_example"
+    );
+    let RdapResponse::EntitySearchResults(search_handle) = search_handle else {
+        panic!()
+    };
+    assert_eq!(search_handle.results.len(), 1);
+    let RdapResponse::EntitySearchResults(search_name) = search_name else {
+        panic!()
+    };
+    assert_eq!(search_name.results.len(), 1);
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_network_and_autnum_in_pg_WHEN_search_by_handle_and_name_THEN_each_returned_once() {
+    // GIVEN
+    let jig = PgTestJig::new_common_config(
+        CommonConfig::builder()
+            .network_search_by_handle_enable(true)
+            .network_search_by_name_enable(true)
+            .autnum_search_by_handle_enable(true)
+            .autnum_search_by_name_enable(true)
+            .build(),
+    )
+    .await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    let mut network = Network::builder()
+        .cidr("10.0.0.0/24")
+        .handle("NET-10-0-0-0-1")
+        .name("EXAMPLE-NET")
+        .build()
+        .expect("cidr parsing");
+    // a range covered by two CIDRs
+    network.end_address = Some("10.0.2.255".to_string());
+    tx.add_network(&network).await.expect("add network in tx");
+    tx.add_autnum(
+        &Autnum::builder()
+            .autnum_range(700..710)
+            .handle("AS700")
+            .name("EXAMPLE-AS")
+            .build(),
+    )
+    .await
+    .expect("add autnum in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let networks_by_handle = jig
+        .pg
+        .search_networks_by_handle("NET-10*")
+        .await
+        .expect("searching networks by handle");
+    let networks_by_name = jig
+        .pg
+        .search_networks_by_name("EXAMPLE*")
+        .await
+        .expect("searching networks by name");
+    let autnums_by_handle = jig
+        .pg
+        .search_autnums_by_handle("AS7*")
+        .await
+        .expect("searching autnums by handle");
+    let autnums_by_name = jig
+        .pg
+        .search_autnums_by_name("EXAMPLE*")
+        .await
+        .expect("searching autnums by name");
+
+    // THEN
+    for networks in [networks_by_handle, networks_by_name] {
+        let RdapResponse::NetworkSearchResults(networks) = networks else {
+            panic!()
+        };
+        assert_eq!(networks.results.len(), 1);
+    }
+    for autnums in [autnums_by_handle, autnums_by_name] {
+        let RdapResponse::AutnumSearchResults(autnums) = autnums else {
+            panic!()
+        };
+        assert_eq!(autnums.results.len(), 1);
+    }
+}
+
+#[rstest]
+#[case(700, Some((700, 710)))]
+#[case(705, Some((705, 705)))]
+#[case(710, Some((700, 710)))]
+#[case(711, None)]
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_autnums_in_pg_WHEN_lookup_autnum_by_num_THEN_last_added_autnum_returned(
+    #[case] num: u32,
+    #[case] expected: Option<(u32, u32)>,
+) {
+    // GIVEN
+    let jig = PgTestJig::new().await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.add_autnum(&Autnum::builder().autnum_range(700..710).build())
+        .await
+        .expect("add autnum in tx");
+    tx.add_autnum(&Autnum::builder().autnum_range(705..705).build())
+        .await
+        .expect("add autnum in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = jig
+        .pg
+        .get_autnum_by_num(num)
+        .await
+        .expect("getting autnum by num");
+
+    // THEN
+    match expected {
+        Some((start, end)) => {
+            let RdapResponse::Autnum(autnum) = actual else {
+                panic!()
+            };
+            assert_eq!(
+                *autnum.start_autnum.as_ref().expect("startNum is none"),
+                Numberish::<u32>::from(start)
+            );
+            assert_eq!(
+                *autnum.end_autnum.as_ref().expect("endNum is none"),
+                Numberish::<u32>::from(end)
+            );
+        }
+        None => {
+            let RdapResponse::ErrorResponse(error) = actual else {
+                panic!()
+            };
+            assert_eq!(error.error_code, 404)
+        }
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_autnum_added_twice_in_pg_WHEN_search_autnums_by_handle_THEN_one_autnum_returned() {
+    // GIVEN
+    let jig = PgTestJig::new_common_config(
+        CommonConfig::builder()
+            .autnum_search_by_handle_enable(true)
+            .build(),
+    )
+    .await;
+    for name in ["FIRST", "SECOND"] {
+        let mut tx = jig.pg.new_tx().await.expect("new transaction");
+        tx.add_autnum(
+            &Autnum::builder()
+                .autnum_range(700..710)
+                .handle("AS700")
+                .name(name)
+                .build(),
+        )
+        .await
+        .expect("add autnum in tx");
+        tx.commit().await.expect("tx commit");
+    }
+
+    // WHEN
+    let actual = jig
+        .pg
+        .search_autnums_by_handle("AS7*")
+        .await
+        .expect("searching autnums by handle");
+
+    // THEN
+    let RdapResponse::AutnumSearchResults(autnums) = actual else {
+        panic!()
+    };
+    assert_eq!(autnums.results.len(), 1);
+    assert_eq!(autnums.results[0].name.as_deref(), Some("SECOND"));
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_invalid_search_WHEN_search_domain_by_name_THEN_no_domains_returned() {
+    // GIVEN
+    let jig = PgTestJig::new_common_config(
+        CommonConfig::builder()
+            .domain_search_by_name_enable(true)
+            .build(),
+    )
+    .await;
+
+    // WHEN
+    let actual = jig
+        .pg
+        .search_domains_by_name("foo.example.com")
+        .await
+        .expect("searching domains by name");
+
+    // THEN
+    let RdapResponse::DomainSearchResults(domains) = actual else {
+        panic!()
+    };
+    assert!(domains.results.is_empty());
+}
+
+#[rstest]
+#[case(&["192.168.0.0/16", "192.168.0.0/8", "192.168.0.0/24"], "192.168.0.1", "192.168.0.0", "192.168.0.255")]
+#[case(&["192.168.0.0/16", "192.168.0.0/8", "192.168.0.0/24"], "192.168.1.1", "192.168.0.0", "192.168.255.255")]
+#[case(&["2001::/64", "2001::/56", "2001::/20"], "2001::1", "2001::", "2001::ffff:ffff:ffff:ffff")]
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_contained_networks_in_pg_WHEN_lookup_network_by_address_THEN_most_specific_network_returned(
+    #[case] cidrs: &[&str],
+    #[case] addr: &str,
+    #[case] start: &str,
+    #[case] end: &str,
+) {
+    // GIVEN
+    let jig = PgTestJig::new().await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    for cidr in cidrs {
+        tx.add_network(
+            &Network::builder()
+                .cidr(*cidr)
+                .build()
+                .expect("cidr parsing"),
+        )
+        .await
+        .expect("add network in tx");
+    }
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = jig
+        .pg
+        .get_network_by_ipaddr(addr)
+        .await
+        .expect("getting network by address");
+
+    // THEN
+    let RdapResponse::Network(network) = actual else {
+        panic!()
+    };
+    assert_eq!(
+        *network
+            .start_address
+            .as_ref()
+            .expect("startAddress is none"),
+        start
+    );
+    assert_eq!(
+        *network.end_address.as_ref().expect("endAddress is none"),
+        end
+    );
+}
+
+#[rstest]
+#[case("192.168.0.0/16", "192.168.0.0/24", Some("192.168.0.0"))]
+#[case("192.168.0.0/16", "192.168.0.0/16", Some("192.168.0.0"))]
+#[case("192.168.0.0/16", "192.168.0.0/8", None)]
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_network_in_pg_WHEN_lookup_network_by_cidr_THEN_network_returned(
+    #[case] cidr: &str,
+    #[case] lookup: &str,
+    #[case] start: Option<&str>,
+) {
+    // GIVEN
+    let jig = PgTestJig::new().await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.add_network(&Network::builder().cidr(cidr).build().expect("cidr parsing"))
+        .await
+        .expect("add network in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = jig
+        .pg
+        .get_network_by_cidr(lookup)
+        .await
+        .expect("getting network by cidr");
+
+    // THEN
+    match start {
+        Some(start) => {
+            let RdapResponse::Network(network) = actual else {
+                panic!()
+            };
+            assert_eq!(
+                network
+                    .start_address
+                    .as_ref()
+                    .expect("startAddress is none"),
+                start
+            );
+        }
+        None => {
+            let RdapResponse::ErrorResponse(error) = actual else {
+                panic!()
+            };
+            assert_eq!(error.error_code, 404)
+        }
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_network_error_in_pg_WHEN_lookup_network_by_address_THEN_redirect_returned() {
+    // GIVEN
+    let jig = PgTestJig::new().await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    tx.add_network_err(
+        &NetworkId::builder()
+            .network_id(NetworkIdType::Range {
+                start_address: "10.0.0.0".to_string(),
+                end_address: "10.0.1.255".to_string(),
+            })
+            .build(),
+        &Rfc9083Error::redirect()
+            .url("https://other.example.com")
+            .build(),
+    )
+    .await
+    .expect("add network error in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = jig
+        .pg
+        .get_network_by_ipaddr("10.0.1.1")
+        .await
+        .expect("getting network by address");
+
+    // THEN
+    assert!(actual.is_redirect());
+}
+
+#[tokio::test]
+#[ignore = "requires initdb and pg_ctl"]
+async fn GIVEN_help_in_pg_WHEN_lookup_help_THEN_get_host_or_default_help() {
+    // GIVEN
+    let jig = PgTestJig::new().await;
+    let mut tx = jig.pg.new_tx().await.expect("new transaction");
+    for (host, description) in [(None, "foo"), (Some("bar.example.com"), "bar")] {
+        tx.add_srv_help(
+            &Help::response()
+                .notice(Notice(
+                    NoticeOrRemark::builder()
+                        .description_entry(description.to_string())
+                        .build(),
+                ))
+                .build(),
+            host,
+        )
+        .await
+        .expect("adding srv help");
+    }
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let default_help = jig.pg.get_srv_help(None).await.expect("getting srv help");
+    let host_help = jig
+        .pg
+        .get_srv_help(Some("bar.example.com"))
+        .await
+        .expect("getting srv help");
+
+    // THEN
+    let expected_default = Help::response()
+        .notice(Notice(
+            NoticeOrRemark::builder()
+                .description_entry("foo".to_string())
+                .build(),
+        ))
+        .build()
+        .to_response();
+    assert_eq!(default_help, expected_default);
+    let RdapResponse::Help(host_help) = host_help else {
+        panic!()
+    };
+    let notice = host_help
+        .common
+        .notices
+        .expect("no notices in srvhelp")
+        .first()
+        .expect("notices empty")
+        .to_owned();
+    assert_eq!(
+        notice
+            .description
+            .as_ref()
+            .expect("no description")
+            .vec()
+            .first()
+            .expect("no description in notice"),
+        "bar"
+    );
+}
@@ -0,0 +1,901 @@
+#![allow(non_snake_case)]
+
+use {
+    icann_rdap_common::{
+        prelude::{Numberish, ToResponse},
+        response::{
+            Autnum, Domain, Entity, Help, Nameserver, Network, Notice, NoticeOrRemark,
+            RdapResponse, Rfc9083Error,
+        },
+    },
+    icann_rdap_srv::storage::{
+        data::{DomainId, NetworkId, NetworkIdType},
+        sqlite::{config::SqliteConfig, ops::Sqlite},
+        CommonConfig, StoreOps,
+    },
+    rstest::rstest,
+    test_dir::{DirBuilder, TestDir},
+};
+
+/// Holds a SQLite database in a temporary directory for the duration of a test.
+struct SqliteTestJig {
+    sqlite: Sqlite,
+    _db_dir: TestDir,
+}
+
+impl SqliteTestJig {
+    async fn new() -> Self {
+        Self::new_common_config(CommonConfig::default()).await
+    }
+
+    async fn new_common_config(common_config: CommonConfig) -> Self {
+        let db_dir = TestDir::temp();
+        let db_url = format!("sqlite://{}", db_dir.path("rdap.db").display());
+        let sqlite = Sqlite::new(
+            SqliteConfig::builder()
+                .db_url(db_url)
+                .common_config(common_config)
+                .build(),
+        )
+        .await
+        .expect("opening SQLite database");
+        sqlite.init().await.expect("initializing storage");
+        Self {
+            sqlite,
+            _db_dir: db_dir,
+        }
+    }
+}
+
+#[tokio::test]
+async fn GIVEN_domain_in_sqlite_WHEN_new_truncate_tx_THEN_no_domain_in_sqlite() {
+    // GIVEN
+    let jig = SqliteTestJig::new().await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let tx = jig.sqlite.new_truncate_tx().await.expect("new truncate tx");
+    tx.commit().await.expect("tx commit");
+
+    // THEN
+    let actual = jig
+        .sqlite
+        .get_domain_by_ldh("foo.example")
+        .await
+        .expect("getting domain by ldh");
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, 404)
+}
+
+#[tokio::test]
+async fn GIVEN_domain_in_sqlite_WHEN_reopen_database_THEN_domain_returned() {
+    // GIVEN
+    let db_dir = TestDir::temp();
+    let config = SqliteConfig::builder()
+        .db_url(format!("sqlite://{}", db_dir.path("rdap.db").display()))
+        .common_config(CommonConfig::default())
+        .build();
+    let sqlite = Sqlite::new(config.clone())
+        .await
+        .expect("opening SQLite database");
+    sqlite.init().await.expect("initializing storage");
+    let mut tx = sqlite.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+    drop(sqlite);
+
+    // WHEN
+    let sqlite = Sqlite::new(config)
+        .await
+        .expect("reopening SQLite database");
+    sqlite.init().await.expect("initializing storage");
+
+    // THEN
+    let actual = sqlite
+        .get_domain_by_ldh("foo.example")
+        .await
+        .expect("getting domain by ldh");
+    let RdapResponse::Domain(domain) = actual else {
+        panic!()
+    };
+    assert_eq!(domain.ldh_name.as_deref(), Some("foo.example"));
+}
+
+#[tokio::test]
+async fn GIVEN_domain_in_sqlite_WHEN_rollback_THEN_no_domain_in_sqlite() {
+    // GIVEN
+    let jig = SqliteTestJig::new().await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example").build())
+        .await
+        .expect("add domain in tx");
+
+    // WHEN
+    tx.rollback().await.expect("tx rollback");
+
+    // THEN
+    let actual = jig
+        .sqlite
+        .get_domain_by_ldh("foo.example")
+        .await
+        .expect("getting domain by ldh");
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, 404)
+}
+
+#[tokio::test]
+async fn GIVEN_domain_in_sqlite_WHEN_delete_domain_THEN_no_domain_in_sqlite() {
+    // GIVEN
+    let jig = SqliteTestJig::new().await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::builder()
+            .ldh_name("foo.example")
+            .nameserver(
+                Nameserver::builder()
+                    .ldh_name("ns.foo.example")
+                    .address("192.0.2.1")
+                    .build()
+                    .expect("building nameserver"),
+            )
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.delete_domain(&DomainId::builder().ldh_name("foo.example").build())
+        .await
+        .expect("delete domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // THEN
+    let actual = jig
+        .sqlite
+        .get_domain_by_ldh("foo.example")
+        .await
+        .expect("getting domain by ldh");
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, 404)
+}
+
+#[tokio::test]
+async fn GIVEN_network_in_sqlite_WHEN_delete_network_THEN_no_network_in_sqlite() {
+    // GIVEN
+    let jig = SqliteTestJig::new().await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.add_network(
+        &Network::builder()
+            .cidr("192.0.2.0/24")
+            .build()
+            .expect("cidr parsing"),
+    )
+    .await
+    .expect("add network in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.delete_network(
+        &NetworkId::builder()
+            .network_id(NetworkIdType::Cidr(
+                "192.0.2.0/24".parse().expect("parsing cidr"),
+            ))
+            .build(),
+    )
+    .await
+    .expect("delete network in tx");
+    tx.commit().await.expect("tx commit");
+
+    // THEN
+    let actual = jig
+        .sqlite
+        .get_network_by_ipaddr("192.0.2.1")
+        .await
+        .expect("getting network by address");
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, 404)
+}
+
+#[tokio::test]
+async fn GIVEN_idn_in_sqlite_WHEN_lookup_domain_by_ldh_and_unicode_THEN_domain_returned() {
+    // GIVEN
+    let jig = SqliteTestJig::new().await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::idn()
+            .unicode_name("café.example")
+            .ldh_name("xn--caf-dma.example")
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let by_ldh = jig
+        .sqlite
+        .get_domain_by_ldh("xn--caf-dma.example")
+        .await
+        .expect("getting domain by ldh");
+    let by_unicode = jig
+        .sqlite
+        .get_domain_by_unicode("café.example")
+        .await
+        .expect("getting domain by unicode");
+
+    // THEN
+    let RdapResponse::Domain(by_ldh) = by_ldh else {
+        panic!()
+    };
+    let RdapResponse::Domain(by_unicode) = by_unicode else {
+        panic!()
+    };
+    assert_eq!(by_ldh, by_unicode);
+    assert_eq!(
+        by_ldh.unicode_name.as_ref().expect("unicodeName is none"),
+        "café.example"
+    );
+}
+
+#[tokio::test]
+async fn GIVEN_domain_error_in_sqlite_WHEN_lookup_domain_by_ldh_THEN_redirect_returned() {
+    // GIVEN
+    let jig = SqliteTestJig::new().await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.add_domain_err(
+        &DomainId::builder().ldh_name("foo.example").build(),
+        &Rfc9083Error::redirect()
+            .url("https://other.example.com")
+            .build(),
+    )
+    .await
+    .expect("add domain error in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = jig
+        .sqlite
+        .get_domain_by_ldh("foo.example")
+        .await
+        .expect("getting domain by ldh");
+
+    // THEN
+    assert!(actual.is_redirect());
+}
+
+#[rstest]
+#[case("foo.example.*", &["foo.example.com", "foo.example.net"])]
+#[case("foo*.example.com", &["foo.example.com", "foobar.example.com"])]
+#[case("bar*.example.com", &[])]
+#[case("FOO*.example.com", &[])]
+#[case("foo.example.com", &[])]
+#[tokio::test]
+async fn GIVEN_domains_in_sqlite_WHEN_search_domain_by_name_THEN_domains_returned(
+    #[case] search: &str,
+    #[case] expected: &[&str],
+) {
+    // GIVEN
+    let jig = SqliteTestJig::new_common_config(
+        CommonConfig::builder()
+            .domain_search_by_name_enable(true)
+            .build(),
+    )
+    .await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    for ldh in ["foo.example.com", "foo.example.net", "foobar.example.com"] {
+        tx.add_domain(&Domain::builder().ldh_name(ldh).build())
+            .await
+            .expect("add domain in tx");
+    }
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = jig
+        .sqlite
+        .search_domains_by_name(search)
+        .await
+        .expect("searching domains by name");
+
+    // THEN
+    let RdapResponse::DomainSearchResults(domains) = actual else {
+        panic!()
+    };
+    let actual = domains
+        .results
+        .iter()
+        .filter_map(|d| d.ldh_name.as_deref())
+        .collect::<Vec<&str>>();
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn GIVEN_domain_in_sqlite_but_search_not_enabled_WHEN_search_domain_by_name_THEN_not_implemented(
+) {
+    // GIVEN
+    let jig = SqliteTestJig::new().await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.add_domain(&Domain::builder().ldh_name("foo.example.com").build())
+        .await
+        .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = jig
+        .sqlite
+        .search_domains_by_name("foo.example.*")
+        .await
+        .expect("searching domains by name");
+
+    // THEN
+    let RdapResponse::ErrorResponse(error) = actual else {
+        panic!()
+    };
+    assert_eq!(error.error_code, 501)
+}
+
+#[tokio::test]
+async fn GIVEN_domain_with_nameservers_in_sqlite_WHEN_search_by_ns_THEN_domain_returned() {
+    // GIVEN
+    let jig = SqliteTestJig::new_common_config(
+        CommonConfig::builder()
+            .domain_search_by_ns_ip_enable(true)
+            .domain_search_by_ns_ldh_name_enable(true)
+            .build(),
+    )
+    .await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.add_domain(
+        &Domain::builder()
+            .ldh_name("foo.example.com")
+            .nameservers(vec![
+                Nameserver::builder()
+                    .ldh_name("ns1.example.net")
+                    .address("10.0.0.1")
+                    .build()
+                    .expect("building nameserver"),
+                Nameserver::builder()
+                    .ldh_name("ns2.example.net")
+                    .address("10.0.0.2")
+                    .build()
+                    .expect("building nameserver"),
+            ])
+            .build(),
+    )
+    .await
+    .expect("add domain in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let by_ip = jig
+        .sqlite
+        .search_domains_by_ns_ip("10.0.0.2".parse().expect("ip address"))
+        .await
+        .expect("searching domains by ns ip");
+    let by_name = jig
+        .sqlite
+        .search_domains_by_ns_ldh_name("ns*.example.net")
+        .await
+        .expect("searching domains by ns name");
+
+    // THEN
+    let RdapResponse::DomainSearchResults(by_ip) = by_ip else {
+        panic!()
+    };
+    assert_eq!(by_ip.results.len(), 1);
+    let RdapResponse::DomainSearchResults(by_name) = by_name else {
+        panic!()
+    };
+    assert_eq!(by_name.results.len(), 1);
+}
+
+#[tokio::test]
+async fn GIVEN_nameserver_in_sqlite_WHEN_lookup_and_search_THEN_nameserver_returned() {
+    // GIVEN
+    let jig = SqliteTestJig::new_common_config(
+        CommonConfig::builder()
+            .nameserver_search_by_name_enable(true)
+            .nameserver_search_by_ip_enable(true)
+            .build(),
+    )
+    .await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.add_nameserver(
+        &Nameserver::builder()
+            .ldh_name("ns.foo.example")
+            .address("2001:db8::1")
+            .build()
+            .expect("building nameserver"),
+    )
+    .await
+    .expect("add nameserver in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let by_ldh = jig
+        .sqlite
+        .get_nameserver_by_ldh("ns.foo.example")
+        .await
+        .expect("getting nameserver by ldh");
+    let by_name = jig
+        .sqlite
+        .search_nameservers_by_name("ns.foo*")
+        .await
+        .expect("searching nameservers by name");
+    let by_ip = jig
+        .sqlite
+        .search_nameservers_by_ip("2001:db8::1".parse().expect("ip address"))
+        .await
+        .expect("searching nameservers by ip");
+
+    // THEN
+    let RdapResponse::Nameserver(nameserver) = by_ldh else {
+        panic!()
+    };
+    assert_eq!(
+        nameserver.ldh_name.as_ref().expect("ldhName is none"),
+        "ns.foo.example"
+    );
+    let RdapResponse::NameserverSearchResults(by_name) = by_name else {
+        panic!()
+    };
+    assert_eq!(by_name.results.len(), 1);
+    let RdapResponse::NameserverSearchResults(by_ip) = by_ip else {
+        panic!()
+    };
+    assert_eq!(by_ip.results.len(), 1);
+}
+
+#[tokio::test]
+async fn GIVEN_entity_in_sqlite_WHEN_lookup_and_search_THEN_entity_returned() {
+    // GIVEN
+    let jig = SqliteTestJig::new_common_config(
+        CommonConfig::builder()
+            .entity_search_by_handle_enable(true)
+            .entity_search_by_full_name_enable(true)
+            .build(),
+    )
+    .await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.add_entity(
+        &Entity::builder()
+            .handle("foo-1234_example")
+            .contact(
+                icann_rdap_common::contact::Contact::builder()
+                    .full_name("Bob Smurd")
+                    .build(),
+            )
+            .build(),
+    )
+    .await
+    .expect("add entity in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let by_handle = jig
+        .sqlite
+        .get_entity_by_handle("foo-1234_example")
+        .await
+        .expect("getting entity by handle");
+    let search_handle = jig
+        .sqlite
+        .search_entities_by_handle("foo*.example")
+        .await
+        .expect("searching entities by handle");
+    let search_name = jig
+        .sqlite
+        .search_entities_by_full_name("Bob*")
+        .await
+        .expect("searching entities by full name");
+
+    // THEN
+    let RdapResponse::Entity(entity) = by_handle else {
+        panic!()
+    };
+    assert_eq!(
+        entity
+            .object_common
+            .handle
+            .as_ref()
+            .expect("handle is none"),
+        "foo-1234_example"
+    );
+    let RdapResponse::EntitySearchResults(search_handle) = search_handle else {
+        panic!()
+    };
+    assert_eq!(search_handle.results.len(), 1);
+    let RdapResponse::EntitySearchResults(search_name) = search_name else {
+        panic!()
+    };
+    assert_eq!(search_name.results.len(), 1);
+}
+
+#[tokio::test]
+async fn GIVEN_network_and_autnum_in_sqlite_WHEN_search_by_handle_and_name_THEN_each_returned_once()
+{
+    // GIVEN
+    let jig = SqliteTestJig::new_common_config(
+        CommonConfig::builder()
+            .network_search_by_handle_enable(true)
+            .network_search_by_name_enable(true)
+            .autnum_search_by_handle_enable(true)
+            .autnum_search_by_name_enable(true)
+            .build(),
+    )
+    .await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    let mut network = Network::builder()
+        .cidr("10.0.0.0/24")
+        .handle("NET-10-0-0-0-1")
+        .name("EXAMPLE-NET")
+        .build()
+        .expect("cidr parsing");
+    // a range covered by two CIDRs
+    network.end_address = Some("10.0.2.255".to_string());
+    tx.add_network(&network).await.expect("add network in tx");
+    tx.add_autnum(
+        &Autnum::builder()
+            .autnum_range(700..710)
+            .handle("AS700")
+            .name("EXAMPLE-AS")
+            .build(),
+    )
+    .await
+    .expect("add autnum in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let networks_by_handle = jig
+        .sqlite
+        .search_networks_by_handle("NET-10*")
+        .await
+        .expect("searching networks by handle");
+    let networks_by_name = jig
+        .sqlite
+        .search_networks_by_name("EXAMPLE*")
+        .await
+        .expect("searching networks by name");
+    let autnums_by_handle = jig
+        .sqlite
+        .search_autnums_by_handle("AS7*")
+        .await
+        .expect("searching autnums by handle");
+    let autnums_by_name = jig
+        .sqlite
+        .search_autnums_by_name("EXAMPLE*")
+        .await
+        .expect("searching autnums by name");
+
+    // THEN
+    for networks in [networks_by_handle, networks_by_name] {
+        let RdapResponse::NetworkSearchResults(networks) = networks else {
+            panic!()
+        };
+        assert_eq!(networks.results.len(), 1);
+    }
+    for autnums in [autnums_by_handle, autnums_by_name] {
+        let RdapResponse::AutnumSearchResults(autnums) = autnums else {
+            panic!()
+        };
+        assert_eq!(autnums.results.len(), 1);
+    }
+}
+
+#[rstest]
+#[case(700, Some((700, 710)))]
+#[case(705, Some((705, 705)))]
+#[case(710, Some((700, 710)))]
+#[case(711, None)]
+#[tokio::test]
+async fn GIVEN_autnums_in_sqlite_WHEN_lookup_autnum_by_num_THEN_last_added_autnum_returned(
+    #[case] num: u32,
+    #[case] expected: Option<(u32, u32)>,
+) {
+    // GIVEN
+    let jig = SqliteTestJig::new().await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.add_autnum(&Autnum::builder().autnum_range(700..710).build())
+        .await
+        .expect("add autnum in tx");
+    tx.add_autnum(&Autnum::builder().autnum_range(705..705).build())
+        .await
+        .expect("add autnum in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = jig
+        .sqlite
+        .get_autnum_by_num(num)
+        .await
+        .expect("getting autnum by num");
+
+    // THEN
+    match expected {
+        Some((start, end)) => {
+            let RdapResponse::Autnum(autnum) = actual else {
+                panic!()
+            };
+            assert_eq!(
+                *autnum.start_autnum.as_ref().expect("startNum is none"),
+                Numberish::<u32>::from(start)
+            );
+            assert_eq!(
+                *autnum.end_autnum.as_ref().expect("endNum is none"),
+                Numberish::<u32>::from(end)
+            );
+        }
+        None => {
+            let RdapResponse::ErrorResponse(error) = actual else {
+                panic!()
+            };
+            assert_eq!(error.error_code, 404)
+        }
+    }
+}
+
+#[tokio::test]
+async fn GIVEN_autnum_added_again_in_sqlite_WHEN_search_and_lookup_THEN_autnum_replaced() {
+    // GIVEN
+    let jig = SqliteTestJig::new_common_config(
+        CommonConfig::builder()
+            .autnum_search_by_handle_enable(true)
+            .build(),
+    )
+    .await;
+    let first = Autnum::builder()
+        .autnum_range(700..710)
+        .handle("AS700")
+        .name("FIRST")
+        .build();
+    let overlapping = Autnum::builder()
+        .autnum_range(705..705)
+        .handle("AS705")
+        .build();
+    let second = Autnum::builder()
+        .autnum_range(700..710)
+        .handle("AS700")
+        .name("SECOND")
+        .build();
+    for autnum in [first, overlapping, second] {
+        let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+        tx.add_autnum(&autnum).await.expect("add autnum in tx");
+        tx.commit().await.expect("tx commit");
+    }
+
+    // WHEN
+    let searched = jig
+        .sqlite
+        .search_autnums_by_handle("AS70*")
+        .await
+        .expect("searching autnums by handle");
+    let looked_up = jig
+        .sqlite
+        .get_autnum_by_num(705)
+        .await
+        .expect("getting autnum by num");
+
+    // THEN
+    let RdapResponse::AutnumSearchResults(autnums) = searched else {
+        panic!()
+    };
+    let names = autnums
+        .results
+        .iter()
+        .map(|a| a.name.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec![Some("SECOND"), None]);
+    let RdapResponse::Autnum(autnum) = looked_up else {
+        panic!()
+    };
+    assert_eq!(autnum.name.as_deref(), Some("SECOND"));
+}
+
+#[rstest]
+#[case(&["192.168.0.0/16", "192.168.0.0/8", "192.168.0.0/24"], "192.168.0.1", "192.168.0.0", "192.168.0.255")]
+#[case(&["192.168.0.0/16", "192.168.0.0/8", "192.168.0.0/24"], "192.168.1.1", "192.168.0.0", "192.168.255.255")]
+#[case(&["2001::/64", "2001::/56", "2001::/20"], "2001::1", "2001::", "2001::ffff:ffff:ffff:ffff")]
+#[tokio::test]
+async fn GIVEN_contained_networks_in_sqlite_WHEN_lookup_network_by_address_THEN_most_specific_network_returned(
+    #[case] cidrs: &[&str],
+    #[case] addr: &str,
+    #[case] start: &str,
+    #[case] end: &str,
+) {
+    // GIVEN
+    let jig = SqliteTestJig::new().await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    for cidr in cidrs {
+        tx.add_network(
+            &Network::builder()
+                .cidr(*cidr)
+                .build()
+                .expect("cidr parsing"),
+        )
+        .await
+        .expect("add network in tx");
+    }
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = jig
+        .sqlite
+        .get_network_by_ipaddr(addr)
+        .await
+        .expect("getting network by address");
+
+    // THEN
+    let RdapResponse::Network(network) = actual else {
+        panic!()
+    };
+    assert_eq!(
+        *network
+            .start_address
+            .as_ref()
+            .expect("startAddress is none"),
+        start
+    );
+    assert_eq!(
+        *network.end_address.as_ref().expect("endAddress is none"),
+        end
+    );
+}
+
+#[rstest]
+#[case("192.168.0.0/16", "192.168.0.0/24", Some("192.168.0.0"))]
+#[case("192.168.0.0/16", "192.168.0.0/16", Some("192.168.0.0"))]
+#[case("192.168.0.0/16", "192.168.0.0/8", None)]
+#[tokio::test]
+async fn GIVEN_network_in_sqlite_WHEN_lookup_network_by_cidr_THEN_network_returned(
+    #[case] cidr: &str,
+    #[case] lookup: &str,
+    #[case] start: Option<&str>,
+) {
+    // GIVEN
+    let jig = SqliteTestJig::new().await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.add_network(&Network::builder().cidr(cidr).build().expect("cidr parsing"))
+        .await
+        .expect("add network in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = jig
+        .sqlite
+        .get_network_by_cidr(lookup)
+        .await
+        .expect("getting network by cidr");
+
+    // THEN
+    match start {
+        Some(start) => {
+            let RdapResponse::Network(network) = actual else {
+                panic!()
+            };
+            assert_eq!(
+                network
+                    .start_address
+                    .as_ref()
+                    .expect("startAddress is none"),
+                start
+            );
+        }
+        None => {
+            let RdapResponse::ErrorResponse(error) = actual else {
+                panic!()
+            };
+            assert_eq!(error.error_code, 404)
+        }
+    }
+}
+
+#[tokio::test]
+async fn GIVEN_network_error_in_sqlite_WHEN_lookup_network_by_address_THEN_redirect_returned() {
+    // GIVEN
+    let jig = SqliteTestJig::new().await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    tx.add_network_err(
+        &NetworkId::builder()
+            .network_id(NetworkIdType::Range {
+                start_address: "10.0.0.0".to_string(),
+                end_address: "10.0.1.255".to_string(),
+            })
+            .build(),
+        &Rfc9083Error::redirect()
+            .url("https://other.example.com")
+            .build(),
+    )
+    .await
+    .expect("add network error in tx");
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let actual = jig
+        .sqlite
+        .get_network_by_ipaddr("10.0.1.1")
+        .await
+        .expect("getting network by address");
+
+    // THEN
+    assert!(actual.is_redirect());
+}
+
+#[tokio::test]
+async fn GIVEN_help_in_sqlite_WHEN_lookup_help_THEN_get_host_or_default_help() {
+    // GIVEN
+    let jig = SqliteTestJig::new().await;
+    let mut tx = jig.sqlite.new_tx().await.expect("new transaction");
+    for (host, description) in [(None, "foo"), (Some("bar.example.com"), "bar")] {
+        tx.add_srv_help(
+            &Help::response()
+                .notice(Notice(
+                    NoticeOrRemark::builder()
+                        .description_entry(description.to_string())
+                        .build(),
+                ))
+                .build(),
+            host,
+        )
+        .await
+        .expect("adding srv help");
+    }
+    tx.commit().await.expect("tx commit");
+
+    // WHEN
+    let default_help = jig
+        .sqlite
+        .get_srv_help(None)
+        .await
+        .expect("getting srv help");
+    let host_help = jig
+        .sqlite
+        .get_srv_help(Some("bar.example.com"))
+        .await
+        .expect("getting srv help");
+
+    // THEN
+    let expected_default = Help::response()
+        .notice(Notice(
+            NoticeOrRemark::builder()
+                .description_entry("foo".to_string())
+                .build(),
+        ))
+        .build()
+        .to_response();
+    assert_eq!(default_help, expected_default);
+    let RdapResponse::Help(host_help) = host_help else {
+        panic!()
+    };
+    let notice = host_help
+        .common
+        .notices
+        .expect("no notices in srvhelp")
+        .first()
+        .expect("notices empty")
+        .to_owned();
+    assert_eq!(
+        notice
+            .description
+            .as_ref()
+            .expect("no description")
+            .vec()
+            .first()
+            .expect("no description in notice"),
+        "bar"
+    );
+}
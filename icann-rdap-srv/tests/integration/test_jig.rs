@@ -1,9 +1,14 @@
 use {
     assert_cmd::Command,
+    axum_client_ip::SecureClientIpSource,
     icann_rdap_srv::{
-        config::{JsContactConversion, ListenConfig},
+        auth::AuthConfig,
+        config::{JsContactConversion, ListenConfig, RateLimitConfig, SearchConfig, TlsConfig},
+        health::Readiness,
+        rdap::redaction::RedactionPolicy,
         server::{AppState, Listener},
         storage::{
+            data::DataStatus,
             mem::{config::MemConfig, ops::Mem},
             CommonConfig,
         },
@@ -83,77 +88,104 @@ pub struct SrvTestJig {
 impl SrvTestJig {
     pub async fn new() -> Self {
         let mem = Mem::default();
-        let app_state = AppState {
-            storage: mem.clone(),
-            bootstrap: false,
-            jscontact_conversion: JsContactConversion::None,
-        };
-        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
-        let listener = Listener::listen(&ListenConfig::default())
-            .await
-            .expect("listening on interface");
-        let rdap_base = listener.rdap_base();
-        tokio::spawn(async move {
-            listener
-                .start_with_state(app_state)
-                .await
-                .expect("starting server");
-        });
-        Self { mem, rdap_base }
+        Self::start(app_state(&mem), ListenConfig::default()).await
     }
 
     pub async fn new_common_config(common_config: CommonConfig) -> Self {
+        let mem_config = MemConfig::builder().common_config(common_config).build();
+        let mem = Mem::new(mem_config);
+        Self::start(app_state(&mem), ListenConfig::default()).await
+    }
+
+    pub async fn new_search_config(
+        common_config: CommonConfig,
+        search_config: SearchConfig,
+    ) -> Self {
         let mem_config = MemConfig::builder().common_config(common_config).build();
         let mem = Mem::new(mem_config);
         let app_state = AppState {
-            storage: mem.clone(),
-            bootstrap: false,
-            jscontact_conversion: JsContactConversion::None,
+            search_config,
+            ..app_state(&mem)
         };
-        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
-        let listener = Listener::listen(&ListenConfig::default())
-            .await
-            .expect("listening on interface");
-        let rdap_base = listener.rdap_base();
-        tokio::spawn(async move {
-            listener
-                .start_with_state(app_state)
-                .await
-                .expect("starting server");
-        });
-        Self { mem, rdap_base }
+        Self::start(app_state, ListenConfig::default()).await
     }
 
     pub async fn new_bootstrap() -> Self {
         let mem = Mem::default();
         let app_state = AppState {
-            storage: mem.clone(),
             bootstrap: true,
-            jscontact_conversion: JsContactConversion::None,
+            ..app_state(&mem)
         };
-        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
-        let listener = Listener::listen(&ListenConfig::default())
-            .await
-            .expect("listening on interface");
-        let rdap_base = listener.rdap_base();
-        tokio::spawn(async move {
-            listener
-                .start_with_state(app_state)
-                .await
-                .expect("starting server");
-        });
-        Self { mem, rdap_base }
+        Self::start(app_state, ListenConfig::default()).await
     }
 
     pub async fn new_jscontact_conversion(jscontact_conversion: JsContactConversion) -> Self {
         let mem = Mem::default();
         let app_state = AppState {
-            storage: mem.clone(),
-            bootstrap: false,
             jscontact_conversion,
+            ..app_state(&mem)
+        };
+        Self::start(app_state, ListenConfig::default()).await
+    }
+
+    pub async fn new_redaction_policy(redaction_policy: RedactionPolicy) -> Self {
+        let mem = Mem::default();
+        let app_state = AppState {
+            redaction_policy: Some(redaction_policy),
+            ..app_state(&mem)
+        };
+        Self::start(app_state, ListenConfig::default()).await
+    }
+
+    pub async fn new_redaction_policy_behind_proxy(
+        redaction_policy: RedactionPolicy,
+        client_ip_source: SecureClientIpSource,
+    ) -> Self {
+        let mem = Mem::default();
+        let app_state = AppState {
+            redaction_policy: Some(redaction_policy),
+            client_ip_source: Some(client_ip_source),
+            ..app_state(&mem)
+        };
+        Self::start(app_state, ListenConfig::default()).await
+    }
+
+    pub async fn new_auth(auth: AuthConfig) -> Self {
+        let mem = Mem::default();
+        let app_state = AppState {
+            auth: Some(auth),
+            ..app_state(&mem)
         };
+        Self::start(app_state, ListenConfig::default()).await
+    }
+
+    pub async fn new_readiness(readiness: Readiness) -> Self {
+        let mem = Mem::default();
+        let app_state = AppState {
+            readiness,
+            ..app_state(&mem)
+        };
+        Self::start(app_state, ListenConfig::default()).await
+    }
+
+    pub async fn new_rate_limit(rate_limit: RateLimitConfig) -> Self {
+        let mem = Mem::default();
+        let app_state = AppState {
+            rate_limit: Some(rate_limit),
+            ..app_state(&mem)
+        };
+        Self::start(app_state, ListenConfig::default()).await
+    }
+
+    pub async fn new_tls(tls: TlsConfig) -> Self {
+        let mem = Mem::default();
+        Self::start(app_state(&mem), ListenConfig::builder().tls(tls).build()).await
+    }
+
+    async fn start(app_state: AppState<Mem>, listen_config: ListenConfig) -> Self {
+        let mem = app_state.storage.clone();
         let _ = tracing_subscriber::fmt().with_test_writer().try_init();
-        let listener = Listener::listen(&ListenConfig::default())
+        let listener = Listener::listen(&listen_config)
             .await
             .expect("listening on interface");
         let rdap_base = listener.rdap_base();
@@ -166,3 +198,19 @@ impl SrvTestJig {
         Self { mem, rdap_base }
     }
 }
+
+/// The state of a ready server using the storage, with everything else off.
+fn app_state(mem: &Mem) -> AppState<Mem> {
+    AppState {
+        storage: mem.clone(),
+        bootstrap: false,
+        jscontact_conversion: JsContactConversion::None,
+        search_config: SearchConfig::default(),
+        redaction_policy: None,
+        auth: None,
+        rate_limit: None,
+        client_ip_source: None,
+        readiness: Readiness::ready(),
+        data_status: DataStatus::default(),
+    }
+}